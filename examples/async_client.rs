@@ -0,0 +1,19 @@
+use kilnfi_connect_rs::{AsyncKiln, Kiln};
+
+// cargo run --example async_client --features async
+// the blocking `Kiln` is still there alongside, unless `default-features = false`
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let api_token: String = std::env::var("KILN_API_TOKEN")
+        .expect("KILN_API_TOKEN is not set in the environment. It is required.");
+
+    let kiln: AsyncKiln = Kiln::builder()
+        .api_token(api_token)
+        .base_url("https://api.kiln.fi/v1")
+        .seal()
+        .build_async()?;
+
+    let _data = kiln.ethereum().network_stats().await?;
+
+    Ok(())
+}
@@ -6,7 +6,6 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let _kiln: Kiln = Kiln::builder()
         .api_token(api_token)
-        // no trailing slash, or else it will break
         .base_url("https://api.kiln.fi/v1")
         .seal()
         .build()?;
@@ -0,0 +1,566 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::errors::KilnError;
+
+// Validated addresses : parsed once, so a malformed one never reaches the Kiln API.
+// Each type keeps the canonical text form (EIP-55 checksum, lowercase bech32...).
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("invalid {kind} `{input}`: {reason}")]
+pub struct AddressError {
+    kind: &'static str,
+    input: String,
+    reason: String,
+}
+
+impl From<AddressError> for KilnError {
+    fn from(error: AddressError) -> Self {
+        KilnError::InvalidInput(error.to_string())
+    }
+}
+
+macro_rules! address {
+    ($name:ident, $kind:literal, $parse:ident) => {
+        #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(String);
+
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = AddressError;
+
+            fn from_str(input: &str) -> Result<Self, Self::Err> {
+                $parse(input).map(Self).map_err(|reason| AddressError {
+                    kind: $kind,
+                    input: input.to_owned(),
+                    reason,
+                })
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&self.0)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                String::deserialize(deserializer)?
+                    .parse()
+                    .map_err(de::Error::custom)
+            }
+        }
+    };
+}
+
+// 0x + 20 bytes : all lowercase / all uppercase, or mixed case matching the EIP-55 checksum
+address!(EthAddress, "ethereum address", parse_eth);
+// bech32, the hrp of a supported chain, validator operator (`cosmosvaloper1..`) included
+address!(CosmosAddress, "cosmos address", parse_cosmos);
+// base58, 32 bytes
+address!(SolanaPubkey, "solana pubkey", parse_solana);
+// base58 prefix + public key + blake2b checksum, polkadot (0), kusama (2) or any other network
+address!(Ss58Address, "ss58 address", parse_ss58);
+// base58check tz1 / tz2 / tz3 / KT1
+address!(TezosAddress, "tezos address", parse_tezos);
+// named (`kiln.poolv1.near`) or implicit (64 hex) account
+address!(NearAccountId, "near account id", parse_near);
+// bech32 payment (addr) or stake address, mainnet or testnet
+address!(CardanoAddress, "cardano address", parse_cardano);
+// bech32 erd1.., 32 bytes
+address!(ErdAddress, "multiversx address", parse_erd);
+// 0x + 48 bytes, an ethereum validator
+address!(BlsPubkey, "bls pubkey", parse_bls);
+
+const COSMOS_PREFIXES: [&str; 9] = [
+    "cosmos", "celestia", "fetch", "inj", "kava", "osmo", "zeta", "dydx", "noble",
+];
+
+impl CosmosAddress {
+    // for the chains sharing a request struct : `CosmosAddress::parse_with_prefix(input, "osmo")`
+    pub fn parse_with_prefix(input: &str, prefix: &str) -> Result<Self, AddressError> {
+        let address: Self = input.parse()?;
+
+        if address.prefix() != prefix {
+            return Err(AddressError {
+                kind: "cosmos address",
+                input: input.to_owned(),
+                reason: format!("expected a `{prefix}` address"),
+            });
+        }
+
+        Ok(address)
+    }
+
+    // `osmovaloper1..` for "osmo" : the validator fields of the transaction requests
+    pub(crate) fn check_validator(&self, prefix: &str) -> Result<(), AddressError> {
+        self.check(prefix, true)
+    }
+
+    // `osmo1..` for "osmo" : the delegator, grantee... fields of the transaction requests
+    pub(crate) fn check_account(&self, prefix: &str) -> Result<(), AddressError> {
+        self.check(prefix, false)
+    }
+
+    fn check(&self, prefix: &str, validator: bool) -> Result<(), AddressError> {
+        if self.prefix() == prefix && self.is_validator() == validator {
+            return Ok(());
+        }
+
+        let expected = if validator {
+            format!("{prefix}valoper")
+        } else {
+            prefix.to_owned()
+        };
+
+        Err(AddressError {
+            kind: "cosmos address",
+            input: self.0.clone(),
+            reason: format!("expected a `{expected}1..` address"),
+        })
+    }
+
+    // chain prefix, "cosmos" for both cosmos1.. and cosmosvaloper1..
+    pub fn prefix(&self) -> &str {
+        let hrp = self.hrp();
+
+        hrp.strip_suffix("valoper").unwrap_or(hrp)
+    }
+
+    pub fn is_validator(&self) -> bool {
+        self.hrp().ends_with("valoper")
+    }
+
+    fn hrp(&self) -> &str {
+        self.0.rsplit_once('1').map_or("", |(hrp, _)| hrp)
+    }
+}
+
+impl Ss58Address {
+    // network prefix : 0 polkadot, 2 kusama, 42 substrate
+    pub fn network(&self) -> u16 {
+        bs58::decode(&self.0)
+            .into_vec()
+            .ok()
+            .and_then(|data| ss58_prefix(&data))
+            .map_or(0, |(network, _)| network)
+    }
+
+    // a polkadot (0) address for the polkadot transaction requests, a kusama (2) one for kusama
+    pub(crate) fn check_network(&self, network: u16) -> Result<(), AddressError> {
+        if self.network() == network {
+            return Ok(());
+        }
+
+        Err(AddressError {
+            kind: "ss58 address",
+            input: self.0.clone(),
+            reason: format!(
+                "expected an address of the network {network}, got {}",
+                self.network()
+            ),
+        })
+    }
+}
+
+// An address of a response : validated when it parses, kept as the api sent it otherwise, so a
+// format the SDK doesn't know yet never fails the whole response.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Lenient<A> {
+    Valid(A),
+    Unchecked(String),
+}
+
+impl<A: AsRef<str>> Lenient<A> {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Lenient::Valid(address) => address.as_ref(),
+            Lenient::Unchecked(address) => address,
+        }
+    }
+
+    pub fn valid(&self) -> Option<&A> {
+        match self {
+            Lenient::Valid(address) => Some(address),
+            Lenient::Unchecked(_) => None,
+        }
+    }
+}
+
+impl<A: FromStr> From<String> for Lenient<A> {
+    fn from(input: String) -> Self {
+        match input.parse() {
+            Ok(address) => Lenient::Valid(address),
+            Err(_) => Lenient::Unchecked(input),
+        }
+    }
+}
+
+impl<A: AsRef<str>> fmt::Display for Lenient<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<A: AsRef<str>> AsRef<str> for Lenient<A> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<A: AsRef<str>> Serialize for Lenient<A> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de, A: FromStr> Deserialize<'de> for Lenient<A> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::from)
+    }
+}
+
+fn parse_hex(input: &str, bytes: usize) -> Result<&str, String> {
+    let hex = input.strip_prefix("0x").ok_or("missing the 0x prefix")?;
+
+    if hex.len() != bytes * 2 || !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(format!("expected {bytes} hex encoded bytes"));
+    }
+
+    Ok(hex)
+}
+
+fn eip55(hex: &str) -> String {
+    use tiny_keccak::Hasher;
+
+    let lowercase = hex.to_ascii_lowercase();
+    let mut hash = [0u8; 32];
+    let mut keccak = tiny_keccak::Keccak::v256();
+    keccak.update(lowercase.as_bytes());
+    keccak.finalize(&mut hash);
+
+    let checksummed: String = lowercase
+        .chars()
+        .enumerate()
+        .map(|(i, char)| {
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+
+            if nibble >= 8 {
+                char.to_ascii_uppercase()
+            } else {
+                char
+            }
+        })
+        .collect();
+
+    format!("0x{checksummed}")
+}
+
+fn parse_eth(input: &str) -> Result<String, String> {
+    let hex = parse_hex(input, 20)?;
+    let checksummed = eip55(hex);
+
+    let mixed_case = hex.bytes().any(|byte| byte.is_ascii_lowercase())
+        && hex.bytes().any(|byte| byte.is_ascii_uppercase());
+
+    if mixed_case && checksummed[2..] != *hex {
+        return Err("wrong EIP-55 checksum".to_owned());
+    }
+
+    Ok(checksummed)
+}
+
+fn parse_bls(input: &str) -> Result<String, String> {
+    parse_hex(input, 48).map(|hex| format!("0x{}", hex.to_ascii_lowercase()))
+}
+
+fn parse_bech32(input: &str) -> Result<(String, Vec<u8>), String> {
+    let (hrp, data) = bech32::decode(input).map_err(|error| error.to_string())?;
+
+    Ok((hrp.to_lowercase(), data))
+}
+
+fn parse_cosmos(input: &str) -> Result<String, String> {
+    let (hrp, data) = parse_bech32(input)?;
+    let prefix = hrp.strip_suffix("valoper").unwrap_or(&hrp);
+
+    if !COSMOS_PREFIXES.contains(&prefix) {
+        return Err(format!("unknown prefix `{hrp}`"));
+    }
+
+    if data.len() != 20 && data.len() != 32 {
+        return Err(format!("expected 20 or 32 bytes, got {}", data.len()));
+    }
+
+    Ok(input.to_ascii_lowercase())
+}
+
+fn parse_cardano(input: &str) -> Result<String, String> {
+    let (hrp, _) = parse_bech32(input)?;
+
+    if !["addr", "addr_test", "stake", "stake_test"].contains(&hrp.as_str()) {
+        return Err(format!("unknown prefix `{hrp}`"));
+    }
+
+    Ok(input.to_ascii_lowercase())
+}
+
+fn parse_erd(input: &str) -> Result<String, String> {
+    let (hrp, data) = parse_bech32(input)?;
+
+    if hrp != "erd" {
+        return Err(format!("unknown prefix `{hrp}`"));
+    }
+
+    if data.len() != 32 {
+        return Err(format!("expected 32 bytes, got {}", data.len()));
+    }
+
+    Ok(input.to_ascii_lowercase())
+}
+
+fn parse_solana(input: &str) -> Result<String, String> {
+    let data = bs58::decode(input)
+        .into_vec()
+        .map_err(|error| error.to_string())?;
+
+    if data.len() != 32 {
+        return Err(format!("expected 32 bytes, got {}", data.len()));
+    }
+
+    Ok(input.to_owned())
+}
+
+// (network, prefix length)
+fn ss58_prefix(data: &[u8]) -> Option<(u16, usize)> {
+    match *data.first()? {
+        first @ 0..=63 => Some((first.into(), 1)),
+        first @ 64..=127 => {
+            let second = *data.get(1)?;
+            let lower = (first << 2) | (second >> 6);
+            let upper = second & 0b0011_1111;
+
+            Some((u16::from(lower) | (u16::from(upper) << 8), 2))
+        }
+        _ => None,
+    }
+}
+
+fn parse_ss58(input: &str) -> Result<String, String> {
+    let data = bs58::decode(input)
+        .into_vec()
+        .map_err(|error| error.to_string())?;
+
+    let (_, prefix_len) = ss58_prefix(&data).ok_or("unknown network prefix")?;
+
+    // 32 bytes public key, 33 bytes for ecdsa, then 2 bytes of checksum
+    if data.len() != prefix_len + 34 && data.len() != prefix_len + 35 {
+        return Err(format!("unexpected length of {} bytes", data.len()));
+    }
+
+    let (payload, checksum) = data.split_at(data.len() - 2);
+    let hash = blake2b_simd::State::new()
+        .update(b"SS58PRE")
+        .update(payload)
+        .finalize();
+
+    if hash.as_bytes()[..2] != *checksum {
+        return Err("wrong checksum".to_owned());
+    }
+
+    Ok(input.to_owned())
+}
+
+fn parse_tezos(input: &str) -> Result<String, String> {
+    let data = bs58::decode(input)
+        .with_check(None)
+        .into_vec()
+        .map_err(|error| error.to_string())?;
+
+    // tz1, tz2, tz3, KT1
+    let prefixes: [[u8; 3]; 4] = [[6, 161, 159], [6, 161, 161], [6, 161, 164], [2, 90, 121]];
+
+    if data.len() != 23 || !prefixes.iter().any(|prefix| data.starts_with(prefix)) {
+        return Err("expected a tz1, tz2, tz3 or KT1 address".to_owned());
+    }
+
+    Ok(input.to_owned())
+}
+
+fn parse_near(input: &str) -> Result<String, String> {
+    if !(2..=64).contains(&input.len()) {
+        return Err("expected 2 to 64 characters".to_owned());
+    }
+
+    let separator = |byte: u8| matches!(byte, b'-' | b'_' | b'.');
+
+    let valid = input
+        .bytes()
+        .all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit() || separator(byte))
+        && input
+            .bytes()
+            .zip(input.bytes().skip(1))
+            .all(|(previous, byte)| !(separator(previous) && separator(byte)))
+        && !input.starts_with(|char: char| separator(char as u8))
+        && !input.ends_with(|char: char| separator(char as u8));
+
+    if !valid {
+        return Err(
+            "expected lowercase alphanumeric parts separated by `.`, `-` or `_`".to_owned(),
+        );
+    }
+
+    Ok(input.to_owned())
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod address_test {
+
+    use super::*;
+
+    #[test]
+    fn ethereum_addresses_are_checksummed() {
+        let checksummed = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+
+        let address: EthAddress = checksummed.to_lowercase().parse().unwrap();
+        assert_eq!(address.as_str(), checksummed);
+
+        assert!(checksummed.parse::<EthAddress>().is_ok());
+        assert!("0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+            .parse::<EthAddress>()
+            .is_err());
+        assert!("5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+            .parse::<EthAddress>()
+            .is_err());
+        assert!(format!("0x{}", "ab".repeat(48))
+            .parse::<BlsPubkey>()
+            .is_ok());
+    }
+
+    #[test]
+    fn cosmos_addresses_have_a_known_prefix() {
+        let validator: CosmosAddress = "cosmosvaloper1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc56kct20"
+            .parse()
+            .unwrap();
+
+        assert_eq!(validator.prefix(), "cosmos");
+        assert!(validator.is_validator());
+
+        let osmo = "osmo1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5helwsw";
+        assert!(CosmosAddress::parse_with_prefix(osmo, "osmo").is_ok());
+        assert!(CosmosAddress::parse_with_prefix(osmo, "cosmos").is_err());
+
+        // the chain and the role : an osmosis account isn't a cosmos one, nor a validator
+        let osmo: CosmosAddress = osmo.parse().unwrap();
+        assert!(osmo.check_account("osmo").is_ok());
+        assert!(osmo.check_account("cosmos").is_err());
+        assert!(osmo.check_validator("osmo").is_err());
+        assert!(validator.check_validator("cosmos").is_ok());
+        assert!(validator.check_account("cosmos").is_err());
+        assert!(validator.check_validator("dydx").is_err());
+
+        // bad checksum, unknown chain
+        assert!("cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xv"
+            .parse::<CosmosAddress>()
+            .is_err());
+        assert!(
+            "erd1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5z5tpwxqergd3c8g7rusq4707q5"
+                .parse::<CosmosAddress>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn other_chains() {
+        assert!(
+            "erd1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5z5tpwxqergd3c8g7rusq4707q5"
+                .parse::<ErdAddress>()
+                .is_ok()
+        );
+        assert!(
+            "stake1uyqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydpk8qpxs5mw"
+                .parse::<CardanoAddress>()
+                .is_ok()
+        );
+        assert!("4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw"
+            .parse::<SolanaPubkey>()
+            .is_ok());
+        assert!("4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vig"
+            .parse::<SolanaPubkey>()
+            .is_err());
+        assert!("tz1KjMn6Hb23eu1rNemou6ytAzzNxzvaYHyK"
+            .parse::<TezosAddress>()
+            .is_ok());
+        assert!("tz1KjMn6Hb23eu1rNemou6ytAzzNxzvaYHyJ"
+            .parse::<TezosAddress>()
+            .is_err());
+        assert!("kiln.poolv1.near".parse::<NearAccountId>().is_ok());
+        assert!("Kiln..near".parse::<NearAccountId>().is_err());
+    }
+
+    #[test]
+    fn ss58_addresses_know_their_network() {
+        let polkadot: Ss58Address = "12KeSVQBwS9AjRA976mnJouSAoQuS5bkWudT367GBEHE8Ls"
+            .parse()
+            .unwrap();
+        let kusama: Ss58Address = "CbeARaCxXBbUrE5xArpY7Lkj9611oLe8Q1tgQNiBtRFnrrh"
+            .parse()
+            .unwrap();
+
+        assert_eq!(polkadot.network(), 0);
+        assert_eq!(kusama.network(), 2);
+        assert!(polkadot.check_network(0).is_ok());
+        assert!(kusama.check_network(0).is_err());
+        assert!(polkadot.check_network(2).is_err());
+        assert!("12KeSVQBwS9AjRA976mnJouSAoQuS5bkWudT367GBEHE8Lt"
+            .parse::<Ss58Address>()
+            .is_err());
+    }
+
+    #[test]
+    fn serde_rejects_invalid_addresses() {
+        let wallet: EthAddress =
+            serde_json::from_str("\"0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed\"").unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&wallet).unwrap(),
+            "\"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed\""
+        );
+        assert!(serde_json::from_str::<EthAddress>("\"0x1234\"").is_err());
+    }
+
+    #[test]
+    fn response_addresses_are_lenient() {
+        let owner: Lenient<EthAddress> =
+            serde_json::from_str("\"0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed\"").unwrap();
+
+        assert_eq!(
+            owner.valid().map(EthAddress::as_str),
+            Some("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed")
+        );
+
+        let owner: Lenient<EthAddress> = serde_json::from_str("\"0x1234\"").unwrap();
+
+        assert_eq!(owner, Lenient::Unchecked("0x1234".into()));
+        assert_eq!(serde_json::to_string(&owner).unwrap(), "\"0x1234\"");
+    }
+}
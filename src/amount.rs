@@ -0,0 +1,77 @@
+// Monetary values : the api encoding by default, an exact `rust_decimal::Decimal` with the `decimal` feature.
+// With the feature, a value is read from a json string ("12.5") as well as a json number (12.5).
+// Integers up to u64::MAX and decimals up to 17 significant digits are exact as json numbers,
+// anything bigger is rounded by the json parser before it gets here : Kiln sends those as strings.
+
+// token amounts the api sends as strings (wei, lamports, uatom...)
+#[cfg(not(feature = "decimal"))]
+pub type Amount = String;
+#[cfg(feature = "decimal")]
+pub type Amount = rust_decimal::Decimal;
+
+// token amounts the api sends as integers
+#[cfg(not(feature = "decimal"))]
+pub type IntAmount = u64;
+#[cfg(feature = "decimal")]
+pub type IntAmount = rust_decimal::Decimal;
+
+// usd values and prices
+#[cfg(not(feature = "decimal"))]
+pub type FloatAmount = f64;
+#[cfg(feature = "decimal")]
+pub type FloatAmount = rust_decimal::Decimal;
+
+#[cfg(all(test, feature = "decimal", feature = "blocking"))]
+mod amount_test {
+
+    use std::str::FromStr;
+
+    use rust_decimal::Decimal;
+
+    use crate::sdk::{Eigenlayer, Reward};
+
+    fn decimal(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    #[test]
+    fn strings_and_numbers_are_exact() {
+        let reward: Reward = serde_json::from_str(
+            r#"{
+                "date": "2024-01-01T00:00:00Z",
+                "rewards": "123456789012345678901234",
+                "active_balance": "32000000000000000000",
+                "net_apy": 3.1,
+                "rewards_usd": "0.1",
+                "stake_balance_usd": "1.10",
+                "rewards_balance_usd": 0.1,
+                "active_balance_usd": 12345.678
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(reward.rewards, decimal("123456789012345678901234"));
+        assert_eq!(reward.active_balance, decimal("32000000000000000000"));
+        assert_eq!(reward.rewards_usd, decimal("0.1"));
+        assert_eq!(reward.stake_balance_usd.to_string(), "1.10");
+        assert_eq!(reward.rewards_balance_usd, Some(decimal("0.1")));
+        assert_eq!(reward.active_balance_usd, Some(decimal("12345.678")));
+    }
+
+    #[test]
+    fn integers_are_exact() {
+        let eigenlayer: Eigenlayer = serde_json::from_str(
+            r#"{
+                "restaked": 1234.56,
+                "total_restaked": 18446744073709551615,
+                "total_is_restakable": 1,
+                "total": 2,
+                "eigenpods": []
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(eigenlayer.total_restaked, Decimal::from(u64::MAX));
+        assert_eq!(eigenlayer.restaked, decimal("1234.56"));
+    }
+}
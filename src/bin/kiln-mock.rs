@@ -0,0 +1,82 @@
+// A local Kiln API serving the json fixtures, for the tests of the applications built on the SDK :
+// cargo run --features mock --bin kiln-mock -- --port 8080 --token kiln_test_token
+use std::process::ExitCode;
+use std::time::Duration;
+
+use kilnfi_connect_rs::mock::{Fault, MockServer};
+
+const USAGE: &str = "\
+usage: kiln-mock [options]
+
+  --port <port>            port to listen on, 127.0.0.1 only (default: a free one)
+  --fixtures <dir>         fixtures directory, laid out as <module>/<endpoint>.json
+  --token <token>          answer 401 to the requests without this bearer token
+  --fault <status>[@path]  answer with this status, the requests under `path` only when given
+  --delay <ms>[@path]      answer that many milliseconds late";
+
+fn main() -> ExitCode {
+    match run(std::env::args().skip(1).collect()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("kiln-mock: {error}\n\n{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), String> {
+    let mut builder = MockServer::builder();
+    let mut faults: Vec<Fault> = Vec::new();
+
+    let mut args = args.into_iter();
+
+    while let Some(flag) = args.next() {
+        if flag == "--help" || flag == "-h" {
+            println!("{USAGE}");
+            return Ok(());
+        }
+
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for `{flag}`"))?;
+
+        let invalid = |_| format!("invalid value `{value}` for `{flag}`");
+
+        match flag.as_str() {
+            "--port" => builder = builder.port(value.parse().map_err(invalid)?),
+            "--fixtures" => builder = builder.fixtures(&value),
+            "--token" => builder = builder.api_token(&value),
+            "--fault" | "--delay" => {
+                let (amount, path) = match value.split_once('@') {
+                    Some((amount, path)) => (amount, Some(path)),
+                    None => (value.as_str(), None),
+                };
+
+                let fault = if flag == "--fault" {
+                    Fault::status(amount.parse().map_err(invalid)?)
+                } else {
+                    Fault::delay(Duration::from_millis(amount.parse().map_err(invalid)?))
+                };
+
+                faults.push(match path {
+                    Some(path) => fault.path(path),
+                    None => fault,
+                });
+            }
+            _ => return Err(format!("unknown option `{flag}`")),
+        }
+    }
+
+    let server = builder.start().map_err(|error| error.to_string())?;
+
+    for fault in faults {
+        server.inject(fault);
+    }
+
+    println!("Kiln mock API listening on {}", server.url());
+
+    // serves until killed
+    loop {
+        std::thread::park();
+    }
+}
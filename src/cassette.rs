@@ -0,0 +1,515 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "async")]
+use crate::transport::AsyncTransport;
+use crate::transport::{HttpRequest, HttpResponse, Transport, TransportError};
+
+// Record and replay : `CassetteTransport::record` sends through another Transport and writes every
+// request with its response to a json cassette file, the `Authorization` header scrubbed.
+// `CassetteTransport::replay` answers from that file without the network, for deterministic
+// tests on traffic captured once.
+// A Transport of a `Kiln` and an AsyncTransport of an `AsyncKiln` : a replay works for both,
+// a recording for the one its transport is.
+#[derive(Debug)]
+pub struct CassetteTransport {
+    path: PathBuf,
+    mode: Mode,
+    cassette: Mutex<Cassette>,
+    // left out of the match on replay
+    ignored_query_params: Vec<String>,
+}
+
+#[derive(Debug)]
+enum Mode {
+    Record(Arc<dyn Transport>),
+    #[cfg(feature = "async")]
+    RecordAsync(Arc<dyn AsyncTransport>),
+    // how many times each interaction was replayed
+    Replay(Mutex<Vec<usize>>),
+}
+
+// what the `Authorization` header is recorded as
+pub const SCRUBBED: &str = "[scrubbed]";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Cassette {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RecordedRequest {
+    method: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Option<Body>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Body,
+}
+
+// the exact bytes : a string when they're utf-8 (json bodies), an array of bytes otherwise (reports)
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+enum Body {
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+impl From<Vec<u8>> for Body {
+    fn from(bytes: Vec<u8>) -> Self {
+        match String::from_utf8(bytes) {
+            Ok(text) => Body::Text(text),
+            Err(error) => Body::Bytes(error.into_bytes()),
+        }
+    }
+}
+
+impl From<&Body> for Vec<u8> {
+    fn from(body: &Body) -> Self {
+        match body {
+            Body::Text(text) => text.as_bytes().to_vec(),
+            Body::Bytes(bytes) => bytes.clone(),
+        }
+    }
+}
+
+impl CassetteTransport {
+    // starts an empty cassette, the file is written again after every interaction
+    pub fn record(path: impl Into<PathBuf>, transport: impl Transport + 'static) -> Self {
+        Self {
+            path: path.into(),
+            mode: Mode::Record(Arc::new(transport)),
+            cassette: Mutex::new(Cassette::default()),
+            ignored_query_params: Vec::new(),
+        }
+    }
+
+    // `record`, sending through the AsyncTransport of an `AsyncKiln`
+    #[cfg(feature = "async")]
+    pub fn record_async(
+        path: impl Into<PathBuf>,
+        transport: impl AsyncTransport + 'static,
+    ) -> Self {
+        Self {
+            path: path.into(),
+            mode: Mode::RecordAsync(Arc::new(transport)),
+            cassette: Mutex::new(Cassette::default()),
+            ignored_query_params: Vec::new(),
+        }
+    }
+
+    // A request is answered by the first recorded interaction with the same method, path, query
+    // (but the ignored parameters) and body that wasn't replayed yet (the last one once they all
+    // were), whatever the host.
+    // One missing from the cassette is a `KilnError::Transport`.
+    pub fn replay(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let cassette: Cassette = serde_json::from_slice(&std::fs::read(&path)?)?;
+
+        Ok(Self {
+            mode: Mode::Replay(Mutex::new(vec![0; cassette.interactions.len()])),
+            path,
+            cassette: Mutex::new(cassette),
+            ignored_query_params: Vec::new(),
+        })
+    }
+
+    // Query parameters whose value changes from one run to the next, like an `end_date` set to
+    // `Utc::now()` : `.ignore_query_params(["end_date"])`.
+    // The request matches whatever their value, the others still have to be the same.
+    pub fn ignore_query_params(self, params: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            ignored_query_params: params.into_iter().map(Into::into).collect(),
+            ..self
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn record_interaction(
+        &self,
+        request: HttpRequest,
+        response: &HttpResponse,
+    ) -> Result<(), TransportError> {
+        let headers = request
+            .headers
+            .into_iter()
+            .map(|(key, value)| {
+                if key.eq_ignore_ascii_case("authorization") {
+                    (key, SCRUBBED.to_owned())
+                } else {
+                    (key, value)
+                }
+            })
+            .collect();
+
+        let interaction = Interaction {
+            request: RecordedRequest {
+                method: request.method.as_ref().to_owned(),
+                url: request.url,
+                headers,
+                body: request.body.map(Body::from),
+            },
+            response: RecordedResponse {
+                status: response.status,
+                headers: response.headers.clone(),
+                body: Body::from(response.body.clone()),
+            },
+        };
+
+        let mut cassette = self
+            .cassette
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        cassette.interactions.push(interaction);
+
+        // a blocking write, even with the `async` feature : the cassette is only used by tests
+        std::fs::write(&self.path, serde_json::to_vec_pretty(&*cassette)?)?;
+
+        Ok(())
+    }
+
+    // the target without the ignored query parameters
+    fn matched_target(&self, url: &str) -> String {
+        let target = target(url);
+
+        let Some((path, query)) = target.split_once('?') else {
+            return target.to_owned();
+        };
+
+        let query: Vec<&str> = query
+            .split('&')
+            .filter(|param| {
+                let name = param.split('=').next().unwrap_or(param);
+
+                !self
+                    .ignored_query_params
+                    .iter()
+                    .any(|ignored| ignored == name)
+            })
+            .collect();
+
+        if query.is_empty() {
+            path.to_owned()
+        } else {
+            format!("{path}?{}", query.join("&"))
+        }
+    }
+
+    fn replay_interaction(
+        &self,
+        request: &HttpRequest,
+        replayed: &Mutex<Vec<usize>>,
+    ) -> Result<HttpResponse, TransportError> {
+        let cassette = self
+            .cassette
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let mut replayed = replayed
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let body = request.body.clone().map(Body::from);
+        let request_target = self.matched_target(&request.url);
+
+        let matching: Vec<usize> = cassette
+            .interactions
+            .iter()
+            .enumerate()
+            .filter(|(_, interaction)| {
+                interaction.request.method == request.method.as_ref()
+                    && self.matched_target(&interaction.request.url) == request_target
+                    && interaction.request.body == body
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        let index = matching
+            .iter()
+            .find(|index| replayed[**index] == 0)
+            .or(matching.last())
+            .copied()
+            .ok_or_else(|| {
+                format!(
+                    "no interaction recorded in {} for {} {}",
+                    self.path.display(),
+                    request.method.as_ref(),
+                    request_target
+                )
+            })?;
+
+        replayed[index] += 1;
+
+        let response = &cassette.interactions[index].response;
+
+        Ok(HttpResponse {
+            status: response.status,
+            headers: response.headers.clone(),
+            body: Vec::from(&response.body),
+        })
+    }
+}
+
+impl Transport for CassetteTransport {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse, TransportError> {
+        match &self.mode {
+            Mode::Record(transport) => {
+                // no answer, nothing to replay : the error is not recorded
+                let response = transport.send(request.clone())?;
+
+                self.record_interaction(request, &response)?;
+
+                Ok(response)
+            }
+            #[cfg(feature = "async")]
+            Mode::RecordAsync(_) => {
+                Err("a cassette recording through an AsyncTransport is sent by an AsyncKiln".into())
+            }
+            Mode::Replay(replayed) => self.replay_interaction(&request, replayed),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl AsyncTransport for CassetteTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, TransportError> {
+        match &self.mode {
+            Mode::RecordAsync(transport) => {
+                // no answer, nothing to replay : the error is not recorded
+                let response = transport.send(request.clone()).await?;
+
+                self.record_interaction(request, &response)?;
+
+                Ok(response)
+            }
+            Mode::Record(_) => {
+                Err("a cassette recording through a Transport is sent by a Kiln".into())
+            }
+            Mode::Replay(replayed) => self.replay_interaction(&request, replayed),
+        }
+    }
+}
+
+// "/v1/eth/stakes?wallets=0x..." for "https://api.kiln.fi/v1/eth/stakes?wallets=0x..."
+fn target(url: &str) -> &str {
+    let without_scheme = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+
+    without_scheme
+        .find('/')
+        .map(|index| &without_scheme[index..])
+        .unwrap_or("/")
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod cassette_test {
+
+    use super::*;
+    use crate::mock::{Fault, MockServer};
+    use crate::transport::UreqTransport;
+    use crate::{Kiln, KilnError};
+    use chrono::Utc;
+
+    fn cassette_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("kiln_cassette_{name}_{}.json", std::process::id()))
+    }
+
+    fn kiln_with(base_url: &str, transport: CassetteTransport) -> Kiln {
+        Kiln::builder()
+            .api_token("kiln_test_token")
+            .base_url(base_url)
+            .transport(transport)
+            .seal()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn records_then_replays_without_the_network() {
+        let path = cassette_path("replay");
+
+        let recorded = {
+            let server = MockServer::start().unwrap();
+            let kiln = kiln_with(
+                &server.url(),
+                CassetteTransport::record(&path, UreqTransport::default()),
+            );
+
+            let stakes = kiln.ethereum().stakes().wallets(["0x0"]).send().unwrap();
+            let account = kiln.accounts().post("Tanjiro", "Kamado").unwrap();
+
+            (stakes, account)
+        };
+
+        let cassette = std::fs::read_to_string(&path).unwrap();
+
+        assert!(!cassette.contains("kiln_test_token"));
+        assert!(cassette.contains(SCRUBBED));
+
+        // the mock server is gone, and the host doesn't matter
+        let kiln = kiln_with(
+            "https://api.kiln.fi/v1",
+            CassetteTransport::replay(&path).unwrap(),
+        );
+
+        let stakes = kiln.ethereum().stakes().wallets(["0x0"]).send().unwrap();
+        let account = kiln.accounts().post("Tanjiro", "Kamado").unwrap();
+
+        assert_eq!(stakes.data.len(), recorded.0.data.len());
+        assert_eq!(account.data.id, recorded.1.data.id);
+
+        // another body is another request
+        let error = kiln.accounts().post("Nezuko", "Kamado").unwrap_err();
+
+        assert!(matches!(error, KilnError::Transport(_)));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn identical_requests_are_replayed_in_order() {
+        let path = cassette_path("order");
+
+        {
+            let server = MockServer::start().unwrap();
+
+            server.inject(Fault::status(500).times(1));
+
+            let kiln = kiln_with(
+                &server.url(),
+                CassetteTransport::record(&path, UreqTransport::default()),
+            );
+
+            assert!(kiln.deployments().get().is_err());
+            assert!(kiln.deployments().get().is_ok());
+        }
+
+        let kiln = kiln_with(
+            "https://api.kiln.fi/v1",
+            CassetteTransport::replay(&path).unwrap(),
+        );
+
+        assert_eq!(kiln.deployments().get().unwrap_err().status(), Some(500));
+        assert!(kiln.deployments().get().is_ok());
+        // the last one again
+        assert!(kiln.deployments().get().is_ok());
+
+        assert!(matches!(
+            kiln.solana().get_network_stats().unwrap_err(),
+            KilnError::Transport(_)
+        ));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn ignored_query_params_match_whatever_their_value() {
+        let path = cassette_path("ignored");
+        let since = "2024-01-01T00:00:00Z".parse().unwrap();
+
+        // `until` now, to the nanosecond
+        {
+            let server = MockServer::start().unwrap();
+            let kiln = kiln_with(
+                &server.url(),
+                CassetteTransport::record(&path, UreqTransport::default()),
+            );
+
+            kiln.cosmos()
+                .rewards()
+                .since(since)
+                .until(Utc::now())
+                .send()
+                .unwrap();
+            kiln.cosmos()
+                .operations()
+                .validators(["cosmosvaloper1a"])
+                .since(since)
+                .until(Utc::now())
+                .send()
+                .unwrap();
+        }
+
+        let kiln = kiln_with(
+            "https://api.kiln.fi/v1",
+            CassetteTransport::replay(&path).unwrap(),
+        );
+
+        // another now
+        assert!(matches!(
+            kiln.cosmos()
+                .rewards()
+                .since(since)
+                .until(Utc::now())
+                .send()
+                .unwrap_err(),
+            KilnError::Transport(_)
+        ));
+
+        let kiln = kiln_with(
+            "https://api.kiln.fi/v1",
+            CassetteTransport::replay(&path)
+                .unwrap()
+                .ignore_query_params(["end_date"]),
+        );
+
+        kiln.cosmos()
+            .rewards()
+            .since(since)
+            .until(Utc::now())
+            .send()
+            .unwrap();
+        kiln.cosmos()
+            .operations()
+            .validators(["cosmosvaloper1a"])
+            .since(since)
+            .send()
+            .unwrap();
+
+        // the other parameters still count
+        assert!(matches!(
+            kiln.cosmos()
+                .operations()
+                .validators(["cosmosvaloper1b"])
+                .since(since)
+                .until(Utc::now())
+                .send()
+                .unwrap_err(),
+            KilnError::Transport(_)
+        ));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn binary_bodies_round_trip() {
+        let bytes = vec![0x50, 0x4b, 0x03, 0x04, 0xff];
+
+        let json = serde_json::to_string(&Body::from(bytes.clone())).unwrap();
+        let body: Body = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(Vec::from(&body), bytes);
+        assert_eq!(
+            serde_json::to_string(&Body::from(b"{}".to_vec())).unwrap(),
+            r#""{}""#
+        );
+    }
+}
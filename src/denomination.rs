@@ -0,0 +1,315 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// Typed amounts : a base unit (Wei, Lamports, UAtom...) is what the Kiln API takes,
+// a display unit (Eth, Sol, Dot...) is the same amount written with decimals.
+// Both hold an integer number of base units, so every conversion is lossless.
+pub trait Denomination: Copy + FromStr<Err = ParseAmountError> + fmt::Display {
+    // "wei", "ETH", "uatom"... the chain denom for the base units
+    const SYMBOL: &'static str;
+    // one unit is 10^DECIMALS base units
+    const DECIMALS: u32;
+
+    fn from_base_units(base_units: u128) -> Self;
+
+    fn base_units(self) -> u128;
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("invalid {symbol} amount `{input}`: {reason}")]
+pub struct ParseAmountError {
+    symbol: &'static str,
+    input: String,
+    reason: &'static str,
+}
+
+fn parse_units(symbol: &'static str, input: &str, decimals: u32) -> Result<u128, ParseAmountError> {
+    let error = |reason| ParseAmountError {
+        symbol,
+        input: input.to_owned(),
+        reason,
+    };
+
+    let (integer, fraction) = input.split_once('.').unwrap_or((input, ""));
+    // "1.500" is 1.5 : trailing zeros never lose precision
+    let fraction = fraction.trim_end_matches('0');
+
+    if integer.is_empty() || !integer.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(error("expected an unsigned decimal number"));
+    }
+
+    if !fraction.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(error("expected an unsigned decimal number"));
+    }
+
+    let fraction_digits = u32::try_from(fraction.len()).unwrap_or(u32::MAX);
+
+    if fraction_digits > decimals {
+        return Err(error("more decimal places than the denomination has"));
+    }
+
+    let overflow = || error("amount too large");
+
+    let integer: u128 = integer.parse().map_err(|_| overflow())?;
+    let fraction: u128 = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse().map_err(|_| overflow())?
+    };
+
+    integer
+        .checked_mul(10u128.pow(decimals))
+        .and_then(|units| units.checked_add(fraction * 10u128.pow(decimals - fraction_digits)))
+        .ok_or_else(overflow)
+}
+
+fn format_units(f: &mut fmt::Formatter<'_>, base_units: u128, decimals: u32) -> fmt::Result {
+    let one = 10u128.pow(decimals);
+    let (integer, fraction) = (base_units / one, base_units % one);
+
+    if fraction == 0 {
+        return write!(f, "{integer}");
+    }
+
+    let fraction = format!("{fraction:0width$}", width = decimals as usize);
+
+    write!(f, "{integer}.{}", fraction.trim_end_matches('0'))
+}
+
+// what the API takes : serialized as a json string of base units, read from a string or an integer
+macro_rules! base_unit {
+    ($name:ident, $symbol:literal) => {
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(u128);
+
+        impl $name {
+            pub const fn new(base_units: u128) -> Self {
+                Self(base_units)
+            }
+
+            pub const fn get(self) -> u128 {
+                self.0
+            }
+
+            pub fn checked_add(self, other: Self) -> Option<Self> {
+                self.0.checked_add(other.0).map(Self)
+            }
+
+            pub fn checked_sub(self, other: Self) -> Option<Self> {
+                self.0.checked_sub(other.0).map(Self)
+            }
+        }
+
+        impl Denomination for $name {
+            const SYMBOL: &'static str = $symbol;
+            const DECIMALS: u32 = 0;
+
+            fn from_base_units(base_units: u128) -> Self {
+                Self(base_units)
+            }
+
+            fn base_units(self) -> u128 {
+                self.0
+            }
+        }
+
+        impl From<u128> for $name {
+            fn from(base_units: u128) -> Self {
+                Self(base_units)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseAmountError;
+
+            fn from_str(input: &str) -> Result<Self, Self::Err> {
+                parse_units($symbol, input, 0).map(Self)
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer
+                    .deserialize_any(BaseUnitsVisitor($symbol))
+                    .map(Self)
+            }
+        }
+    };
+}
+
+// the same base units, written with decimals : `"1.5".parse::<Eth>()`, `Wei::from(eth)`
+macro_rules! display_unit {
+    ($name:ident, $symbol:literal, $base:ident, $decimals:literal) => {
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(u128);
+
+        impl Denomination for $name {
+            const SYMBOL: &'static str = $symbol;
+            const DECIMALS: u32 = $decimals;
+
+            fn from_base_units(base_units: u128) -> Self {
+                Self(base_units)
+            }
+
+            fn base_units(self) -> u128 {
+                self.0
+            }
+        }
+
+        impl From<$base> for $name {
+            fn from(base: $base) -> Self {
+                Self(base.get())
+            }
+        }
+
+        impl From<$name> for $base {
+            fn from(amount: $name) -> Self {
+                $base::new(amount.0)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                format_units(f, self.0, $decimals)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseAmountError;
+
+            fn from_str(input: &str) -> Result<Self, Self::Err> {
+                parse_units($symbol, input, $decimals).map(Self)
+            }
+        }
+    };
+}
+
+struct BaseUnitsVisitor(&'static str);
+
+impl Visitor<'_> for BaseUnitsVisitor {
+    type Value = u128;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "an integer amount of {}", self.0)
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<u128, E> {
+        Ok(value.into())
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<u128, E> {
+        parse_units(self.0, value, 0).map_err(E::custom)
+    }
+}
+
+// ethereum, polygon
+base_unit!(Wei, "wei");
+display_unit!(Gwei, "gwei", Wei, 9);
+display_unit!(Eth, "ETH", Wei, 18);
+
+// solana
+base_unit!(Lamports, "lamports");
+display_unit!(Sol, "SOL", Lamports, 9);
+
+// polkadot, kusama
+base_unit!(Planck, "planck");
+display_unit!(Dot, "DOT", Planck, 10);
+display_unit!(Ksm, "KSM", Planck, 12);
+
+// tezos
+base_unit!(Mutez, "mutez");
+display_unit!(Tez, "XTZ", Mutez, 6);
+
+// cardano
+base_unit!(Lovelace, "lovelace");
+display_unit!(Ada, "ADA", Lovelace, 6);
+
+// near
+base_unit!(YoctoNear, "yoctonear");
+
+// cosmos based chains, the symbol is the chain denom
+base_unit!(UAtom, "uatom");
+base_unit!(UTia, "utia");
+base_unit!(Afet, "afet");
+base_unit!(Inj, "inj");
+base_unit!(Ukava, "ukava");
+base_unit!(Uosmo, "uosmo");
+base_unit!(Azeta, "azeta");
+base_unit!(Adydx, "adydx");
+base_unit!(Uusdc, "uusdc");
+
+#[cfg(all(test, feature = "blocking"))]
+mod denomination_test {
+
+    use super::*;
+
+    #[test]
+    fn display_units_parse_and_format_losslessly() {
+        let eth: Eth = "1.5".parse().unwrap();
+
+        assert_eq!(Wei::from(eth), Wei::new(1_500_000_000_000_000_000));
+        assert_eq!(eth.to_string(), "1.5");
+        assert_eq!(Eth::from(Wei::new(1)).to_string(), "0.000000000000000001");
+        assert_eq!(Gwei::from(Wei::from(eth)).to_string(), "1500000000");
+        assert_eq!("32.000".parse::<Dot>().unwrap().to_string(), "32");
+        assert_eq!(
+            Planck::from("1".parse::<Ksm>().unwrap()).get(),
+            1_000_000_000_000
+        );
+    }
+
+    #[test]
+    fn too_many_decimal_places_is_an_error() {
+        assert!("0.0000000001".parse::<Sol>().is_err());
+        assert!("1.5".parse::<Lamports>().is_err());
+        assert!("-1".parse::<Tez>().is_err());
+        assert!("".parse::<Ada>().is_err());
+        assert!("1e18".parse::<Wei>().is_err());
+        assert!("340282366920938463463374607431768211456"
+            .parse::<Wei>()
+            .is_err());
+    }
+
+    #[test]
+    fn base_units_are_json_strings() {
+        let wei: Wei = "32000000000000000000".parse().unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&wei).unwrap(),
+            "\"32000000000000000000\""
+        );
+        assert_eq!(
+            serde_json::from_str::<Wei>("\"32000000000000000000\"").unwrap(),
+            wei
+        );
+        assert_eq!(
+            serde_json::from_str::<UAtom>("1000").unwrap(),
+            UAtom::new(1000)
+        );
+        assert!(serde_json::from_str::<UAtom>("1000.5").is_err());
+    }
+
+    #[test]
+    fn coin_amount_checks_the_denom() {
+        let coin: crate::sdk::TxStakeCoin =
+            serde_json::from_str(r#"{"denom":"uatom","amount":"1000"}"#).unwrap();
+
+        assert_eq!(coin.amount_in::<UAtom>(), Some(UAtom::new(1000)));
+        assert_eq!(coin.amount_in::<Uosmo>(), None);
+    }
+}
@@ -0,0 +1,144 @@
+use strum_macros::AsRefStr;
+
+use crate::errors::KilnError;
+use crate::sdk::ChainID;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum KilnEnvironment {
+    Mainnet,
+    Testnet,
+    Devnet,
+}
+
+impl KilnEnvironment {
+    pub fn base_url(&self) -> &'static str {
+        match self {
+            KilnEnvironment::Mainnet => "https://api.kiln.fi/v1",
+            KilnEnvironment::Testnet => "https://api.testnet.kiln.fi/v1",
+            KilnEnvironment::Devnet => "https://api.devnet.kiln.fi/v1",
+        }
+    }
+
+    // None for anything that isn't a Kiln host : proxies, mock servers...
+    pub fn from_host(host: &str) -> Option<Self> {
+        match host {
+            "api.kiln.fi" => Some(KilnEnvironment::Mainnet),
+            "api.testnet.kiln.fi" => Some(KilnEnvironment::Testnet),
+            "api.devnet.kiln.fi" => Some(KilnEnvironment::Devnet),
+            _ => None,
+        }
+    }
+
+    // mainnet serves mainnet chains, testnet and devnet serve test chains (Holesky, Sepolia...),
+    // a network the SDK doesn't know could be either
+    pub fn supports(&self, chain_id: &ChainID) -> bool {
+        if let ChainID::Unknown(_) = chain_id {
+            return true;
+        }
+
+        match self {
+            KilnEnvironment::Mainnet => !chain_id.is_testnet(),
+            KilnEnvironment::Testnet | KilnEnvironment::Devnet => chain_id.is_testnet(),
+        }
+    }
+}
+
+// Parses and normalizes a base url : http(s) only, no query nor fragment, no trailing slash.
+pub(crate) fn normalize_base_url(
+    base_url: &str,
+) -> Result<(String, Option<KilnEnvironment>), KilnError> {
+    let invalid =
+        |reason: &str| KilnError::InvalidInput(format!("base url `{base_url}`: {reason}"));
+
+    let url = url::Url::parse(base_url.trim()).map_err(|error| invalid(&error.to_string()))?;
+
+    if !matches!(url.scheme(), "https" | "http") {
+        return Err(invalid("the scheme must be http or https"));
+    }
+
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err(invalid("a query string or a fragment can't be part of it"));
+    }
+
+    let environment = url.host_str().and_then(KilnEnvironment::from_host);
+
+    let normalized = url.as_str().trim_end_matches('/').to_owned();
+
+    Ok((normalized, environment))
+}
+
+// A transaction crafted for a chain the environment doesn't serve is most likely a configuration
+// mistake : a warning with the `tracing` feature, `KilnEnvironment::supports` tells the callers.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn warn_on_chain_mismatch(environment: Option<KilnEnvironment>, chain_id: &ChainID) {
+    #[cfg(feature = "tracing")]
+    if let Some(environment) = environment.filter(|environment| !environment.supports(chain_id)) {
+        tracing::warn!(
+            environment = environment.as_ref(),
+            chain_id = ?chain_id,
+            "chain id doesn't match the Kiln environment"
+        );
+    }
+}
+
+#[cfg(test)]
+mod environment_test {
+
+    use super::*;
+
+    #[test]
+    fn base_url_is_normalized() {
+        let (url, environment) = normalize_base_url("https://api.kiln.fi/v1/").unwrap();
+
+        assert_eq!(url, "https://api.kiln.fi/v1");
+        assert_eq!(environment, Some(KilnEnvironment::Mainnet));
+
+        let (url, environment) = normalize_base_url(" http://localhost:8080/v1// ").unwrap();
+
+        assert_eq!(url, "http://localhost:8080/v1");
+        assert_eq!(environment, None);
+    }
+
+    #[test]
+    fn invalid_base_urls_are_rejected() {
+        for base_url in [
+            "api.kiln.fi/v1",
+            "ftp://api.kiln.fi/v1",
+            "https://api.kiln.fi/v1?wallets=0x0",
+            "",
+        ] {
+            assert!(
+                matches!(
+                    normalize_base_url(base_url),
+                    Err(KilnError::InvalidInput(_))
+                ),
+                "{base_url} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn presets_round_trip() {
+        for environment in [
+            KilnEnvironment::Mainnet,
+            KilnEnvironment::Testnet,
+            KilnEnvironment::Devnet,
+        ] {
+            let (url, detected) = normalize_base_url(environment.base_url()).unwrap();
+
+            assert_eq!(url, environment.base_url());
+            assert_eq!(detected, Some(environment));
+        }
+    }
+
+    #[test]
+    fn test_chains_belong_to_testnet() {
+        assert!(KilnEnvironment::Testnet.supports(&ChainID::Holesky));
+        assert!(!KilnEnvironment::Mainnet.supports(&ChainID::Holesky));
+        assert!(KilnEnvironment::Mainnet.supports(&ChainID::EthereumMainnet));
+        assert!(!KilnEnvironment::Devnet.supports(&ChainID::EthereumMainnet));
+        assert!(KilnEnvironment::Testnet.supports(&ChainID::Hoodi));
+        assert!(KilnEnvironment::Mainnet.supports(&ChainID::Unknown(101)));
+    }
+}
@@ -1,11 +1,178 @@
+use std::time::Duration;
+
+use crate::retry::parse_retry_after;
+use crate::token::TokenError;
+use crate::transport::{HttpResponse, TransportError};
+
 #[derive(thiserror::Error, Debug)]
-pub enum Error {
-    #[error("Generic error: {0}")]
-    Generic(String),
+#[non_exhaustive]
+pub enum KilnError {
+    // the request never got an answer : dns, tls, connection reset, timeout...
+    #[error("Transport error: {0}")]
+    Transport(#[source] TransportError),
 
-    #[error("Static error: {0}")]
-    Static(&'static str),
+    // the TokenProvider could not give a token, nothing was sent
+    #[error("Token provider error: {0}")]
+    Token(#[source] TokenError),
+
+    // the Kiln API answered with a non-2xx status
+    #[error(transparent)]
+    Api(#[from] ApiError),
+
+    // the Kiln API answered with a 2xx status but the payload doesn't match the model
+    #[error("Deserialization error at `{path}`: {source}")]
+    Deserialize {
+        path: String,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("Serialization error: {0}")]
+    Serialize(#[source] serde_json::Error),
+
+    // rejected by the SDK before any request is sent
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
 
     #[error(transparent)]
     IO(#[from] std::io::Error),
 }
+
+impl KilnError {
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            KilnError::Api(api_error) => Some(api_error.status),
+            _ => None,
+        }
+    }
+
+    // "retry later" : the same request may succeed if sent again
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            KilnError::Transport(_) | KilnError::IO(_) => true,
+            KilnError::Api(api_error) => api_error.is_retryable(),
+            _ => false,
+        }
+    }
+
+    // "your request is wrong" : sending it again won't help
+    pub fn is_client_error(&self) -> bool {
+        match self {
+            KilnError::InvalidInput(_) | KilnError::Serialize(_) => true,
+            KilnError::Api(api_error) => {
+                (400..500).contains(&api_error.status) && !api_error.is_retryable()
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug, Clone)]
+#[error("Kiln API error {status}: {}", message.as_deref().unwrap_or(body))]
+pub struct ApiError {
+    pub status: u16,
+    // "message" field of the Kiln json error body
+    pub message: Option<String>,
+    // validation details of a 422 (or any other structured details), kept as raw json
+    pub details: Option<serde_json::Value>,
+    // raw response body
+    pub body: String,
+    // Retry-After header of 429/503 responses
+    pub retry_after: Option<Duration>,
+}
+
+impl ApiError {
+    pub(crate) fn from_response(response: &HttpResponse) -> Self {
+        let raw = String::from_utf8_lossy(&response.body).into_owned();
+
+        let json = serde_json::from_slice::<serde_json::Value>(&response.body).ok();
+
+        let message = json.as_ref().and_then(|json| match json.get("message") {
+            Some(serde_json::Value::String(message)) => Some(message.clone()),
+            // validation libraries tend to return one message per invalid field
+            Some(serde_json::Value::Array(messages)) => Some(
+                messages
+                    .iter()
+                    .filter_map(|message| message.as_str())
+                    .collect::<Vec<&str>>()
+                    .join("; "),
+            ),
+            _ => json
+                .get("error")
+                .and_then(|error| error.as_str())
+                .map(String::from),
+        });
+
+        let details = json.and_then(|json| {
+            ["errors", "details", "issues"]
+                .iter()
+                .find_map(|key| json.get(key).cloned())
+        });
+
+        let retry_after = response.header("retry-after").and_then(parse_retry_after);
+
+        Self {
+            status: response.status,
+            message,
+            details,
+            body: raw,
+            retry_after,
+        }
+    }
+
+    pub fn is_retryable(&self) -> bool {
+        matches!(self.status, 408 | 425 | 429 | 500..=599)
+    }
+}
+
+#[cfg(test)]
+mod errors_test {
+
+    use super::*;
+
+    fn response(status: u16, body: &[u8]) -> HttpResponse {
+        HttpResponse {
+            status,
+            headers: Vec::new(),
+            body: body.to_vec(),
+        }
+    }
+
+    #[test]
+    fn api_error_parses_the_kiln_body() {
+        let api_error = ApiError::from_response(&response(422, br#"{"message":"Validation failed","errors":[{"path":["wallets"],"message":"Required"}]}"#));
+
+        assert_eq!(api_error.message.as_deref(), Some("Validation failed"));
+        assert!(api_error.details.is_some());
+
+        let error = KilnError::from(api_error);
+
+        assert_eq!(error.status(), Some(422));
+        assert!(error.is_client_error());
+        assert!(!error.is_retryable());
+    }
+
+    #[test]
+    fn api_error_keeps_non_json_bodies() {
+        let api_error = ApiError::from_response(&response(502, b"<html>Bad Gateway</html>"));
+
+        assert!(api_error.message.is_none());
+        assert_eq!(api_error.body, "<html>Bad Gateway</html>");
+
+        let error = KilnError::from(api_error);
+
+        assert!(error.is_retryable());
+        assert!(!error.is_client_error());
+    }
+
+    #[test]
+    fn rate_limit_is_retryable() {
+        let error = KilnError::from(ApiError::from_response(&response(
+            429,
+            br#"{"message":"Too Many Requests"}"#,
+        )));
+
+        assert!(error.is_retryable());
+        assert!(!error.is_client_error());
+    }
+}
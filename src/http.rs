@@ -0,0 +1,551 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use serde::{de::DeserializeOwned, Serialize};
+use zeroize::Zeroize;
+
+use crate::errors::{ApiError, KilnError};
+use crate::interceptor::Interceptor;
+use crate::mode::{for_each_mode, Mode};
+use crate::rate_limit::{Budget, RateLimit, RateLimiter};
+use crate::response_format::PaginatedData;
+#[cfg(feature = "tracing")]
+use crate::response_format::Pagination;
+use crate::retry::RetryPolicy;
+use crate::secret::Secret;
+#[cfg(feature = "async")]
+use crate::transport::AsyncTransport;
+use crate::transport::{HttpOptions, HttpRequest, HttpResponse, Method, Transport};
+
+const ACCEPT_JSON: &str = "application/json; charset=utf-8";
+const ACCEPT_OCTET_STREAM: &str = "application/octet-stream";
+
+// What KilnBuilder collects for the HttpClient, unset fields get the defaults.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct HttpConfig {
+    pub(crate) transport: Option<Arc<dyn Transport>>,
+    #[cfg(feature = "async")]
+    pub(crate) async_transport: Option<Arc<dyn AsyncTransport>>,
+    pub(crate) http_options: HttpOptions,
+    pub(crate) retry_policy: Option<RetryPolicy>,
+    pub(crate) rate_limit: Option<RateLimit>,
+    pub(crate) transaction_rate_limit: Option<RateLimit>,
+    pub(crate) interceptors: Vec<Arc<dyn Interceptor>>,
+}
+
+// Shared by every Kiln*Client created from the same Kiln.
+// Builds the requests, hands them to the Transport and decodes the responses.
+#[derive(Debug)]
+pub(crate) struct HttpClient<M: Mode> {
+    transport: Arc<M::Transport>,
+    retry_policy: RetryPolicy,
+    rate_limiter: Arc<RateLimiter>,
+    interceptors: Arc<[Arc<dyn Interceptor>]>,
+    token_provider: Arc<M::TokenProvider>,
+}
+
+// not derived : it would ask for the transport and the token provider to be Clone
+impl<M: Mode> Clone for HttpClient<M> {
+    fn clone(&self) -> Self {
+        Self {
+            transport: Arc::clone(&self.transport),
+            retry_policy: self.retry_policy.clone(),
+            rate_limiter: Arc::clone(&self.rate_limiter),
+            interceptors: Arc::clone(&self.interceptors),
+            token_provider: Arc::clone(&self.token_provider),
+        }
+    }
+}
+
+impl<M: Mode> HttpClient<M> {
+    // the transport of the config is left to the caller, it only fits one mode
+    pub(crate) fn new(
+        transport: Arc<M::Transport>,
+        config: HttpConfig,
+        token_provider: Arc<M::TokenProvider>,
+    ) -> Self {
+        Self {
+            transport,
+            retry_policy: config.retry_policy.unwrap_or_else(RetryPolicy::disabled),
+            rate_limiter: Arc::new(RateLimiter::new(
+                config.rate_limit,
+                config.transaction_rate_limit,
+            )),
+            interceptors: config.interceptors.into(),
+            token_provider,
+        }
+    }
+}
+
+for_each_mode! { <M>
+    impl HttpClient<M> {
+        pub(crate) async fn get<T: DeserializeOwned>(
+            &self,
+            operation: &'static str,
+            url: &str,
+        ) -> Result<T, KilnError> {
+            let response = self
+                .send(operation, Method::Get, url, ACCEPT_JSON, None, Budget::Read)
+                .await?;
+
+            decode::<T>(&response.body)
+        }
+
+        // the pagination goes to the tracing span from the decoded body, not from a second parse
+        pub(crate) async fn get_paginated<T: DeserializeOwned>(
+            &self,
+            operation: &'static str,
+            url: &str,
+        ) -> Result<PaginatedData<T>, KilnError> {
+            let sent = self
+                .send_traced(operation, Method::Get, url, ACCEPT_JSON, None, Budget::Read)
+                .await?;
+
+            let page = decode::<PaginatedData<T>>(&sent.response.body)?;
+
+            #[cfg(feature = "tracing")]
+            record_pagination(&sent.span, &page.pagination);
+
+            Ok(page)
+        }
+
+        pub(crate) async fn get_bytes(
+            &self,
+            operation: &'static str,
+            url: &str,
+        ) -> Result<Vec<u8>, KilnError> {
+            let response = self
+                .send(
+                    operation,
+                    Method::Get,
+                    url,
+                    ACCEPT_OCTET_STREAM,
+                    None,
+                    Budget::Read,
+                )
+                .await?;
+
+            Ok(response.body)
+        }
+
+        pub(crate) async fn post<B: Serialize + ?Sized, T: DeserializeOwned>(
+            &self,
+            operation: &'static str,
+            url: &str,
+            body: &B,
+        ) -> Result<T, KilnError> {
+            self.post_with_budget(operation, url, body, Budget::Read)
+                .await
+        }
+
+        // crafting, preparing or broadcasting a transaction : charged to the transaction budget
+        pub(crate) async fn post_tx<B: Serialize + ?Sized, T: DeserializeOwned>(
+            &self,
+            operation: &'static str,
+            url: &str,
+            body: &B,
+        ) -> Result<T, KilnError> {
+            self.post_with_budget(operation, url, body, Budget::Transaction)
+                .await
+        }
+
+        async fn post_with_budget<B: Serialize + ?Sized, T: DeserializeOwned>(
+            &self,
+            operation: &'static str,
+            url: &str,
+            body: &B,
+            budget: Budget,
+        ) -> Result<T, KilnError> {
+            let body: Vec<u8> = serde_json::to_vec(body).map_err(KilnError::Serialize)?;
+
+            let response = self
+                .send(
+                    operation,
+                    Method::Post,
+                    url,
+                    ACCEPT_JSON,
+                    Some(body),
+                    budget,
+                )
+                .await?;
+
+            decode::<T>(&response.body)
+        }
+
+        pub(crate) async fn put<B: Serialize + ?Sized, T: DeserializeOwned>(
+            &self,
+            operation: &'static str,
+            url: &str,
+            body: &B,
+        ) -> Result<T, KilnError> {
+            let body: Vec<u8> = serde_json::to_vec(body).map_err(KilnError::Serialize)?;
+
+            let response = self
+                .send(
+                    operation,
+                    Method::Put,
+                    url,
+                    ACCEPT_JSON,
+                    Some(body),
+                    Budget::Read,
+                )
+                .await?;
+
+            decode::<T>(&response.body)
+        }
+
+        pub(crate) async fn delete<T: DeserializeOwned>(
+            &self,
+            operation: &'static str,
+            url: &str,
+        ) -> Result<T, KilnError> {
+            let response = self
+                .send(
+                    operation,
+                    Method::Delete,
+                    url,
+                    ACCEPT_JSON,
+                    None,
+                    Budget::Read,
+                )
+                .await?;
+
+            decode::<T>(&response.body)
+        }
+
+        async fn send(
+            &self,
+            operation: &'static str,
+            method: Method,
+            url: &str,
+            accept: &str,
+            body: Option<Vec<u8>>,
+            budget: Budget,
+        ) -> Result<HttpResponse, KilnError> {
+            let sent = self
+                .send_traced(operation, method, url, accept, body, budget)
+                .await?;
+
+            Ok(sent.response)
+        }
+
+        // `operation` names the SDK call ("kiln.eth.get_stakes") in the tracing span
+        #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+        async fn send_traced(
+            &self,
+            operation: &'static str,
+            method: Method,
+            url: &str,
+            accept: &str,
+            body: Option<Vec<u8>>,
+            budget: Budget,
+        ) -> Result<Sent, KilnError> {
+            let mut headers: Vec<(String, String)> = vec![("accept".into(), accept.into())];
+
+            if body.is_some() {
+                headers.push(("content-type".into(), "application/json".into()));
+            }
+
+            let request = HttpRequest {
+                method,
+                url: url.into(),
+                headers,
+                body,
+            };
+
+            // span names are static : the operation goes to `otel.name`, which OpenTelemetry exporters use as the span name
+            // the headers (and the bearer token) are never recorded
+            #[cfg(feature = "tracing")]
+            let span = tracing::info_span!(
+                "kiln",
+                otel.name = operation,
+                kiln.operation = operation,
+                http.method = method.as_ref(),
+                url.path = request.path(),
+                http.status_code = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+                attempts = tracing::field::Empty,
+                pagination.current_page = tracing::field::Empty,
+                pagination.total_pages = tracing::field::Empty,
+                pagination.total_entries = tracing::field::Empty,
+                error = tracing::field::Empty,
+            );
+
+            #[cfg(feature = "tracing")]
+            let started_at = Instant::now();
+
+            let mut attempts: u32 = 0;
+
+            #[cfg(feature = "tracing")]
+            let result = in_span(&span, || {
+                self.send_with_retries(request, budget, &mut attempts)
+            })
+            .await;
+
+            #[cfg(not(feature = "tracing"))]
+            let result = self.send_with_retries(request, budget, &mut attempts).await;
+
+            #[cfg(feature = "tracing")]
+            {
+                span.record("attempts", attempts);
+
+                record_outcome(&span, &result, started_at.elapsed());
+            }
+
+            Ok(Sent {
+                response: result?,
+                #[cfg(feature = "tracing")]
+                span,
+            })
+        }
+
+        async fn send_with_retries(
+            &self,
+            request: HttpRequest,
+            budget: Budget,
+            attempts: &mut u32,
+        ) -> Result<HttpResponse, KilnError> {
+            let method = request.method;
+
+            let mut attempt: u32 = 1;
+
+            let mut refreshed = false;
+
+            loop {
+                *attempts = attempt;
+
+                // every attempt counts against the quota, retries included
+                let wait = self.rate_limiter.acquire(budget);
+
+                if !wait.is_zero() {
+                    sleep(wait).await;
+                }
+
+                let token = self
+                    .token_provider
+                    .token()
+                    .await
+                    .map_err(KilnError::Token)?;
+
+                let mut attempt_request = request.clone();
+
+                attempt_request
+                    .headers
+                    .push(("Authorization".into(), bearer(&token)));
+
+                for interceptor in self.interceptors.iter() {
+                    interceptor.on_request(&mut attempt_request);
+                }
+
+                let started_at = Instant::now();
+
+                let result = self.transport.send(attempt_request.clone()).await;
+
+                let elapsed = started_at.elapsed();
+
+                for interceptor in self.interceptors.iter() {
+                    match &result {
+                        Ok(response) => interceptor.on_response(&attempt_request, response, elapsed),
+                        Err(error) => interceptor.on_transport_error(&attempt_request, error, elapsed),
+                    }
+                }
+
+                zeroize_authorization(&mut attempt_request);
+
+                let error: KilnError = match result {
+                    Ok(response) if (200..300).contains(&response.status) => return Ok(response),
+                    // the token may have been rotated since it was read : ask for a new one, once
+                    Ok(response) if response.status == 401 && !refreshed => {
+                        refreshed = true;
+
+                        self.token_provider.refresh().await;
+
+                        continue;
+                    }
+                    Ok(response) => ApiError::from_response(&response).into(),
+                    Err(error) => KilnError::Transport(error),
+                };
+
+                match self.retry_policy.delay(method, attempt, &error) {
+                    Some(delay) => sleep(delay).await,
+                    None => return Err(error),
+                }
+
+                attempt += 1;
+            }
+        }
+    }
+}
+
+// "Bearer <token>" written in a buffer of the exact size : no reallocation leaves a copy behind
+fn bearer(token: &Secret) -> String {
+    let mut authorization = String::with_capacity("Bearer ".len() + token.expose().len());
+
+    authorization.push_str("Bearer ");
+    authorization.push_str(token.expose());
+
+    authorization
+}
+
+// Wipes the header once the attempt is over. The copy handed to the Transport is its own :
+// ureq and reqwest move it into their header maps, which are dropped without being zeroized.
+fn zeroize_authorization(request: &mut HttpRequest) {
+    for (key, value) in request.headers.iter_mut() {
+        if key.eq_ignore_ascii_case("authorization") {
+            value.zeroize();
+        }
+    }
+}
+
+// A successful response, with the span of the call to record what its decoded body says
+struct Sent {
+    response: HttpResponse,
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
+
+fn decode<T: DeserializeOwned>(body: &[u8]) -> Result<T, KilnError> {
+    let deserializer = &mut serde_json::Deserializer::from_slice(body);
+
+    serde_path_to_error::deserialize::<_, T>(deserializer).map_err(|error| KilnError::Deserialize {
+        path: error.path().to_string(),
+        source: error.into_inner(),
+    })
+}
+
+#[cfg(feature = "tracing")]
+fn record_outcome(
+    span: &tracing::Span,
+    result: &Result<HttpResponse, KilnError>,
+    elapsed: std::time::Duration,
+) {
+    span.record("latency_ms", elapsed.as_millis() as u64);
+
+    match result {
+        Ok(response) => {
+            span.record("http.status_code", response.status);
+        }
+        Err(error) => {
+            if let Some(status) = error.status() {
+                span.record("http.status_code", status);
+            }
+
+            span.record("error", tracing::field::display(error));
+        }
+    }
+}
+
+// only the paginated endpoints, once their body is decoded
+#[cfg(feature = "tracing")]
+fn record_pagination(span: &tracing::Span, pagination: &Pagination) {
+    span.record("pagination.current_page", pagination.current_page);
+    span.record("pagination.total_pages", pagination.total_pages);
+    span.record("pagination.total_entries", pagination.total_entries);
+}
+
+#[cfg(all(test, feature = "tracing", feature = "blocking"))]
+mod http_test {
+
+    use std::sync::Mutex;
+
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    use super::*;
+    use crate::transport::TransportError;
+    use crate::Kiln;
+
+    // keeps every field of every span, as "name=value"
+    #[derive(Debug, Default)]
+    struct Capture {
+        fields: Mutex<Vec<String>>,
+    }
+
+    impl Visit for &Capture {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.fields
+                .lock()
+                .unwrap()
+                .push(format!("{}={:?}", field.name(), value));
+        }
+    }
+
+    impl Subscriber for &'static Capture {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut visitor: &Capture = self;
+
+            span.record(&mut visitor);
+
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _: &Id, values: &Record<'_>) {
+            let mut visitor: &Capture = self;
+
+            values.record(&mut visitor);
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, _: &Event<'_>) {}
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[derive(Debug)]
+    struct PaginatedTransport;
+
+    impl Transport for PaginatedTransport {
+        fn send(&self, _: HttpRequest) -> Result<HttpResponse, TransportError> {
+            Ok(HttpResponse {
+                status: 200,
+                headers: Vec::new(),
+                body: br#"{"data":[],"pagination":{"current_page":2,"next_page":3,"previous_page":1,"page_size":10,"total_pages":5,"total_entries":42}}"#.to_vec(),
+            })
+        }
+    }
+
+    #[test]
+    fn span_describes_the_call_without_the_token() {
+        let capture: &'static Capture = Box::leak(Box::default());
+
+        let kiln = Kiln::builder()
+            .api_token("kiln_super_secret_token")
+            .base_url("https://api.kiln.fi/v1")
+            .transport(PaginatedTransport)
+            .seal()
+            .build()
+            .unwrap();
+
+        tracing::subscriber::with_default(capture, || {
+            kiln.ethereum().stakes().wallets(["0x0"]).send().unwrap()
+        });
+
+        let fields = capture.fields.lock().unwrap();
+
+        for expected in [
+            "otel.name=\"kiln.eth.get_stakes\"",
+            "url.path=\"/v1/eth/stakes\"",
+            "http.method=\"GET\"",
+            "http.status_code=200",
+            "attempts=1",
+            "pagination.current_page=2",
+            "pagination.total_entries=42",
+        ] {
+            assert!(
+                fields.iter().any(|field| field == expected),
+                "missing {expected} in {fields:?}"
+            );
+        }
+
+        assert!(fields.iter().any(|field| field.starts_with("latency_ms=")));
+        assert!(!fields.iter().any(|field| field.contains("secret")));
+    }
+}
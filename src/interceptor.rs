@@ -0,0 +1,113 @@
+use std::time::Duration;
+
+use crate::transport::{HttpRequest, HttpResponse, TransportError};
+
+// Sees every request sent by any Kiln*Client of a Kiln, and every answer before deserialization.
+// Called once per attempt (retries included), in the order they were added to the KilnBuilder.
+// The `Authorization` header carries the api token : `{:?}` redacts it, never log its value.
+pub trait Interceptor: std::fmt::Debug + Send + Sync {
+    // before sending : add headers (correlation id...), log the body...
+    fn on_request(&self, _request: &mut HttpRequest) {}
+
+    // any status, 2xx or not
+    fn on_response(&self, _request: &HttpRequest, _response: &HttpResponse, _elapsed: Duration) {}
+
+    // no answer : dns, tls, connection reset, timeout...
+    fn on_transport_error(
+        &self,
+        _request: &HttpRequest,
+        _error: &TransportError,
+        _elapsed: Duration,
+    ) {
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod interceptor_test {
+
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::transport::Transport;
+    use crate::Kiln;
+
+    #[derive(Debug)]
+    struct EchoTransport;
+
+    impl Transport for EchoTransport {
+        fn send(&self, request: HttpRequest) -> Result<HttpResponse, TransportError> {
+            let correlation_id = request
+                .headers
+                .iter()
+                .find(|(key, _)| key == "x-correlation-id")
+                .map(|(_, value)| value.clone());
+
+            Ok(HttpResponse {
+                status: if correlation_id.is_some() { 200 } else { 400 },
+                headers: Vec::new(),
+                body: br#"{"data":[]}"#.to_vec(),
+            })
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct Recorder {
+        name: &'static str,
+        seen: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Interceptor for Recorder {
+        fn on_request(&self, request: &mut HttpRequest) {
+            request
+                .headers
+                .push(("x-correlation-id".into(), "42".into()));
+
+            self.seen
+                .lock()
+                .unwrap()
+                .push(format!("{} request {}", self.name, request.path()));
+        }
+
+        fn on_response(&self, request: &HttpRequest, response: &HttpResponse, _: Duration) {
+            self.seen.lock().unwrap().push(format!(
+                "{} response {} {}",
+                self.name,
+                request.path(),
+                response.status
+            ));
+        }
+    }
+
+    #[test]
+    fn interceptors_see_requests_and_responses_in_order() {
+        let seen: Arc<Mutex<Vec<String>>> = Arc::default();
+
+        let kiln = Kiln::builder()
+            .api_token("kiln_test_token")
+            .base_url("https://api.kiln.fi/v1")
+            .transport(EchoTransport)
+            .interceptor(Recorder {
+                name: "first",
+                seen: Arc::clone(&seen),
+            })
+            .interceptor(Recorder {
+                name: "second",
+                seen: Arc::clone(&seen),
+            })
+            .seal()
+            .build()
+            .unwrap();
+
+        kiln.deployments().get().unwrap();
+
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![
+                "first request /v1/deployments",
+                "second request /v1/deployments",
+                "first response /v1/deployments 200",
+                "second response /v1/deployments 200",
+            ]
+        );
+    }
+}
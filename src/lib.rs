@@ -1,7 +1,36 @@
+pub mod address;
+mod amount;
+pub mod cassette;
+pub mod denomination;
+mod environment;
 mod errors;
+mod http;
+pub mod interceptor;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+mod mode;
+mod pagination;
 mod prelude;
+mod query;
+mod rate_limit;
 mod response_format;
-mod sdk;
+mod retry;
+pub mod sdk;
+mod secret;
+pub mod token;
+pub mod transport;
+
+pub use amount::{Amount, FloatAmount, IntAmount};
+pub use environment::KilnEnvironment;
+pub use errors::{ApiError, KilnError};
+pub use mode::{Async, Blocking, Mode};
+pub use pagination::{PageOptions, Pages};
+pub use rate_limit::RateLimit;
+pub use response_format::{PaginatedData, Pagination, ReturnedData};
+pub use retry::RetryPolicy;
+#[cfg(feature = "decimal")]
+pub use rust_decimal::Decimal;
+pub use secret::Secret;
 
 use sdk::{
     account::KilnAccountClient, cardano::KilnCardanoClient, celestia::KilnCelestiaClient,
@@ -15,8 +44,17 @@ use sdk::{
     zetachain::KilnZetachainClient,
 };
 
+use crate::http::{HttpClient, HttpConfig};
+use crate::interceptor::Interceptor;
 use crate::prelude::*;
+#[cfg(feature = "async")]
+use crate::token::AsyncTokenProvider;
+use crate::token::{StaticToken, TokenProvider};
+#[cfg(feature = "async")]
+use crate::transport::AsyncTransport;
+use crate::transport::{HttpOptions, Transport};
 use std::marker::PhantomData;
+use std::sync::Arc;
 
 #[derive(Default, Clone)]
 pub struct BaseUrl(String);
@@ -33,113 +71,130 @@ pub struct NotSealed;
 #[derive(Default, Clone)]
 pub struct Sealed;
 
-#[derive(Default, Clone)]
-pub struct Token(String);
+#[derive(Clone)]
+pub struct Token(Arc<dyn TokenProvider>);
+
+// Set by `async_token_provider`, only an `AsyncKiln` can be built with it
+#[cfg(feature = "async")]
+#[derive(Clone)]
+pub struct AsyncToken(Arc<dyn AsyncTokenProvider>);
 
 #[derive(Clone, Debug)]
-pub struct Kiln {
-    api_token: String,
+pub struct Kiln<M: Mode = Blocking> {
     base_url: String,
+    environment: Option<KilnEnvironment>,
+    http: HttpClient<M>,
 }
 
+// Every call returns a future, see `KilnBuilder::build_async`
+#[cfg(feature = "async")]
+pub type AsyncKiln = Kiln<Async>;
+
 impl Kiln {
     pub fn builder() -> KilnBuilder<NoUrl, NoToken, NotSealed> {
         KilnBuilder::default()
     }
+}
 
-    pub fn accounts(&self) -> KilnAccountClient {
+impl<M: Mode> Kiln<M> {
+    // None when the base url isn't a Kiln host (proxy, mock server...)
+    pub fn environment(&self) -> Option<KilnEnvironment> {
+        self.environment
+    }
+
+    pub fn accounts(&self) -> KilnAccountClient<M> {
         KilnAccountClient::from(self)
     }
 
-    pub fn cardano(&self) -> KilnCardanoClient {
+    pub fn cardano(&self) -> KilnCardanoClient<M> {
         KilnCardanoClient::from(self)
     }
 
-    pub fn celestia(&self) -> KilnCelestiaClient {
+    pub fn celestia(&self) -> KilnCelestiaClient<M> {
         KilnCelestiaClient::from(self)
     }
 
-    pub fn cosmos(&self) -> KilnCosmosClient {
+    pub fn cosmos(&self) -> KilnCosmosClient<M> {
         KilnCosmosClient::from(self)
     }
 
-    pub fn defi(&self) -> KilnDefiClient {
+    pub fn defi(&self) -> KilnDefiClient<M> {
         KilnDefiClient::from(self)
     }
 
-    pub fn deployments(&self) -> KilnDeploymentsClient {
+    pub fn deployments(&self) -> KilnDeploymentsClient<M> {
         KilnDeploymentsClient::from(self)
     }
 
-    pub fn dydx(&self) -> KilnDydxClient {
+    pub fn dydx(&self) -> KilnDydxClient<M> {
         KilnDydxClient::from(self)
     }
 
-    pub fn eigenlayer(&self) -> KilnEigenlayerClient {
+    pub fn eigenlayer(&self) -> KilnEigenlayerClient<M> {
         KilnEigenlayerClient::from(self)
     }
 
-    pub fn ethereum(&self) -> KilnEthereumClient {
+    pub fn ethereum(&self) -> KilnEthereumClient<M> {
         KilnEthereumClient::from(self)
     }
 
-    pub fn ethereum_onchain(&self) -> KilnEthereumOnchainClient {
+    pub fn ethereum_onchain(&self) -> KilnEthereumOnchainClient<M> {
         KilnEthereumOnchainClient::from(self)
     }
 
-    pub fn fetch_ai(&self) -> KilnFetchaiClient {
+    pub fn fetch_ai(&self) -> KilnFetchaiClient<M> {
         KilnFetchaiClient::from(self)
     }
 
-    pub fn injective(&self) -> KilnInjectiveClient {
+    pub fn injective(&self) -> KilnInjectiveClient<M> {
         KilnInjectiveClient::from(self)
     }
 
-    pub fn kava(&self) -> KilnKavaClient {
+    pub fn kava(&self) -> KilnKavaClient<M> {
         KilnKavaClient::from(self)
     }
 
-    pub fn kusama(&self) -> KilnKusamaClient {
+    pub fn kusama(&self) -> KilnKusamaClient<M> {
         KilnKusamaClient::from(self)
     }
 
-    pub fn multiversx(&self) -> KilnMultiversxClient {
+    pub fn multiversx(&self) -> KilnMultiversxClient<M> {
         KilnMultiversxClient::from(self)
     }
 
-    pub fn near(&self) -> KilnNearClient {
+    pub fn near(&self) -> KilnNearClient<M> {
         KilnNearClient::from(self)
     }
 
-    pub fn noble(&self) -> KilnNobleClient {
+    pub fn noble(&self) -> KilnNobleClient<M> {
         KilnNobleClient::from(self)
     }
 
-    pub fn organisations(&self) -> KilnOrganisationClient {
+    pub fn organisations(&self) -> KilnOrganisationClient<M> {
         KilnOrganisationClient::from(self)
     }
 
-    pub fn osmosis(&self) -> KilnOsmosisClient {
+    pub fn osmosis(&self) -> KilnOsmosisClient<M> {
         KilnOsmosisClient::from(self)
     }
 
-    pub fn polkadot(&self) -> KilnPolkadotClient {
+    pub fn polkadot(&self) -> KilnPolkadotClient<M> {
         KilnPolkadotClient::from(self)
     }
 
-    pub fn polygon(&self) -> KilnPolygonClient {
+    pub fn polygon(&self) -> KilnPolygonClient<M> {
         KilnPolygonClient::from(self)
     }
 
-    pub fn solana(&self) -> KilnSolanaClient {
+    pub fn solana(&self) -> KilnSolanaClient<M> {
         KilnSolanaClient::from(self)
     }
 
-    pub fn tezos(&self) -> KilnTezosClient {
+    pub fn tezos(&self) -> KilnTezosClient<M> {
         KilnTezosClient::from(self)
     }
 
-    pub fn zetachain(&self) -> KilnZetachainClient {
+    pub fn zetachain(&self) -> KilnZetachainClient<M> {
         KilnZetachainClient::from(self)
     }
 }
@@ -148,6 +203,7 @@ impl Kiln {
 pub struct KilnBuilder<BU, T, Seal> {
     base_url: BU,
     api_token: T,
+    http_config: HttpConfig,
     marker_seal: PhantomData<Seal>,
 }
 
@@ -158,30 +214,223 @@ impl KilnBuilder<NoUrl, NoToken, NotSealed> {
 }
 
 impl<Seal> KilnBuilder<BaseUrl, Token, Seal> {
+    // Defaults to the ureq transport when `transport` isn't set.
+    #[cfg(feature = "blocking")]
     pub fn build(self) -> Result<Kiln> {
+        let (base_url, environment) = environment::normalize_base_url(&self.base_url.0)?;
+
+        #[cfg(feature = "async")]
+        if self.http_config.async_transport.is_some() {
+            return Err(KilnError::InvalidInput(
+                "an async transport is set : build an AsyncKiln with `build_async`".into(),
+            ));
+        }
+
+        let transport = match self.http_config.transport.clone() {
+            Some(transport) => transport,
+            None => transport::default_transport(&self.http_config.http_options),
+        };
+
         Ok(Kiln {
-            api_token: self.api_token.0,
-            base_url: self.base_url.0,
+            base_url,
+            environment,
+            http: HttpClient::new(transport, self.http_config, self.api_token.0),
         })
     }
+
+    #[cfg(feature = "async")]
+    pub fn build_async(self) -> Result<AsyncKiln> {
+        let token_provider = AsyncToken(Arc::new(SharedToken(self.api_token.0)));
+
+        KilnBuilder {
+            base_url: self.base_url,
+            api_token: token_provider,
+            http_config: self.http_config,
+            marker_seal: PhantomData::<Seal>,
+        }
+        .build_async()
+    }
+}
+
+#[cfg(feature = "async")]
+impl<Seal> KilnBuilder<BaseUrl, AsyncToken, Seal> {
+    // Defaults to the reqwest transport when `async_transport` isn't set.
+    pub fn build_async(self) -> Result<AsyncKiln> {
+        let (base_url, environment) = environment::normalize_base_url(&self.base_url.0)?;
+
+        if self.http_config.transport.is_some() {
+            return Err(KilnError::InvalidInput(
+                "a blocking transport is set : set it with `async_transport` for an AsyncKiln"
+                    .into(),
+            ));
+        }
+
+        let transport = match self.http_config.async_transport.clone() {
+            Some(transport) => transport,
+            None => transport::default_async_transport(&self.http_config.http_options)
+                .map_err(KilnError::Transport)?,
+        };
+
+        Ok(Kiln {
+            base_url,
+            environment,
+            http: HttpClient::new(transport, self.http_config, self.api_token.0),
+        })
+    }
+}
+
+// A `TokenProvider` given to the builder, for an AsyncKiln
+#[cfg(feature = "async")]
+#[derive(Debug)]
+struct SharedToken(Arc<dyn TokenProvider>);
+
+#[cfg(feature = "async")]
+impl TokenProvider for SharedToken {
+    fn token(&self) -> core::result::Result<Secret, token::TokenError> {
+        self.0.token()
+    }
+
+    fn refresh(&self) {
+        self.0.refresh()
+    }
 }
 
 impl<BU, T> KilnBuilder<BU, T, NotSealed> {
-    pub fn api_token(self, api_token: impl Into<String>) -> KilnBuilder<BU, Token, NotSealed> {
+    pub fn api_token(self, api_token: impl Into<Secret>) -> KilnBuilder<BU, Token, NotSealed> {
+        self.token_provider(StaticToken::new(api_token))
+    }
+
+    // For rotated tokens : asked for the token before every request, refreshed on a 401.
+    pub fn token_provider(
+        self,
+        token_provider: impl TokenProvider + 'static,
+    ) -> KilnBuilder<BU, Token, NotSealed> {
+        KilnBuilder {
+            api_token: Token(Arc::new(token_provider)),
+            base_url: self.base_url,
+            http_config: self.http_config,
+            marker_seal: PhantomData,
+        }
+    }
+
+    // Like `token_provider`, for a token that must be awaited. Only `build_async` is left.
+    #[cfg(feature = "async")]
+    pub fn async_token_provider(
+        self,
+        token_provider: impl AsyncTokenProvider + 'static,
+    ) -> KilnBuilder<BU, AsyncToken, NotSealed> {
         KilnBuilder {
-            api_token: Token(api_token.into()),
+            api_token: AsyncToken(Arc::new(token_provider)),
             base_url: self.base_url,
+            http_config: self.http_config,
             marker_seal: PhantomData,
         }
     }
 
+    // Validated and normalized by `build()`, a trailing slash is fine.
     pub fn base_url(self, base_url: &str) -> KilnBuilder<BaseUrl, T, NotSealed> {
         KilnBuilder {
             base_url: BaseUrl(base_url.into()),
             api_token: self.api_token,
+            http_config: self.http_config,
             marker_seal: PhantomData,
         }
     }
+
+    pub fn environment(self, environment: KilnEnvironment) -> KilnBuilder<BaseUrl, T, NotSealed> {
+        self.base_url(environment.base_url())
+    }
+
+    // What a `Kiln` sends with, defaults to ureq when not set.
+    pub fn transport(self, transport: impl Transport + 'static) -> KilnBuilder<BU, T, NotSealed> {
+        KilnBuilder {
+            http_config: HttpConfig {
+                transport: Some(Arc::new(transport)),
+                ..self.http_config
+            },
+            ..self
+        }
+    }
+
+    // What an `AsyncKiln` sends with, defaults to reqwest when not set.
+    #[cfg(feature = "async")]
+    pub fn async_transport(
+        self,
+        transport: impl AsyncTransport + 'static,
+    ) -> KilnBuilder<BU, T, NotSealed> {
+        KilnBuilder {
+            http_config: HttpConfig {
+                async_transport: Some(Arc::new(transport)),
+                ..self.http_config
+            },
+            ..self
+        }
+    }
+
+    // Timeouts, connection pool, user-agent and gzip of the default transport.
+    pub fn http_options(self, http_options: HttpOptions) -> KilnBuilder<BU, T, NotSealed> {
+        KilnBuilder {
+            http_config: HttpConfig {
+                http_options,
+                ..self.http_config
+            },
+            ..self
+        }
+    }
+
+    // Can be called several times, interceptors run in the order they were added.
+    pub fn interceptor(
+        self,
+        interceptor: impl Interceptor + 'static,
+    ) -> KilnBuilder<BU, T, NotSealed> {
+        let mut interceptors = self.http_config.interceptors;
+
+        interceptors.push(Arc::new(interceptor));
+
+        KilnBuilder {
+            http_config: HttpConfig {
+                interceptors,
+                ..self.http_config
+            },
+            ..self
+        }
+    }
+
+    // No retries when not set.
+    pub fn retry_policy(self, retry_policy: RetryPolicy) -> KilnBuilder<BU, T, NotSealed> {
+        KilnBuilder {
+            http_config: HttpConfig {
+                retry_policy: Some(retry_policy),
+                ..self.http_config
+            },
+            ..self
+        }
+    }
+
+    // Budget of every endpoint but the transaction ones, shared by every Kiln*Client of the built Kiln.
+    // Unlimited when not set.
+    pub fn rate_limit(self, rate_limit: RateLimit) -> KilnBuilder<BU, T, NotSealed> {
+        KilnBuilder {
+            http_config: HttpConfig {
+                rate_limit: Some(rate_limit),
+                ..self.http_config
+            },
+            ..self
+        }
+    }
+
+    // Budget of the calls crafting, preparing or broadcasting a transaction (`post_*_tx`...),
+    // independent from the read one.
+    // Unlimited when not set.
+    pub fn transaction_rate_limit(self, rate_limit: RateLimit) -> KilnBuilder<BU, T, NotSealed> {
+        KilnBuilder {
+            http_config: HttpConfig {
+                transaction_rate_limit: Some(rate_limit),
+                ..self.http_config
+            },
+            ..self
+        }
+    }
 }
 
 impl<BU, T> KilnBuilder<BU, T, NotSealed> {
@@ -189,6 +438,7 @@ impl<BU, T> KilnBuilder<BU, T, NotSealed> {
         KilnBuilder {
             base_url: self.base_url,
             api_token: self.api_token,
+            http_config: self.http_config,
             marker_seal: PhantomData,
         }
     }
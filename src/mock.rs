@@ -0,0 +1,1131 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::transport::{HttpRequest, HttpResponse, Method};
+
+// A Kiln API on localhost for integration tests : the json files of `fixtures/` served over plain
+// http, and the errors the real API answers with (401, 422, 429, 500, slow responses) on demand.
+// `Kiln::builder().base_url(&server.url())` runs the real client against it, offline.
+// Shut down when dropped.
+#[derive(Debug)]
+pub struct MockServer {
+    address: SocketAddr,
+    state: Arc<State>,
+    accept_loop: Option<JoinHandle<()>>,
+}
+
+#[derive(Debug)]
+struct State {
+    fixtures: PathBuf,
+    api_token: Option<String>,
+    running: AtomicBool,
+    routes: Mutex<Vec<Route>>,
+    faults: Mutex<Vec<Fault>>,
+    requests: Mutex<Vec<HttpRequest>>,
+}
+
+#[derive(Debug)]
+struct Route {
+    method: Method,
+    path: String,
+    response: HttpResponse,
+}
+
+#[derive(Clone, Debug)]
+pub struct MockServerBuilder {
+    fixtures: PathBuf,
+    api_token: Option<String>,
+    port: u16,
+}
+
+impl Default for MockServerBuilder {
+    fn default() -> Self {
+        Self {
+            fixtures: PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures")),
+            api_token: None,
+            port: 0,
+        }
+    }
+}
+
+impl MockServerBuilder {
+    // a directory laid out like `fixtures/` : `<module>/<endpoint>.json`
+    pub fn fixtures(self, fixtures: impl Into<PathBuf>) -> Self {
+        Self {
+            fixtures: fixtures.into(),
+            ..self
+        }
+    }
+
+    // requests without `Authorization: Bearer <api_token>` get a 401. Any token is accepted by default
+    pub fn api_token(self, api_token: impl Into<String>) -> Self {
+        Self {
+            api_token: Some(api_token.into()),
+            ..self
+        }
+    }
+
+    // 0, the default, lets the OS pick a free port
+    pub fn port(self, port: u16) -> Self {
+        Self { port, ..self }
+    }
+
+    pub fn start(self) -> io::Result<MockServer> {
+        let listener = TcpListener::bind(("127.0.0.1", self.port))?;
+        let address = listener.local_addr()?;
+
+        let state = Arc::new(State {
+            fixtures: self.fixtures,
+            api_token: self.api_token,
+            running: AtomicBool::new(true),
+            routes: Mutex::new(Vec::new()),
+            faults: Mutex::new(Vec::new()),
+            requests: Mutex::new(Vec::new()),
+        });
+
+        let accept_loop = {
+            let state = Arc::clone(&state);
+
+            std::thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if !state.running.load(Ordering::SeqCst) {
+                        break;
+                    }
+
+                    let Ok(stream) = stream else { continue };
+                    let state = Arc::clone(&state);
+
+                    // one thread per connection : a slow response doesn't hold the others back
+                    std::thread::spawn(move || {
+                        // the client hanging up early (a timeout) is not the server's problem
+                        let _ = state.serve(address, stream);
+                    });
+                }
+            })
+        };
+
+        Ok(MockServer {
+            address,
+            state,
+            accept_loop: Some(accept_loop),
+        })
+    }
+}
+
+impl MockServer {
+    pub fn builder() -> MockServerBuilder {
+        MockServerBuilder::default()
+    }
+
+    // the fixtures of this crate, on a free port, any token accepted
+    pub fn start() -> io::Result<Self> {
+        MockServerBuilder::default().start()
+    }
+
+    // "http://127.0.0.1:<port>/v1", the base url to give to the KilnBuilder
+    pub fn url(&self) -> String {
+        format!("http://{}/v1", self.address)
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    // answers `method path` with this status and body instead of the fixture, until reset.
+    // A path without a query string matches whatever the query, one with a query string
+    // ("/v1/ada/stakes?page_size=2&current_page=1") only that query, and wins over the former.
+    pub fn respond(
+        &self,
+        method: Method,
+        path: impl Into<String>,
+        status: u16,
+        body: impl Into<Vec<u8>>,
+    ) {
+        let path = path.into();
+        let mut routes = self.state.routes.lock().unwrap();
+
+        routes.retain(|route| route.method != method || route.path != path);
+        routes.push(Route {
+            method,
+            path,
+            response: json_response(status, body.into()),
+        });
+    }
+
+    // faults are matched in the order they were injected
+    pub fn inject(&self, fault: Fault) {
+        self.state.faults.lock().unwrap().push(fault);
+    }
+
+    // every request received so far, the faulty ones included
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.state.requests.lock().unwrap().clone()
+    }
+
+    // forgets the responses, the faults and the requests
+    pub fn reset(&self) {
+        self.state.routes.lock().unwrap().clear();
+        self.state.faults.lock().unwrap().clear();
+        self.state.requests.lock().unwrap().clear();
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.state.running.store(false, Ordering::SeqCst);
+
+        // wakes the accept loop up so it sees the flag
+        let _ = TcpStream::connect(self.address);
+
+        if let Some(accept_loop) = self.accept_loop.take() {
+            let _ = accept_loop.join();
+        }
+    }
+}
+
+// An error the server answers with instead of the fixture : `Fault::status(429).retry_after(1).times(2)`
+#[derive(Clone, Debug)]
+pub struct Fault {
+    status: Option<u16>,
+    delay: Duration,
+    retry_after: Option<u64>,
+    path: Option<String>,
+    times: Option<usize>,
+}
+
+impl Fault {
+    // this status, with a json body shaped like the Kiln one (`message`, and `errors` for a 422)
+    pub fn status(status: u16) -> Self {
+        Self {
+            status: Some(status),
+            delay: Duration::ZERO,
+            retry_after: None,
+            path: None,
+            times: None,
+        }
+    }
+
+    // the usual response, this late : past the client timeout it's a `KilnError::Transport`
+    pub fn delay(delay: Duration) -> Self {
+        Self {
+            status: None,
+            delay,
+            retry_after: None,
+            path: None,
+            times: None,
+        }
+    }
+
+    // Retry-After header, in seconds
+    pub fn retry_after(self, seconds: u64) -> Self {
+        Self {
+            retry_after: Some(seconds),
+            ..self
+        }
+    }
+
+    // only the requests whose path starts with this one ("/v1/eth"), every request by default
+    pub fn path(self, path: impl Into<String>) -> Self {
+        Self {
+            path: Some(path.into()),
+            ..self
+        }
+    }
+
+    // only the next `times` matching requests, then the server answers normally again
+    pub fn times(self, times: usize) -> Self {
+        Self {
+            times: Some(times.max(1)),
+            ..self
+        }
+    }
+
+    fn matches(&self, path: &str) -> bool {
+        self.path
+            .as_deref()
+            .is_none_or(|prefix| path.starts_with(prefix))
+    }
+
+    fn response(&self) -> HttpResponse {
+        let status = self.status.unwrap_or(200);
+        let mut response = json_response(status, error_body(status).into_bytes());
+
+        if let Some(retry_after) = self.retry_after {
+            response
+                .headers
+                .push(("Retry-After".into(), retry_after.to_string()));
+        }
+
+        response
+    }
+}
+
+impl State {
+    fn serve(&self, address: SocketAddr, mut stream: TcpStream) -> io::Result<()> {
+        let mut reader = BufReader::new(stream.try_clone()?);
+
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next().unwrap_or_default();
+        let target = parts.next().unwrap_or("/");
+
+        let mut headers: Vec<(String, String)> = Vec::new();
+
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line)?;
+
+            let Some((key, value)) = line.trim_end().split_once(':') else {
+                break;
+            };
+
+            headers.push((key.trim().to_owned(), value.trim().to_owned()));
+        }
+
+        let content_length = headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, value)| value.parse::<usize>().ok())
+            .unwrap_or(0);
+
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body)?;
+
+        let method = match method {
+            "GET" => Method::Get,
+            "POST" => Method::Post,
+            "PUT" => Method::Put,
+            "DELETE" => Method::Delete,
+            _ => {
+                return write_response(
+                    &mut stream,
+                    &json_response(405, error_body(405).into_bytes()),
+                )
+            }
+        };
+
+        let request = HttpRequest {
+            method,
+            url: format!("http://{address}{target}"),
+            headers,
+            body: (!body.is_empty()).then_some(body),
+        };
+
+        let response = self.respond(&request);
+
+        self.requests.lock().unwrap().push(request);
+
+        write_response(&mut stream, &response)
+    }
+
+    fn respond(&self, request: &HttpRequest) -> HttpResponse {
+        let path = request.path();
+
+        if let Some(fault) = self.take_fault(path) {
+            std::thread::sleep(fault.delay);
+
+            if fault.status.is_some() {
+                return fault.response();
+            }
+        }
+
+        if let Some(api_token) = &self.api_token {
+            let bearer = format!("Bearer {api_token}");
+
+            let authorized = request
+                .headers
+                .iter()
+                .any(|(key, value)| key.eq_ignore_ascii_case("authorization") && *value == bearer);
+
+            if !authorized {
+                return json_response(401, error_body(401).into_bytes());
+            }
+        }
+
+        let routes = self.routes.lock().unwrap();
+
+        let target = request
+            .url
+            .split_once('?')
+            .map(|(_, query)| format!("{path}?{query}"));
+
+        let route = |matches: &dyn Fn(&Route) -> bool| {
+            routes
+                .iter()
+                .find(|route| route.method == request.method && matches(route))
+        };
+
+        if let Some(route) = route(&|route| Some(&route.path) == target.as_ref())
+            .or_else(|| route(&|route| route.path == path))
+        {
+            return route.response.clone();
+        }
+
+        let fixture = fixture_path(&self.fixtures, request.method, path)
+            .and_then(|fixture| std::fs::read(fixture).ok());
+
+        match fixture {
+            Some(body) => json_response(200, body),
+            None => {
+                let message = format!("no fixture for {} {path}", request.method.as_ref());
+
+                json_response(
+                    404,
+                    serde_json::json!({ "message": message })
+                        .to_string()
+                        .into_bytes(),
+                )
+            }
+        }
+    }
+
+    // the first fault matching the path, used up after its `times`
+    fn take_fault(&self, path: &str) -> Option<Fault> {
+        let mut faults = self.faults.lock().unwrap();
+
+        let index = faults.iter().position(|fault| fault.matches(path))?;
+        let fault = faults[index].clone();
+
+        match &mut faults[index].times {
+            Some(1) => {
+                faults.remove(index);
+            }
+            Some(times) => *times -= 1,
+            None => {}
+        }
+
+        Some(fault)
+    }
+}
+
+// the url prefix of each protocol and the fixture directories to look into, most specific first
+const PROTOCOLS: &[(&str, &[&str])] = &[
+    ("eth/onchain", &["ethereum_onchain"]),
+    ("eth/eigenlayer", &["eigenlayer"]),
+    ("eth", &["ethereum"]),
+    ("sol", &["solana"]),
+    ("atom", &["cosmos"]),
+    ("tia", &["celestia", "cosmos"]),
+    ("fet", &["fetch_ai", "cosmos"]),
+    ("inj", &["injective", "cosmos"]),
+    ("kava", &["kava", "cosmos"]),
+    ("osmo", &["osmosis", "cosmos"]),
+    ("zeta", &["zetachain", "cosmos"]),
+    ("dydx", &["dydx", "cosmos"]),
+    ("noble", &["noble", "cosmos"]),
+    ("ada", &["cardano"]),
+    ("dot", &["polkadot"]),
+    ("ksm", &["kusama"]),
+    ("near", &["near"]),
+    ("pol", &["polygon"]),
+    ("xtz", &["tezos"]),
+    ("egld", &["multiversx"]),
+    ("defi", &["defi"]),
+];
+
+// `GET /v1/eth/stakes` is `ethereum/stakes.json`, `POST /v1/sol/transaction/prepare` is
+// `solana/prepare_tx.json`, `POST /v1/dot/transaction/bond` is `polkadot/bond_tx.json` or the
+// `polkadot/tx.json` every transaction crafting endpoint shares
+fn fixture_path(fixtures: &Path, method: Method, path: &str) -> Option<PathBuf> {
+    let path = path.strip_prefix("/v1/")?.trim_end_matches('/');
+
+    let existing = |directory: &str, names: &[String]| {
+        names
+            .iter()
+            .map(|name| fixtures.join(directory).join(format!("{name}.json")))
+            .find(|fixture| fixture.is_file())
+    };
+
+    match (method, path) {
+        (Method::Get, "accounts") => return existing("account", &["accounts".into()]),
+        (_, "accounts") => return existing("account", &["account".into()]),
+        (Method::Get, "deployments") => return existing("deployments", &["deployments".into()]),
+        _ => {}
+    }
+
+    // the reports are bytes, they have no fixture
+    if let Some(id) = path.strip_prefix("accounts/") {
+        return match id.split_once('/') {
+            None => existing("account", &["account".into()]),
+            Some((_, "portofolio")) => existing("account", &["portofolio".into()]),
+            Some(_) => None,
+        };
+    }
+
+    if let Some(id) = path.strip_prefix("organisations/") {
+        return (!id.contains('/'))
+            .then(|| existing("organisation", &["portofolio".into()]))
+            .flatten();
+    }
+
+    let (prefix, directories) = PROTOCOLS.iter().find(|(prefix, _)| {
+        path.strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    })?;
+
+    let endpoint = path[prefix.len()..].trim_start_matches('/');
+    let endpoint = endpoint
+        .strip_prefix("v1/")
+        .or_else(|| endpoint.strip_prefix("v2/"))
+        .unwrap_or(endpoint);
+
+    let name = match endpoint.strip_prefix("transaction/") {
+        Some("prepare") => "prepare_tx".to_owned(),
+        Some("broadcast") => "broadcast_tx".to_owned(),
+        Some("status") => "tx_status".to_owned(),
+        Some("decode") => "tx_decoding".to_owned(),
+        Some(action) => action.replace(['/', '-'], "_"),
+        None => endpoint.replace(['/', '-'], "_"),
+    };
+
+    let names = match method {
+        Method::Get => vec![name],
+        Method::Post | Method::Put | Method::Delete => vec![
+            format!("post_{name}"),
+            format!("{name}_tx"),
+            name,
+            "tx".to_owned(),
+        ],
+    };
+
+    directories
+        .iter()
+        .find_map(|directory| existing(directory, &names))
+}
+
+fn json_response(status: u16, body: Vec<u8>) -> HttpResponse {
+    HttpResponse {
+        status,
+        headers: vec![("Content-Type".into(), "application/json".into())],
+        body,
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        422 => "Unprocessable Entity",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Unknown",
+    }
+}
+
+fn error_body(status: u16) -> String {
+    match status {
+        // the validation errors, one message per invalid field
+        422 => serde_json::json!({
+            "message": ["amount must be a positive integer string"],
+            "error": reason(status),
+            "errors": [{ "path": ["amount"], "message": "Expected a positive integer string" }],
+        }),
+        _ => serde_json::json!({ "message": reason(status) }),
+    }
+    .to_string()
+}
+
+fn write_response(stream: &mut TcpStream, response: &HttpResponse) -> io::Result<()> {
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\n",
+        response.status,
+        reason(response.status)
+    );
+
+    for (key, value) in &response.headers {
+        head.push_str(&format!("{key}: {value}\r\n"));
+    }
+
+    head.push_str(&format!(
+        "Content-Length: {}\r\nConnection: close\r\n\r\n",
+        response.body.len()
+    ));
+
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(&response.body)?;
+    stream.flush()
+}
+
+// Paginated stakes answered a page at a time, for the `Pages` tests of both clients.
+#[cfg(test)]
+mod pages_test_support {
+
+    use super::*;
+
+    fn fixture_stake(fixture: &str) -> serde_json::Value {
+        let file = format!("{}/fixtures/{fixture}", env!("CARGO_MANIFEST_DIR"));
+        let json: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(file).unwrap()).unwrap();
+
+        json["data"][0].clone()
+    }
+
+    // `kiln.ethereum().stakes().wallets(["0x0"])` one stake per page, its `validator_index` is
+    // the page number
+    pub(super) fn serve_eth_stakes(server: &MockServer, total_pages: u64) {
+        let stake = fixture_stake("ethereum/stakes.json");
+
+        for page in 1..=total_pages {
+            let mut stake = stake.clone();
+            stake["validator_index"] = page.to_string().into();
+
+            let body = serde_json::json!({
+                "data": [stake],
+                "pagination": {
+                    "current_page": page,
+                    "next_page": page + 1,
+                    "previous_page": page - 1,
+                    "page_size": 1,
+                    "total_pages": total_pages,
+                    "total_entries": total_pages,
+                },
+            });
+
+            server.respond(
+                Method::Get,
+                format!("/v1/eth/stakes?wallets=0x0&current_page={page}&page_size=1"),
+                200,
+                body.to_string(),
+            );
+        }
+    }
+
+    // `kiln.cardano().stakes().wallets(["addr1a"])` two stakes per page, then a last page of one :
+    // "stake_1", "stake_2", "stake_3"
+    pub(super) fn serve_ada_stakes(server: &MockServer) {
+        let stake = fixture_stake("cardano/stakes.json");
+
+        let page = |stakes: std::ops::RangeInclusive<u64>| {
+            let data: Vec<serde_json::Value> = stakes
+                .map(|index| {
+                    let mut stake = stake.clone();
+                    stake["stake_address"] = format!("stake_{index}").into();
+                    stake
+                })
+                .collect();
+
+            serde_json::json!({ "data": data }).to_string()
+        };
+
+        for (current_page, stakes) in [(1, 1..=2), (2, 3..=3)] {
+            server.respond(
+                Method::Get,
+                format!("/v1/ada/stakes?wallets=addr1a&page_size=2&current_page={current_page}"),
+                200,
+                page(stakes),
+            );
+        }
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod mock_test {
+
+    use std::num::NonZeroU64;
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::transport::HttpOptions;
+    use crate::{Kiln, KilnError, PageOptions, RetryPolicy};
+
+    const ACCOUNT_ID: &str = "9e1c62a4-2b01-4cd8-a2a4-74c57dcd2be4";
+
+    fn kiln_for(server: &MockServer) -> Kiln {
+        Kiln::builder()
+            .api_token("kiln_test_token")
+            .base_url(&server.url())
+            .seal()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn serves_the_fixtures_to_the_client() {
+        let server = MockServer::builder()
+            .api_token("kiln_test_token")
+            .start()
+            .unwrap();
+
+        let kiln = kiln_for(&server);
+
+        let stakes = kiln.ethereum().stakes().wallets(["0x0"]).send().unwrap();
+        assert!(!stakes.data.is_empty());
+
+        let account = kiln.accounts().post("Tanjiro", "Kamado").unwrap();
+        assert_eq!(account.data.id, uuid::Uuid::from_str(ACCOUNT_ID).unwrap());
+
+        kiln.solana().get_network_stats().unwrap();
+        kiln.celestia().get_network_stats().unwrap();
+        kiln.deployments().get().unwrap();
+
+        let requests = server.requests();
+
+        assert_eq!(requests.len(), 5);
+        assert_eq!(requests[0].path(), "/v1/eth/stakes");
+        assert!(requests[0].url.contains("wallets=0x0"));
+        assert_eq!(requests[1].method, Method::Post);
+        assert!(requests[1].body.is_some());
+    }
+
+    #[test]
+    fn pages_are_fetched_until_the_last_one() {
+        let server = MockServer::start().unwrap();
+
+        pages_test_support::serve_eth_stakes(&server, 3);
+        pages_test_support::serve_ada_stakes(&server);
+
+        let kiln = kiln_for(&server);
+
+        let eth_stakes: Vec<String> = kiln
+            .ethereum()
+            .stakes()
+            .wallets(["0x0"])
+            .pages(PageOptions::default().page_size(NonZeroU64::MIN))
+            .map(|stake| stake.map(|stake| stake.validator_index))
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(eth_stakes, ["1", "2", "3"]);
+
+        let ada_stakes: Vec<String> = kiln
+            .cardano()
+            .stakes()
+            .wallets(["addr1a"])
+            .pages(PageOptions::default().page_size(NonZeroU64::new(2).unwrap()))
+            .map(|stake| stake.map(|stake| stake.stake_address))
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(ada_stakes, ["stake_1", "stake_2", "stake_3"]);
+        assert_eq!(server.requests().len(), 5);
+    }
+
+    #[test]
+    fn fixture_paths_follow_the_urls() {
+        let fixtures = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures"));
+
+        let fixture = |method, path: &str| {
+            fixture_path(fixtures, method, path)
+                .map(|fixture| fixture.strip_prefix(fixtures).unwrap().to_owned())
+        };
+
+        assert_eq!(
+            fixture(Method::Get, "/v1/eth/onchain/v2/stakes"),
+            Some("ethereum_onchain/stakes.json".into())
+        );
+        assert_eq!(
+            fixture(Method::Get, "/v1/eth/eigenlayer/liquid/withdrawals"),
+            Some("eigenlayer/liquid_withdrawals.json".into())
+        );
+        assert_eq!(
+            fixture(Method::Post, "/v1/sol/transaction/prepare"),
+            Some("solana/prepare_tx.json".into())
+        );
+        assert_eq!(
+            fixture(Method::Post, "/v1/eth/stake"),
+            Some("ethereum/stake_tx.json".into())
+        );
+        assert_eq!(
+            fixture(Method::Post, "/v1/dot/transaction/chill"),
+            Some("polkadot/chill_tx.json".into())
+        );
+        assert_eq!(
+            fixture(Method::Post, "/v1/dot/transaction/nominate"),
+            Some("polkadot/tx.json".into())
+        );
+        assert_eq!(
+            fixture(Method::Post, "/v1/noble/transaction/burn-usdc"),
+            Some("noble/burn_usdc_tx.json".into())
+        );
+        assert_eq!(
+            fixture(Method::Post, "/v1/sol/stakes"),
+            Some("solana/post_stakes.json".into())
+        );
+        assert_eq!(
+            fixture(Method::Get, "/v1/tia/stakes"),
+            Some("cosmos/stakes.json".into())
+        );
+        assert_eq!(fixture(Method::Get, "/v1/solana/stakes"), None);
+        assert_eq!(
+            fixture(Method::Get, &format!("/v1/accounts/{ACCOUNT_ID}/reports")),
+            None
+        );
+        assert_eq!(
+            fixture(
+                Method::Get,
+                &format!("/v1/accounts/{ACCOUNT_ID}/portofolio")
+            ),
+            Some("account/portofolio.json".into())
+        );
+        assert_eq!(
+            fixture(Method::Get, &format!("/v1/organisations/{ACCOUNT_ID}")),
+            Some("organisation/portofolio.json".into())
+        );
+    }
+
+    #[test]
+    fn wrong_token_is_unauthorized() {
+        let server = MockServer::builder()
+            .api_token("another_token")
+            .start()
+            .unwrap();
+
+        let error = kiln_for(&server).deployments().get().unwrap_err();
+
+        assert_eq!(error.status(), Some(401));
+        assert!(error.is_client_error());
+    }
+
+    #[test]
+    fn rate_limit_is_retried_then_answered() {
+        let server = MockServer::start().unwrap();
+
+        server.inject(Fault::status(429).retry_after(0).path("/v1/sol").times(2));
+
+        let kiln = Kiln::builder()
+            .api_token("kiln_test_token")
+            .base_url(&server.url())
+            .retry_policy(
+                RetryPolicy::default()
+                    .max_attempts(3)
+                    .initial_backoff(Duration::ZERO),
+            )
+            .seal()
+            .build()
+            .unwrap();
+
+        // the other paths are not affected
+        kiln.deployments().get().unwrap();
+        kiln.solana().get_network_stats().unwrap();
+
+        assert_eq!(server.requests().len(), 4);
+    }
+
+    #[test]
+    fn validation_errors_keep_their_details() {
+        let server = MockServer::start().unwrap();
+
+        server.inject(Fault::status(422));
+
+        let error = kiln_for(&server).accounts().post("", "").unwrap_err();
+
+        match error {
+            KilnError::Api(api_error) => {
+                assert_eq!(api_error.status, 422);
+                assert_eq!(
+                    api_error.message.as_deref(),
+                    Some("amount must be a positive integer string")
+                );
+                assert!(api_error.details.is_some());
+            }
+            other => panic!("expected an api error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn server_errors_are_retryable() {
+        let server = MockServer::start().unwrap();
+
+        server.inject(Fault::status(500));
+
+        let error = kiln_for(&server).deployments().get().unwrap_err();
+
+        assert_eq!(error.status(), Some(500));
+        assert!(error.is_retryable());
+    }
+
+    #[test]
+    fn slow_responses_time_out() {
+        let server = MockServer::start().unwrap();
+
+        server.inject(Fault::delay(Duration::from_millis(500)).times(1));
+
+        let kiln = Kiln::builder()
+            .api_token("kiln_test_token")
+            .base_url(&server.url())
+            .http_options(HttpOptions::default().timeout(Some(Duration::from_millis(50))))
+            .seal()
+            .build()
+            .unwrap();
+
+        let error = kiln.deployments().get().unwrap_err();
+
+        assert!(matches!(error, KilnError::Transport(_)));
+
+        // the delay was used up
+        kiln.deployments().get().unwrap();
+    }
+
+    #[test]
+    fn responses_can_be_overridden() {
+        let server = MockServer::start().unwrap();
+
+        server.respond(Method::Get, "/v1/deployments", 200, r#"{"data":[]}"#);
+
+        assert!(kiln_for(&server)
+            .deployments()
+            .get()
+            .unwrap()
+            .data
+            .is_empty());
+
+        server.reset();
+
+        assert!(!kiln_for(&server)
+            .deployments()
+            .get()
+            .unwrap()
+            .data
+            .is_empty());
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn unknown_paths_are_not_found() {
+        let server = MockServer::start().unwrap();
+
+        let account_id = uuid::Uuid::from_str(ACCOUNT_ID).unwrap();
+
+        let error = kiln_for(&server)
+            .accounts()
+            .get_reports(account_id)
+            .unwrap_err();
+
+        assert_eq!(error.status(), Some(404));
+    }
+}
+
+// the same server, driven through the ReqwestTransport of the `async` feature
+#[cfg(all(test, feature = "async"))]
+mod mock_async_test {
+
+    use std::num::NonZeroU64;
+    use std::pin::Pin;
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use futures_core::Stream;
+
+    use super::*;
+    use crate::transport::{HttpOptions, ReqwestTransport};
+    use crate::{Async, AsyncKiln, Kiln, KilnError, PageOptions, Pages, RetryPolicy};
+
+    fn kiln_for(server: &MockServer) -> AsyncKiln {
+        Kiln::builder()
+            .api_token("kiln_test_token")
+            .base_url(&server.url())
+            .async_transport(ReqwestTransport::default())
+            .seal()
+            .build_async()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn serves_the_fixtures_to_the_client() {
+        let server = MockServer::builder()
+            .api_token("kiln_test_token")
+            .start()
+            .unwrap();
+
+        let kiln = kiln_for(&server);
+
+        let stakes = kiln
+            .ethereum()
+            .stakes()
+            .wallets(["0x0"])
+            .send()
+            .await
+            .unwrap();
+        assert!(!stakes.data.is_empty());
+
+        let account = kiln.accounts().post("Tanjiro", "Kamado").await.unwrap();
+        assert_eq!(
+            account.data.id,
+            uuid::Uuid::from_str("9e1c62a4-2b01-4cd8-a2a4-74c57dcd2be4").unwrap()
+        );
+
+        kiln.solana().get_network_stats().await.unwrap();
+
+        let requests = server.requests();
+
+        assert_eq!(requests.len(), 3);
+        assert!(requests[0]
+            .headers
+            .iter()
+            .any(|(key, value)| key.eq_ignore_ascii_case("authorization")
+                && value == "Bearer kiln_test_token"));
+        assert_eq!(requests[1].method, Method::Post);
+        assert!(requests[1].body.is_some());
+    }
+
+    // `StreamExt::collect` without pulling in futures-util
+    async fn collect<T>(mut pages: Pages<T, Async>) -> Result<Vec<T>, KilnError> {
+        let mut items = Vec::new();
+
+        while let Some(item) = std::future::poll_fn(|cx| Pin::new(&mut pages).poll_next(cx)).await {
+            items.push(item?);
+        }
+
+        Ok(items)
+    }
+
+    #[tokio::test]
+    async fn pages_are_streamed_until_the_last_one() {
+        let server = MockServer::start().unwrap();
+
+        pages_test_support::serve_eth_stakes(&server, 3);
+        pages_test_support::serve_ada_stakes(&server);
+
+        let kiln = kiln_for(&server);
+
+        let eth_stakes = collect(
+            kiln.ethereum()
+                .stakes()
+                .wallets(["0x0"])
+                .pages(PageOptions::default().page_size(NonZeroU64::MIN)),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            eth_stakes
+                .iter()
+                .map(|stake| stake.validator_index.as_str())
+                .collect::<Vec<_>>(),
+            ["1", "2", "3"]
+        );
+
+        let ada_stakes = collect(
+            kiln.cardano()
+                .stakes()
+                .wallets(["addr1a"])
+                .pages(PageOptions::default().page_size(NonZeroU64::new(2).unwrap())),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            ada_stakes
+                .iter()
+                .map(|stake| stake.stake_address.as_str())
+                .collect::<Vec<_>>(),
+            ["stake_1", "stake_2", "stake_3"]
+        );
+        assert_eq!(server.requests().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn rate_limit_is_retried_then_answered() {
+        let server = MockServer::start().unwrap();
+
+        server.inject(Fault::status(429).retry_after(0).times(2));
+
+        let kiln = Kiln::builder()
+            .api_token("kiln_test_token")
+            .base_url(&server.url())
+            .retry_policy(
+                RetryPolicy::default()
+                    .max_attempts(3)
+                    .initial_backoff(Duration::ZERO),
+            )
+            .seal()
+            .build_async()
+            .unwrap();
+
+        kiln.deployments().get().await.unwrap();
+
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn api_errors_keep_their_status_and_details() {
+        let server = MockServer::start().unwrap();
+
+        server.inject(Fault::status(422).times(1));
+        server.inject(Fault::status(500).times(1));
+
+        let kiln = kiln_for(&server);
+
+        match kiln.accounts().post("", "").await.unwrap_err() {
+            KilnError::Api(api_error) => {
+                assert_eq!(api_error.status, 422);
+                assert!(api_error.details.is_some());
+            }
+            other => panic!("expected an api error, got {other:?}"),
+        }
+
+        let error = kiln.deployments().get().await.unwrap_err();
+
+        assert_eq!(error.status(), Some(500));
+        assert!(error.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn slow_responses_time_out() {
+        let server = MockServer::start().unwrap();
+
+        server.inject(Fault::delay(Duration::from_millis(500)).times(1));
+
+        let kiln = Kiln::builder()
+            .api_token("kiln_test_token")
+            .base_url(&server.url())
+            .http_options(HttpOptions::default().timeout(Some(Duration::from_millis(50))))
+            .seal()
+            .build_async()
+            .unwrap();
+
+        let error = kiln.deployments().get().await.unwrap_err();
+
+        assert!(matches!(error, KilnError::Transport(_)));
+
+        kiln.deployments().get().await.unwrap();
+    }
+
+    // the features add up : enabling `async` leaves the blocking client as it was
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn blocking_client_is_still_there() {
+        let server = MockServer::start().unwrap();
+
+        let kiln: Kiln = Kiln::builder()
+            .api_token("kiln_test_token")
+            .base_url(&server.url())
+            .seal()
+            .build()
+            .unwrap();
+
+        kiln.deployments().get().unwrap();
+
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn transport_of_the_other_mode_is_rejected() {
+        let error = Kiln::builder()
+            .api_token("kiln_test_token")
+            .base_url("https://api.kiln.fi/v1")
+            .async_transport(ReqwestTransport::default())
+            .seal()
+            .build()
+            .unwrap_err();
+
+        assert!(matches!(error, KilnError::InvalidInput(_)));
+    }
+}
@@ -0,0 +1,100 @@
+use std::fmt::Debug;
+
+// Whether a Kiln, and the Kiln*Client, builders and Pages made from it, block or return futures.
+// `Kiln` is `Kiln<Blocking>` (the `blocking` feature, on by default), `AsyncKiln` is `Kiln<Async>`
+// (the `async` feature) : both can be enabled at once.
+pub trait Mode: sealed::Sealed + Clone + Debug + Send + Sync + 'static {}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Blocking;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Async;
+
+impl Mode for Blocking {}
+
+#[cfg(feature = "async")]
+impl Mode for Async {}
+
+pub(crate) mod sealed {
+    use std::num::NonZeroU64;
+
+    use crate::errors::KilnError;
+    use crate::pagination::Page;
+
+    // What differs between the modes, out of reach of the callers
+    pub trait Sealed {
+        type Transport: ?Sized + std::fmt::Debug + Send + Sync;
+        type TokenProvider: ?Sized + std::fmt::Debug + Send + Sync;
+        // how `Pages` fetches its pages
+        type Fetch<T>: Send;
+    }
+
+    impl Sealed for super::Blocking {
+        type Transport = dyn crate::transport::Transport;
+        type TokenProvider = dyn crate::token::TokenProvider;
+        type Fetch<T> = Box<dyn FnMut(NonZeroU64) -> Result<Page<T>, KilnError> + Send>;
+    }
+
+    #[cfg(feature = "async")]
+    impl Sealed for super::Async {
+        type Transport = dyn crate::transport::AsyncTransport;
+        type TokenProvider = dyn crate::token::AsyncTokenProvider;
+        type Fetch<T> = crate::pagination::AsyncFetch<T>;
+    }
+}
+
+// The names `for_each_mode!` brings in scope of the blocking items
+#[cfg(feature = "blocking")]
+pub(crate) mod blocking {
+    pub(crate) use std::thread::sleep;
+
+    #[cfg(feature = "tracing")]
+    pub(crate) fn in_span<T>(span: &tracing::Span, call: impl FnOnce() -> T) -> T {
+        span.in_scope(call)
+    }
+}
+
+// The names `for_each_mode!` brings in scope of the async items
+#[cfg(feature = "async")]
+pub(crate) mod nonblocking {
+    pub(crate) use tokio::time::sleep;
+
+    #[cfg(feature = "tracing")]
+    pub(crate) async fn in_span<F: std::future::Future>(
+        span: &tracing::Span,
+        call: impl FnOnce() -> F,
+    ) -> F::Output {
+        tracing::Instrument::instrument(call(), span.clone()).await
+    }
+}
+
+// Compiles the items once per enabled mode, `<M>` naming the mode inside them :
+// stripped of their `async` and `.await` for `Blocking`, kept as they are for `Async`.
+macro_rules! for_each_mode {
+    (<$mode:ident> $($item:item)*) => {
+        #[cfg(feature = "blocking")]
+        const _: () = {
+            #[allow(dead_code)]
+            type $mode = $crate::mode::Blocking;
+
+            #[allow(unused_imports)]
+            use $crate::mode::blocking::*;
+
+            $(#[maybe_async::must_be_sync] $item)*
+        };
+
+        #[cfg(feature = "async")]
+        const _: () = {
+            #[allow(dead_code)]
+            type $mode = $crate::mode::Async;
+
+            #[allow(unused_imports)]
+            use $crate::mode::nonblocking::*;
+
+            $(#[maybe_async::must_be_async] $item)*
+        };
+    };
+}
+
+pub(crate) use for_each_mode;
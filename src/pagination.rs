@@ -0,0 +1,275 @@
+use std::collections::VecDeque;
+use std::num::NonZeroU64;
+
+#[cfg(feature = "async")]
+use std::{future::Future, pin::Pin, task::Context, task::Poll};
+
+use crate::errors::KilnError;
+#[cfg(feature = "async")]
+use crate::mode::Async;
+use crate::mode::{Blocking, Mode};
+
+pub(crate) const DEFAULT_PAGE_SIZE: NonZeroU64 = match NonZeroU64::new(25) {
+    Some(page_size) => page_size,
+    None => NonZeroU64::MIN,
+};
+
+// How `iter_*` walks the pages : `page_size` items per request, stops after `max_items` if set
+#[derive(Clone, Copy, Debug)]
+pub struct PageOptions {
+    pub(crate) page_size: NonZeroU64,
+    pub(crate) max_items: Option<usize>,
+}
+
+impl Default for PageOptions {
+    fn default() -> Self {
+        Self {
+            page_size: DEFAULT_PAGE_SIZE,
+            max_items: None,
+        }
+    }
+}
+
+impl PageOptions {
+    pub fn page_size(self, page_size: NonZeroU64) -> Self {
+        Self { page_size, ..self }
+    }
+
+    pub fn max_items(self, max_items: usize) -> Self {
+        Self {
+            max_items: Some(max_items),
+            ..self
+        }
+    }
+}
+
+// One fetched page, `last` once the api says there's nothing after it
+pub struct Page<T> {
+    pub(crate) items: Vec<T>,
+    pub(crate) last: bool,
+}
+
+#[cfg(feature = "async")]
+type PageFuture<T> = Pin<Box<dyn Future<Output = Result<Page<T>, KilnError>> + Send>>;
+
+// with the page being fetched, if any
+#[cfg(feature = "async")]
+pub struct AsyncFetch<T> {
+    fetch: Box<dyn FnMut(NonZeroU64) -> PageFuture<T> + Send>,
+    in_flight: Option<PageFuture<T>>,
+}
+
+// Items of a paginated endpoint, a page is only fetched once the previous one is consumed.
+// An `Iterator` from a `Kiln`, a `Stream` from an `AsyncKiln`.
+// Stops after the first error.
+pub struct Pages<T, M: Mode = Blocking> {
+    fetch: M::Fetch<T>,
+    items: VecDeque<T>,
+    next_page: Option<NonZeroU64>,
+    remaining: Option<usize>,
+}
+
+impl<T, M: Mode> std::fmt::Debug for Pages<T, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pages")
+            .field("buffered", &self.items.len())
+            .field("next_page", &self.next_page)
+            .field("remaining", &self.remaining)
+            .finish()
+    }
+}
+
+// nothing is pinned in place, the in flight page is boxed
+impl<T, M: Mode> Unpin for Pages<T, M> {}
+
+#[cfg(feature = "blocking")]
+impl<T> Pages<T, Blocking> {
+    pub(crate) fn new(
+        first_page: NonZeroU64,
+        options: PageOptions,
+        fetch: impl FnMut(NonZeroU64) -> Result<Page<T>, KilnError> + Send + 'static,
+    ) -> Self {
+        Self {
+            fetch: Box::new(fetch),
+            items: VecDeque::new(),
+            next_page: Some(first_page),
+            remaining: options.max_items,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> Pages<T, Async> {
+    pub(crate) fn new<F>(
+        first_page: NonZeroU64,
+        options: PageOptions,
+        mut fetch: impl FnMut(NonZeroU64) -> F + Send + 'static,
+    ) -> Self
+    where
+        F: Future<Output = Result<Page<T>, KilnError>> + Send + 'static,
+    {
+        Self {
+            fetch: AsyncFetch {
+                fetch: Box::new(move |page| Box::pin(fetch(page))),
+                in_flight: None,
+            },
+            items: VecDeque::new(),
+            next_page: Some(first_page),
+            remaining: options.max_items,
+        }
+    }
+}
+
+impl<T, M: Mode> Pages<T, M> {
+    // None once the cap is reached
+    fn pop(&mut self) -> Option<T> {
+        if self.remaining == Some(0) {
+            return None;
+        }
+
+        let item = self.items.pop_front()?;
+
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining -= 1;
+        }
+
+        Some(item)
+    }
+
+    fn exhausted(&self) -> bool {
+        self.remaining == Some(0) || (self.items.is_empty() && self.next_page.is_none())
+    }
+
+    // Err ends the iteration
+    fn push_page(&mut self, page: Result<Page<T>, KilnError>) -> Result<(), KilnError> {
+        let current_page = self.next_page.take();
+
+        let page = page?;
+
+        if !page.last {
+            self.next_page = current_page.and_then(|current_page| current_page.checked_add(1));
+        }
+
+        self.items.extend(page.items);
+
+        Ok(())
+    }
+}
+
+impl<T> Iterator for Pages<T, Blocking> {
+    type Item = Result<T, KilnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.pop() {
+                return Some(Ok(item));
+            }
+
+            if self.exhausted() {
+                return None;
+            }
+
+            let page = self.next_page?;
+            let fetched = (self.fetch)(page);
+
+            if let Err(error) = self.push_page(fetched) {
+                return Some(Err(error));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> futures_core::Stream for Pages<T, Async> {
+    type Item = Result<T, KilnError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        loop {
+            if let Some(item) = this.pop() {
+                return Poll::Ready(Some(Ok(item)));
+            }
+
+            if let Some(in_flight) = this.fetch.in_flight.as_mut() {
+                let fetched = std::task::ready!(in_flight.as_mut().poll(cx));
+                this.fetch.in_flight = None;
+
+                if let Err(error) = this.push_page(fetched) {
+                    return Poll::Ready(Some(Err(error)));
+                }
+
+                continue;
+            }
+
+            if this.exhausted() {
+                return Poll::Ready(None);
+            }
+
+            let Some(page) = this.next_page else {
+                return Poll::Ready(None);
+            };
+
+            this.fetch.in_flight = Some((this.fetch.fetch)(page));
+        }
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod pagination_test {
+
+    use super::*;
+
+    fn pages(
+        total_pages: u64,
+        options: PageOptions,
+    ) -> (Pages<u64>, std::sync::mpsc::Receiver<u64>) {
+        let (fetched, pages_fetched) = std::sync::mpsc::channel();
+        let page_size = options.page_size.get();
+
+        let pages = Pages::<_, Blocking>::new(NonZeroU64::MIN, options, move |page: NonZeroU64| {
+            let _ = fetched.send(page.get());
+            let first = (page.get() - 1) * page_size;
+
+            Ok(Page {
+                items: (first..first + page_size).collect(),
+                last: page.get() >= total_pages,
+            })
+        });
+
+        (pages, pages_fetched)
+    }
+
+    #[test]
+    fn walks_every_page() {
+        let page_size = NonZeroU64::new(2).unwrap();
+        let (pages, pages_fetched) = pages(3, PageOptions::default().page_size(page_size));
+
+        let items: Vec<u64> = pages.collect::<Result<_, _>>().unwrap();
+
+        assert_eq!(items, vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(pages_fetched.try_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn stops_fetching_at_the_cap() {
+        let page_size = NonZeroU64::new(2).unwrap();
+        let options = PageOptions::default().page_size(page_size).max_items(3);
+        let (pages, pages_fetched) = pages(10, options);
+
+        let items: Vec<u64> = pages.collect::<Result<_, _>>().unwrap();
+
+        assert_eq!(items, vec![0, 1, 2]);
+        assert_eq!(pages_fetched.try_iter().collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn stops_after_an_error() {
+        let mut pages = Pages::<u64>::new(NonZeroU64::MIN, PageOptions::default(), |_| {
+            Err(KilnError::InvalidInput("boom".into()))
+        });
+
+        assert!(matches!(pages.next(), Some(Err(_))));
+        assert!(pages.next().is_none());
+    }
+}
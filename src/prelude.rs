@@ -1,3 +1,3 @@
-pub use crate::errors::Error;
+pub use crate::errors::KilnError;
 
-pub type Result<T> = core::result::Result<T, Error>;
+pub type Result<T> = core::result::Result<T, KilnError>;
@@ -0,0 +1,235 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use url::form_urlencoded::byte_serialize;
+
+// Query string of the GET endpoints.
+// Values are percent-encoded, lists are comma-joined and left out when empty, so are the unset
+// options, dates are RFC3339 (`2024-01-31T00:00:00Z`).
+#[derive(Debug, Default)]
+pub(crate) struct Query {
+    pairs: Vec<(&'static str, String)>,
+}
+
+impl Query {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn value(mut self, key: &'static str, value: impl QueryValue) -> Self {
+        self.pairs.push((key, encode(&value.to_query_value())));
+        self
+    }
+
+    pub(crate) fn option(self, key: &'static str, value: Option<impl QueryValue>) -> Self {
+        match value {
+            Some(value) => self.value(key, value),
+            None => self,
+        }
+    }
+
+    pub(crate) fn list<V: QueryValue>(
+        mut self,
+        key: &'static str,
+        values: impl IntoIterator<Item = V>,
+    ) -> Self {
+        // commas separate the items, they stay unencoded
+        let joined = values
+            .into_iter()
+            .map(|value| encode(&value.to_query_value()))
+            .collect::<Vec<String>>()
+            .join(",");
+
+        if !joined.is_empty() {
+            self.pairs.push((key, joined));
+        }
+
+        self
+    }
+
+    // `{base}?{query}`, or just `{base}` when there's nothing to send
+    pub(crate) fn url(&self, base: &str) -> String {
+        if self.pairs.is_empty() {
+            return base.to_owned();
+        }
+
+        let query = self
+            .pairs
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<String>>()
+            .join("&");
+
+        format!("{base}?{query}")
+    }
+}
+
+fn encode(value: &str) -> String {
+    byte_serialize(value.as_bytes()).collect()
+}
+
+pub(crate) trait QueryValue {
+    fn to_query_value(&self) -> String;
+}
+
+impl<T: QueryValue + ?Sized> QueryValue for &T {
+    fn to_query_value(&self) -> String {
+        (**self).to_query_value()
+    }
+}
+
+impl QueryValue for str {
+    fn to_query_value(&self) -> String {
+        self.to_owned()
+    }
+}
+
+impl QueryValue for String {
+    fn to_query_value(&self) -> String {
+        self.clone()
+    }
+}
+
+impl QueryValue for DateTime<Utc> {
+    fn to_query_value(&self) -> String {
+        self.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+    }
+}
+
+macro_rules! display_query_value {
+    ($($type:ty),*) => {
+        $(
+            impl QueryValue for $type {
+                fn to_query_value(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+display_query_value!(bool, u32, u64, i64, usize, std::num::NonZeroU64, uuid::Uuid);
+
+// The url a Kiln*Client call sends, for the endpoint tests of the sdk modules.
+#[cfg(all(test, feature = "blocking"))]
+pub(crate) mod query_test_support {
+
+    use std::sync::{Arc, Mutex};
+
+    use crate::transport::{HttpRequest, HttpResponse, Transport, TransportError};
+    use crate::Kiln;
+
+    pub(crate) const BASE_URL: &str = "https://api.kiln.fi/v1";
+
+    #[derive(Debug, Default)]
+    struct Recorder {
+        urls: Mutex<Vec<String>>,
+    }
+
+    impl Transport for Arc<Recorder> {
+        fn send(&self, request: HttpRequest) -> Result<HttpResponse, TransportError> {
+            self.urls.lock().unwrap().push(request.url);
+
+            // whatever the endpoint, only the url matters here
+            Ok(HttpResponse {
+                status: 500,
+                headers: Vec::new(),
+                body: Vec::new(),
+            })
+        }
+    }
+
+    pub(crate) fn requested_url<R>(call: impl FnOnce(&Kiln) -> R) -> String {
+        let recorder = Arc::new(Recorder::default());
+
+        let kiln = Kiln::builder()
+            .api_token("kiln_test_token")
+            .base_url(BASE_URL)
+            .transport(Arc::clone(&recorder))
+            .seal()
+            .build()
+            .unwrap();
+
+        let _ = call(&kiln);
+
+        let urls = recorder.urls.lock().unwrap();
+
+        assert_eq!(urls.len(), 1, "expected exactly one request");
+
+        urls[0].clone()
+    }
+
+    pub(crate) fn date(rfc3339: &str) -> chrono::DateTime<chrono::Utc> {
+        rfc3339.parse().unwrap()
+    }
+
+    // a json file of `fixtures/`, deserialized like a response body : panics with the json
+    // path of the field that doesn't match the model
+    pub(crate) fn fixture<T: serde::de::DeserializeOwned>(path: &str) -> T {
+        let file = format!("{}/fixtures/{path}", env!("CARGO_MANIFEST_DIR"));
+        let body = std::fs::read(&file).unwrap_or_else(|error| panic!("{file}: {error}"));
+        let deserializer = &mut serde_json::Deserializer::from_slice(&body);
+
+        serde_path_to_error::deserialize(deserializer)
+            .unwrap_or_else(|error| panic!("{path}: `{}` {}", error.path(), error.inner()))
+    }
+}
+
+#[cfg(test)]
+mod query_test {
+
+    use super::*;
+
+    #[test]
+    fn values_are_percent_encoded() {
+        let url = Query::new()
+            .value("tx_serialized", "a b&c=d/+")
+            .url("https://api.kiln.fi/v1/eth/transaction/decode");
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/transaction/decode?tx_serialized=a+b%26c%3Dd%2F%2B"
+        );
+    }
+
+    #[test]
+    fn lists_are_comma_joined_and_omitted_when_empty() {
+        let url = Query::new()
+            .list("validators", ["0xa", "0xb"])
+            .list("wallets", Vec::<String>::new())
+            .list("validator_indexes", [1u64, 2])
+            .url("https://api.kiln.fi/v1/eth/stakes");
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/stakes?validators=0xa,0xb&validator_indexes=1,2"
+        );
+    }
+
+    #[test]
+    fn dates_are_rfc3339() {
+        let date: DateTime<Utc> = "2024-01-31T10:00:00+02:00".parse().unwrap();
+
+        let url = Query::new().value("start_date", date).url("/rewards");
+
+        assert_eq!(url, "/rewards?start_date=2024-01-31T08%3A00%3A00Z");
+    }
+
+    #[test]
+    fn unset_options_are_left_out() {
+        let url = Query::new()
+            .option("end_date", None::<DateTime<Utc>>)
+            .option("end_epoch", Some(20u64))
+            .url("/rewards");
+
+        assert_eq!(url, "/rewards?end_epoch=20");
+    }
+
+    #[test]
+    fn empty_query_is_left_out() {
+        assert_eq!(
+            Query::new()
+                .list("wallets", Vec::<String>::new())
+                .url("/stakes"),
+            "/stakes"
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// `requests` per `period`, with bursts of up to `burst` requests (defaults to `requests`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RateLimit {
+    requests: u32,
+    period: Duration,
+    burst: u32,
+}
+
+impl RateLimit {
+    pub fn new(requests: u32, period: Duration) -> Self {
+        let requests = requests.max(1);
+
+        Self {
+            requests,
+            period,
+            burst: requests,
+        }
+    }
+
+    pub fn per_second(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(1))
+    }
+
+    pub fn per_minute(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(60))
+    }
+
+    pub fn burst(self, burst: u32) -> Self {
+        Self {
+            burst: burst.max(1),
+            ..self
+        }
+    }
+
+    fn tokens_per_second(&self) -> f64 {
+        self.requests as f64 / self.period.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+#[derive(Debug)]
+struct BucketState {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    limit: RateLimit,
+    state: Mutex<BucketState>,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            state: Mutex::new(BucketState {
+                tokens: limit.burst as f64,
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    // Takes a token and returns how long to wait before using it.
+    // The token is taken even when the bucket is empty, so concurrent callers queue up
+    // instead of all waking up at the same time.
+    fn acquire(&self) -> Duration {
+        // a poisoned lock only means another thread panicked while holding it, the state stays valid
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let now = Instant::now();
+        let rate = self.limit.tokens_per_second();
+
+        let elapsed = now.duration_since(state.refilled_at).as_secs_f64();
+
+        state.tokens = (state.tokens + elapsed * rate).min(self.limit.burst as f64);
+        state.refilled_at = now;
+        state.tokens -= 1.0;
+
+        if state.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-state.tokens / rate)
+        }
+    }
+}
+
+// The budget a request is charged to, given by the HttpClient method the Kiln*Client calls
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Budget {
+    // the reads, and the writes that don't craft anything (accounts, stakes tagging...)
+    Read,
+    // crafting, preparing and broadcasting transactions, whatever the chain and the path
+    Transaction,
+}
+
+// Shared by every Kiln*Client created from the same Kiln (and its clones).
+#[derive(Debug, Default)]
+pub(crate) struct RateLimiter {
+    read: Option<TokenBucket>,
+    transaction: Option<TokenBucket>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(read: Option<RateLimit>, transaction: Option<RateLimit>) -> Self {
+        Self {
+            read: read.map(TokenBucket::new),
+            transaction: transaction.map(TokenBucket::new),
+        }
+    }
+
+    // How long a request of this budget must wait before being sent.
+    pub(crate) fn acquire(&self, budget: Budget) -> Duration {
+        let bucket = match budget {
+            Budget::Read => &self.read,
+            Budget::Transaction => &self.transaction,
+        };
+
+        bucket
+            .as_ref()
+            .map(TokenBucket::acquire)
+            .unwrap_or(Duration::ZERO)
+    }
+}
+
+#[cfg(test)]
+mod rate_limit_test {
+
+    use super::*;
+
+    #[test]
+    fn burst_is_free_then_requests_wait() {
+        let bucket = TokenBucket::new(RateLimit::per_second(10).burst(2));
+
+        assert_eq!(bucket.acquire(), Duration::ZERO);
+        assert_eq!(bucket.acquire(), Duration::ZERO);
+
+        let wait = bucket.acquire();
+
+        assert!(wait > Duration::from_millis(90));
+        assert!(wait <= Duration::from_millis(100));
+
+        // the next caller queues behind the previous one
+        assert!(bucket.acquire() > Duration::from_millis(190));
+    }
+
+    #[test]
+    fn transactions_have_their_own_budget() {
+        let limiter = RateLimiter::new(
+            Some(RateLimit::per_minute(1)),
+            Some(RateLimit::per_minute(1)),
+        );
+
+        assert_eq!(limiter.acquire(Budget::Read), Duration::ZERO);
+        assert_eq!(limiter.acquire(Budget::Transaction), Duration::ZERO);
+
+        assert!(limiter.acquire(Budget::Read) > Duration::from_secs(50));
+        assert!(limiter.acquire(Budget::Transaction) > Duration::from_secs(50));
+    }
+
+    #[test]
+    fn unset_budget_is_unlimited() {
+        let limiter = RateLimiter::new(None, Some(RateLimit::per_minute(1)));
+
+        for _ in 0..100 {
+            assert_eq!(limiter.acquire(Budget::Read), Duration::ZERO);
+        }
+    }
+}
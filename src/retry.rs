@@ -0,0 +1,285 @@
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use crate::errors::KilnError;
+use crate::transport::Method;
+
+// Applies to idempotent requests (GET, PUT, DELETE).
+// POST (transaction crafting, broadcasting...) is only retried with `retry_non_idempotent(true)`.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    respect_retry_after: bool,
+    max_retry_after: Duration,
+    retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(10),
+            jitter: true,
+            respect_retry_after: true,
+            max_retry_after: Duration::from_secs(60),
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    // a single attempt, what a Kiln gets when no policy is set
+    pub fn disabled() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    // total number of attempts, the first one included
+    pub fn max_attempts(self, max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            ..self
+        }
+    }
+
+    // doubled after each failed attempt, up to max_backoff
+    pub fn initial_backoff(self, initial_backoff: Duration) -> Self {
+        Self {
+            initial_backoff,
+            ..self
+        }
+    }
+
+    pub fn max_backoff(self, max_backoff: Duration) -> Self {
+        Self {
+            max_backoff,
+            ..self
+        }
+    }
+
+    // waits a random duration between half and all of the backoff
+    pub fn jitter(self, jitter: bool) -> Self {
+        Self { jitter, ..self }
+    }
+
+    // waits for the duration of the Retry-After header of 429/503 responses when present
+    pub fn respect_retry_after(self, respect_retry_after: bool) -> Self {
+        Self {
+            respect_retry_after,
+            ..self
+        }
+    }
+
+    // a longer Retry-After (a day, a far away date) is cut down to this
+    pub fn max_retry_after(self, max_retry_after: Duration) -> Self {
+        Self {
+            max_retry_after,
+            ..self
+        }
+    }
+
+    pub fn retry_non_idempotent(self, retry_non_idempotent: bool) -> Self {
+        Self {
+            retry_non_idempotent,
+            ..self
+        }
+    }
+
+    // How long to wait before the next attempt, None when the error must be returned.
+    // `attempt` is the number of the attempt that just failed, starting at 1.
+    pub(crate) fn delay(
+        &self,
+        method: Method,
+        attempt: u32,
+        error: &KilnError,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts || !error.is_retryable() {
+            return None;
+        }
+
+        if method == Method::Post && !self.retry_non_idempotent {
+            return None;
+        }
+
+        if self.respect_retry_after {
+            if let KilnError::Api(api_error) = error {
+                if let Some(retry_after) = api_error.retry_after {
+                    return Some(retry_after.min(self.max_retry_after));
+                }
+            }
+        }
+
+        let exponent: u32 = (attempt - 1).min(31);
+
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(exponent))
+            .min(self.max_backoff);
+
+        if !self.jitter {
+            return Some(backoff);
+        }
+
+        let half = backoff / 2;
+
+        let random: u64 = std::collections::hash_map::RandomState::new()
+            .build_hasher()
+            .finish();
+
+        let jitter = Duration::from_nanos(random % (half.as_nanos() as u64).max(1));
+
+        Some(half + jitter)
+    }
+}
+
+// Retry-After is either a number of seconds or an HTTP date
+pub(crate) fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+
+    (date.with_timezone(&chrono::Utc) - chrono::Utc::now())
+        .to_std()
+        .ok()
+}
+
+#[cfg(test)]
+mod retry_test {
+
+    use super::*;
+    use crate::errors::ApiError;
+    use crate::transport::HttpResponse;
+
+    fn api_error(status: u16, retry_after: Option<&str>) -> KilnError {
+        let headers = retry_after
+            .map(|value| vec![("Retry-After".to_owned(), value.to_owned())])
+            .unwrap_or_default();
+
+        ApiError::from_response(&HttpResponse {
+            status,
+            headers,
+            body: Vec::new(),
+        })
+        .into()
+    }
+
+    #[test]
+    fn backoff_is_exponential_and_capped() {
+        let policy = RetryPolicy::default()
+            .max_attempts(10)
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(500))
+            .jitter(false);
+
+        let error = api_error(503, None);
+
+        let delays: Vec<Option<Duration>> = (1..=4)
+            .map(|attempt| policy.delay(Method::Get, attempt, &error))
+            .collect();
+
+        assert_eq!(
+            delays,
+            vec![
+                Some(Duration::from_millis(100)),
+                Some(Duration::from_millis(200)),
+                Some(Duration::from_millis(400)),
+                Some(Duration::from_millis(500)),
+            ]
+        );
+    }
+
+    #[test]
+    fn jitter_stays_within_the_backoff() {
+        let policy = RetryPolicy::default().initial_backoff(Duration::from_millis(100));
+
+        let delay = policy.delay(Method::Get, 1, &api_error(500, None)).unwrap();
+
+        assert!(delay >= Duration::from_millis(50));
+        assert!(delay <= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn retry_after_wins_over_backoff() {
+        let policy = RetryPolicy::default();
+
+        let delay = policy.delay(Method::Get, 1, &api_error(429, Some("7")));
+
+        assert_eq!(delay, Some(Duration::from_secs(7)));
+    }
+
+    #[test]
+    fn retry_after_is_capped() {
+        let error = api_error(429, Some("86400"));
+
+        assert_eq!(
+            RetryPolicy::default().delay(Method::Get, 1, &error),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            RetryPolicy::default()
+                .max_retry_after(Duration::from_secs(5))
+                .delay(Method::Get, 1, &error),
+            Some(Duration::from_secs(5))
+        );
+    }
+
+    #[test]
+    fn stops_after_max_attempts() {
+        let policy = RetryPolicy::default().max_attempts(2);
+
+        let error = api_error(500, None);
+
+        assert!(policy.delay(Method::Get, 1, &error).is_some());
+        assert!(policy.delay(Method::Get, 2, &error).is_none());
+    }
+
+    #[test]
+    fn post_needs_an_explicit_opt_in() {
+        let error = api_error(503, None);
+
+        assert!(RetryPolicy::default()
+            .delay(Method::Post, 1, &error)
+            .is_none());
+
+        assert!(RetryPolicy::default()
+            .retry_non_idempotent(true)
+            .delay(Method::Post, 1, &error)
+            .is_some());
+    }
+
+    #[test]
+    fn client_errors_are_not_retried() {
+        let policy = RetryPolicy::default();
+
+        assert!(policy
+            .delay(Method::Get, 1, &api_error(422, None))
+            .is_none());
+        assert!(policy
+            .delay(Method::Get, 1, &api_error(401, None))
+            .is_none());
+    }
+
+    #[test]
+    fn parses_both_retry_after_formats() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+
+        let in_a_minute = (chrono::Utc::now() + chrono::Duration::seconds(60)).to_rfc2822();
+
+        let delay = parse_retry_after(&in_a_minute).unwrap();
+
+        assert!(delay <= Duration::from_secs(60));
+        assert!(delay > Duration::from_secs(50));
+
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+}
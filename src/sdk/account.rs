@@ -1,9 +1,13 @@
-use std::io::Read;
-
 use serde::{Deserialize, Serialize};
 
+use crate::errors::KilnError;
+use crate::http::HttpClient;
+use crate::mode::{for_each_mode, Blocking, Mode};
+use crate::query::Query;
 use crate::{response_format::ReturnedData, Kiln};
 
+use super::Portofolio;
+
 #[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct Account {
@@ -28,159 +32,148 @@ struct AccountPosttRequest {
 }
 
 #[derive(Clone, Debug)]
-pub struct KilnAccountClient {
-    bearer_token: String,
+pub struct KilnAccountClient<M: Mode = Blocking> {
     base_url: String,
+    http: HttpClient<M>,
 }
 
-impl From<&Kiln> for KilnAccountClient {
-    fn from(kiln: &Kiln) -> Self {
+impl<M: Mode> From<&Kiln<M>> for KilnAccountClient<M> {
+    fn from(kiln: &Kiln<M>) -> Self {
         let url: String = format!("{}/accounts", kiln.base_url);
-        let bearer_token: String = format!("Bearer {}", kiln.api_token);
 
         Self {
-            bearer_token,
             base_url: url,
+            http: kiln.http.clone(),
         }
     }
 }
 
-impl KilnAccountClient {
-    pub fn get_by_uuid(&self, id: uuid::Uuid) -> Result<ReturnedData<Account>, ureq::Error> {
-        let id_param = id.to_string();
+for_each_mode! { <M>
+    impl KilnAccountClient<M> {
+        pub async fn get_by_uuid(&self, id: uuid::Uuid) -> Result<ReturnedData<Account>, KilnError> {
+            let id_param = id.to_string();
 
-        let url: String = format!("{}/{}", self.base_url, id_param);
+            let url: String = format!("{}/{}", self.base_url, id_param);
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Account>>();
+            self.http.get("kiln.accounts.get_by_uuid", &url).await
+        }
 
-        data
-    }
+        pub async fn get_all(&self) -> Result<ReturnedData<Vec<Account>>, KilnError> {
+            self.http.get("kiln.accounts.get_all", &self.base_url).await
+        }
 
-    pub fn get_all(&self) -> Result<ReturnedData<Vec<Account>>, ureq::Error> {
-        let data = ureq::get(&self.base_url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<Account>>>();
+        pub async fn post(
+            &self,
+            name: &str,
+            description: &str,
+        ) -> Result<ReturnedData<Account>, KilnError> {
+            let send_body = AccountPosttRequest {
+                name: name.into(),
+                description: description.into(),
+            };
+
+            self.http
+                .post("kiln.accounts.post", &self.base_url, &send_body)
+                .await
+        }
 
-        data
-    }
+        pub async fn put(
+            &self,
+            id: uuid::Uuid,
+            name: &str,
+            description: &str,
+        ) -> Result<ReturnedData<Account>, KilnError> {
+            let id_param = id.to_string();
 
-    pub fn post(
-        &self,
-        name: &str,
-        description: &str,
-    ) -> Result<ReturnedData<Account>, ureq::Error> {
-        let send_body = AccountPosttRequest {
-            name: name.into(),
-            description: description.into(),
-        };
-
-        let data = ureq::post(&self.base_url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(&send_body)?
-            .body_mut()
-            .read_json::<ReturnedData<Account>>();
-
-        data
-    }
+            let url: String = format!("{}/{}", self.base_url, id_param);
 
-    pub fn put(
-        &self,
-        id: uuid::Uuid,
-        name: &str,
-        description: &str,
-    ) -> Result<ReturnedData<Account>, ureq::Error> {
-        let id_param = id.to_string();
-
-        let url: String = format!("{}/{}", self.base_url, id_param);
-
-        let send_body = AccountPutRequest {
-            name: name.into(),
-            description: description.into(),
-        };
-
-        let data = ureq::put(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(&send_body)?
-            .body_mut()
-            .read_json::<ReturnedData<Account>>();
-
-        data
-    }
+            let send_body = AccountPutRequest {
+                name: name.into(),
+                description: description.into(),
+            };
+
+            self.http.put("kiln.accounts.put", &url, &send_body).await
+        }
 
-    pub fn delete(&self, id: uuid::Uuid) -> Result<ReturnedData<Account>, ureq::Error> {
-        let id_param = id.to_string();
+        pub async fn delete(&self, id: uuid::Uuid) -> Result<ReturnedData<Account>, KilnError> {
+            let id_param = id.to_string();
 
-        let url: String = format!("{}/{}", self.base_url, id_param);
+            let url: String = format!("{}/{}", self.base_url, id_param);
 
-        let data = ureq::delete(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Account>>();
+            self.http.delete("kiln.accounts.delete", &url).await
+        }
+
+        pub async fn portofolio(
+            &self,
+            id: uuid::Uuid,
+            refresh: bool,
+        ) -> Result<ReturnedData<Portofolio>, KilnError> {
+            let url = Query::new()
+                .value("refresh", refresh)
+                .url(&format!("{}/{}/portofolio", self.base_url, id));
+
+            self.http.get("kiln.accounts.portofolio", &url).await
+        }
 
-        data
+        pub async fn get_reports(&self, id: uuid::Uuid) -> Result<Vec<u8>, KilnError> {
+            let id: String = id.to_string();
+
+            let url: String = format!("{}/{}/reports", self.base_url, id);
+
+            self.http.get_bytes("kiln.accounts.get_reports", &url).await
+        }
     }
+}
 
-    pub fn portofolio(
-        &self,
-        id: uuid::Uuid,
-        refresh: bool,
-    ) -> Result<ReturnedData<Account>, ureq::Error> {
-        let id_param: String = id.to_string();
+#[cfg(all(test, feature = "blocking"))]
+mod accounts_test {
 
-        let refresh: String = refresh.to_string();
+    use std::str::FromStr;
 
-        let url: String = format!(
-            "{}/{}/portofolio?refresh={}",
-            self.base_url, id_param, refresh
-        );
+    use super::*;
+    use crate::query::query_test_support::{fixture, requested_url};
+
+    const ACCOUNT_ID: &str = "9e1c62a4-2b01-4cd8-a2a4-74c57dcd2be4";
+
+    #[test]
+    fn get_by_uuid_url() {
+        let uuid = uuid::Uuid::from_str(ACCOUNT_ID).unwrap();
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Account>>();
+        let url = requested_url(|kiln| kiln.accounts().get_by_uuid(uuid));
 
-        data
+        assert_eq!(url, format!("https://api.kiln.fi/v1/accounts/{ACCOUNT_ID}"));
     }
 
-    pub fn get_reports(&self, id: uuid::Uuid) -> Result<Vec<u8>, ureq::Error> {
-        let id: String = id.to_string();
+    #[test]
+    fn get_all_url() {
+        let url = requested_url(|kiln| kiln.accounts().get_all());
 
-        let url: String = format!("{}/{}/reports", self.base_url, id);
+        assert_eq!(url, "https://api.kiln.fi/v1/accounts");
+    }
 
-        let mut file_bytes: Vec<u8> = Vec::new();
+    #[test]
+    fn portofolio_url() {
+        let uuid = uuid::Uuid::from_str(ACCOUNT_ID).unwrap();
 
-        ureq::get(url)
-            .header("accept", "application/octet-stream")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .as_reader()
-            .read_to_end(&mut file_bytes)?;
+        let url = requested_url(|kiln| kiln.accounts().portofolio(uuid, true));
 
-        Ok(file_bytes)
+        assert_eq!(
+            url,
+            format!("https://api.kiln.fi/v1/accounts/{ACCOUNT_ID}/portofolio?refresh=true")
+        );
     }
-}
 
-#[cfg(test)]
-mod accounts_test {
+    #[test]
+    fn get_reports_url() {
+        let uuid = uuid::Uuid::from_str(ACCOUNT_ID).unwrap();
 
-    use std::str::FromStr;
+        let url = requested_url(|kiln| kiln.accounts().get_reports(uuid));
 
-    use super::*;
+        assert_eq!(
+            url,
+            format!("https://api.kiln.fi/v1/accounts/{ACCOUNT_ID}/reports")
+        );
+    }
 
     // the #[ignore] are here because the temporary free api token isn't valid anymore
 
@@ -192,7 +185,6 @@ mod accounts_test {
 
         let kiln: Kiln = Kiln::builder()
             .api_token(api_token)
-            // no trailing slash, or else it will break
             .base_url("https://api.kiln.fi/v1")
             .seal()
             .build()
@@ -213,7 +205,6 @@ mod accounts_test {
 
         let kiln: Kiln = Kiln::builder()
             .api_token(api_token)
-            // no trailing slash, or else it will break
             .base_url("https://api.kiln.fi/v1")
             .seal()
             .build()
@@ -232,7 +223,6 @@ mod accounts_test {
 
         let kiln: Kiln = Kiln::builder()
             .api_token(api_token)
-            // no trailing slash, or else it will break
             .base_url("https://api.kiln.fi/v1")
             .seal()
             .build()
@@ -253,7 +243,6 @@ mod accounts_test {
 
         let kiln: Kiln = Kiln::builder()
             .api_token(api_token)
-            // no trailing slash, or else it will break
             .base_url("https://api.kiln.fi/v1")
             .seal()
             .build()
@@ -273,7 +262,6 @@ mod accounts_test {
 
         let kiln: Kiln = Kiln::builder()
             .api_token(api_token)
-            // no trailing slash, or else it will break
             .base_url("https://api.kiln.fi/v1")
             .seal()
             .build()
@@ -295,7 +283,6 @@ mod accounts_test {
 
         let kiln: Kiln = Kiln::builder()
             .api_token(api_token)
-            // no trailing slash, or else it will break
             .base_url("https://api.kiln.fi/v1")
             .seal()
             .build()
@@ -307,4 +294,58 @@ mod accounts_test {
 
         dbg!(data);
     }
+
+    #[test]
+    fn responses_match_the_fixtures() {
+        let accounts: ReturnedData<Vec<Account>> = fixture("account/accounts.json");
+        let account: ReturnedData<Account> = fixture("account/account.json");
+
+        assert_eq!(accounts.data[1].description, None);
+        assert_eq!(account.data.id, accounts.data[0].id);
+
+        let portofolio: ReturnedData<Portofolio> = fixture("account/portofolio.json");
+
+        assert_eq!(portofolio.data.protocols.len(), 3);
+        assert!(portofolio.data.protocols[2].name.is_unknown());
+    }
+
+    #[test]
+    fn portofolio_totals_by_protocol() {
+        use crate::sdk::Protocol;
+        use crate::FloatAmount;
+
+        let portofolio: Portofolio =
+            fixture::<ReturnedData<Portofolio>>("account/portofolio.json").data;
+
+        let ethereum = portofolio.protocol(&Protocol::Ethereum).unwrap();
+
+        assert_eq!(ethereum.token, "ETH");
+        assert_eq!(ethereum.exited_usd(), FloatAmount::default());
+        assert_eq!(
+            ethereum.exiting_usd(),
+            ethereum.exiting_stakes.as_ref().unwrap().amount_usd
+        );
+
+        let balances = portofolio.balance_usd_by_protocol();
+
+        assert_eq!(balances.len(), 3);
+        assert_eq!(
+            balances[&Protocol::Solana],
+            portofolio.protocols[1].total_balance.amount_usd
+        );
+        assert_eq!(
+            portofolio.activating_usd_by_protocol()[&Protocol::Ethereum],
+            ethereum.activating_usd()
+        );
+        assert_eq!(
+            portofolio.rewards_usd_by_protocol()[&Protocol::Unknown("polkadot".into())],
+            portofolio.protocols[2].total_rewards.amount_usd
+        );
+
+        let restaked: Vec<_> = portofolio.eigenlayer().collect();
+
+        assert_eq!(restaked.len(), 1);
+        assert_eq!(restaked[0].0, &Protocol::Ethereum);
+        assert_eq!(restaked[0].1.eigenpods.len(), 1);
+    }
 }
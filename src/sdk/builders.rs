@@ -0,0 +1,299 @@
+use uuid::Uuid;
+
+use crate::errors::KilnError;
+
+use super::{GetStakesRequest, ResponseFormat, RewardRequest, ValidatorOperationsRequest};
+
+// Typestate of the read request builders : `since` (the start date) is required,
+// `until` (the end date) is left out of the query when not set.
+#[derive(Default, Clone, Debug)]
+pub struct NoStartDate;
+
+#[derive(Clone, Debug)]
+pub struct StartDate(pub(crate) chrono::DateTime<chrono::Utc>);
+
+pub(crate) fn filter<T>(items: impl IntoIterator<Item = impl Into<T>>) -> Option<Vec<T>> {
+    Some(items.into_iter().map(Into::into).collect())
+}
+
+// `kiln.<chain>().stakes()` of the cosmos based chains
+#[derive(Debug)]
+pub struct GetStakesBuilder<'a, C> {
+    pub(crate) client: &'a C,
+    validators: Option<Vec<String>>,
+    delegators: Option<Vec<String>>,
+    accounts: Option<Vec<Uuid>>,
+}
+
+impl<'a, C> GetStakesBuilder<'a, C> {
+    pub(crate) fn new(client: &'a C) -> Self {
+        Self {
+            client,
+            validators: None,
+            delegators: None,
+            accounts: None,
+        }
+    }
+
+    pub fn validators(self, validators: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            validators: filter(validators),
+            ..self
+        }
+    }
+
+    pub fn delegators(self, delegators: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            delegators: filter(delegators),
+            ..self
+        }
+    }
+
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
+    }
+
+    pub fn into_request(self) -> GetStakesRequest {
+        GetStakesRequest {
+            validators: self.validators,
+            delegators: self.delegators,
+            accounts: self.accounts,
+        }
+    }
+}
+
+// `kiln.<chain>().rewards()` : daily rewards, or per epoch once `epochs` is set
+#[derive(Debug)]
+pub struct RewardsBuilder<'a, C, S> {
+    pub(crate) client: &'a C,
+    stakes_addresses: Option<Vec<String>>,
+    wallets: Option<Vec<String>>,
+    pool_ids: Option<Vec<String>>,
+    accounts: Option<Vec<Uuid>>,
+    format: ResponseFormat,
+    start_date: S,
+    end_date: Option<chrono::DateTime<chrono::Utc>>,
+    include_usd: bool,
+    epochs: Option<(u64, u64)>,
+}
+
+impl<'a, C> RewardsBuilder<'a, C, NoStartDate> {
+    pub(crate) fn new(client: &'a C) -> Self {
+        Self {
+            client,
+            stakes_addresses: None,
+            wallets: None,
+            pool_ids: None,
+            accounts: None,
+            format: ResponseFormat::default(),
+            start_date: NoStartDate,
+            end_date: None,
+            include_usd: false,
+            epochs: None,
+        }
+    }
+}
+
+impl<'a, C, S> RewardsBuilder<'a, C, S> {
+    pub fn stakes_addresses(
+        self,
+        stakes_addresses: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            stakes_addresses: filter(stakes_addresses),
+            ..self
+        }
+    }
+
+    pub fn wallets(self, wallets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            wallets: filter(wallets),
+            ..self
+        }
+    }
+
+    pub fn pool_ids(self, pool_ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            pool_ids: filter(pool_ids),
+            ..self
+        }
+    }
+
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
+    }
+
+    pub fn format(self, format: ResponseFormat) -> Self {
+        Self { format, ..self }
+    }
+
+    // daily rewards only, the per epoch ones have no usd value : `into_request` rejects both
+    pub fn include_usd(self) -> Self {
+        Self {
+            include_usd: true,
+            ..self
+        }
+    }
+
+    pub fn epochs(self, start_epoch: u64, end_epoch: u64) -> Self {
+        Self {
+            epochs: Some((start_epoch, end_epoch)),
+            ..self
+        }
+    }
+
+    pub fn until(self, end_date: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            end_date: Some(end_date),
+            ..self
+        }
+    }
+
+    pub fn since(
+        self,
+        start_date: chrono::DateTime<chrono::Utc>,
+    ) -> RewardsBuilder<'a, C, StartDate> {
+        RewardsBuilder {
+            client: self.client,
+            stakes_addresses: self.stakes_addresses,
+            wallets: self.wallets,
+            pool_ids: self.pool_ids,
+            accounts: self.accounts,
+            format: self.format,
+            start_date: StartDate(start_date),
+            end_date: self.end_date,
+            include_usd: self.include_usd,
+            epochs: self.epochs,
+        }
+    }
+}
+
+impl<C> RewardsBuilder<'_, C, StartDate> {
+    pub fn into_request(self) -> Result<RewardRequest, KilnError> {
+        Ok(match self.epochs {
+            Some(_) if self.include_usd => {
+                return Err(KilnError::InvalidInput(
+                    "`include_usd` is for the daily rewards, not the per epoch ones".into(),
+                ));
+            }
+            Some((start_epoch, end_epoch)) => RewardRequest::Epoch {
+                stakes_addresses: self.stakes_addresses,
+                wallets: self.wallets,
+                pool_ids: self.pool_ids,
+                accounts: self.accounts,
+                format: self.format,
+                start_date: self.start_date.0,
+                end_date: self.end_date,
+                start_epoch,
+                end_epoch,
+            },
+            None => RewardRequest::Daily {
+                stakes_addresses: self.stakes_addresses,
+                wallets: self.wallets,
+                pool_ids: self.pool_ids,
+                accounts: self.accounts,
+                format: self.format,
+                start_date: self.start_date.0,
+                end_date: self.end_date,
+                include_usd: self.include_usd,
+            },
+        })
+    }
+}
+
+// `kiln.<chain>().operations()` of the cosmos based chains
+#[derive(Debug)]
+pub struct ValidatorOperationsBuilder<'a, C, S> {
+    pub(crate) client: &'a C,
+    validators: Option<Vec<String>>,
+    delegators: Option<Vec<String>>,
+    authz: bool,
+    accounts: Option<Vec<Uuid>>,
+    start_date: S,
+    end_date: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl<'a, C> ValidatorOperationsBuilder<'a, C, NoStartDate> {
+    pub(crate) fn new(client: &'a C) -> Self {
+        Self {
+            client,
+            validators: None,
+            delegators: None,
+            authz: false,
+            accounts: None,
+            start_date: NoStartDate,
+            end_date: None,
+        }
+    }
+}
+
+impl<'a, C, S> ValidatorOperationsBuilder<'a, C, S> {
+    pub fn validators(self, validators: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            validators: filter(validators),
+            ..self
+        }
+    }
+
+    pub fn delegators(self, delegators: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            delegators: filter(delegators),
+            ..self
+        }
+    }
+
+    pub fn authz(self) -> Self {
+        Self {
+            authz: true,
+            ..self
+        }
+    }
+
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
+    }
+
+    pub fn until(self, end_date: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            end_date: Some(end_date),
+            ..self
+        }
+    }
+
+    pub fn since(
+        self,
+        start_date: chrono::DateTime<chrono::Utc>,
+    ) -> ValidatorOperationsBuilder<'a, C, StartDate> {
+        ValidatorOperationsBuilder {
+            client: self.client,
+            validators: self.validators,
+            delegators: self.delegators,
+            authz: self.authz,
+            accounts: self.accounts,
+            start_date: StartDate(start_date),
+            end_date: self.end_date,
+        }
+    }
+}
+
+impl<C> ValidatorOperationsBuilder<'_, C, StartDate> {
+    pub fn into_request(self) -> ValidatorOperationsRequest {
+        ValidatorOperationsRequest {
+            validators: self.validators,
+            delegators: self.delegators,
+            authz: self.authz,
+            accounts: self.accounts,
+            start_date: self.start_date.0,
+            end_date: self.end_date,
+        }
+    }
+}
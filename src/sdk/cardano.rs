@@ -1,11 +1,19 @@
-use std::io::Read;
+use std::num::NonZeroU64;
 
 use serde::{Deserialize, Serialize};
-use strum_macros::AsRefStr;
 use uuid::Uuid;
 
+use crate::address::CardanoAddress;
+use crate::amount::{Amount, FloatAmount};
+use crate::denomination::Lovelace;
+use crate::errors::KilnError;
+use crate::http::HttpClient;
+use crate::mode::{for_each_mode, Blocking, Mode};
+use crate::pagination::{Page, PageOptions, Pages};
+use crate::query::Query;
 use crate::{response_format::ReturnedData, sdk::RewardRequest, Kiln};
 
+use super::builders::{filter, NoStartDate, RewardsBuilder, StartDate};
 use super::{
     ChainStakes, CreateStakeRequest, OperationsResponse, PostStakesResponse, ResponseFormat,
     Reward, StakeOperationsRequest,
@@ -20,17 +28,17 @@ pub struct CardanoBroadcastTxResponse {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct CardanoCert {
-    #[serde(rename = "PascalCase")]
-    stake_delegation: String,
+    #[serde(rename = "StakeDelegation")]
+    pub stake_delegation: CardanoStakeDelegation,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct CardanoGetStakesRequest {
-    pub wallets: Vec<String>,
-    pub vaults: Vec<String>,
-    pub pool_ids: Vec<String>,
-    pub accounts: Vec<Uuid>,
+    pub wallets: Option<Vec<String>>,
+    pub vaults: Option<Vec<String>>,
+    pub pool_ids: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
     pub current_page: Option<u64>,
     pub page_size: Option<u64>,
 }
@@ -38,7 +46,7 @@ pub struct CardanoGetStakesRequest {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct CardanoNetworkStats {
-    pub ada_price_usd: f64,
+    pub ada_price_usd: FloatAmount,
     pub nb_validators: u64,
     pub network_gross_apy: f64,
     pub supply_staked_percent: f64,
@@ -62,16 +70,16 @@ pub struct CardanoPrepareTxResponse {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct CardanoReportsRequest {
-    pub stake_addresses: Vec<String>,
-    pub wallets: Vec<String>,
-    pub accounts: Vec<String>,
+    pub stake_addresses: Option<Vec<String>>,
+    pub wallets: Option<Vec<String>>,
+    pub accounts: Option<Vec<String>>,
     pub format: ResponseFormat,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct CardanoStakeCredentials {
-    #[serde(rename = "PascalCase")]
+    #[serde(rename = "Key")]
     pub key: String,
 }
 
@@ -86,7 +94,7 @@ pub struct CardanoStakeDelegation {
 #[serde(rename_all = "snake_case")]
 pub struct CardanoStakeTxResponse {
     pub unsigned_tx_hash: String,
-    pub unsigned_tx_serialiazed: String,
+    pub unsigned_tx_serialized: String,
     pub inputs: Vec<CardanoStakeTxInput>,
 }
 
@@ -101,7 +109,7 @@ pub struct CardanoStakeTxOutput {
 #[serde(rename_all = "snake_case")]
 pub struct CardanoStakeTxRequest {
     pub account_id: Uuid,
-    pub wallet: String,
+    pub wallet: CardanoAddress,
     pub pool_id: String,
 }
 
@@ -136,18 +144,18 @@ pub struct CardanoTxDecodeResponse {
 pub struct CardanoTxDecodingBody {
     pub inputs: Vec<CardanoStakeTxInput>,
     pub outputs: Vec<CardanoStakeTxOutput>,
-    pub fee: String,
+    pub fee: Amount,
     pub ttl: String,
     pub certs: Vec<CardanoCert>,
     pub witness_cert: serde_json::Value,
     pub is_valid: bool,
 }
 
-#[derive(Serialize, Deserialize, AsRefStr, Debug)]
-#[serde(rename_all = "snake_case")]
-pub enum CardanoTxStatus {
-    Success,
-    PendingConfirmation,
+string_enum! {
+    pub enum CardanoTxStatus {
+        Success => "success",
+        PendingConfirmation => "pending_confirmation",
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -167,8 +175,8 @@ pub struct CardanoTxStatusReceipt {
     pub slot: u64,
     pub index: u64,
     pub output_amount: Vec<CardanoTxStatusOutputAmount>,
-    pub fees: String,
-    pub deposit: String,
+    pub fees: Amount,
+    pub deposit: Amount,
     pub size: u64,
     pub invalid_before: Option<String>,
     pub invalid_hereafter: Option<String>,
@@ -194,385 +202,667 @@ pub struct CardanoTxStatusResponse {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct CardanoWithdrawRewardsTxRequest {
-    pub wallet: String,
-    pub amount_lovelace: Option<String>,
+    pub wallet: CardanoAddress,
+    pub amount_lovelace: Option<Lovelace>,
 }
 
 #[derive(Clone, Debug)]
-pub struct KilnCardanoClient {
-    bearer_token: String,
+pub struct KilnCardanoClient<M: Mode = Blocking> {
     base_url: String,
+    http: HttpClient<M>,
 }
 
-impl From<&Kiln> for KilnCardanoClient {
-    fn from(kiln: &Kiln) -> Self {
+impl<M: Mode> From<&Kiln<M>> for KilnCardanoClient<M> {
+    fn from(kiln: &Kiln<M>) -> Self {
         let url: String = format!("{}/ada", kiln.base_url);
-        let bearer_token: String = format!("Bearer {}", kiln.api_token);
 
         Self {
-            bearer_token,
             base_url: url,
+            http: kiln.http.clone(),
         }
     }
 }
 
-impl KilnCardanoClient {
-    pub fn get_stakes(
-        &self,
-        get_stakes_request: &CardanoGetStakesRequest,
-    ) -> Result<ReturnedData<Vec<ChainStakes>>, ureq::Error> {
-        let CardanoGetStakesRequest {
-            wallets,
-            vaults,
-            pool_ids,
-            accounts,
-            current_page,
-            page_size,
-        } = get_stakes_request;
+for_each_mode! { <M>
+    impl KilnCardanoClient<M> {
+        pub async fn get_stakes(
+            &self,
+            get_stakes_request: &CardanoGetStakesRequest,
+        ) -> Result<ReturnedData<Vec<ChainStakes>>, KilnError> {
+            let CardanoGetStakesRequest {
+                wallets,
+                vaults,
+                pool_ids,
+                accounts,
+                current_page,
+                page_size,
+            } = get_stakes_request;
+
+            let url = Query::new()
+                .list("wallets", wallets.iter().flatten())
+                .list("vaults", vaults.iter().flatten())
+                .list("pool_ids", pool_ids.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .value("page_size", page_size.unwrap_or(1))
+                .value("current_page", current_page.unwrap_or(1))
+                .url(&format!("{}/stakes", self.base_url));
+
+            self.http.get("kiln.ada.get_stakes", &url).await
+        }
+
+        pub async fn get_network_stats(&self) -> Result<ReturnedData<CardanoNetworkStats>, KilnError> {
+            let url: String = format!("{}/network-stats", self.base_url);
 
-        let wallets = wallets.join(",");
+            self.http.get("kiln.ada.get_network_stats", &url).await
+        }
+
+        pub async fn get_operations(
+            &self,
+            operation_request: StakeOperationsRequest,
+        ) -> Result<ReturnedData<Vec<OperationsResponse>>, KilnError> {
+            let StakeOperationsRequest {
+                stake_addresses,
+                wallets,
+                pool_ids,
+                accounts,
+                start_date,
+                end_date,
+            } = operation_request;
+
+            let url = Query::new()
+                .list("stake_addresses", stake_addresses.iter().flatten())
+                .list("wallets", wallets.iter().flatten())
+                .list("pool_ids", pool_ids.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .value("start_date", start_date)
+                .option("end_date", end_date.as_ref())
+                .url(&format!("{}/operations", self.base_url));
+
+            self.http.get("kiln.ada.get_operations", &url).await
+        }
 
-        let vaults = vaults.join(",");
+        pub async fn get_reports(
+            &self,
+            reports_request: &CardanoReportsRequest,
+        ) -> Result<Vec<u8>, KilnError> {
+            let CardanoReportsRequest {
+                stake_addresses,
+                wallets,
+                accounts,
+                format,
+            } = reports_request;
 
-        let pool_ids = pool_ids.join(",");
+            let url = Query::new()
+                .list("stake_addresses", stake_addresses.iter().flatten())
+                .list("wallets", wallets.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .value("format", format.as_ref())
+                .url(&format!("{}/reports", self.base_url));
 
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
+            self.http.get_bytes("kiln.ada.get_reports", &url).await
+        }
 
-        let current_page = current_page.unwrap_or(1).to_string();
+        pub async fn get_rewards(
+            &self,
+            reward_request: RewardRequest,
+        ) -> Result<ReturnedData<Vec<Reward>>, KilnError> {
+            let url: String = match reward_request {
+                RewardRequest::Epoch {
+                    stakes_addresses,
+                    wallets,
+                    pool_ids,
+                    accounts,
+                    format,
+                    start_date,
+                    end_date,
+                    start_epoch,
+                    end_epoch,
+                } => Query::new()
+                    .list("stake_addresses", stakes_addresses.iter().flatten())
+                    .list("pool_ids", pool_ids.iter().flatten())
+                    .list("wallets", wallets.iter().flatten())
+                    .list("accounts", accounts.iter().flatten())
+                    .value("format", format.as_ref())
+                    .value("start_date", start_date)
+                    .option("end_date", end_date.as_ref())
+                    .value("start_epoch", start_epoch)
+                    .value("end_epoch", end_epoch)
+                    .url(&format!("{}/rewards", self.base_url)),
+                RewardRequest::Daily {
+                    stakes_addresses,
+                    wallets,
+                    pool_ids,
+                    accounts,
+                    format,
+                    start_date,
+                    end_date,
+                    include_usd,
+                } => Query::new()
+                    .list("stake_addresses", stakes_addresses.iter().flatten())
+                    .list("pool_ids", pool_ids.iter().flatten())
+                    .list("wallets", wallets.iter().flatten())
+                    .list("accounts", accounts.iter().flatten())
+                    .value("format", format.as_ref())
+                    .value("start_date", start_date)
+                    .option("end_date", end_date.as_ref())
+                    .value("include_usd", include_usd)
+                    .url(&format!("{}/rewards", self.base_url)),
+            };
+
+            self.http.get("kiln.ada.get_rewards", &url).await
+        }
 
-        let page_size = page_size.unwrap_or(1).to_string();
+        pub async fn get_tx_decoding(
+            &self,
+            tx_serialized: &str,
+        ) -> Result<ReturnedData<CardanoTxDecodeResponse>, KilnError> {
+            let url = Query::new()
+                .value("tx_serialized", tx_serialized)
+                .url(&format!("{}/transaction/decode", self.base_url));
 
-        let url: String = format!(
-            "{}/stakes?wallets={}&vaults={}&pool_ids={}&accounts={}&page_size={}&current_page={}",
-            self.base_url, wallets, vaults, pool_ids, accounts, page_size, current_page
-        );
+            self.http.get("kiln.ada.get_tx_decoding", &url).await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<ChainStakes>>>();
+        pub async fn post_stakes(
+            &self,
+            create_stake_request: &CreateStakeRequest,
+        ) -> Result<ReturnedData<Vec<PostStakesResponse>>, KilnError> {
+            let url: String = format!("{}/stakes", self.base_url,);
 
-        data
-    }
+            self.http
+                .post("kiln.ada.post_stakes", &url, create_stake_request)
+                .await
+        }
 
-    pub fn get_network_stats(&self) -> Result<ReturnedData<CardanoNetworkStats>, ureq::Error> {
-        let url: String = format!("{}/network-stats", self.base_url);
+        pub async fn post_stake_tx(
+            &self,
+            cardano_stake_tx_request: &CardanoStakeTxRequest,
+        ) -> Result<ReturnedData<CardanoStakeTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/stake", self.base_url,);
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<CardanoNetworkStats>>();
+            self.http
+                .post_tx("kiln.ada.post_stake_tx", &url, cardano_stake_tx_request)
+                .await
+        }
 
-        data
-    }
+        pub async fn post_withdraw_rewards_tx(
+            &self,
+            withdraw_rewards_tx_request: &CardanoWithdrawRewardsTxRequest,
+        ) -> Result<ReturnedData<CardanoStakeTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/withdraw-rewards", self.base_url,);
+
+            self.http
+                .post_tx(
+                    "kiln.ada.post_withdraw_rewards_tx",
+                    &url,
+                    withdraw_rewards_tx_request,
+                )
+                .await
+        }
 
-    pub fn get_operations(
-        &self,
-        operation_request: StakeOperationsRequest,
-    ) -> Result<ReturnedData<Vec<OperationsResponse>>, ureq::Error> {
-        let StakeOperationsRequest {
-            stake_addresses,
-            wallets,
-            pool_ids,
-            accounts,
-            start_date,
-            end_date,
-        } = operation_request;
+        pub async fn post_unstake_tx(
+            &self,
+            wallet: &str,
+        ) -> Result<ReturnedData<CardanoStakeTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/unstake", self.base_url,);
 
-        let stake_addresses = stake_addresses.join(",");
+            self.http
+                .post_tx("kiln.ada.post_unstake_tx", &url, wallet)
+                .await
+        }
 
-        let wallets = wallets.join(",");
+        pub async fn post_prepare_tx(
+            &self,
+            cardano_prepare_tx_request: &CardanoPrepareTxRequest,
+        ) -> Result<ReturnedData<CardanoPrepareTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/prepare", self.base_url,);
 
-        let pool_ids = pool_ids.join(",");
+            self.http
+                .post_tx("kiln.ada.post_prepare_tx", &url, cardano_prepare_tx_request)
+                .await
+        }
 
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
+        pub async fn post_broadcast_tx(
+            &self,
+            tx_serialized: &str,
+        ) -> Result<ReturnedData<CardanoBroadcastTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/broadcast", self.base_url,);
 
-        let url: String = format!(
-            "{}/operations?stake_addresses={}&wallets={}&pool_ids={}&accounts={}&start_date={}&end_date={}",self.base_url, stake_addresses, wallets,pool_ids, accounts, start_date, end_date
-        );
+            self.http
+                .post_tx("kiln.ada.post_broadcast_tx", &url, tx_serialized)
+                .await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<OperationsResponse>>>();
+        pub async fn get_tx_status(
+            &self,
+            tx_hash: &str,
+        ) -> Result<ReturnedData<CardanoTxStatusResponse>, KilnError> {
+            let url = Query::new()
+                .value("tx_hash", tx_hash)
+                .url(&format!("{}/transaction/status", self.base_url));
 
-        data
+            self.http.get("kiln.ada.get_tx_status", &url).await
+        }
     }
+}
 
-    pub fn get_reports(
-        &self,
-        reports_request: &CardanoReportsRequest,
-    ) -> Result<Vec<u8>, ureq::Error> {
-        let CardanoReportsRequest {
-            stake_addresses,
-            wallets,
-            accounts,
-            format,
-        } = reports_request;
+impl<M: Mode> KilnCardanoClient<M> {
+    pub fn stakes(&self) -> CardanoStakesBuilder<'_, M> {
+        CardanoStakesBuilder::new(self)
+    }
 
-        let stake_addresses = stake_addresses.join(",");
+    pub fn rewards(&self) -> RewardsBuilder<'_, Self, NoStartDate> {
+        RewardsBuilder::new(self)
+    }
 
-        let wallets = wallets.join(",");
+    pub fn operations(&self) -> CardanoOperationsBuilder<'_, NoStartDate, M> {
+        CardanoOperationsBuilder::new(self)
+    }
+}
 
-        let accounts = accounts.join(",");
+for_each_mode! { <M>
+    impl KilnCardanoClient<M> {
+        // every stake of the request, from its `current_page` on.
+        // No pagination in the response : a page shorter than `page_size` is the last one.
+        pub fn iter_stakes(
+            &self,
+            get_stakes_request: CardanoGetStakesRequest,
+            options: PageOptions,
+        ) -> Pages<ChainStakes, M> {
+            let client = self.clone();
+            let first_page = get_stakes_request
+                .current_page
+                .and_then(NonZeroU64::new)
+                .unwrap_or(NonZeroU64::MIN);
+
+            Pages::<_, M>::new(first_page, options, move |page| {
+                let request = CardanoGetStakesRequest {
+                    current_page: Some(page.get()),
+                    page_size: Some(options.page_size.get()),
+                    ..get_stakes_request.clone()
+                };
+
+                fetch_stakes_page(client.clone(), request)
+            })
+        }
+    }
 
-        let url: String = format!(
-            "{}/reports?stake_addresses={}&wallets={}&accounts={}&format={}",
-            self.base_url,
-            stake_addresses,
-            wallets,
-            accounts,
-            format.as_ref()
-        );
+    async fn fetch_stakes_page(
+        client: KilnCardanoClient<M>,
+        get_stakes_request: CardanoGetStakesRequest,
+    ) -> Result<Page<ChainStakes>, KilnError> {
+        let page_size = get_stakes_request.page_size.unwrap_or(1);
+        let ReturnedData { data } = client.get_stakes(&get_stakes_request).await?;
+
+        Ok(Page {
+            last: (data.len() as u64) < page_size,
+            items: data,
+        })
+    }
+}
 
-        let mut file_bytes: Vec<u8> = Vec::new();
+// `kiln.cardano().stakes()`
+#[derive(Debug)]
+pub struct CardanoStakesBuilder<'a, M: Mode = Blocking> {
+    client: &'a KilnCardanoClient<M>,
+    wallets: Option<Vec<String>>,
+    vaults: Option<Vec<String>>,
+    pool_ids: Option<Vec<String>>,
+    accounts: Option<Vec<Uuid>>,
+    current_page: Option<u64>,
+    page_size: Option<u64>,
+}
 
-        ureq::get(url)
-            .header("accept", "application/octet-stream")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .as_reader()
-            .read_to_end(&mut file_bytes)?;
+impl<'a, M: Mode> CardanoStakesBuilder<'a, M> {
+    fn new(client: &'a KilnCardanoClient<M>) -> Self {
+        Self {
+            client,
+            wallets: None,
+            vaults: None,
+            pool_ids: None,
+            accounts: None,
+            current_page: None,
+            page_size: None,
+        }
+    }
 
-        Ok(file_bytes)
+    pub fn wallets(self, wallets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            wallets: filter(wallets),
+            ..self
+        }
     }
 
-    pub fn get_rewards(
-        &self,
-        reward_request: RewardRequest,
-    ) -> Result<ReturnedData<Vec<Reward>>, ureq::Error> {
-        let url: String = match reward_request {
-            RewardRequest::Epoch {
-                stakes_addresses,
-                wallets,
-                pool_ids,
-                accounts,
-                format,
-                start_date,
-                end_date,
-                start_epoch,
-                end_epoch,
-            } => {
-                let stakes_addresses = stakes_addresses.join(",");
-
-                let pool_ids = pool_ids.join(",");
-
-                let wallets = wallets.join(",");
-
-                let accounts = accounts
-                    .iter()
-                    .map(|id| id.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",");
-
-                let url = format!(
-            "{}/rewards?stake_addresses={}&pool_ids={}&wallets={}&accounts={}&format={}&start_date={}&end_date={}&start_epoch={}&end_epoch={}",
-            self.base_url,
-            stakes_addresses,
-            pool_ids,
-            wallets,
-            accounts,
-            format.as_ref(),
-            start_date,
-            end_date,
-            start_epoch,
-            end_epoch
-        );
+    pub fn vaults(self, vaults: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            vaults: filter(vaults),
+            ..self
+        }
+    }
 
-                url
-            }
-            RewardRequest::Daily {
-                stakes_addresses,
-                wallets,
-                pool_ids,
-                accounts,
-                format,
-                start_date,
-                end_date,
-                include_usd,
-            } => {
-                let stakes_addresses = stakes_addresses.join(",");
-
-                let pool_ids = pool_ids.join(",");
-
-                let wallets = wallets.join(",");
-
-                let accounts = accounts
-                    .iter()
-                    .map(|id| id.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",");
-
-                let url = format!(
-            "{}/rewards?stake_addresses={}&pool_ids={}&wallets={}&accounts={}&format={}&start_date={}&end_date={}&include_usd={}",
-            self.base_url,
-            stakes_addresses,
-            pool_ids,
-            wallets,
-            accounts,
-            format.as_ref(),
-            start_date,
-            end_date,
-            include_usd
-        );
+    pub fn pool_ids(self, pool_ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            pool_ids: filter(pool_ids),
+            ..self
+        }
+    }
 
-                url
-            }
-        };
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<Reward>>>();
+    pub fn current_page(self, current_page: u64) -> Self {
+        Self {
+            current_page: Some(current_page),
+            ..self
+        }
+    }
 
-        data
+    pub fn page_size(self, page_size: u64) -> Self {
+        Self {
+            page_size: Some(page_size),
+            ..self
+        }
     }
 
-    pub fn get_tx_decoding(
-        &self,
-        tx_serialized: &str,
-    ) -> Result<CardanoTxDecodeResponse, ureq::Error> {
-        let url: String = format!(
-            "{}/transaction/decode?tx_serialized={}",
-            self.base_url, tx_serialized
-        );
+    pub fn into_request(self) -> CardanoGetStakesRequest {
+        CardanoGetStakesRequest {
+            wallets: self.wallets,
+            vaults: self.vaults,
+            pool_ids: self.pool_ids,
+            accounts: self.accounts,
+            current_page: self.current_page,
+            page_size: self.page_size,
+        }
+    }
+}
+
+for_each_mode! { <M>
+    impl CardanoStakesBuilder<'_, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<ChainStakes>>, KilnError> {
+            let client = self.client;
+
+            client.get_stakes(&self.into_request()).await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<CardanoTxDecodeResponse>();
+        // the stakes of every page, from `current_page` on
+        pub fn pages(self, options: PageOptions) -> Pages<ChainStakes, M> {
+            let client = self.client;
 
-        data
+            client.iter_stakes(self.into_request(), options)
+        }
     }
+}
 
-    pub fn post_stakes(
-        &self,
-        create_stake_request: &CreateStakeRequest,
-    ) -> Result<ReturnedData<Vec<PostStakesResponse>>, ureq::Error> {
-        let url: String = format!("{}/stakes", self.base_url,);
+for_each_mode! { <M>
+    impl RewardsBuilder<'_, KilnCardanoClient<M>, StartDate> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<Reward>>, KilnError> {
+            let client = self.client;
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(create_stake_request)?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<PostStakesResponse>>>();
+            client.get_rewards(self.into_request()?).await
+        }
+    }
+}
 
-        data
+// `kiln.cardano().operations()`
+#[derive(Debug)]
+pub struct CardanoOperationsBuilder<'a, S, M: Mode = Blocking> {
+    client: &'a KilnCardanoClient<M>,
+    stake_addresses: Option<Vec<String>>,
+    wallets: Option<Vec<String>>,
+    pool_ids: Option<Vec<String>>,
+    accounts: Option<Vec<Uuid>>,
+    start_date: S,
+    end_date: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl<'a, M: Mode> CardanoOperationsBuilder<'a, NoStartDate, M> {
+    fn new(client: &'a KilnCardanoClient<M>) -> Self {
+        Self {
+            client,
+            stake_addresses: None,
+            wallets: None,
+            pool_ids: None,
+            accounts: None,
+            start_date: NoStartDate,
+            end_date: None,
+        }
     }
+}
 
-    pub fn post_stake_tx(
-        &self,
-        cardano_stake_tx_request: &CardanoStakeTxRequest,
-    ) -> Result<ReturnedData<CardanoStakeTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/stake", self.base_url,);
+impl<'a, S, M: Mode> CardanoOperationsBuilder<'a, S, M> {
+    pub fn stake_addresses(
+        self,
+        stake_addresses: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            stake_addresses: filter(stake_addresses),
+            ..self
+        }
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(cardano_stake_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<CardanoStakeTxResponse>>();
+    pub fn wallets(self, wallets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            wallets: filter(wallets),
+            ..self
+        }
+    }
 
-        data
+    pub fn pool_ids(self, pool_ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            pool_ids: filter(pool_ids),
+            ..self
+        }
     }
 
-    pub fn post_withdraw_rewards_tx(
-        &self,
-        withdraw_rewards_tx_request: &CardanoWithdrawRewardsTxRequest,
-    ) -> Result<ReturnedData<CardanoStakeTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/withdraw-rewards", self.base_url,);
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(withdraw_rewards_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<CardanoStakeTxResponse>>();
+    pub fn until(self, end_date: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            end_date: Some(end_date),
+            ..self
+        }
+    }
 
-        data
+    pub fn since(
+        self,
+        start_date: chrono::DateTime<chrono::Utc>,
+    ) -> CardanoOperationsBuilder<'a, StartDate, M> {
+        CardanoOperationsBuilder {
+            client: self.client,
+            stake_addresses: self.stake_addresses,
+            wallets: self.wallets,
+            pool_ids: self.pool_ids,
+            accounts: self.accounts,
+            start_date: StartDate(start_date),
+            end_date: self.end_date,
+        }
     }
+}
 
-    pub fn post_unstake_tx(
-        &self,
-        wallet: &str,
-    ) -> Result<ReturnedData<CardanoStakeTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/unstake", self.base_url,);
+impl<M: Mode> CardanoOperationsBuilder<'_, StartDate, M> {
+    pub fn into_request(self) -> StakeOperationsRequest {
+        StakeOperationsRequest {
+            stake_addresses: self.stake_addresses,
+            wallets: self.wallets,
+            pool_ids: self.pool_ids,
+            accounts: self.accounts,
+            start_date: self.start_date.0,
+            end_date: self.end_date,
+        }
+    }
+}
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(wallet)?
-            .body_mut()
-            .read_json::<ReturnedData<CardanoStakeTxResponse>>();
+for_each_mode! { <M>
+    impl CardanoOperationsBuilder<'_, StartDate, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<OperationsResponse>>, KilnError> {
+            let client = self.client;
 
-        data
+            client.get_operations(self.into_request()).await
+        }
     }
+}
 
-    pub fn post_prepare_tx(
-        &self,
-        cardano_prepare_tx_request: &CardanoPrepareTxRequest,
-    ) -> Result<ReturnedData<CardanoPrepareTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/prepare", self.base_url,);
+#[cfg(all(test, feature = "blocking"))]
+mod cardano_test {
+
+    use super::*;
+    use crate::query::query_test_support::{date, fixture, requested_url};
+
+    #[test]
+    fn get_stakes() {
+        let url = requested_url(|kiln| {
+            kiln.cardano().get_stakes(&CardanoGetStakesRequest {
+                wallets: Some(vec!["addr1a".into()]),
+                current_page: Some(2),
+                ..Default::default()
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/ada/stakes?wallets=addr1a&page_size=1&current_page=2"
+        );
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(cardano_prepare_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<CardanoPrepareTxResponse>>();
+    #[test]
+    fn iter_stakes_first_page() {
+        let url = requested_url(|kiln| {
+            kiln.cardano()
+                .stakes()
+                .wallets(["addr1a"])
+                .current_page(3)
+                .pages(PageOptions::default().page_size(NonZeroU64::new(50).unwrap()))
+                .next()
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/ada/stakes?wallets=addr1a&page_size=50&current_page=3"
+        );
+    }
+
+    #[test]
+    fn get_rewards() {
+        let url = requested_url(|kiln| {
+            kiln.cardano().get_rewards(RewardRequest::Daily {
+                stakes_addresses: None,
+                wallets: Some(vec!["addr1a".into()]),
+                pool_ids: None,
+                accounts: None,
+                format: ResponseFormat::Daily,
+                start_date: date("2024-01-01T00:00:00Z"),
+                end_date: Some(date("2024-01-31T00:00:00Z")),
+                include_usd: false,
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/ada/rewards?wallets=addr1a&format=daily&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z&include_usd=false"
+        );
+    }
 
-        data
+    #[test]
+    fn get_operations() {
+        let url = requested_url(|kiln| {
+            kiln.cardano().get_operations(StakeOperationsRequest {
+                stake_addresses: Some(vec!["stake1a".into()]),
+                wallets: None,
+                pool_ids: None,
+                accounts: None,
+                start_date: date("2024-01-01T00:00:00Z"),
+                end_date: Some(date("2024-01-31T00:00:00Z")),
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/ada/operations?stake_addresses=stake1a&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z"
+        );
+    }
+
+    #[test]
+    fn get_reports() {
+        let url = requested_url(|kiln| {
+            kiln.cardano().get_reports(&CardanoReportsRequest {
+                stake_addresses: None,
+                wallets: Some(vec!["addr1a".into()]),
+                accounts: None,
+                format: ResponseFormat::Daily,
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/ada/reports?wallets=addr1a&format=daily"
+        );
     }
 
-    pub fn post_broadcast_tx(
-        &self,
-        tx_serialized: &str,
-    ) -> Result<ReturnedData<CardanoBroadcastTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/broadcast", self.base_url,);
+    #[test]
+    fn get_tx_status() {
+        let url = requested_url(|kiln| kiln.cardano().get_tx_status("0xhash"));
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(tx_serialized)?
-            .body_mut()
-            .read_json::<ReturnedData<CardanoBroadcastTxResponse>>();
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/ada/transaction/status?tx_hash=0xhash"
+        );
+    }
 
-        data
+    #[test]
+    fn get_tx_decoding() {
+        let url = requested_url(|kiln| kiln.cardano().get_tx_decoding("ab+/="));
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/ada/transaction/decode?tx_serialized=ab%2B%2F%3D"
+        );
     }
 
-    pub fn get_tx_status(
-        &self,
-        tx_hash: &str,
-    ) -> Result<ReturnedData<CardanoTxStatusResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/status?tx_hash={}", self.base_url, tx_hash);
-
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<CardanoTxStatusResponse>>();
+    #[test]
+    fn responses_match_the_fixtures() {
+        let decoded: ReturnedData<CardanoTxDecodeResponse> = fixture("cardano/tx_decoding.json");
+        let status: ReturnedData<CardanoTxStatusResponse> = fixture("cardano/tx_status.json");
 
-        data
+        assert_eq!(
+            decoded.data.body.certs[0].stake_delegation.pool_keyhash,
+            "pool10rdglgh4pzvkf936p2m669qzarr9dusrhmmz9nultm3uvq4eh5k"
+        );
+        assert_eq!(status.data.status, CardanoTxStatus::Success);
+
+        fixture::<ReturnedData<Vec<ChainStakes>>>("cardano/stakes.json");
+        fixture::<ReturnedData<CardanoNetworkStats>>("cardano/network_stats.json");
+        fixture::<ReturnedData<Vec<Reward>>>("cardano/rewards.json");
+        fixture::<ReturnedData<Vec<PostStakesResponse>>>("cardano/post_stakes.json");
+        fixture::<ReturnedData<CardanoStakeTxResponse>>("cardano/tx.json");
+        fixture::<ReturnedData<CardanoPrepareTxResponse>>("cardano/prepare_tx.json");
+        fixture::<ReturnedData<CardanoBroadcastTxResponse>>("cardano/broadcast_tx.json");
+    }
+
+    #[test]
+    fn operations_are_told_apart_by_their_type() {
+        let operations: ReturnedData<Vec<OperationsResponse>> = fixture("cardano/operations.json");
+
+        assert!(matches!(
+            operations.data[0],
+            OperationsResponse::StakeRegisteration { epoch: 445, .. }
+        ));
+        assert!(operations.data.last().unwrap().is_unknown());
+        assert_eq!(
+            operations
+                .data
+                .iter()
+                .filter(|operation| operation.is_unknown())
+                .count(),
+            1
+        );
     }
 }
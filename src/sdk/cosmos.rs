@@ -1,10 +1,18 @@
-use std::io::Read;
-
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::address::{AddressError, CosmosAddress};
+use crate::amount::FloatAmount;
+use crate::denomination::UAtom;
+use crate::errors::KilnError;
+use crate::http::HttpClient;
+use crate::mode::{for_each_mode, Blocking, Mode};
+use crate::query::Query;
 use crate::{response_format::ReturnedData, Kiln};
 
+use super::builders::{
+    GetStakesBuilder, NoStartDate, RewardsBuilder, StartDate, ValidatorOperationsBuilder,
+};
 use super::{
     BroadcastTxRequest, BroadcastTxResponse, GetStakesRequest, GetStakesResponse,
     PostStakesRequest, PostStakesResponse, PrepareTxRequest, PrepareTxResponse, ReportsRequest,
@@ -12,10 +20,13 @@ use super::{
     TxResponse, TxStatusResponse, ValidatorOperationsRequest, WithdrawRewardsTxRequest,
 };
 
+// bech32 prefix of the cosmos addresses : `cosmos1..`, `cosmosvaloper1..`
+const PREFIX: &str = "cosmos";
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct CosmosNetworkStatsResponse {
-    pub atom_price_usd: f64,
+    pub atom_price_usd: FloatAmount,
     pub nb_validators: u64,
     pub net_gross_apy: f64,
     pub supply_staked_percent: f64,
@@ -28,10 +39,17 @@ pub struct CosmosNetworkStatsResponse {
 pub struct CosmosStakeTxRequest {
     pub account_id: Uuid,
     pub pubkey: String,
-    pub validator: String,
-    pub amount_uatom: String,
+    pub validator: CosmosAddress,
+    pub amount_uatom: UAtom,
     pub restake_rewards: bool,
-    pub grantee_address: String,
+    pub grantee_address: CosmosAddress,
+}
+
+impl CosmosStakeTxRequest {
+    pub(crate) fn check_addresses(&self, prefix: &str) -> Result<(), AddressError> {
+        self.validator.check_validator(prefix)?;
+        self.grantee_address.check_account(prefix)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -40,8 +58,14 @@ pub struct CosmosUnstakeRewardsTxRequest {
     // Pubkey
     pub pubkey: String,
     // Address
-    pub validator: String,
-    pub amount_uatom: String,
+    pub validator: CosmosAddress,
+    pub amount_uatom: UAtom,
+}
+
+impl CosmosUnstakeRewardsTxRequest {
+    pub(crate) fn check_addresses(&self, prefix: &str) -> Result<(), AddressError> {
+        self.validator.check_validator(prefix)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -50,404 +74,606 @@ pub struct CosmosRedelegateTxRequest {
     pub account_id: Uuid,
     // Pubkey
     pub pubkey: String,
-    pub validator_source: String,
-    pub validator_destination: String,
-    pub amount_uatom: String,
+    pub validator_source: CosmosAddress,
+    pub validator_destination: CosmosAddress,
+    pub amount_uatom: UAtom,
+}
+
+impl CosmosRedelegateTxRequest {
+    pub(crate) fn check_addresses(&self, prefix: &str) -> Result<(), AddressError> {
+        self.validator_source.check_validator(prefix)?;
+        self.validator_destination.check_validator(prefix)
+    }
 }
 
 #[derive(Clone, Debug)]
-pub struct KilnCosmosClient {
-    pub bearer_token: String,
+pub struct KilnCosmosClient<M: Mode = Blocking> {
     pub base_url: String,
+    http: HttpClient<M>,
 }
 
-impl From<&Kiln> for KilnCosmosClient {
-    fn from(kiln: &Kiln) -> Self {
+impl<M: Mode> From<&Kiln<M>> for KilnCosmosClient<M> {
+    fn from(kiln: &Kiln<M>) -> Self {
         let url: String = format!("{}/atom", kiln.base_url);
-        let bearer_token: String = format!("Bearer {}", kiln.api_token);
 
         Self {
-            bearer_token,
             base_url: url,
+            http: kiln.http.clone(),
         }
     }
 }
 
-impl KilnCosmosClient {
-    pub fn get_stakes(
-        &self,
-        cosmos_stake_request: &GetStakesRequest,
-    ) -> Result<ReturnedData<Vec<GetStakesResponse>>, ureq::Error> {
-        let GetStakesRequest {
-            validators,
-            delegators,
-            accounts,
-        } = cosmos_stake_request;
-
-        let validators = validators.join(",");
-
-        let delegators = delegators.join(",");
-
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let url: String = format!(
-            "{}/stakes?validators={}&delegators={}&accounts={}",
-            self.base_url, validators, delegators, accounts
-        );
+for_each_mode! { <M>
+    impl KilnCosmosClient<M> {
+        pub async fn get_stakes(
+            &self,
+            cosmos_stake_request: &GetStakesRequest,
+        ) -> Result<ReturnedData<Vec<GetStakesResponse>>, KilnError> {
+            let GetStakesRequest {
+                validators,
+                delegators,
+                accounts,
+            } = cosmos_stake_request;
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<GetStakesResponse>>>();
+            let url = Query::new()
+                .list("validators", validators.iter().flatten())
+                .list("delegators", delegators.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .url(&format!("{}/stakes", self.base_url));
 
-        data
-    }
+            self.http.get("kiln.atom.get_stakes", &url).await
+        }
 
-    pub fn post_stakes(
-        &self,
-        cosmos_create_stake_request: &PostStakesRequest,
-    ) -> Result<ReturnedData<PostStakesResponse>, ureq::Error> {
-        let url = format!("{}/stakes", self.base_url);
+        pub async fn post_stakes(
+            &self,
+            cosmos_create_stake_request: &PostStakesRequest,
+        ) -> Result<ReturnedData<PostStakesResponse>, KilnError> {
+            let url = format!("{}/stakes", self.base_url);
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(cosmos_create_stake_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PostStakesResponse>>();
+            self.http
+                .post("kiln.atom.post_stakes", &url, cosmos_create_stake_request)
+                .await
+        }
 
-        data
-    }
+        pub async fn get_rewards(
+            &self,
+            reward_request: RewardRequest,
+        ) -> Result<ReturnedData<Vec<Reward>>, KilnError> {
+            let url: String = match reward_request {
+                RewardRequest::Epoch {
+                    stakes_addresses,
+                    wallets,
+                    pool_ids,
+                    accounts,
+                    format,
+                    start_date,
+                    end_date,
+                    start_epoch,
+                    end_epoch,
+                } => Query::new()
+                    .list("stake_addresses", stakes_addresses.iter().flatten())
+                    .list("pool_ids", pool_ids.iter().flatten())
+                    .list("wallets", wallets.iter().flatten())
+                    .list("accounts", accounts.iter().flatten())
+                    .value("format", format.as_ref())
+                    .value("start_date", start_date)
+                    .option("end_date", end_date.as_ref())
+                    .value("start_epoch", start_epoch)
+                    .value("end_epoch", end_epoch)
+                    .url(&format!("{}/rewards", self.base_url)),
+                RewardRequest::Daily {
+                    stakes_addresses,
+                    wallets,
+                    pool_ids,
+                    accounts,
+                    format,
+                    start_date,
+                    end_date,
+                    include_usd,
+                } => Query::new()
+                    .list("stake_addresses", stakes_addresses.iter().flatten())
+                    .list("pool_ids", pool_ids.iter().flatten())
+                    .list("wallets", wallets.iter().flatten())
+                    .list("accounts", accounts.iter().flatten())
+                    .value("format", format.as_ref())
+                    .value("start_date", start_date)
+                    .option("end_date", end_date.as_ref())
+                    .value("include_usd", include_usd)
+                    .url(&format!("{}/rewards", self.base_url)),
+            };
+
+            self.http.get("kiln.atom.get_rewards", &url).await
+        }
 
-    pub fn get_rewards(
-        &self,
-        reward_request: RewardRequest,
-    ) -> Result<ReturnedData<Vec<Reward>>, ureq::Error> {
-        let url: String = match reward_request {
-            RewardRequest::Epoch {
-                stakes_addresses,
-                wallets,
-                pool_ids,
+        pub async fn get_operations(
+            &self,
+            validator_operations_request: &ValidatorOperationsRequest,
+        ) -> Result<ReturnedData<Vec<StakingOperation>>, KilnError> {
+            let ValidatorOperationsRequest {
+                validators,
+                delegators,
                 accounts,
-                format,
+                authz,
                 start_date,
                 end_date,
-                start_epoch,
-                end_epoch,
-            } => {
-                let stakes_addresses = stakes_addresses.join(",");
-
-                let pool_ids = pool_ids.join(",");
-
-                let wallets = wallets.join(",");
-
-                let accounts = accounts
-                    .iter()
-                    .map(|id| id.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",");
-
-                let url = format!(
-            "{}/rewards?stake_addresses={}&pool_ids={}&wallets={}&accounts={}&format={}&start_date={}&end_date={}&start_epoch={}&end_epoch={}",
-            self.base_url,
-            stakes_addresses,
-            pool_ids,
-            wallets,
-            accounts,
-            format.as_ref(),
-            start_date,
-            end_date,
-            start_epoch,
-            end_epoch
-        );
+            } = validator_operations_request;
+
+            let url = Query::new()
+                .list("validators", validators.iter().flatten())
+                .value("authz", authz)
+                .list("delegators", delegators.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .value("start_date", start_date)
+                .option("end_date", end_date.as_ref())
+                .url(&format!("{}/operations", self.base_url));
+
+            self.http.get("kiln.atom.get_operations", &url).await
+        }
 
-                url
-            }
-            RewardRequest::Daily {
-                stakes_addresses,
-                wallets,
-                pool_ids,
+        pub async fn get_network_stats(
+            &self,
+        ) -> Result<ReturnedData<CosmosNetworkStatsResponse>, KilnError> {
+            let url: String = format!("{}/network-stats", self.base_url);
+
+            self.http.get("kiln.atom.get_network_stats", &url).await
+        }
+
+        pub async fn get_reports(
+            &self,
+            reports_request: &ReportsRequest,
+        ) -> Result<Vec<u8>, KilnError> {
+            let ReportsRequest {
+                validators,
+                delegators,
                 accounts,
-                format,
-                start_date,
-                end_date,
-                include_usd,
-            } => {
-                let stakes_addresses = stakes_addresses.join(",");
-
-                let pool_ids = pool_ids.join(",");
-
-                let wallets = wallets.join(",");
-
-                let accounts = accounts
-                    .iter()
-                    .map(|id| id.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",");
-
-                let url = format!(
-            "{}/rewards?stake_addresses={}&pool_ids={}&wallets={}&accounts={}&format={}&start_date={}&end_date={}&include_usd={}",
-            self.base_url,
-            stakes_addresses,
-            pool_ids,
-            wallets,
-            accounts,
-            format.as_ref(),
-            start_date,
-            end_date,
-            include_usd
-        );
+            } = reports_request;
 
-                url
-            }
-        };
+            let url = Query::new()
+                .list("validators", validators.iter().flatten())
+                .list("delegators", delegators.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .url(&format!("{}/reports", self.base_url));
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<Reward>>>();
+            self.http.get_bytes("kiln.atom.get_reports", &url).await
+        }
 
-        data
-    }
+        pub async fn post_stake_tx(
+            &self,
+            transaction_stake_request: &CosmosStakeTxRequest,
+        ) -> Result<ReturnedData<TxResponse>, KilnError> {
+            transaction_stake_request.check_addresses(PREFIX)?;
 
-    pub fn get_operations(
-        &self,
-        validator_operations_request: &ValidatorOperationsRequest,
-    ) -> Result<ReturnedData<Vec<StakingOperation>>, ureq::Error> {
-        let ValidatorOperationsRequest {
-            validators,
-            delegators,
-            accounts,
-            authz,
-            start_date,
-            end_date,
-        } = validator_operations_request;
-
-        let validators = validators.join(",");
-
-        let delegators = delegators.join(",");
-
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let url: String = format!(
-            "{}/operations?validators={}&authz={}&delegators={}&accounts={}&start_date={}&end_date={}",
-            self.base_url, validators, authz, delegators, accounts, start_date, end_date
-        );
+            let url: String = format!("{}/transaction/stake", self.base_url,);
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<StakingOperation>>>();
+            self.http
+                .post_tx("kiln.atom.post_stake_tx", &url, transaction_stake_request)
+                .await
+        }
 
-        data
-    }
+        pub async fn post_withdraw_rewards_tx(
+            &self,
+            withdraw_rewards_transaction_request: &WithdrawRewardsTxRequest,
+        ) -> Result<ReturnedData<TxResponse>, KilnError> {
+            withdraw_rewards_transaction_request.check_addresses(PREFIX)?;
+
+            let url: String = format!("{}/transaction/withdraw-rewards", self.base_url,);
+
+            self.http
+                .post_tx(
+                    "kiln.atom.post_withdraw_rewards_tx",
+                    &url,
+                    withdraw_rewards_transaction_request,
+                )
+                .await
+        }
 
-    pub fn get_network_stats(
-        &self,
-    ) -> Result<ReturnedData<CosmosNetworkStatsResponse>, ureq::Error> {
-        let url: String = format!("{}/network-stats", self.base_url);
+        pub async fn post_restake_rewards_tx(
+            &self,
+            restake_rewards_transaction_request: &RestakeRewardsTxRequest,
+        ) -> Result<ReturnedData<TxResponse>, KilnError> {
+            restake_rewards_transaction_request.check_addresses(PREFIX)?;
+
+            let url: String = format!("{}/transaction/restake-rewards", self.base_url,);
+
+            self.http
+                .post_tx(
+                    "kiln.atom.post_restake_rewards_tx",
+                    &url,
+                    restake_rewards_transaction_request,
+                )
+                .await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<CosmosNetworkStatsResponse>>();
+        pub async fn unstake_rewards_transaction(
+            &self,
+            unstake_rewards_transaction_request: &CosmosUnstakeRewardsTxRequest,
+        ) -> Result<ReturnedData<TxResponse>, KilnError> {
+            unstake_rewards_transaction_request.check_addresses(PREFIX)?;
+
+            let url: String = format!("{}/transaction/unstake", self.base_url,);
+
+            self.http
+                .post_tx(
+                    "kiln.atom.unstake_rewards_transaction",
+                    &url,
+                    unstake_rewards_transaction_request,
+                )
+                .await
+        }
 
-        data
-    }
+        pub async fn post_redelegate_tx(
+            &self,
+            cosmos_redelegate_transaction_request: &CosmosRedelegateTxRequest,
+        ) -> Result<ReturnedData<TxResponse>, KilnError> {
+            cosmos_redelegate_transaction_request.check_addresses(PREFIX)?;
+
+            let url: String = format!("{}/transaction/redelegate", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.atom.post_redelegate_tx",
+                    &url,
+                    cosmos_redelegate_transaction_request,
+                )
+                .await
+        }
 
-    pub fn get_reports(&self, reports_request: &ReportsRequest) -> Result<Vec<u8>, ureq::Error> {
-        let ReportsRequest {
-            validators,
-            delegators,
-            accounts,
-        } = reports_request;
+        pub async fn post_prepare_tx(
+            &self,
+            prepare_transaction_request: &PrepareTxRequest,
+        ) -> Result<ReturnedData<PrepareTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/prepare", self.base_url,);
+
+            self.http
+                .post_tx(
+                    "kiln.atom.post_prepare_tx",
+                    &url,
+                    prepare_transaction_request,
+                )
+                .await
+        }
 
-        let validators = validators.join(",");
+        pub async fn post_broadcast_tx(
+            &self,
+            broadcast_transaction_request: &BroadcastTxRequest,
+        ) -> Result<ReturnedData<BroadcastTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/broadcast", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.atom.post_broadcast_tx",
+                    &url,
+                    broadcast_transaction_request,
+                )
+                .await
+        }
 
-        let delegators = delegators.join(",");
+        pub async fn get_tx_status(
+            &self,
+            tx_hash: &str,
+        ) -> Result<ReturnedData<TxStatusResponse>, KilnError> {
+            let url = Query::new()
+                .value("tx_hash", tx_hash)
+                .url(&format!("{}/transaction/status", self.base_url));
 
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
+            self.http.get("kiln.atom.get_tx_status", &url).await
+        }
 
-        let url: String = format!(
-            "{}/reports?validators={}&delegators={}&accounts={}",
-            self.base_url, validators, delegators, accounts
-        );
+        pub async fn get_tx_decoding(
+            &self,
+            tx_serialized: &str,
+        ) -> Result<ReturnedData<TxDecodingResponse>, KilnError> {
+            let url = Query::new()
+                .value("tx_serialized", tx_serialized)
+                .url(&format!("{}/transaction/decode", self.base_url));
 
-        let mut file_bytes: Vec<u8> = Vec::new();
+            self.http.get("kiln.atom.get_tx_decoding", &url).await
+        }
+    }
+}
 
-        ureq::get(url)
-            .header("accept", "application/octet-stream")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .as_reader()
-            .read_to_end(&mut file_bytes)?;
+impl<M: Mode> KilnCosmosClient<M> {
+    pub fn stakes(&self) -> GetStakesBuilder<'_, Self> {
+        GetStakesBuilder::new(self)
+    }
 
-        Ok(file_bytes)
+    pub fn rewards(&self) -> RewardsBuilder<'_, Self, NoStartDate> {
+        RewardsBuilder::new(self)
     }
 
-    pub fn post_stake_tx(
-        &self,
-        transaction_stake_request: &CosmosStakeTxRequest,
-    ) -> Result<ReturnedData<TxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/stake", self.base_url,);
+    pub fn operations(&self) -> ValidatorOperationsBuilder<'_, Self, NoStartDate> {
+        ValidatorOperationsBuilder::new(self)
+    }
+}
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(transaction_stake_request)?
-            .body_mut()
-            .read_json::<ReturnedData<TxResponse>>();
+for_each_mode! { <M>
+    impl GetStakesBuilder<'_, KilnCosmosClient<M>> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<GetStakesResponse>>, KilnError> {
+            let client = self.client;
 
-        data
+            client.get_stakes(&self.into_request()).await
+        }
     }
+}
+
+for_each_mode! { <M>
+    impl RewardsBuilder<'_, KilnCosmosClient<M>, StartDate> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<Reward>>, KilnError> {
+            let client = self.client;
 
-    pub fn post_withdraw_rewards_tx(
-        &self,
-        withdraw_rewards_transaction_request: &WithdrawRewardsTxRequest,
-    ) -> Result<ReturnedData<TxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/withdraw-rewards", self.base_url,);
+            client.get_rewards(self.into_request()?).await
+        }
+    }
+}
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(withdraw_rewards_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<TxResponse>>();
+for_each_mode! { <M>
+    impl ValidatorOperationsBuilder<'_, KilnCosmosClient<M>, StartDate> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<StakingOperation>>, KilnError> {
+            let client = self.client;
 
-        data
+            client.get_operations(&self.into_request()).await
+        }
     }
+}
 
-    pub fn post_restake_rewards_tx(
-        &self,
-        restake_rewards_transaction_request: &RestakeRewardsTxRequest,
-    ) -> Result<ReturnedData<TxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/restake-rewards", self.base_url,);
+#[cfg(all(test, feature = "blocking"))]
+mod cosmos_test {
+
+    use super::*;
+    use crate::query::query_test_support::{date, fixture, requested_url};
+    use crate::sdk::{ResponseFormat, StakeState, TxStakeMessage};
+
+    #[test]
+    fn get_stakes() {
+        let url = requested_url(|kiln| {
+            kiln.cosmos().get_stakes(&GetStakesRequest {
+                validators: Some(vec!["atomvaloper1a".into(), "atomvaloper1b".into()]),
+                accounts: Some(vec![Uuid::nil()]),
+                ..Default::default()
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/atom/stakes?validators=atomvaloper1a,atomvaloper1b&accounts=00000000-0000-0000-0000-000000000000"
+        );
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(restake_rewards_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<TxResponse>>();
+    #[test]
+    fn get_rewards() {
+        let url = requested_url(|kiln| {
+            kiln.cosmos().get_rewards(RewardRequest::Daily {
+                stakes_addresses: None,
+                wallets: Some(vec!["atom1a".into()]),
+                pool_ids: None,
+                accounts: None,
+                format: ResponseFormat::Daily,
+                start_date: date("2024-01-01T00:00:00Z"),
+                end_date: Some(date("2024-01-31T00:00:00Z")),
+                include_usd: true,
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/atom/rewards?wallets=atom1a&format=daily&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z&include_usd=true"
+        );
+    }
 
-        data
+    #[test]
+    fn get_operations() {
+        let url = requested_url(|kiln| {
+            kiln.cosmos().get_operations(&ValidatorOperationsRequest {
+                validators: None,
+                delegators: Some(vec!["atom1a".into()]),
+                authz: false,
+                accounts: None,
+                start_date: date("2024-01-01T00:00:00Z"),
+                end_date: Some(date("2024-01-31T00:00:00Z")),
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/atom/operations?authz=false&delegators=atom1a&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z"
+        );
     }
 
-    pub fn unstake_rewards_transaction(
-        &self,
-        unstake_rewards_transaction_request: &CosmosUnstakeRewardsTxRequest,
-    ) -> Result<ReturnedData<TxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/unstake", self.base_url,);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(unstake_rewards_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<TxResponse>>();
-        data
+    #[test]
+    fn stakes_builder() {
+        let url = requested_url(|kiln| {
+            kiln.cosmos()
+                .stakes()
+                .delegators(["atom1a", "atom1b"])
+                .send()
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/atom/stakes?delegators=atom1a,atom1b"
+        );
     }
 
-    pub fn post_redelegate_tx(
-        &self,
-        cosmos_redelegate_transaction_request: &CosmosRedelegateTxRequest,
-    ) -> Result<ReturnedData<TxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/redelegate", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(cosmos_redelegate_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<TxResponse>>();
-        data
+    #[test]
+    fn rewards_builder_epochs() {
+        let url = requested_url(|kiln| {
+            kiln.cosmos()
+                .rewards()
+                .wallets(["atom1a"])
+                .epochs(10, 20)
+                .since(date("2024-01-01T00:00:00Z"))
+                .until(date("2024-01-31T00:00:00Z"))
+                .send()
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/atom/rewards?wallets=atom1a&format=daily&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z&start_epoch=10&end_epoch=20"
+        );
     }
 
-    pub fn post_prepare_tx(
-        &self,
-        prepare_transaction_request: &PrepareTxRequest,
-    ) -> Result<ReturnedData<PrepareTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/prepare", self.base_url,);
+    #[test]
+    fn operations_builder() {
+        let url = requested_url(|kiln| {
+            kiln.cosmos()
+                .operations()
+                .validators(["atomvaloper1a"])
+                .authz()
+                .since(date("2024-01-01T00:00:00Z"))
+                .until(date("2024-01-31T00:00:00Z"))
+                .send()
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/atom/operations?validators=atomvaloper1a&authz=true&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z"
+        );
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(prepare_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PrepareTxResponse>>();
+    #[test]
+    fn end_date_is_left_out_until_set() {
+        let url = requested_url(|kiln| {
+            kiln.cosmos()
+                .operations()
+                .delegators(["atom1a"])
+                .since(date("2024-01-01T00:00:00Z"))
+                .send()
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/atom/operations?authz=false&delegators=atom1a&start_date=2024-01-01T00%3A00%3A00Z"
+        );
+    }
 
-        data
+    #[test]
+    fn usd_of_epoch_rewards_is_rejected() {
+        let kiln = Kiln::builder()
+            .api_token("kiln_test_token")
+            .base_url("https://api.kiln.fi/v1")
+            .seal()
+            .build()
+            .unwrap();
+
+        let cosmos = kiln.cosmos();
+        let rewards = cosmos
+            .rewards()
+            .wallets(["atom1a"])
+            .epochs(10, 20)
+            .include_usd()
+            .since(date("2024-01-01T00:00:00Z"));
+
+        assert!(matches!(rewards.send(), Err(KilnError::InvalidInput(_))));
     }
 
-    pub fn post_broadcast_tx(
-        &self,
-        broadcast_transaction_request: &BroadcastTxRequest,
-    ) -> Result<ReturnedData<BroadcastTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/broadcast", self.base_url);
+    #[test]
+    fn get_reports() {
+        let url = requested_url(|kiln| {
+            kiln.cosmos().get_reports(&ReportsRequest {
+                delegators: Some(vec!["atom1a".into(), "atom1b".into()]),
+                ..Default::default()
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/atom/reports?delegators=atom1a,atom1b"
+        );
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(broadcast_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<BroadcastTxResponse>>();
+    #[test]
+    fn get_tx_status() {
+        let url = requested_url(|kiln| kiln.cosmos().get_tx_status("A1B2C3"));
 
-        data
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/atom/transaction/status?tx_hash=A1B2C3"
+        );
     }
 
-    pub fn get_tx_status(
-        &self,
-        tx_hash: &str,
-    ) -> Result<ReturnedData<TxStatusResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/status?tx_hash={}", self.base_url, tx_hash);
+    #[test]
+    fn get_tx_decoding() {
+        let url = requested_url(|kiln| kiln.cosmos().get_tx_decoding("Cp8B+/=="));
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<TxStatusResponse>>();
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/atom/transaction/decode?tx_serialized=Cp8B%2B%2F%3D%3D"
+        );
+    }
 
-        data
+    #[test]
+    fn responses_match_the_fixtures() {
+        let stakes: ReturnedData<Vec<GetStakesResponse>> = fixture("cosmos/stakes.json");
+        let tx: ReturnedData<TxResponse> = fixture("cosmos/tx.json");
+
+        assert_eq!(stakes.data[0].state, StakeState::Active);
+        assert!(matches!(
+            &tx.data.message,
+            TxStakeMessage::Value { value, .. } if value.amount.amount_in::<UAtom>() == Some(UAtom::new(1_000_000))
+        ));
+        assert!(matches!(
+            tx.data.messages[1],
+            TxStakeMessage::Restake {
+                authorization_type: 1,
+                ..
+            }
+        ));
+
+        fixture::<ReturnedData<Vec<Reward>>>("cosmos/rewards.json");
+        fixture::<ReturnedData<CosmosNetworkStatsResponse>>("cosmos/network_stats.json");
+        fixture::<ReturnedData<PostStakesResponse>>("cosmos/post_stakes.json");
+        fixture::<ReturnedData<PrepareTxResponse>>("cosmos/prepare_tx.json");
+        fixture::<ReturnedData<BroadcastTxResponse>>("cosmos/broadcast_tx.json");
+        fixture::<ReturnedData<TxStatusResponse>>("cosmos/tx_status.json");
+        fixture::<ReturnedData<TxDecodingResponse>>("cosmos/tx_decoding.json");
     }
 
-    pub fn get_tx_decoding(
-        &self,
-        tx_serialized: &str,
-    ) -> Result<ReturnedData<TxDecodingResponse>, ureq::Error> {
-        let url: String = format!(
-            "{}/transaction/decode?tx_serialized={}",
-            self.base_url, tx_serialized
+    #[test]
+    fn operations_are_told_apart_by_their_type() {
+        let operations: ReturnedData<Vec<StakingOperation>> = fixture("cosmos/operations.json");
+
+        assert!(matches!(
+            operations.data[0],
+            StakingOperation::Delegate {
+                block: 19341288,
+                ..
+            }
+        ));
+        assert!(matches!(
+            &operations.data[3],
+            StakingOperation::Exec { executed_operations, .. }
+                if matches!(executed_operations[0], StakingOperation::Delegate { .. })
+        ));
+        assert!(operations.data[4].is_unknown());
+        assert_eq!(operations.data[4].operation_type(), "bank.MsgSend");
+        assert_eq!(operations.data[2].operation_type(), "authz.MsgGrant");
+    }
+
+    #[test]
+    fn operations_serialize_back_to_the_api_json() {
+        let operations: ReturnedData<Vec<StakingOperation>> = fixture("cosmos/operations.json");
+        let json: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/fixtures/cosmos/operations.json"
+            ))
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&operations.data).unwrap(),
+            json["data"]
         );
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<TxDecodingResponse>>();
+    #[test]
+    fn a_known_operation_type_with_a_missing_field_is_an_error() {
+        let error = serde_json::from_str::<StakingOperation>(
+            r#"{ "type": "staking.MsgDelegate", "time": "2024-02-01T10:12:44Z" }"#,
+        )
+        .unwrap_err();
 
-        data
+        assert!(error.to_string().contains("missing field `block`"));
     }
 }
@@ -1,80 +1,149 @@
+use crate::errors::KilnError;
+use crate::http::HttpClient;
+use crate::mode::{for_each_mode, Blocking, Mode};
+use crate::query::Query;
 use crate::{response_format::ReturnedData, Kiln};
 
 use super::{NetworkStats, Operations, Stakes};
 
-pub struct KilnDefiClient {
-    bearer_token: String,
+#[derive(Clone, Debug)]
+pub struct KilnDefiClient<M: Mode = Blocking> {
     base_url: String,
+    http: HttpClient<M>,
 }
 
-impl From<&Kiln> for KilnDefiClient {
-    fn from(kiln: &Kiln) -> Self {
+impl<M: Mode> From<&Kiln<M>> for KilnDefiClient<M> {
+    fn from(kiln: &Kiln<M>) -> Self {
         let url: String = format!("{}/defi", kiln.base_url);
-        let bearer_token: String = format!("Bearer {}", kiln.api_token);
 
         Self {
-            bearer_token,
             base_url: url,
+            http: kiln.http.clone(),
         }
     }
 }
 
-impl KilnDefiClient {
-    pub fn get_stakes(
-        &self,
-        wallets: Vec<String>,
-        vaults: Vec<String>,
-    ) -> Result<ReturnedData<Vec<Stakes>>, ureq::Error> {
-        let wallets = wallets.join(",");
+for_each_mode! { <M>
+    impl KilnDefiClient<M> {
+        pub async fn get_stakes(
+            &self,
+            wallets: Vec<String>,
+            vaults: Vec<String>,
+        ) -> Result<ReturnedData<Vec<Stakes>>, KilnError> {
+            let url = Query::new()
+                .list("wallets", wallets)
+                .list("vaults", vaults)
+                .url(&format!("{}/stakes", self.base_url));
+
+            self.http.get("kiln.defi.get_stakes", &url).await
+        }
 
-        let vaults = vaults.join(",");
+        pub async fn get_operations(&self) -> Result<ReturnedData<Vec<Operations>>, KilnError> {
+            let url: String = format!("{}/operations", self.base_url);
 
-        let url: String = format!(
-            "{}/stakes?wallets={}&vaults={}",
-            self.base_url, wallets, vaults
-        );
+            self.http.get("kiln.defi.get_operations", &url).await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<Stakes>>>();
+        pub async fn get_network_stats(&self) -> Result<ReturnedData<Vec<NetworkStats>>, KilnError> {
+            let url: String = format!("{}/network-stats", self.base_url);
 
-        data
+            self.http.get("kiln.defi.get_network_stats", &url).await
+        }
     }
+}
 
-    pub fn get_operations(&self) -> Result<ReturnedData<Vec<Operations>>, ureq::Error> {
-        let url: String = format!("{}/operations", self.base_url);
+impl<M: Mode> KilnDefiClient<M> {
+    pub fn stakes(&self) -> DefiStakesBuilder<'_, M> {
+        DefiStakesBuilder::new(self)
+    }
+}
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<Operations>>>();
+// `kiln.defi().stakes()`
+#[derive(Debug)]
+pub struct DefiStakesBuilder<'a, M: Mode = Blocking> {
+    client: &'a KilnDefiClient<M>,
+    wallets: Vec<String>,
+    vaults: Vec<String>,
+}
 
-        data
+impl<'a, M: Mode> DefiStakesBuilder<'a, M> {
+    fn new(client: &'a KilnDefiClient<M>) -> Self {
+        Self {
+            client,
+            wallets: Vec::new(),
+            vaults: Vec::new(),
+        }
     }
 
-    pub fn get_network_stats(&self) -> Result<ReturnedData<Vec<NetworkStats>>, ureq::Error> {
-        let url: String = format!("{}/network-stats", self.base_url);
+    pub fn wallets(self, wallets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            wallets: wallets.into_iter().map(Into::into).collect(),
+            ..self
+        }
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<NetworkStats>>>();
+    pub fn vaults(self, vaults: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            vaults: vaults.into_iter().map(Into::into).collect(),
+            ..self
+        }
+    }
+}
 
-        data
+for_each_mode! { <M>
+    impl DefiStakesBuilder<'_, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<Stakes>>, KilnError> {
+            self.client.get_stakes(self.wallets, self.vaults).await
+        }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "blocking"))]
 mod defi_test {
 
     use super::*;
+    use crate::query::query_test_support::{fixture, requested_url};
+    use crate::sdk::{Operation, Protocol};
+
+    #[test]
+    fn get_stakes_url() {
+        let url = requested_url(|kiln| {
+            kiln.defi()
+                .get_stakes(vec!["0xa".into(), "0xb".into()], vec![])
+        });
+
+        assert_eq!(url, "https://api.kiln.fi/v1/defi/stakes?wallets=0xa,0xb");
+    }
+
+    #[test]
+    fn get_operations_url() {
+        let url = requested_url(|kiln| kiln.defi().get_operations());
+
+        assert_eq!(url, "https://api.kiln.fi/v1/defi/operations");
+    }
+
+    #[test]
+    fn get_network_stats_url() {
+        let url = requested_url(|kiln| kiln.defi().get_network_stats());
+
+        assert_eq!(url, "https://api.kiln.fi/v1/defi/network-stats");
+    }
+
+    #[test]
+    fn responses_match_the_fixtures() {
+        let operations: ReturnedData<Vec<Operations>> = fixture("defi/operations.json");
+        let stats: ReturnedData<Vec<NetworkStats>> = fixture("defi/network_stats.json");
+
+        assert_eq!(operations.data[1].r#type, Operation::Withdrawal);
+        // a type this version doesn't know yet doesn't fail the response
+        assert_eq!(
+            operations.data[2].r#type,
+            Operation::Unknown("claim".into())
+        );
+        assert_eq!(stats.data[0].protocol, Protocol::AaveV3);
+
+        fixture::<ReturnedData<Vec<Stakes>>>("defi/stakes.json");
+    }
 
     // the #[ignore] are here because the temporary free api token isn't valid anymore
 
@@ -87,7 +156,6 @@ mod defi_test {
 
         let kiln: Kiln = Kiln::builder()
             .api_token(api_token)
-            // no trailing slash, or else it will break
             .base_url("https://api.kiln.fi/v1")
             .seal()
             .build()
@@ -119,7 +187,6 @@ mod defi_test {
 
         let kiln: Kiln = Kiln::builder()
             .api_token(api_token)
-            // no trailing slash, or else it will break
             .base_url("https://api.kiln.fi/v1")
             .seal()
             .build()
@@ -138,7 +205,6 @@ mod defi_test {
 
         let kiln: Kiln = Kiln::builder()
             .api_token(api_token)
-            // no trailing slash, or else it will break
             .base_url("https://api.kiln.fi/v1")
             .seal()
             .build()
@@ -1,17 +1,20 @@
 use serde::{Deserialize, Serialize};
-use strum_macros::{AsRefStr, EnumString};
 use uuid::Uuid;
 
+use crate::amount::Amount;
+use crate::errors::KilnError;
+use crate::http::HttpClient;
+use crate::mode::{for_each_mode, Blocking, Mode};
 use crate::{response_format::ReturnedData, Kiln};
 
 use super::{Chain, ChainID, StakeStatus};
 
-#[derive(Serialize, Deserialize, EnumString, AsRefStr, Debug)]
-#[serde(rename_all = "snake_case")]
-pub enum DeploymentProductType {
-    Defi,
-    Dedicated,
-    Pooling,
+string_enum! {
+    pub enum DeploymentProductType {
+        Defi => "defi",
+        Dedicated => "dedicated",
+        Pooling => "pooling",
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -28,36 +31,147 @@ pub struct Deployment {
     pub status: StakeStatus,
     pub asset_icon: Option<String>,
     pub protocol_icon: Option<String>,
-    pub product_fee: String,
+    pub product_fee: Amount,
 }
 
 #[derive(Clone, Debug)]
-pub struct KilnDeploymentsClient {
-    bearer_token: String,
+pub struct KilnDeploymentsClient<M: Mode = Blocking> {
     base_url: String,
+    http: HttpClient<M>,
 }
 
-impl From<&Kiln> for KilnDeploymentsClient {
-    fn from(kiln: &Kiln) -> Self {
+impl<M: Mode> From<&Kiln<M>> for KilnDeploymentsClient<M> {
+    fn from(kiln: &Kiln<M>) -> Self {
         let url: String = format!("{}/deployments", kiln.base_url);
-        let bearer_token: String = format!("Bearer {}", kiln.api_token);
 
         Self {
-            bearer_token,
             base_url: url,
+            http: kiln.http.clone(),
         }
     }
 }
 
-impl KilnDeploymentsClient {
-    pub fn get(&self) -> Result<ReturnedData<Vec<Deployment>>, ureq::Error> {
-        let data = ureq::get(&self.base_url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<Deployment>>>();
+for_each_mode! { <M>
+    impl KilnDeploymentsClient<M> {
+        pub async fn get(&self) -> Result<ReturnedData<Vec<Deployment>>, KilnError> {
+            self.http.get("kiln.deployments.get", &self.base_url).await
+        }
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod deployments_test {
+
+    use super::*;
+    use crate::query::query_test_support::{fixture, requested_url};
+
+    const DEPLOYMENTS: &str = r#"[
+        {
+            "id": "9e1c62a4-2b01-4cd8-a2a4-74c57dcd2be4",
+            "product_type": "pooling",
+            "name": "eth-pooling",
+            "display_name": "ETH pooling",
+            "description": "",
+            "chain": "eth",
+            "chain_id": 1,
+            "address": "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "status": "active",
+            "asset_icon": null,
+            "protocol_icon": null,
+            "product_fee": "0.08"
+        },
+        {
+            "id": "9e1c62a4-2b01-4cd8-a2a4-74c57dcd2be5",
+            "product_type": "restaking",
+            "name": "sui-native",
+            "display_name": "SUI native",
+            "description": "",
+            "chain": "sui",
+            "chain_id": 101,
+            "address": "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "status": "paused",
+            "asset_icon": null,
+            "protocol_icon": null,
+            "product_fee": "0.1"
+        }
+    ]"#;
+
+    #[test]
+    fn get() {
+        let url = requested_url(|kiln| kiln.deployments().get());
+
+        assert_eq!(url, "https://api.kiln.fi/v1/deployments");
+    }
+
+    #[test]
+    fn unknown_values_still_deserialize() {
+        let deployments: Vec<Deployment> = serde_json::from_str(DEPLOYMENTS).unwrap();
+
+        assert_eq!(deployments[0].product_type, DeploymentProductType::Pooling);
+        assert_eq!(deployments[0].chain, Chain::Eth);
+        assert_eq!(deployments[0].status, StakeStatus::Active);
+
+        let unknown = &deployments[1];
+        assert_eq!(
+            unknown.product_type,
+            DeploymentProductType::Unknown("restaking".into())
+        );
+        assert_eq!(unknown.chain, Chain::Unknown("sui".into()));
+        assert_eq!(unknown.chain_id, ChainID::Unknown(101));
+        assert!(unknown.status.is_unknown());
+    }
+
+    #[test]
+    fn chain_ids_are_numbers() {
+        let ids: Vec<ChainID> = serde_json::from_str(r#"[1, 17000, 560048, "137", 101]"#).unwrap();
+
+        assert_eq!(
+            ids,
+            vec![
+                ChainID::EthereumMainnet,
+                ChainID::Holesky,
+                ChainID::Hoodi,
+                ChainID::PolygonMainnet,
+                ChainID::Unknown(101),
+            ]
+        );
+        assert_eq!(
+            serde_json::to_string(&ids).unwrap(),
+            "[1,17000,560048,137,101]"
+        );
+        assert!(serde_json::from_str::<ChainID>(r#""EthereumMainnet""#).is_err());
+    }
+
+    #[test]
+    fn chain_ids_convert_to_chains() {
+        assert_eq!(ChainID::Hoodi.chain(), Some(Chain::Eth));
+        assert_eq!(ChainID::BaseSepolia.chain(), Some(Chain::Base));
+        assert_eq!(ChainID::Unknown(101).chain(), None);
+        assert_eq!(Chain::Matic.mainnet_id(), Some(ChainID::PolygonMainnet));
+        assert_eq!(Chain::CosmosHub4.mainnet_id(), None);
+        assert_eq!(u64::from(ChainID::ArbitrumSepolia), 421614);
+        assert_eq!(ChainID::from(42161), ChainID::ArbitrumOne);
+    }
+
+    #[test]
+    fn unknown_values_serialize_back() {
+        let deployments: Vec<Deployment> = serde_json::from_str(DEPLOYMENTS).unwrap();
+        let json = serde_json::to_value(&deployments[1]).unwrap();
+
+        assert_eq!(json["product_type"], "restaking");
+        assert_eq!(json["chain"], "sui");
+        assert_eq!(json["chain_id"], 101);
+        assert_eq!("paused".parse::<StakeStatus>().unwrap().as_ref(), "paused");
+    }
+
+    #[test]
+    fn responses_match_the_fixtures() {
+        let deployments: ReturnedData<Vec<Deployment>> = fixture("deployments/deployments.json");
 
-        data
+        assert_eq!(deployments.data[0].chain_id, ChainID::ArbitrumOne);
+        assert!(deployments
+            .data
+            .iter()
+            .all(|deployment| !deployment.status.is_unknown()));
     }
 }
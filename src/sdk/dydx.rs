@@ -1,36 +1,45 @@
-use std::io::Read;
-
-use serde::{Deserialize, Deserializer, Serialize};
-use strum_macros::EnumString;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::address::{AddressError, CosmosAddress};
+use crate::amount::{Amount, FloatAmount};
+use crate::denomination::{Adydx, Uusdc};
+use crate::errors::KilnError;
+use crate::http::HttpClient;
+use crate::mode::{for_each_mode, Blocking, Mode};
+use crate::query::Query;
 use crate::{response_format::ReturnedData, Kiln};
 
+use super::builders::{
+    GetStakesBuilder, NoStartDate, RewardsBuilder, StartDate, ValidatorOperationsBuilder,
+};
 use super::{
     BroadcastTxRequest, BroadcastTxResponse, Chain, ChainID, GetBalanceRequest, GetStakesRequest,
     PostStakesRequest, PostStakesResponse, PrepareTxRequest, PrepareTxResponse, Protocol,
     ReportsRequest, RewardRequest, TxDecodingResponse, TxResponse, TxStakeCoin, TxStatusResponse,
-    WithdrawRewardsTxRequest,
+    ValidatorOperationsRequest, WithdrawRewardsTxRequest,
 };
 
-#[derive(Serialize, Deserialize, Debug)]
-pub enum DydxStakeState {
-    Active,
-    Inactive,
-    Deactivating,
+// bech32 prefix of the dydx addresses : `dydx1..`, `dydxvaloper1..`
+const PREFIX: &str = "dydx";
+
+string_enum! {
+    pub enum DydxStakeState {
+        Active => "Active",
+        Inactive => "Inactive",
+        Deactivating => "Deactivating",
+    }
 }
 
-#[derive(Serialize, Deserialize, EnumString, Debug, Default)]
-pub enum DydxStakePermissionKind {
-    #[default]
-    #[strum(serialize = "Staking.MsgDelegate")]
-    Delegate,
-    #[strum(serialize = "Staking.MsgUndelegate")]
-    Undelegate,
-    #[strum(serialize = "Staking.Redelegate")]
-    Redelegate,
-    #[strum(serialize = "Distribution.MsgWithdrawDelegatorReward")]
-    WithdrawDelegatorReward,
+string_enum! {
+    #[derive(Default)]
+    pub enum DydxStakePermissionKind {
+        #[default]
+        Delegate => "Staking.MsgDelegate",
+        Undelegate => "Staking.MsgUndelegate",
+        Redelegate => "Staking.Redelegate",
+        WithdrawDelegatorReward => "Distribution.MsgWithdrawDelegatorReward",
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -49,8 +58,8 @@ pub struct DydxGetStakesResponsePermission {
 pub struct DydxGetStakesResponseUnbonding {
     pub creation_height: u64,
     pub completion_time: chrono::DateTime<chrono::Utc>,
-    pub balance: String,
-    pub initial_balance: String,
+    pub balance: Amount,
+    pub initial_balance: Amount,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -62,9 +71,9 @@ pub struct DydxGetStakesResponse {
     pub delegated_block: Option<u64>,
     pub undelegated_at: Option<chrono::DateTime<chrono::Utc>>,
     pub undelegated_block: Option<u64>,
-    pub rewards: String,
-    pub available_rewards: String,
-    pub balance: String,
+    pub rewards: Amount,
+    pub available_rewards: Amount,
+    pub balance: Amount,
     pub net_apy: f64,
     pub state: DydxStakeState,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
@@ -72,14 +81,12 @@ pub struct DydxGetStakesResponse {
     pub unbondings: Vec<DydxGetStakesResponseUnbonding>,
 }
 
-#[derive(Serialize, Deserialize, EnumString, Debug)]
-pub enum DydxStakeStatus {
-    #[strum(serialize = "active")]
-    Active,
-    #[strum(serialize = "pending")]
-    Pending,
-    #[strum(serialize = "disabled")]
-    Disabled,
+string_enum! {
+    pub enum DydxStakeStatus {
+        Active => "active",
+        Pending => "pending",
+        Disabled => "disabled",
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -99,143 +106,102 @@ pub struct DydxCreateStakeResponse {
 #[serde(rename_all = "snake_case")]
 pub struct DydxReward {
     pub date: chrono::DateTime<chrono::Utc>,
-    pub rewards: String,
-    pub balance: String,
+    pub rewards: Amount,
+    pub balance: Amount,
     pub net_apy: f64,
-    pub rewards_uusdc: String,
-    pub rewards_usdc_usd: String,
-    pub rewards_usd: String,
-    pub balance_usd: String,
+    pub rewards_uusdc: Amount,
+    pub rewards_usdc_usd: Amount,
+    pub rewards_usd: Amount,
+    pub balance_usd: Amount,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct DydxOperationsRequest {
-    pub validators: Vec<String>,
-    pub delegators: Vec<String>,
+    pub validators: Option<Vec<String>>,
+    pub delegators: Option<Vec<String>>,
     pub authz: bool,
-    pub accounts: Vec<Uuid>,
+    pub accounts: Option<Vec<Uuid>>,
     pub start_date: chrono::DateTime<chrono::Utc>,
-    pub end_date: chrono::DateTime<chrono::Utc>,
-}
-
-#[derive(Serialize, Deserialize, EnumString, Debug)]
-pub enum DydxOperation {
-    #[strum(serialize = "Staking.MsgDelegate", serialize = "staking.MsgDelegate")]
-    Delegate {
-        #[serde(deserialize_with = "deserialize_type")]
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        block: u64,
-        tx_hash: String,
-        tx_gas_used: String,
-        message_index: u64,
-        validator_address: String,
-        delegator_address: String,
-        amount: String,
-        withdraw_rewards: String,
-    },
-    #[strum(
-        serialize = "Staking.MsgUndelegate",
-        serialize = "staking.MsgUndelegate"
-    )]
-    Undelegate {
-        #[serde(deserialize_with = "deserialize_type")]
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        block: u64,
-        tx_hash: String,
-        tx_gas_used: String,
-        message_index: u64,
-        validator_address: String,
-        delegator_address: String,
-        amount: String,
-        withdraw_rewards: String,
-    },
-    #[strum(
-        serialize = "Staking.MsgBeginRedelegate",
-        serialize = "staking.MsgBeginRedelegate"
-    )]
-    BeginRedelegate {
-        #[serde(deserialize_with = "deserialize_type")]
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        block: u64,
-        tx_hash: String,
-        tx_gas_used: String,
-        message_index: u64,
-        validator_address: String,
-        validator_address_source: String,
-        delegator_address: String,
-        amount: String,
-        withdraw_rewards: String,
-        withdraw_rewards_source: String,
-    },
-    #[strum(
-        serialize = "Distribution.MsgWithdrawDelegatorReward",
-        serialize = "distribution.MsgWithdrawDelegatorReward"
-    )]
-    WithdrawDelegatorReward {
-        #[serde(deserialize_with = "deserialize_type")]
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        block: u64,
-        tx_hash: String,
-        tx_gas_used: String,
-        message_index: u64,
-        validator_address: String,
-        delegator_address: String,
-        withdraw_rewards: String,
-    },
-    Grant {
-        #[serde(deserialize_with = "deserialize_type")]
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        block: u64,
-        tx_hash: String,
-        tx_gas_used: String,
-        message_index: u64,
-        grantee: String,
-        granter: String,
-        permission: DydxStakePermissionKind,
-        allow_list: Option<Vec<String>>,
-        denly_list: Option<Vec<String>>,
-    },
-    Exec {
-        #[serde(deserialize_with = "deserialize_type")]
-        r#type: String,
-        validator_address: String,
-        validator_address_source: String,
-        delegator_address: String,
-        amount: String,
-        withdraw_rewards: String,
-        withdraw_rewards_source: String,
-        executed_operations: Option<Vec<DydxOperation>>,
-    },
+    pub end_date: Option<chrono::DateTime<chrono::Utc>>,
 }
 
-fn deserialize_type<'de, D>(deserializer: D) -> Result<String, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let the_type = DydxOperation::deserialize(deserializer)?;
-
-    let the_type = match the_type {
-        DydxOperation::Delegate { .. } => "staking.MsgDelegate".into(),
-        DydxOperation::Undelegate { .. } => "staking.MsgUndelegate".into(),
-        DydxOperation::WithdrawDelegatorReward { .. } => "distr.MsgWithdrawDelegatorReward".into(),
-        DydxOperation::BeginRedelegate { .. } => "staking.MsgBeginRedelegate".into(),
-        DydxOperation::Grant { .. } => "auth.grant".into(),
-        DydxOperation::Exec { .. } => "auth.exec".into(),
-    };
-
-    Ok(the_type)
+operation_enum! {
+    pub enum DydxOperation {
+        Delegate => "staking.MsgDelegate" | "Staking.MsgDelegate" {
+            time: chrono::DateTime<chrono::Utc>,
+            block: u64,
+            tx_hash: String,
+            tx_gas_used: String,
+            message_index: u64,
+            validator_address: String,
+            delegator_address: String,
+            amount: Amount,
+            withdraw_rewards: Amount,
+        },
+        Undelegate => "staking.MsgUndelegate" | "Staking.MsgUndelegate" {
+            time: chrono::DateTime<chrono::Utc>,
+            block: u64,
+            tx_hash: String,
+            tx_gas_used: String,
+            message_index: u64,
+            validator_address: String,
+            delegator_address: String,
+            amount: Amount,
+            withdraw_rewards: Amount,
+        },
+        BeginRedelegate => "staking.MsgBeginRedelegate" | "Staking.MsgBeginRedelegate" {
+            time: chrono::DateTime<chrono::Utc>,
+            block: u64,
+            tx_hash: String,
+            tx_gas_used: String,
+            message_index: u64,
+            validator_address: String,
+            validator_address_source: String,
+            delegator_address: String,
+            amount: Amount,
+            withdraw_rewards: Amount,
+            withdraw_rewards_source: String,
+        },
+        WithdrawDelegatorReward => "distribution.MsgWithdrawDelegatorReward" | "Distribution.MsgWithdrawDelegatorReward" {
+            time: chrono::DateTime<chrono::Utc>,
+            block: u64,
+            tx_hash: String,
+            tx_gas_used: String,
+            message_index: u64,
+            validator_address: String,
+            delegator_address: String,
+            withdraw_rewards: Amount,
+        },
+        Grant => "authz.MsgGrant" | "Authz.MsgGrant" {
+            time: chrono::DateTime<chrono::Utc>,
+            block: u64,
+            tx_hash: String,
+            tx_gas_used: String,
+            message_index: u64,
+            grantee: String,
+            granter: String,
+            permission: DydxStakePermissionKind,
+            allow_list: Option<Vec<String>>,
+            deny_list: Option<Vec<String>>,
+        },
+        Exec => "authz.MsgExec" | "Authz.MsgExec" {
+            time: chrono::DateTime<chrono::Utc>,
+            block: u64,
+            tx_hash: String,
+            tx_gas_used: String,
+            message_index: u64,
+            grantee: String,
+            // the operations the grantee executed on behalf of the granter
+            executed_operations: Vec<DydxOperation>,
+        },
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct DydxNetworkStatsResponse {
-    pub dydx_price_usd: f64,
+    pub dydx_price_usd: FloatAmount,
     pub nb_validators: u64,
     pub net_gross_apy: f64,
     pub supply_staked_percent: f64,
@@ -248,8 +214,14 @@ pub struct DydxNetworkStatsResponse {
 pub struct DydxStakeTxRequest {
     pub account_id: Uuid,
     pub pubkey: String,
-    pub validator: String,
-    pub amount_adydx: String,
+    pub validator: CosmosAddress,
+    pub amount_adydx: Adydx,
+}
+
+impl DydxStakeTxRequest {
+    pub(crate) fn check_addresses(&self, prefix: &str) -> Result<(), AddressError> {
+        self.validator.check_validator(prefix)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -258,8 +230,14 @@ pub struct DydxUnstakeTxRequest {
     // Pubkey
     pub pubkey: String,
     // Address
-    pub validator: String,
-    pub amount_udydx: String,
+    pub validator: CosmosAddress,
+    pub amount_adydx: Adydx,
+}
+
+impl DydxUnstakeTxRequest {
+    pub(crate) fn check_addresses(&self, prefix: &str) -> Result<(), AddressError> {
+        self.validator.check_validator(prefix)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -268,427 +246,598 @@ pub struct DydxRedelegateTxRequest {
     pub account_id: Uuid,
     // Pubkey
     pub pubkey: String,
-    pub validator_source: String,
-    pub validar_destination: String,
-    pub amount_udydx: String,
+    pub validator_source: CosmosAddress,
+    pub validar_destination: CosmosAddress,
+    pub amount_adydx: Adydx,
+}
+
+impl DydxRedelegateTxRequest {
+    pub(crate) fn check_addresses(&self, prefix: &str) -> Result<(), AddressError> {
+        self.validator_source.check_validator(prefix)?;
+        self.validar_destination.check_account(prefix)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct NobleIBCTransferRequest {
     pub pubkey: String,
-    pub amount_uusdc: String,
+    pub amount_uusdc: Uusdc,
 }
 
 #[derive(Clone, Debug)]
-pub struct KilnDydxClient {
-    pub bearer_token: String,
+pub struct KilnDydxClient<M: Mode = Blocking> {
     pub base_url: String,
+    http: HttpClient<M>,
 }
 
-impl From<&Kiln> for KilnDydxClient {
-    fn from(kiln: &Kiln) -> Self {
+impl<M: Mode> From<&Kiln<M>> for KilnDydxClient<M> {
+    fn from(kiln: &Kiln<M>) -> Self {
         let url: String = format!("{}/dydx", kiln.base_url);
-        let bearer_token: String = format!("Bearer {}", kiln.api_token);
 
         Self {
-            bearer_token,
             base_url: url,
+            http: kiln.http.clone(),
         }
     }
 }
 
-impl KilnDydxClient {
-    pub fn get_stakes(
-        &self,
-        dydx_stake_request: &GetStakesRequest,
-    ) -> Result<ReturnedData<DydxGetStakesResponse>, ureq::Error> {
-        let GetStakesRequest {
-            validators,
-            delegators,
-            accounts,
-        } = dydx_stake_request;
-
-        let validators = validators.join(",");
-
-        let delegators = delegators.join(",");
-
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let url: String = format!(
-            "{}/stakes?validators={}&delegators={}&accounts={}",
-            self.base_url, validators, delegators, accounts
-        );
+for_each_mode! { <M>
+    impl KilnDydxClient<M> {
+        pub async fn get_stakes(
+            &self,
+            dydx_stake_request: &GetStakesRequest,
+        ) -> Result<ReturnedData<Vec<DydxGetStakesResponse>>, KilnError> {
+            let GetStakesRequest {
+                validators,
+                delegators,
+                accounts,
+            } = dydx_stake_request;
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<DydxGetStakesResponse>>();
+            let url = Query::new()
+                .list("validators", validators.iter().flatten())
+                .list("delegators", delegators.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .url(&format!("{}/stakes", self.base_url));
 
-        data
-    }
+            self.http.get("kiln.dydx.get_stakes", &url).await
+        }
 
-    pub fn post_stakes(
-        &self,
-        post_stakes_request: &PostStakesRequest,
-    ) -> Result<ReturnedData<PostStakesResponse>, ureq::Error> {
-        let url = format!("{}/stakes", self.base_url);
+        pub async fn post_stakes(
+            &self,
+            post_stakes_request: &PostStakesRequest,
+        ) -> Result<ReturnedData<PostStakesResponse>, KilnError> {
+            let url = format!("{}/stakes", self.base_url);
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(post_stakes_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PostStakesResponse>>();
+            self.http
+                .post("kiln.dydx.post_stakes", &url, post_stakes_request)
+                .await
+        }
 
-        data
-    }
+        pub async fn get_rewards(
+            &self,
+            reward_request: RewardRequest,
+        ) -> Result<ReturnedData<Vec<DydxReward>>, KilnError> {
+            let url: String = match reward_request {
+                RewardRequest::Epoch {
+                    stakes_addresses,
+                    wallets,
+                    pool_ids,
+                    accounts,
+                    format,
+                    start_date,
+                    end_date,
+                    start_epoch,
+                    end_epoch,
+                } => Query::new()
+                    .list("stake_addresses", stakes_addresses.iter().flatten())
+                    .list("pool_ids", pool_ids.iter().flatten())
+                    .list("wallets", wallets.iter().flatten())
+                    .list("accounts", accounts.iter().flatten())
+                    .value("format", format.as_ref())
+                    .value("start_date", start_date)
+                    .option("end_date", end_date.as_ref())
+                    .value("start_epoch", start_epoch)
+                    .value("end_epoch", end_epoch)
+                    .url(&format!("{}/rewards", self.base_url)),
+                RewardRequest::Daily {
+                    stakes_addresses,
+                    wallets,
+                    pool_ids,
+                    accounts,
+                    format,
+                    start_date,
+                    end_date,
+                    include_usd,
+                } => Query::new()
+                    .list("stake_addresses", stakes_addresses.iter().flatten())
+                    .list("pool_ids", pool_ids.iter().flatten())
+                    .list("wallets", wallets.iter().flatten())
+                    .list("accounts", accounts.iter().flatten())
+                    .value("format", format.as_ref())
+                    .value("start_date", start_date)
+                    .option("end_date", end_date.as_ref())
+                    .value("include_usd", include_usd)
+                    .url(&format!("{}/rewards", self.base_url)),
+            };
+
+            self.http.get("kiln.dydx.get_rewards", &url).await
+        }
 
-    pub fn get_rewards(
-        &self,
-        reward_request: RewardRequest,
-    ) -> Result<ReturnedData<Vec<DydxReward>>, ureq::Error> {
-        let url: String = match reward_request {
-            RewardRequest::Epoch {
-                stakes_addresses,
-                wallets,
-                pool_ids,
+        pub async fn get_operations(
+            &self,
+            dydx_operations_request: &DydxOperationsRequest,
+        ) -> Result<ReturnedData<Vec<DydxOperation>>, KilnError> {
+            let DydxOperationsRequest {
+                validators,
+                delegators,
                 accounts,
-                format,
+                authz,
                 start_date,
                 end_date,
-                start_epoch,
-                end_epoch,
-            } => {
-                let stakes_addresses = stakes_addresses.join(",");
-
-                let pool_ids = pool_ids.join(",");
-
-                let wallets = wallets.join(",");
-
-                let accounts = accounts
-                    .iter()
-                    .map(|id| id.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",");
-
-                let url = format!(
-            "{}/rewards?stake_addresses={}&pool_ids={}&wallets={}&accounts={}&format={}&start_date={}&end_date={}&start_epoch={}&end_epoch={}",
-            self.base_url,
-            stakes_addresses,
-            pool_ids,
-            wallets,
-            accounts,
-            format.as_ref(),
-            start_date,
-            end_date,
-            start_epoch,
-            end_epoch
-        );
+            } = dydx_operations_request;
+
+            let url = Query::new()
+                .list("validators", validators.iter().flatten())
+                .value("authz", authz)
+                .list("delegators", delegators.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .value("start_date", start_date)
+                .option("end_date", end_date.as_ref())
+                .url(&format!("{}/operations", self.base_url));
+
+            self.http.get("kiln.dydx.get_operations", &url).await
+        }
 
-                url
-            }
-            RewardRequest::Daily {
-                stakes_addresses,
-                wallets,
-                pool_ids,
+        pub async fn get_network_stats(
+            &self,
+        ) -> Result<ReturnedData<DydxNetworkStatsResponse>, KilnError> {
+            let url: String = format!("{}/network-stats", self.base_url);
+
+            self.http.get("kiln.dydx.get_network_stats", &url).await
+        }
+
+        pub async fn get_reports(
+            &self,
+            dydx_reports_request: &ReportsRequest,
+        ) -> Result<Vec<u8>, KilnError> {
+            let ReportsRequest {
+                validators,
+                delegators,
                 accounts,
-                format,
-                start_date,
-                end_date,
-                include_usd,
-            } => {
-                let stakes_addresses = stakes_addresses.join(",");
-
-                let pool_ids = pool_ids.join(",");
-
-                let wallets = wallets.join(",");
-
-                let accounts = accounts
-                    .iter()
-                    .map(|id| id.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",");
-
-                let url = format!(
-            "{}/rewards?stake_addresses={}&pool_ids={}&wallets={}&accounts={}&format={}&start_date={}&end_date={}&include_usd={}",
-            self.base_url,
-            stakes_addresses,
-            pool_ids,
-            wallets,
-            accounts,
-            format.as_ref(),
-            start_date,
-            end_date,
-            include_usd
-        );
+            } = dydx_reports_request;
 
-                url
-            }
-        };
+            let url = Query::new()
+                .list("validators", validators.iter().flatten())
+                .list("delegators", delegators.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .url(&format!("{}/reports", self.base_url));
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<DydxReward>>>();
+            self.http.get_bytes("kiln.dydx.get_reports", &url).await
+        }
 
-        data
-    }
+        pub async fn post_get_balance(
+            &self,
+            get_balance_request: &GetBalanceRequest,
+        ) -> Result<ReturnedData<TxStakeCoin>, KilnError> {
+            let url: String = format!("{}/balance", self.base_url);
 
-    pub fn get_operations(
-        &self,
-        dydx_operations_request: &DydxOperationsRequest,
-    ) -> Result<ReturnedData<Vec<DydxOperation>>, ureq::Error> {
-        let DydxOperationsRequest {
-            validators,
-            delegators,
-            accounts,
-            authz,
-            start_date,
-            end_date,
-        } = dydx_operations_request;
-
-        let validators = validators.join(",");
-
-        let delegators = delegators.join(",");
-
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let url: String = format!(
-            "{}/operations?validators={}&authz={}&delegators={}&accounts={}&start_date={}&end_date={}",
-            self.base_url, validators, authz, delegators, accounts, start_date, end_date
-        );
+            self.http
+                .post("kiln.dydx.post_get_balance", &url, get_balance_request)
+                .await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<DydxOperation>>>();
+        pub async fn post_stake_tx(
+            &self,
+            transaction_stake_request: &DydxStakeTxRequest,
+        ) -> Result<ReturnedData<TxResponse>, KilnError> {
+            transaction_stake_request.check_addresses(PREFIX)?;
 
-        data
-    }
+            let url: String = format!("{}/transaction/stake", self.base_url,);
 
-    pub fn get_network_stats(&self) -> Result<ReturnedData<DydxNetworkStatsResponse>, ureq::Error> {
-        let url: String = format!("{}/network-stats", self.base_url);
+            self.http
+                .post_tx("kiln.dydx.post_stake_tx", &url, transaction_stake_request)
+                .await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<DydxNetworkStatsResponse>>();
+        pub async fn post_withdraw_rewards_tx(
+            &self,
+            withdraw_rewards_transaction_request: &WithdrawRewardsTxRequest,
+        ) -> Result<ReturnedData<TxResponse>, KilnError> {
+            withdraw_rewards_transaction_request.check_addresses(PREFIX)?;
+
+            let url: String = format!("{}/transaction/withdraw-rewards", self.base_url,);
+
+            self.http
+                .post_tx(
+                    "kiln.dydx.post_withdraw_rewards_tx",
+                    &url,
+                    withdraw_rewards_transaction_request,
+                )
+                .await
+        }
 
-        data
-    }
+        pub async fn post_unstake_tx(
+            &self,
+            unstake_rewards_transaction_request: &DydxUnstakeTxRequest,
+        ) -> Result<ReturnedData<TxResponse>, KilnError> {
+            unstake_rewards_transaction_request.check_addresses(PREFIX)?;
+
+            let url: String = format!("{}/transaction/unstake", self.base_url,);
+
+            self.http
+                .post_tx(
+                    "kiln.dydx.post_unstake_tx",
+                    &url,
+                    unstake_rewards_transaction_request,
+                )
+                .await
+        }
 
-    pub fn get_reports(
-        &self,
-        dydx_reports_request: &ReportsRequest,
-    ) -> Result<Vec<u8>, ureq::Error> {
-        let ReportsRequest {
-            validators,
-            delegators,
-            accounts,
-        } = dydx_reports_request;
-
-        let validators = validators.join(",");
-
-        let delegators = delegators.join(",");
-
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let url: String = format!(
-            "{}/reports?validators={}&delegators={}&accounts={}",
-            self.base_url, validators, delegators, accounts
-        );
+        pub async fn post_redelegate_tx(
+            &self,
+            redelegate_transaction_request: &DydxRedelegateTxRequest,
+        ) -> Result<ReturnedData<TxResponse>, KilnError> {
+            redelegate_transaction_request.check_addresses(PREFIX)?;
+
+            let url: String = format!("{}/transaction/redelegate", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.dydx.post_redelegate_tx",
+                    &url,
+                    redelegate_transaction_request,
+                )
+                .await
+        }
+
+        pub async fn noble_ibc_transfer(
+            &self,
+            noble_ibc_transfer_request: &NobleIBCTransferRequest,
+        ) -> Result<ReturnedData<TxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/noble-ibc-transfer", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.dydx.noble_ibc_transfer",
+                    &url,
+                    noble_ibc_transfer_request,
+                )
+                .await
+        }
 
-        let mut file_bytes: Vec<u8> = Vec::new();
+        pub async fn post_prepare_tx(
+            &self,
+            prepare_transaction_request: &PrepareTxRequest,
+        ) -> Result<ReturnedData<PrepareTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/prepare", self.base_url,);
+
+            self.http
+                .post_tx(
+                    "kiln.dydx.post_prepare_tx",
+                    &url,
+                    prepare_transaction_request,
+                )
+                .await
+        }
 
-        ureq::get(url)
-            .header("accept", "application/octet-stream")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .as_reader()
-            .read_to_end(&mut file_bytes)?;
+        pub async fn post_broadcast_tx(
+            &self,
+            broadcast_transaction_request: &BroadcastTxRequest,
+        ) -> Result<ReturnedData<BroadcastTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/broadcast", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.dydx.post_broadcast_tx",
+                    &url,
+                    broadcast_transaction_request,
+                )
+                .await
+        }
 
-        Ok(file_bytes)
-    }
+        pub async fn get_tx_status(
+            &self,
+            tx_hash: &str,
+        ) -> Result<ReturnedData<TxStatusResponse>, KilnError> {
+            let url = Query::new()
+                .value("tx_hash", tx_hash)
+                .url(&format!("{}/transaction/status", self.base_url));
 
-    pub fn post_get_balance(
-        &self,
-        get_balance_request: &GetBalanceRequest,
-    ) -> Result<ReturnedData<TxStakeCoin>, ureq::Error> {
-        let url: String = format!("{}/balance", self.base_url);
+            self.http.get("kiln.dydx.get_tx_status", &url).await
+        }
+
+        pub async fn get_tx_decoding(
+            &self,
+            tx_serialized: &str,
+        ) -> Result<ReturnedData<TxDecodingResponse>, KilnError> {
+            let url = Query::new()
+                .value("tx_serialized", tx_serialized)
+                .url(&format!("{}/transaction/decode", self.base_url));
+
+            self.http.get("kiln.dydx.get_tx_decoding", &url).await
+        }
+    }
+}
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(get_balance_request)?
-            .body_mut()
-            .read_json::<ReturnedData<TxStakeCoin>>();
+impl<M: Mode> KilnDydxClient<M> {
+    pub fn stakes(&self) -> GetStakesBuilder<'_, Self> {
+        GetStakesBuilder::new(self)
+    }
 
-        data
+    pub fn rewards(&self) -> RewardsBuilder<'_, Self, NoStartDate> {
+        RewardsBuilder::new(self)
     }
 
-    pub fn post_stake_tx(
-        &self,
-        transaction_stake_request: &DydxStakeTxRequest,
-    ) -> Result<ReturnedData<TxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/stake", self.base_url,);
+    pub fn operations(&self) -> ValidatorOperationsBuilder<'_, Self, NoStartDate> {
+        ValidatorOperationsBuilder::new(self)
+    }
+}
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(transaction_stake_request)?
-            .body_mut()
-            .read_json::<ReturnedData<TxResponse>>();
+for_each_mode! { <M>
+    impl GetStakesBuilder<'_, KilnDydxClient<M>> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<DydxGetStakesResponse>>, KilnError> {
+            let client = self.client;
 
-        data
+            client.get_stakes(&self.into_request()).await
+        }
     }
+}
 
-    pub fn post_withdraw_rewards_tx(
-        &self,
-        withdraw_rewards_transaction_request: &WithdrawRewardsTxRequest,
-    ) -> Result<ReturnedData<TxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/withdraw-rewards", self.base_url,);
+for_each_mode! { <M>
+    impl RewardsBuilder<'_, KilnDydxClient<M>, StartDate> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<DydxReward>>, KilnError> {
+            let client = self.client;
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(withdraw_rewards_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<TxResponse>>();
+            client.get_rewards(self.into_request()?).await
+        }
+    }
+}
+
+for_each_mode! { <M>
+    impl ValidatorOperationsBuilder<'_, KilnDydxClient<M>, StartDate> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<DydxOperation>>, KilnError> {
+            let client = self.client;
+            let ValidatorOperationsRequest {
+                validators,
+                delegators,
+                authz,
+                accounts,
+                start_date,
+                end_date,
+            } = self.into_request();
+
+            client
+                .get_operations(&DydxOperationsRequest {
+                    validators,
+                    delegators,
+                    authz,
+                    accounts,
+                    start_date,
+                    end_date,
+                })
+                .await
+        }
+    }
+}
 
-        data
+#[cfg(all(test, feature = "blocking"))]
+mod dydx_test {
+
+    use super::*;
+    use crate::query::query_test_support::{date, fixture, requested_url};
+    use crate::sdk::ResponseFormat;
+
+    #[test]
+    fn get_stakes() {
+        let url = requested_url(|kiln| {
+            kiln.dydx().get_stakes(&GetStakesRequest {
+                validators: Some(vec!["dydxvaloper1a".into(), "dydxvaloper1b".into()]),
+                accounts: Some(vec![Uuid::nil()]),
+                ..Default::default()
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/dydx/stakes?validators=dydxvaloper1a,dydxvaloper1b&accounts=00000000-0000-0000-0000-000000000000"
+        );
     }
 
-    pub fn post_unstake_tx(
-        &self,
-        unstake_rewards_transaction_request: &DydxUnstakeTxRequest,
-    ) -> Result<ReturnedData<TxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/unstake", self.base_url,);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(unstake_rewards_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<TxResponse>>();
-        data
+    #[test]
+    fn get_rewards() {
+        let url = requested_url(|kiln| {
+            kiln.dydx().get_rewards(RewardRequest::Daily {
+                stakes_addresses: None,
+                wallets: Some(vec!["dydx1a".into()]),
+                pool_ids: None,
+                accounts: None,
+                format: ResponseFormat::Daily,
+                start_date: date("2024-01-01T00:00:00Z"),
+                end_date: Some(date("2024-01-31T00:00:00Z")),
+                include_usd: true,
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/dydx/rewards?wallets=dydx1a&format=daily&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z&include_usd=true"
+        );
     }
 
-    pub fn post_redelegate_tx(
-        &self,
-        redelegate_transaction_request: &DydxRedelegateTxRequest,
-    ) -> Result<ReturnedData<TxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/redelegate", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(redelegate_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<TxResponse>>();
-        data
+    #[test]
+    fn get_operations() {
+        let url = requested_url(|kiln| {
+            kiln.dydx().get_operations(&DydxOperationsRequest {
+                validators: None,
+                delegators: Some(vec!["dydx1a".into()]),
+                authz: false,
+                accounts: None,
+                start_date: date("2024-01-01T00:00:00Z"),
+                end_date: Some(date("2024-01-31T00:00:00Z")),
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/dydx/operations?authz=false&delegators=dydx1a&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z"
+        );
     }
 
-    pub fn noble_ibc_transfer(
-        &self,
-        noble_ibc_transfer_request: &NobleIBCTransferRequest,
-    ) -> Result<ReturnedData<TxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/noble-ibc-transfer", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(noble_ibc_transfer_request)?
-            .body_mut()
-            .read_json::<ReturnedData<TxResponse>>();
-        data
+    #[test]
+    fn operations_builder() {
+        let url = requested_url(|kiln| {
+            kiln.dydx()
+                .operations()
+                .delegators(["dydx1a"])
+                .since(date("2024-01-01T00:00:00Z"))
+                .until(date("2024-01-31T00:00:00Z"))
+                .send()
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/dydx/operations?authz=false&delegators=dydx1a&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z"
+        );
     }
 
-    pub fn post_prepare_tx(
-        &self,
-        prepare_transaction_request: &PrepareTxRequest,
-    ) -> Result<ReturnedData<PrepareTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/prepare", self.base_url,);
+    #[test]
+    fn get_reports() {
+        let url = requested_url(|kiln| {
+            kiln.dydx().get_reports(&ReportsRequest {
+                delegators: Some(vec!["dydx1a".into(), "dydx1b".into()]),
+                ..Default::default()
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/dydx/reports?delegators=dydx1a,dydx1b"
+        );
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(prepare_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PrepareTxResponse>>();
+    #[test]
+    fn get_tx_status() {
+        let url = requested_url(|kiln| kiln.dydx().get_tx_status("A1B2C3"));
 
-        data
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/dydx/transaction/status?tx_hash=A1B2C3"
+        );
     }
 
-    pub fn post_broadcast_tx(
-        &self,
-        broadcast_transaction_request: &BroadcastTxRequest,
-    ) -> Result<ReturnedData<BroadcastTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/broadcast", self.base_url);
+    #[test]
+    fn get_tx_decoding() {
+        let url = requested_url(|kiln| kiln.dydx().get_tx_decoding("Cp8B+/=="));
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(broadcast_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<BroadcastTxResponse>>();
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/dydx/transaction/decode?tx_serialized=Cp8B%2B%2F%3D%3D"
+        );
+    }
 
-        data
+    #[test]
+    fn validators_of_another_chain_are_rejected_before_sending() {
+        // nothing listens there : an answer would be a transport error
+        let kiln = Kiln::builder()
+            .api_token("kiln_test_token")
+            .base_url("http://127.0.0.1:9/v1")
+            .seal()
+            .build()
+            .unwrap();
+
+        for validator in [
+            "cosmosvaloper1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc56kct20",
+            // a delegator, not a validator
+            "osmo1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5helwsw",
+        ] {
+            let unstake = DydxUnstakeTxRequest {
+                pubkey: "pubkey".into(),
+                validator: validator.parse().unwrap(),
+                amount_adydx: Adydx::new(1),
+            };
+
+            assert!(matches!(
+                kiln.dydx().post_unstake_tx(&unstake),
+                Err(KilnError::InvalidInput(_))
+            ));
+        }
     }
 
-    pub fn get_tx_status(
-        &self,
-        tx_hash: &str,
-    ) -> Result<ReturnedData<TxStatusResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/status?tx_hash={}", self.base_url, tx_hash);
+    #[test]
+    fn tx_amounts_are_in_adydx() {
+        let validator = "dydxvaloper1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5n95vd6";
+
+        let unstake = serde_json::to_value(DydxUnstakeTxRequest {
+            pubkey: "pubkey".into(),
+            validator: validator.parse().unwrap(),
+            amount_adydx: Adydx::new(1_000_000_000_000_000_000),
+        })
+        .unwrap();
+
+        assert_eq!(unstake["amount_adydx"], "1000000000000000000");
+
+        let redelegate = serde_json::to_value(DydxRedelegateTxRequest {
+            account_id: Uuid::nil(),
+            pubkey: "pubkey".into(),
+            validator_source: validator.parse().unwrap(),
+            validar_destination: validator.parse().unwrap(),
+            amount_adydx: Adydx::new(1),
+        })
+        .unwrap();
+
+        assert_eq!(redelegate["amount_adydx"], "1");
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<TxStatusResponse>>();
+    #[test]
+    fn responses_match_the_fixtures() {
+        let stakes: ReturnedData<Vec<DydxGetStakesResponse>> = fixture("dydx/stakes.json");
+        let balance: ReturnedData<TxStakeCoin> = fixture("dydx/balance.json");
 
-        data
+        assert_eq!(stakes.data[0].state, DydxStakeState::Active);
+        assert_eq!(
+            balance.data.amount_in::<Adydx>(),
+            Some(Adydx::new(1_500_000_000_000_000_000))
+        );
+
+        fixture::<ReturnedData<Vec<DydxReward>>>("dydx/rewards.json");
+        fixture::<ReturnedData<DydxNetworkStatsResponse>>("dydx/network_stats.json");
     }
 
-    pub fn get_tx_decoding(
-        &self,
-        tx_serialized: &str,
-    ) -> Result<ReturnedData<TxDecodingResponse>, ureq::Error> {
-        let url: String = format!(
-            "{}/transaction/decode?tx_serialized={}",
-            self.base_url, tx_serialized
+    #[test]
+    fn operations_are_told_apart_by_their_type() {
+        let operations: ReturnedData<Vec<DydxOperation>> = fixture("dydx/operations.json");
+
+        assert!(matches!(operations.data[0], DydxOperation::Delegate { .. }));
+        assert!(operations.data.last().unwrap().is_unknown());
+        assert_eq!(
+            operations
+                .data
+                .iter()
+                .filter(|operation| operation.is_unknown())
+                .count(),
+            1
         );
+    }
+
+    #[test]
+    fn operation_errors_name_the_type_and_the_field() {
+        let mut operations: serde_json::Value = fixture("dydx/operations.json");
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<TxDecodingResponse>>();
+        operations["data"][1]["block"] = "14541288".into();
 
-        data
+        let error =
+            serde_path_to_error::deserialize::<_, ReturnedData<Vec<DydxOperation>>>(operations)
+                .unwrap_err();
+
+        assert_eq!(error.path().to_string(), "data[1]");
+        assert!(
+            error
+                .inner()
+                .to_string()
+                .starts_with("`staking.MsgBeginRedelegate` operation, at `block`: invalid type"),
+            "{}",
+            error.inner()
+        );
     }
 }
@@ -1,5 +1,12 @@
 use serde::{Deserialize, Serialize};
 
+use crate::address::{BlsPubkey, EthAddress, Lenient};
+use crate::amount::Amount;
+use crate::environment::{self, KilnEnvironment};
+use crate::errors::KilnError;
+use crate::http::HttpClient;
+use crate::mode::{for_each_mode, Blocking, Mode};
+use crate::query::Query;
 use crate::{response_format::ReturnedData, Kiln};
 
 use super::ChainID;
@@ -20,9 +27,9 @@ pub struct OperatorMetadata {
 #[serde(rename_all = "snake_case")]
 pub struct OperatorShare {
     // Address
-    pub strategy: String,
+    pub strategy: Lenient<EthAddress>,
     // Address
-    pub token: String,
+    pub token: Lenient<EthAddress>,
     pub shares: String,
 }
 
@@ -37,7 +44,7 @@ pub struct OperatorAutonomousVerifiableService {
 #[serde(rename_all = "snake_case")]
 pub struct EigenlayerOperator {
     // Address
-    pub address: String,
+    pub address: Lenient<EthAddress>,
     pub metadata: OperatorMetadata,
     pub shares_breakdown: Vec<OperatorShare>,
 }
@@ -53,33 +60,33 @@ pub struct OperatorResponse {
 #[serde(rename_all = "snake_case")]
 pub struct Summary {
     // Address
-    pub eigenpod: String,
+    pub eigenpod: Lenient<EthAddress>,
     // Address
-    pub owner: String,
+    pub owner: Lenient<EthAddress>,
     // Address
-    pub delegated_to: String,
-    pub eigenpod_queuable_restaked_balance: String,
-    pub eigenpod_redelegatable_balance: String,
-    pub eigenpod_available_balance: String,
-    pub eigenpod_pending_balance: String,
-    pub eigenpod_withdrawable_balance: String,
-    pub beaconchain_restaked_balance: String,
-    pub beaconchain_pending_balance: String,
-    pub beaconchain_restakable_balance: String,
+    pub delegated_to: Lenient<EthAddress>,
+    pub eigenpod_queuable_restaked_balance: Amount,
+    pub eigenpod_redelegatable_balance: Amount,
+    pub eigenpod_available_balance: Amount,
+    pub eigenpod_pending_balance: Amount,
+    pub eigenpod_withdrawable_balance: Amount,
+    pub beaconchain_restaked_balance: Amount,
+    pub beaconchain_pending_balance: Amount,
+    pub beaconchain_restakable_balance: Amount,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct AutonomousVerifiableServiceReward {
     pub token_address: String,
-    pub claimable_amount: String,
+    pub claimable_amount: Amount,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct RestakedPoints {
     // Address
-    pub validator_address: String,
+    pub validator_address: Lenient<BlsPubkey>,
     pub points: f64,
 }
 
@@ -87,9 +94,9 @@ pub struct RestakedPoints {
 #[serde(rename_all = "snake_case")]
 pub struct LiquidPoints {
     // Address
-    pub strategy: String,
+    pub strategy: Lenient<EthAddress>,
     // Address
-    pub token: String,
+    pub token: Lenient<EthAddress>,
     pub points: f64,
 }
 
@@ -114,11 +121,11 @@ pub struct Undelegation {
     pub id: String,
     pub shares: String,
     // Address
-    pub staker: String,
+    pub staker: Lenient<EthAddress>,
     // Addreess
-    pub delegated_to: String,
+    pub delegated_to: Lenient<EthAddress>,
     // Address
-    pub withdrawer: String,
+    pub withdrawer: Lenient<EthAddress>,
     pub nonce: String,
     pub block_number: u64,
     pub timestamp: u64,
@@ -126,7 +133,7 @@ pub struct Undelegation {
     pub claimable: bool,
     pub claimable_at_block: u64,
     pub claimed: bool,
-    pub claimed_tx_hash: String,
+    pub claimed_tx_hash: Option<String>,
     pub withdrawable_as_eth: bool,
 }
 
@@ -136,11 +143,11 @@ pub struct LiquidDeposit {
     // Address
     pub id: String,
     // Address
-    pub staker: String,
+    pub staker: Lenient<EthAddress>,
     // Address
-    pub strategy: String,
+    pub strategy: Lenient<EthAddress>,
     // Address
-    pub token: String,
+    pub token: Lenient<EthAddress>,
     pub shares: String,
     pub block_number: u64,
     pub timestamp: u64,
@@ -151,9 +158,9 @@ pub struct LiquidDeposit {
 #[serde(rename_all = "snake_case")]
 pub struct EigenlayerToken {
     // Address
-    pub strategy: String,
+    pub strategy: Lenient<EthAddress>,
     // Address
-    pub token: String,
+    pub token: Lenient<EthAddress>,
     pub shares: String,
 }
 
@@ -163,11 +170,11 @@ pub struct EigenlayerWithdrawal {
     // hash
     pub id: String,
     // Address
-    pub withdrawer: String,
+    pub withdrawer: Lenient<EthAddress>,
     // Address
-    pub staker: String,
+    pub staker: Lenient<EthAddress>,
     // Address
-    pub delegated_to: String,
+    pub delegated_to: Lenient<EthAddress>,
     pub nonce: u64,
     pub tokens: Vec<EigenlayerToken>,
     pub block_number: u64,
@@ -183,10 +190,10 @@ pub struct EigenlayerWithdrawal {
 #[serde(rename_all = "snake_case")]
 pub struct EigenlayerTxRequest {
     // Address
-    pub earner_address: String,
+    pub earner_address: EthAddress,
     // Address
-    pub receiver_address: String,
-    pub token_addresses: Vec<String>,
+    pub receiver_address: EthAddress,
+    pub token_addresses: Vec<EthAddress>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -196,9 +203,9 @@ pub struct EigenlayerTxResponse {
     pub unsigned_tx_hash: String,
     pub unsigned_tx_serialized: String,
     // Address
-    pub to: String,
+    pub to: Lenient<EthAddress>,
     pub contract_call_data: String,
-    pub amount_wei: String,
+    pub amount_wei: Amount,
     pub nonce: u64,
     pub gas_limit: u64,
     pub max_priority_fee_per_gas_wei: String,
@@ -210,219 +217,327 @@ pub struct EigenlayerTxResponse {
 #[serde(rename_all = "snake_case")]
 pub struct EigenlayerPostTx {
     // Address
-    pub wallet: String,
+    pub wallet: EthAddress,
     pub validator_indexes: Vec<u64>,
 }
 
 #[derive(Clone, Debug)]
-pub struct KilnEigenlayerClient {
-    pub bearer_token: String,
+pub struct KilnEigenlayerClient<M: Mode = Blocking> {
     pub base_url: String,
+    environment: Option<KilnEnvironment>,
+    http: HttpClient<M>,
 }
 
-impl From<&Kiln> for KilnEigenlayerClient {
-    fn from(kiln: &Kiln) -> Self {
+impl<M: Mode> From<&Kiln<M>> for KilnEigenlayerClient<M> {
+    fn from(kiln: &Kiln<M>) -> Self {
         let url: String = format!("{}/eth/eigenlayer", kiln.base_url);
-        let bearer_token: String = format!("Bearer {}", kiln.api_token);
 
         Self {
-            bearer_token,
             base_url: url,
+            environment: kiln.environment,
+            http: kiln.http.clone(),
         }
     }
 }
 
-impl KilnEigenlayerClient {
-    pub fn eigenpod(&self, wallet: &str) -> Result<ReturnedData<String>, ureq::Error> {
-        let url: String = format!("{}/eigenpod?wallet={}", self.base_url, wallet);
+for_each_mode! { <M>
+    impl KilnEigenlayerClient<M> {
+        pub fn environment(&self) -> Option<KilnEnvironment> {
+            self.environment
+        }
+
+        pub async fn eigenpod(&self, wallet: &str) -> Result<ReturnedData<String>, KilnError> {
+            let url = Query::new()
+                .value("wallet", wallet)
+                .url(&format!("{}/eigenpod", self.base_url));
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<String>>();
+            self.http.get("kiln.eth.eigenlayer.eigenpod", &url).await
+        }
 
-        data
-    }
+        pub async fn operator(
+            &self,
+            operator_address: &str,
+        ) -> Result<ReturnedData<OperatorResponse>, KilnError> {
+            let url = Query::new()
+                .value("operator", operator_address)
+                .url(&format!("{}/operator", self.base_url));
 
-    pub fn operator(
-        &self,
-        operator_address: &str,
-    ) -> Result<ReturnedData<OperatorResponse>, ureq::Error> {
-        let url: String = format!("{}/operator?operator={}", self.base_url, operator_address);
+            self.http.get("kiln.eth.eigenlayer.operator", &url).await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<OperatorResponse>>();
+        pub async fn summary(&self, wallet: &str) -> Result<ReturnedData<Summary>, KilnError> {
+            let url = Query::new()
+                .value("wallet", wallet)
+                .url(&format!("{}/summary", self.base_url));
 
-        data
-    }
+            self.http.get("kiln.eth.eigenlayer.summary", &url).await
+        }
 
-    pub fn summary(&self, wallet: &str) -> Result<ReturnedData<Summary>, ureq::Error> {
-        let url: String = format!("{}/summary?wallet={}", self.base_url, wallet);
+        pub async fn autonomous_verifiable_service_rewards(
+            &self,
+            wallet: &str,
+        ) -> Result<ReturnedData<Vec<AutonomousVerifiableServiceReward>>, KilnError> {
+            let url = Query::new()
+                .value("wallet", wallet)
+                .url(&format!("{}/avs-rewards", self.base_url));
+
+            self.http
+                .get(
+                    "kiln.eth.eigenlayer.autonomous_verifiable_service_rewards",
+                    &url,
+                )
+                .await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Summary>>();
+        pub async fn avs_rewards(
+            &self,
+            wallet: &str,
+        ) -> Result<ReturnedData<Vec<AutonomousVerifiableServiceReward>>, KilnError> {
+            self.autonomous_verifiable_service_rewards(wallet).await
+        }
 
-        data
-    }
+        pub async fn native_points(
+            &self,
+            wallet: &str,
+        ) -> Result<ReturnedData<NativePoints>, KilnError> {
+            let url = Query::new()
+                .value("wallet", wallet)
+                .url(&format!("{}/native/points", self.base_url));
+
+            self.http
+                .get("kiln.eth.eigenlayer.native_points", &url)
+                .await
+        }
 
-    pub fn autonomous_verifiable_service_rewards(
-        &self,
-        wallet: &str,
-    ) -> Result<ReturnedData<Vec<AutonomousVerifiableServiceReward>>, ureq::Error> {
-        let url: String = format!("{}/avs-rewards?wallet={}", self.base_url, wallet);
+        pub async fn undelegation(
+            &self,
+            wallet: &str,
+        ) -> Result<ReturnedData<Vec<Undelegation>>, KilnError> {
+            let url = Query::new()
+                .value("wallet", wallet)
+                .url(&format!("{}/native/undelegations", self.base_url));
+
+            self.http
+                .get("kiln.eth.eigenlayer.undelegation", &url)
+                .await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<AutonomousVerifiableServiceReward>>>();
+        pub async fn liquid_points(
+            &self,
+            wallet: &str,
+        ) -> Result<ReturnedData<LiquidPointsResponse>, KilnError> {
+            let url = Query::new()
+                .value("wallet", wallet)
+                .url(&format!("{}/liquid/points", self.base_url));
+
+            self.http
+                .get("kiln.eth.eigenlayer.liquid_points", &url)
+                .await
+        }
 
-        data
-    }
+        pub async fn liquid_deposits(
+            &self,
+            wallet: &str,
+        ) -> Result<ReturnedData<Vec<LiquidDeposit>>, KilnError> {
+            let url = Query::new()
+                .value("wallet", wallet)
+                .url(&format!("{}/liquid/deposits", self.base_url));
+
+            self.http
+                .get("kiln.eth.eigenlayer.liquid_deposits", &url)
+                .await
+        }
 
-    pub fn avs_rewards(
-        &self,
-        wallet: &str,
-    ) -> Result<ReturnedData<Vec<AutonomousVerifiableServiceReward>>, ureq::Error> {
-        self.autonomous_verifiable_service_rewards(wallet)
-    }
+        pub async fn withdrawals(
+            &self,
+            wallet: &str,
+        ) -> Result<ReturnedData<Vec<EigenlayerWithdrawal>>, KilnError> {
+            let url = Query::new()
+                .value("wallet", wallet)
+                .url(&format!("{}/liquid/withdrawals", self.base_url));
 
-    pub fn native_points(
-        &self,
-        wallet: &str,
-    ) -> Result<ReturnedData<Vec<AutonomousVerifiableServiceReward>>, ureq::Error> {
-        let url: String = format!("{}/native/points?wallet={}", self.base_url, wallet);
+            self.http.get("kiln.eth.eigenlayer.withdrawals", &url).await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<AutonomousVerifiableServiceReward>>>();
+        pub async fn claim_rewards_tx(
+            &self,
+            claim_rewards_tx_request: EigenlayerTxRequest,
+        ) -> Result<ReturnedData<EigenlayerTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/claim-rewards", self.base_url);
 
-        data
-    }
+            let response: ReturnedData<EigenlayerTxResponse> = self
+                .http
+                .post_tx(
+                    "kiln.eth.eigenlayer.claim_rewards_tx",
+                    &url,
+                    &claim_rewards_tx_request,
+                )
+                .await?;
+
+            environment::warn_on_chain_mismatch(self.environment, &response.data.chain_id);
 
-    pub fn undelegation(&self, wallet: &str) -> Result<ReturnedData<Undelegation>, ureq::Error> {
-        let url: String = format!("{}/native/undelegations?wallet={}", self.base_url, wallet);
+            Ok(response)
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Undelegation>>();
+        pub async fn generate_checkpoint_proofs_tx(
+            &self,
+            generate_checkpoint_proofs_tx: EigenlayerPostTx,
+        ) -> Result<ReturnedData<EigenlayerTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/verify-checkpoint-proofs", self.base_url);
 
-        data
-    }
+            let response: ReturnedData<EigenlayerTxResponse> = self
+                .http
+                .post_tx(
+                    "kiln.eth.eigenlayer.generate_checkpoint_proofs_tx",
+                    &url,
+                    &generate_checkpoint_proofs_tx,
+                )
+                .await?;
 
-    pub fn liquid_points(
-        &self,
-        wallet: &str,
-    ) -> Result<ReturnedData<LiquidPointsResponse>, ureq::Error> {
-        let url: String = format!("{}/liquid/points?wallet={}", self.base_url, wallet);
+            environment::warn_on_chain_mismatch(self.environment, &response.data.chain_id);
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<LiquidPointsResponse>>();
+            Ok(response)
+        }
 
-        data
+        pub async fn verify_withdraw_credentials_tx(
+            &self,
+            verify_withdraw_credentials_tx: EigenlayerPostTx,
+        ) -> Result<ReturnedData<EigenlayerTxResponse>, KilnError> {
+            let url: String = format!(
+                "{}/transaction/verify-withdrawal-credentials",
+                self.base_url
+            );
+
+            let response: ReturnedData<EigenlayerTxResponse> = self
+                .http
+                .post_tx(
+                    "kiln.eth.eigenlayer.verify_withdraw_credentials_tx",
+                    &url,
+                    &verify_withdraw_credentials_tx,
+                )
+                .await?;
+
+            environment::warn_on_chain_mismatch(self.environment, &response.data.chain_id);
+
+            Ok(response)
+        }
     }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod eigenlayer_test {
 
-    pub fn liquid_deposits(
-        &self,
-        wallet: &str,
-    ) -> Result<ReturnedData<Vec<LiquidDeposit>>, ureq::Error> {
-        let url: String = format!("{}/liquid/deposits?wallet={}", self.base_url, wallet);
+    use super::*;
+    use crate::query::query_test_support::{fixture, requested_url};
+
+    #[test]
+    fn eigenpod() {
+        let url = requested_url(|kiln| kiln.eigenlayer().eigenpod("0xa"));
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/eigenlayer/eigenpod?wallet=0xa"
+        );
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<LiquidDeposit>>>();
+    #[test]
+    fn operator() {
+        let url = requested_url(|kiln| kiln.eigenlayer().operator("0xo"));
 
-        data
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/eigenlayer/operator?operator=0xo"
+        );
     }
 
-    pub fn withdrawals(
-        &self,
-        wallet: &str,
-    ) -> Result<ReturnedData<Vec<EigenlayerWithdrawal>>, ureq::Error> {
-        let url: String = format!("{}/liquid/withdrawals?wallet={}", self.base_url, wallet);
+    #[test]
+    fn summary() {
+        let url = requested_url(|kiln| kiln.eigenlayer().summary("0xa"));
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/eigenlayer/summary?wallet=0xa"
+        );
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<EigenlayerWithdrawal>>>();
+    #[test]
+    fn avs_rewards() {
+        let url = requested_url(|kiln| kiln.eigenlayer().avs_rewards("0xa"));
 
-        data
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/eigenlayer/avs-rewards?wallet=0xa"
+        );
     }
 
-    pub fn claim_rewards_tx(
-        &self,
-        claim_rewards_tx_request: EigenlayerTxRequest,
-    ) -> Result<ReturnedData<EigenlayerTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/claim-rewards", self.base_url);
+    #[test]
+    fn native_points() {
+        let url = requested_url(|kiln| kiln.eigenlayer().native_points("0xa"));
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(claim_rewards_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<EigenlayerTxResponse>>();
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/eigenlayer/native/points?wallet=0xa"
+        );
+    }
+
+    #[test]
+    fn undelegation() {
+        let url = requested_url(|kiln| kiln.eigenlayer().undelegation("0xa"));
 
-        data
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/eigenlayer/native/undelegations?wallet=0xa"
+        );
     }
 
-    pub fn generate_checkpoint_proofs_tx(
-        &self,
-        generate_checkpoint_proofs_tx: EigenlayerPostTx,
-    ) -> Result<ReturnedData<EigenlayerTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/verify-checkpoint-proofs", self.base_url);
+    #[test]
+    fn liquid_points() {
+        let url = requested_url(|kiln| kiln.eigenlayer().liquid_points("0xa"));
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/eigenlayer/liquid/points?wallet=0xa"
+        );
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(generate_checkpoint_proofs_tx)?
-            .body_mut()
-            .read_json::<ReturnedData<EigenlayerTxResponse>>();
+    #[test]
+    fn liquid_deposits() {
+        let url = requested_url(|kiln| kiln.eigenlayer().liquid_deposits("0xa"));
 
-        data
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/eigenlayer/liquid/deposits?wallet=0xa"
+        );
     }
 
-    pub fn verify_withdraw_credentials_tx(
-        &self,
-        verify_withdraw_credentials_tx: EigenlayerPostTx,
-    ) -> Result<ReturnedData<EigenlayerTxResponse>, ureq::Error> {
-        let url: String = format!(
-            "{}/transaction/verify-withdrawal-credentials",
-            self.base_url
+    #[test]
+    fn withdrawals() {
+        let url = requested_url(|kiln| kiln.eigenlayer().withdrawals("0xa"));
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/eigenlayer/liquid/withdrawals?wallet=0xa"
         );
+    }
+
+    #[test]
+    fn responses_match_the_fixtures() {
+        let tx: ReturnedData<EigenlayerTxResponse> = fixture("eigenlayer/tx.json");
+        let undelegations: ReturnedData<Vec<Undelegation>> =
+            fixture("eigenlayer/native_undelegations.json");
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(verify_withdraw_credentials_tx)?
-            .body_mut()
-            .read_json::<ReturnedData<EigenlayerTxResponse>>();
+        assert_eq!(tx.data.chain_id, ChainID::EthereumMainnet);
+        assert_eq!(undelegations.data[0].claimed_tx_hash, None);
 
-        data
+        fixture::<ReturnedData<String>>("eigenlayer/eigenpod.json");
+        fixture::<ReturnedData<OperatorResponse>>("eigenlayer/operator.json");
+        fixture::<ReturnedData<Summary>>("eigenlayer/summary.json");
+        fixture::<ReturnedData<Vec<AutonomousVerifiableServiceReward>>>(
+            "eigenlayer/avs_rewards.json",
+        );
+        fixture::<ReturnedData<NativePoints>>("eigenlayer/native_points.json");
+        fixture::<ReturnedData<LiquidPointsResponse>>("eigenlayer/liquid_points.json");
+        fixture::<ReturnedData<Vec<LiquidDeposit>>>("eigenlayer/liquid_deposits.json");
+        fixture::<ReturnedData<Vec<EigenlayerWithdrawal>>>("eigenlayer/liquid_withdrawals.json");
     }
 }
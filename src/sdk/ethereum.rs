@@ -1,88 +1,112 @@
-use std::io::Read;
 use std::num::NonZeroU64;
 
-use serde::Deserializer;
 use serde::{Deserialize, Serialize};
 use strum_macros::AsRefStr;
-use strum_macros::EnumString;
 use uuid::Uuid;
 
+use crate::address::{BlsPubkey, EthAddress, Lenient};
+use crate::amount::{Amount, FloatAmount};
+use crate::denomination::Wei;
+use crate::environment::{self, KilnEnvironment};
+use crate::errors::KilnError;
+use crate::http::HttpClient;
+use crate::mode::{for_each_mode, Blocking, Mode};
+use crate::pagination::{Page, PageOptions, Pages, DEFAULT_PAGE_SIZE};
+use crate::query::Query;
 use crate::response_format::ReturnedData;
 use crate::{response_format::PaginatedData, Kiln};
 
-use super::{BroadcastTxRequest, BroadcastTxResponse, ChainID, TxStatusResponse};
+use super::builders::{filter, NoStartDate, StartDate};
+use super::{BroadcastTxRequest, BroadcastTxResponse, ChainID};
 
-#[derive(Serialize, Deserialize, Debug, AsRefStr)]
+#[derive(Serialize, Deserialize, Debug, Clone, AsRefStr)]
 #[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
 pub enum Scope {
     Kiln,
     Network,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct EthereumScopedStakesRequest {
-    pub validators: Vec<String>,
+    pub validators: Option<Vec<String>>,
     pub scope: Scope,
-    pub wallets: Vec<String>,
-    pub proxies: Vec<String>,
-    pub withdrawal_credentials: Vec<String>,
-    pub validator_indexes: Vec<u64>,
+    pub wallets: Option<Vec<String>>,
+    pub proxies: Option<Vec<String>>,
+    pub withdrawal_credentials: Option<Vec<String>>,
+    pub validator_indexes: Option<Vec<u64>>,
     pub include_eigenlayer: bool,
-    pub accounts: Vec<Uuid>,
+    pub accounts: Option<Vec<Uuid>>,
     pub current_page: NonZeroU64,
     pub page_size: NonZeroU64,
 }
 
-#[derive(Serialize, Deserialize, EnumString, Debug, AsRefStr)]
-pub enum EthereumFilterState {
-    #[strum(serialize = "unknown")]
-    Unknown,
-    #[strum(serialize = "unstaked")]
-    Unstaked,
-    #[strum(serialize = "deposit_in_progress")]
-    DepositInProgress,
-    #[strum(serialize = "pending_initialized")]
-    PendingInitialized,
-    #[strum(serialize = "pending_queued")]
-    PendingQueued,
-    #[strum(serialize = "active_ongoing")]
-    ActiveOngoing,
-    #[strum(serialize = "active_exiting")]
-    ActiveExiting,
-    #[strum(serialize = "active_slashed")]
-    ActiveSlashed,
-    #[strum(serialize = "exited_unslashed")]
-    ExitedUnslashed,
-    #[strum(serialize = "exited_slashed")]
-    ExitedSlashed,
-    #[strum(serialize = "withdrawal_possible")]
-    WithdrawalPossible,
-    #[strum(serialize = "withdrawal_done")]
-    WithdrawalDone,
+string_enum! {
+    pub enum EthereumFilterState {
+        Unstaked => "unstaked",
+        DepositInProgress => "deposit_in_progress",
+        PendingInitialized => "pending_initialized",
+        PendingQueued => "pending_queued",
+        ActiveOngoing => "active_ongoing",
+        ActiveExiting => "active_exiting",
+        ActiveSlashed => "active_slashed",
+        ExitedUnslashed => "exited_unslashed",
+        ExitedSlashed => "exited_slashed",
+        WithdrawalPossible => "withdrawal_possible",
+        WithdrawalDone => "withdrawal_done",
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct EthereumNetworkStakesRequest {
-    pub validators: Vec<String>,
-    pub wallets: Vec<String>,
-    pub proxies: Vec<String>,
-    pub withdrawal_credentials: Vec<String>,
-    pub validator_indexes: Vec<u64>,
+    pub validators: Option<Vec<String>>,
+    pub wallets: Option<Vec<String>>,
+    pub proxies: Option<Vec<String>>,
+    pub withdrawal_credentials: Option<Vec<String>>,
+    pub validator_indexes: Option<Vec<u64>>,
     pub include_eigenlayer: bool,
-    pub accounts: Vec<Uuid>,
+    pub accounts: Option<Vec<Uuid>>,
     pub current_page: NonZeroU64,
-    pub page_size: u64,
-    pub filtered_states: Vec<EthereumFilterState>,
+    pub page_size: NonZeroU64,
+    pub filtered_states: Option<Vec<EthereumFilterState>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum EthereumStakesRequest {
     Network(EthereumNetworkStakesRequest),
     Scoped(EthereumScopedStakesRequest),
 }
 
+impl EthereumStakesRequest {
+    fn current_page(&self) -> NonZeroU64 {
+        match self {
+            EthereumStakesRequest::Network(request) => request.current_page,
+            EthereumStakesRequest::Scoped(request) => request.current_page,
+        }
+    }
+
+    fn page(self, current_page: NonZeroU64, page_size: NonZeroU64) -> Self {
+        match self {
+            EthereumStakesRequest::Network(request) => {
+                EthereumStakesRequest::Network(EthereumNetworkStakesRequest {
+                    current_page,
+                    page_size,
+                    ..request
+                })
+            }
+            EthereumStakesRequest::Scoped(request) => {
+                EthereumStakesRequest::Scoped(EthereumScopedStakesRequest {
+                    current_page,
+                    page_size,
+                    ..request
+                })
+            }
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct EthereumEigenlayer {
@@ -98,33 +122,35 @@ pub struct EthereumEigenlayer {
 #[serde(rename_all = "snake_case")]
 pub struct EthereumStakesResponse {
     // Address
-    pub validator_address: String,
+    pub validator_address: Lenient<BlsPubkey>,
     pub validator_index: String,
     pub state: EthereumFilterState,
-    pub activated_at: chrono::DateTime<chrono::Utc>,
-    pub activated_epoch: u64,
+    // `None` until the validator is activated, or has exited
+    pub activated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub activated_epoch: Option<u64>,
     pub delegated_at: chrono::DateTime<chrono::Utc>,
     pub delegated_block: u64,
-    pub exited_at: chrono::DateTime<chrono::Utc>,
-    pub exited_epoch: u64,
+    pub exited_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub exited_epoch: Option<u64>,
     // Address
-    pub deposit_tx_sender: String,
+    pub deposit_tx_sender: Lenient<EthAddress>,
     // Address
-    pub execution_fee_recipient: String,
+    pub execution_fee_recipient: Lenient<EthAddress>,
     pub withdrawal_credentials: String,
-    pub effective_balance: String,
-    pub balance: String,
-    pub consensus_rewards: String,
-    pub execution_rewards: String,
-    pub rewards: String,
-    pub claimable_execution_rewards: String,
-    pub claimable_consensus_rewards: String,
+    pub effective_balance: Amount,
+    pub balance: Amount,
+    pub consensus_rewards: Amount,
+    pub execution_rewards: Amount,
+    pub rewards: Amount,
+    pub claimable_execution_rewards: Amount,
+    pub claimable_consensus_rewards: Amount,
     pub gross_apy: f64,
     pub is_kiln: bool,
     pub updated_at: chrono::DateTime<chrono::Utc>,
-    pub eigenlayer: EthereumEigenlayer,
-    pub estimated_next_skimming_slot: u64,
-    pub estimated_next_skimming_at: chrono::DateTime<chrono::Utc>,
+    // only with `include_eigenlayer`
+    pub eigenlayer: Option<EthereumEigenlayer>,
+    pub estimated_next_skimming_slot: Option<u64>,
+    pub estimated_next_skimming_at: Option<chrono::DateTime<chrono::Utc>>,
     pub exit_requested: bool,
 }
 
@@ -144,16 +170,16 @@ pub struct EthereumPostStakesRequest {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct EthereumRewardsRequest {
-    pub validators: Vec<String>,
+    pub validators: Option<Vec<String>>,
     pub scope: Scope,
     // Addresses
-    pub wallets: Vec<String>,
+    pub wallets: Option<Vec<String>>,
     // Addresses
-    pub proxies: Vec<String>,
-    pub validator_indexes: Vec<u64>,
-    pub accounts: Vec<Uuid>,
+    pub proxies: Option<Vec<String>>,
+    pub validator_indexes: Option<Vec<u64>>,
+    pub accounts: Option<Vec<Uuid>>,
     pub start_date: chrono::DateTime<chrono::Utc>,
-    pub end_date: chrono::DateTime<chrono::Utc>,
+    pub end_date: Option<chrono::DateTime<chrono::Utc>>,
     pub include_usd: bool,
 }
 
@@ -161,173 +187,140 @@ pub struct EthereumRewardsRequest {
 #[serde(rename_all = "snake_case")]
 pub struct EthereumRewardsResponse {
     pub date: chrono::DateTime<chrono::Utc>,
-    pub consensus_rewards: String,
-    pub execution_rewards: String,
-    pub mev_execution_rewards: String,
-    pub non_mev_execution_rewards: String,
-    pub median_execution_reward: String,
-    pub rewards: String,
-    pub stake_balance: String,
-    pub gross_apy: String,
-    pub cl_apy: String,
-    pub el_apy: String,
-    pub active_validator_count: String,
-    pub rewards_usd: String,
-    pub stake_balance_usd: u64,
+    pub consensus_rewards: Amount,
+    pub execution_rewards: Amount,
+    pub mev_execution_rewards: Amount,
+    pub non_mev_execution_rewards: Amount,
+    pub median_execution_reward: Amount,
+    pub rewards: Amount,
+    pub stake_balance: Amount,
+    pub gross_apy: f64,
+    pub cl_apy: f64,
+    pub el_apy: f64,
+    pub active_validator_count: u64,
+    pub rewards_usd: Amount,
+    pub stake_balance_usd: FloatAmount,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct EthereumOperationsRequest {
     // Addresses
-    pub validators: Vec<String>,
+    pub validators: Option<Vec<String>>,
     // Addresses
-    pub wallets: Vec<String>,
+    pub wallets: Option<Vec<String>>,
     // Addresses
-    pub proxies: Vec<String>,
-    pub validator_indexes: Vec<String>,
-    pub accounts: Vec<Uuid>,
+    pub proxies: Option<Vec<String>>,
+    pub validator_indexes: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
     pub start_date: chrono::DateTime<chrono::Utc>,
-    pub end_date: chrono::DateTime<chrono::Utc>,
-}
-
-fn deserialize_type<'de, D>(deserializer: D) -> Result<String, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let the_type = EthereumOperationsResponse::deserialize(deserializer)?;
-
-    let the_type = match the_type {
-        EthereumOperationsResponse::Deposit { .. } => "deposit".into(),
-        EthereumOperationsResponse::ConsensusWithdrawal { .. } => "consensus_withdrawal".into(),
-        EthereumOperationsResponse::ExecutionReward { .. } => "execution_reward".into(),
-        EthereumOperationsResponse::KilnExitRequest { .. } => "kiln_exit_request".into(),
-        EthereumOperationsResponse::LidoExitRequest { .. } => "lido_exit_request".into(),
-        EthereumOperationsResponse::RioExitRequest { .. } => "rio_exit_request".into(),
-        EthereumOperationsResponse::VoluntaryExit { .. } => "voluntary_exit".into(),
-    };
-
-    Ok(the_type)
+    pub end_date: Option<chrono::DateTime<chrono::Utc>>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-#[serde(rename_all = "snake_case")]
-#[allow(clippy::large_enum_variant)]
-pub enum EthereumOperationsResponse {
-    Deposit {
-        #[serde(deserialize_with = "deserialize_type")]
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        // Address
-        validator_address: String,
-        validator_index: NonZeroU64,
-        tx_hash: String,
-        tx_sender: String,
-        tx_gas_used: String,
-        tx_effective_gas_price: String,
-        // Addresses
-        proxies: Vec<String>,
-        slot: u64,
-        block: u64,
-        block_base_fee: Option<String>,
-        withdrawal_credentials: String,
-        amount: String,
-    },
-    ConsensusWithdrawal {
-        #[serde(deserialize_with = "deserialize_type")]
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        // Address
-        validator_address: String,
-        validator_index: NonZeroU64,
-        slot: u64,
-        block: u64,
-        fee_recipient: String,
-        amount: String,
-    },
-    ExecutionReward {
-        #[serde(deserialize_with = "deserialize_type")]
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        // Address
-        validator_address: String,
-        validator_index: NonZeroU64,
-        slot: u64,
-        block: u64,
-        fee_recipient: String,
-        is_mev_block: bool,
-        mev_payout_tx_hash: String,
-        amount: String,
-    },
-    KilnExitRequest {
-        #[serde(deserialize_with = "deserialize_type")]
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        // Address
-        validator_address: String,
-        validator_index: NonZeroU64,
-        tx_hash: String,
-        tx_gas_used: String,
-        tx_effective_gas_price: Option<String>,
-        // Address
-        tx_sender: String,
-        slot: u64,
-        block: u64,
-        block_base_fee: Option<String>,
-        // Address
-        emitting_contract: String,
-        // Address
-        caller: Option<String>,
-    },
-    LidoExitRequest {
-        #[serde(deserialize_with = "deserialize_type")]
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        // Address
-        validator_address: String,
-        validator_index: NonZeroU64,
-        tx_hash: String,
-        tx_gas_used: String,
-        tx_effective_gas_price: Option<String>,
-        // Address
-        tx_sender: String,
-        slot: u64,
-        block: u64,
-        block_base_fee: Option<String>,
-        // Address
-        emitting_contract: String,
-    },
-    RioExitRequest {
-        #[serde(deserialize_with = "deserialize_type")]
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        // Address
-        validator_address: String,
-        validator_index: NonZeroU64,
-        tx_hash: String,
-        tx_gas_used: String,
-        tx_effective_gas_price: Option<String>,
-        // Address
-        tx_sender: String,
-        slot: u64,
-        block: u64,
-        block_base_fee: Option<String>,
-        // Address
-        emitting_contract: String,
-    },
-    VoluntaryExit {
-        #[serde(deserialize_with = "deserialize_type")]
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        // Address
-        validator_address: String,
-        validator_index: NonZeroU64,
-        slot: u64,
-        block: u64,
-        index_in_payload: u64,
-        message_epoch: u64,
-        message_signature: String,
-    },
+operation_enum! {
+    #[allow(clippy::large_enum_variant)]
+    pub enum EthereumOperationsResponse {
+        Deposit => "deposit" {
+            time: chrono::DateTime<chrono::Utc>,
+            // Address
+            validator_address: Lenient<BlsPubkey>,
+            validator_index: NonZeroU64,
+            tx_hash: String,
+            tx_sender: String,
+            tx_gas_used: String,
+            tx_effective_gas_price: String,
+            // Addresses
+            proxies: Vec<String>,
+            slot: u64,
+            block: u64,
+            block_base_fee: Option<String>,
+            withdrawal_credentials: String,
+            amount: Amount,
+        },
+        ConsensusWithdrawal => "consensus_withdrawal" {
+            time: chrono::DateTime<chrono::Utc>,
+            // Address
+            validator_address: Lenient<BlsPubkey>,
+            validator_index: NonZeroU64,
+            slot: u64,
+            block: u64,
+            fee_recipient: String,
+            amount: Amount,
+        },
+        ExecutionReward => "execution_reward" {
+            time: chrono::DateTime<chrono::Utc>,
+            // Address
+            validator_address: Lenient<BlsPubkey>,
+            validator_index: NonZeroU64,
+            slot: u64,
+            block: u64,
+            fee_recipient: String,
+            is_mev_block: bool,
+            mev_payout_tx_hash: String,
+            amount: Amount,
+        },
+        KilnExitRequest => "kiln_exit_request" {
+            time: chrono::DateTime<chrono::Utc>,
+            // Address
+            validator_address: Lenient<BlsPubkey>,
+            validator_index: NonZeroU64,
+            tx_hash: String,
+            tx_gas_used: String,
+            tx_effective_gas_price: Option<String>,
+            // Address
+            tx_sender: Lenient<EthAddress>,
+            slot: u64,
+            block: u64,
+            block_base_fee: Option<String>,
+            // Address
+            emitting_contract: Lenient<EthAddress>,
+            // Address
+            caller: Option<Lenient<EthAddress>>,
+        },
+        LidoExitRequest => "lido_exit_request" {
+            time: chrono::DateTime<chrono::Utc>,
+            // Address
+            validator_address: Lenient<BlsPubkey>,
+            validator_index: NonZeroU64,
+            tx_hash: String,
+            tx_gas_used: String,
+            tx_effective_gas_price: Option<String>,
+            // Address
+            tx_sender: Lenient<EthAddress>,
+            slot: u64,
+            block: u64,
+            block_base_fee: Option<String>,
+            // Address
+            emitting_contract: Lenient<EthAddress>,
+        },
+        RioExitRequest => "rio_exit_request" {
+            time: chrono::DateTime<chrono::Utc>,
+            // Address
+            validator_address: Lenient<BlsPubkey>,
+            validator_index: NonZeroU64,
+            tx_hash: String,
+            tx_gas_used: String,
+            tx_effective_gas_price: Option<String>,
+            // Address
+            tx_sender: Lenient<EthAddress>,
+            slot: u64,
+            block: u64,
+            block_base_fee: Option<String>,
+            // Address
+            emitting_contract: Lenient<EthAddress>,
+        },
+        VoluntaryExit => "voluntary_exit" {
+            time: chrono::DateTime<chrono::Utc>,
+            // Address
+            validator_address: Lenient<BlsPubkey>,
+            validator_index: NonZeroU64,
+            slot: u64,
+            block: u64,
+            index_in_payload: u64,
+            message_epoch: u64,
+            message_signature: String,
+        },
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -335,7 +328,7 @@ pub enum EthereumOperationsResponse {
 pub struct EthereumNetworkStats {
     pub network_gross_apy: f64,
     pub supply_staked_percent: f64,
-    pub eth_price_usd: f64,
+    pub eth_price_usd: FloatAmount,
     pub estimated_entry_time_seconds: u64,
     pub estimated_exit_time_seconds: u64,
     pub estimated_withdrawal_time_seconds: u64,
@@ -346,9 +339,9 @@ pub struct EthereumNetworkStats {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct GrossAPY {
-    pub last_1d: u64,
-    pub last_7d: u64,
-    pub last_30d: u64,
+    pub last_1d: f64,
+    pub last_7d: f64,
+    pub last_30d: f64,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -358,12 +351,13 @@ pub struct KilnStats {
     pub gross_apy: GrossAPY,
 }
 
-#[derive(Serialize, Deserialize, Debug, Default, AsRefStr, EnumString)]
-#[serde(rename_all = "snake_case")]
-pub enum ValidationKeysFormat {
-    #[default]
-    BatchDeposit,
-    CliDeposit,
+string_enum! {
+    #[derive(Default)]
+    pub enum ValidationKeysFormat {
+        #[default]
+        BatchDeposit => "batch_deposit",
+        CliDeposit => "cli_deposit",
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -371,9 +365,9 @@ pub enum ValidationKeysFormat {
 pub struct PostValidationKeysRequest {
     pub account_id: Uuid,
     // Address
-    pub withdrawal_address: String,
+    pub withdrawal_address: EthAddress,
     // Address
-    pub fee_recipient_address: String,
+    pub fee_recipient_address: EthAddress,
     pub number: u64,
     pub format: ValidationKeysFormat,
 }
@@ -382,8 +376,8 @@ pub struct PostValidationKeysRequest {
 #[serde(rename_all = "snake_case")]
 pub struct PostETHStakesTx {
     pub account_id: Uuid,
-    pub wallet: String,
-    pub amount_wei: String,
+    pub wallet: EthAddress,
+    pub amount_wei: Wei,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -393,7 +387,7 @@ pub struct PostETHStakesTxResponse {
     pub unsigned_tx_serialized: String,
     pub to: String,
     pub contract_call_data: String,
-    pub amount_wei: String,
+    pub amount_wei: Amount,
     pub nonce: u64,
     pub gas_limit: u64,
     pub max_priority_fee_per_gas_wei: String,
@@ -401,8 +395,9 @@ pub struct PostETHStakesTxResponse {
     pub chain_id: ChainID,
 }
 
+// `{ "format": "batch_deposit", .. }`, told apart by their fields
 #[derive(Serialize, Deserialize, Debug)]
-#[serde(rename_all = "snake_case")]
+#[serde(untagged)]
 #[allow(clippy::large_enum_variant)]
 pub enum ValidationKeys {
     BatchResponse {
@@ -451,8 +446,8 @@ pub struct EthereumPrepareTxResponse {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct RequestExitTx {
-    pub wallet: String,
-    pub validators: Vec<String>,
+    pub wallet: EthAddress,
+    pub validators: Vec<BlsPubkey>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -462,7 +457,7 @@ pub struct RequestExitTxResponse {
     pub unsigned_tx_serialized: String,
     pub to: String,
     pub contract_call_data: String,
-    pub amount_wei: String,
+    pub amount_wei: Amount,
     pub nonce: u64,
     pub gas_limit: u64,
     pub max_priority_fee_per_gas_wei: String,
@@ -470,6 +465,56 @@ pub struct RequestExitTxResponse {
     pub chain_id: ChainID,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EthereumTxStatusReceiptLog {
+    pub address: String,
+    pub block_hash: String,
+    pub block_number: u64,
+    pub data: String,
+    pub log_index: u64,
+    pub removed: bool,
+    pub topics: Vec<String>,
+    pub transaction_hash: String,
+    pub transaction_index: u64,
+    pub id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EthereumTxStatusReceipt {
+    pub block_hash: String,
+    pub block_number: u64,
+    pub contract_address: Option<String>,
+    pub cumulative_gas_used: u64,
+    pub effective_gas_price: u64,
+    pub from: String,
+    pub gas_used: u64,
+    pub logs: Vec<EthereumTxStatusReceiptLog>,
+    pub logs_bloom: String,
+    pub status: bool,
+    pub to: String,
+    pub transaction_hash: String,
+    pub transaction_index: u64,
+    pub r#type: String,
+}
+
+string_enum! {
+    pub enum EthereumTxStatus {
+        Success => "success",
+        Error => "error",
+        PendingConfirmation => "pending_confirmation",
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct EthereumTxStatusResponse {
+    pub status: EthereumTxStatus,
+    // `None` while the transaction is pending
+    pub receipt: Option<EthereumTxStatusReceipt>,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct TxDecodingRequest {
@@ -489,19 +534,19 @@ pub struct EthereumTxDecodingResponse {
     pub gas: String,
     pub data: String,
     pub nonce: u64,
-    pub value: String,
+    pub value: Amount,
     pub max_fee_per_gas: String,
     pub max_priority_fee_per_gas: String,
     pub function_name: String,
     pub args: Vec<Vec<String>>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 #[serde(rename_all = "snake_case")]
 pub struct EthereumReportsRequest {
-    pub validators: Vec<String>,
-    pub wallets: Vec<String>,
-    pub accounts: Vec<Uuid>,
+    pub validators: Option<Vec<String>>,
+    pub wallets: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -514,472 +559,1218 @@ pub struct ExitMessageResponse {
 }
 
 #[derive(Clone, Debug)]
-pub struct KilnEthereumClient {
-    pub bearer_token: String,
+pub struct KilnEthereumClient<M: Mode = Blocking> {
     pub base_url: String,
+    environment: Option<KilnEnvironment>,
+    http: HttpClient<M>,
 }
 
-impl From<&Kiln> for KilnEthereumClient {
-    fn from(kiln: &Kiln) -> Self {
+impl<M: Mode> From<&Kiln<M>> for KilnEthereumClient<M> {
+    fn from(kiln: &Kiln<M>) -> Self {
         let url: String = format!("{}/eth", kiln.base_url);
-        let bearer_token: String = format!("Bearer {}", kiln.api_token);
 
         Self {
-            bearer_token,
             base_url: url,
+            environment: kiln.environment,
+            http: kiln.http.clone(),
+        }
+    }
+}
+
+for_each_mode! { <M>
+    impl KilnEthereumClient<M> {
+        pub fn environment(&self) -> Option<KilnEnvironment> {
+            self.environment
+        }
+
+        pub async fn get_stakes(
+            &self,
+            ethereum_stake_request: EthereumStakesRequest,
+        ) -> Result<PaginatedData<Vec<EthereumStakesResponse>>, KilnError> {
+            let url: String = match ethereum_stake_request {
+                EthereumStakesRequest::Network(network_stakes_request) => {
+                    let EthereumNetworkStakesRequest {
+                        validators,
+                        validator_indexes,
+                        accounts,
+                        current_page,
+                        page_size,
+                        filtered_states,
+                        include_eigenlayer,
+                        proxies,
+                        wallets,
+                        withdrawal_credentials,
+                    } = network_stakes_request;
+
+                    Query::new()
+                        .list("validators", validators.iter().flatten())
+                        .list("wallets", wallets.iter().flatten())
+                        .list("proxies", proxies.iter().flatten())
+                        .list(
+                            "withdrawal_credentials",
+                            withdrawal_credentials.iter().flatten(),
+                        )
+                        .list("validator_indexes", validator_indexes.iter().flatten())
+                        .option("include_eigenlayer", include_eigenlayer.then_some(true))
+                        .list("accounts", accounts.iter().flatten())
+                        .value("current_page", current_page)
+                        .value("page_size", page_size)
+                        .list(
+                            "filtered_states",
+                            filtered_states.iter().flatten().map(|state| state.as_ref()),
+                        )
+                        .url(&format!("{}/stakes", self.base_url))
+                }
+                EthereumStakesRequest::Scoped(scoped_stakes_request) => {
+                    let EthereumScopedStakesRequest {
+                        validators,
+                        scope,
+                        validator_indexes,
+                        accounts,
+                        current_page,
+                        page_size,
+                        include_eigenlayer,
+                        proxies,
+                        wallets,
+                        withdrawal_credentials,
+                    } = scoped_stakes_request;
+
+                    Query::new()
+                        .list("validators", validators.iter().flatten())
+                        .value("scope", scope.as_ref())
+                        .list("wallets", wallets.iter().flatten())
+                        .list("proxies", proxies.iter().flatten())
+                        .list(
+                            "withdrawal_credentials",
+                            withdrawal_credentials.iter().flatten(),
+                        )
+                        .list("validator_indexes", validator_indexes.iter().flatten())
+                        .option("include_eigenlayer", include_eigenlayer.then_some(true))
+                        .list("accounts", accounts.iter().flatten())
+                        .value("current_page", current_page)
+                        .value("page_size", page_size)
+                        .url(&format!("{}/stakes", self.base_url))
+                }
+            };
+
+            self.http.get_paginated("kiln.eth.get_stakes", &url).await
+        }
+
+        pub async fn post_stakes(
+            &self,
+            ethereum_create_stake_request: &EthereumPostStakesRequest,
+        ) -> Result<PaginatedData<Vec<EthereumStakesResponse>>, KilnError> {
+            self.http
+                .post(
+                    "kiln.eth.post_stakes",
+                    &self.base_url,
+                    ethereum_create_stake_request,
+                )
+                .await
+        }
+
+        pub async fn get_rewards(
+            &self,
+            ethereum_rewards_request: &EthereumRewardsRequest,
+        ) -> Result<ReturnedData<Vec<EthereumRewardsResponse>>, KilnError> {
+            let EthereumRewardsRequest {
+                validators,
+                scope,
+                wallets,
+                proxies,
+                validator_indexes,
+                accounts,
+                start_date,
+                end_date,
+                include_usd,
+            } = ethereum_rewards_request;
+
+            let url = Query::new()
+                .list("validators", validators.iter().flatten())
+                .value("scope", scope.as_ref())
+                .list("wallets", wallets.iter().flatten())
+                .list("proxies", proxies.iter().flatten())
+                .list("validators_indexes", validator_indexes.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .value("start_date", start_date)
+                .option("end_date", end_date.as_ref())
+                .value("include_usd", include_usd)
+                .url(&format!("{}/rewards", self.base_url));
+
+            self.http.get("kiln.eth.get_rewards", &url).await
+        }
+
+        pub async fn get_operations(
+            &self,
+            ethereum_operations_request: &EthereumOperationsRequest,
+        ) -> Result<ReturnedData<Vec<EthereumOperationsResponse>>, KilnError> {
+            let EthereumOperationsRequest {
+                validators,
+                wallets,
+                proxies,
+                validator_indexes,
+                accounts,
+                start_date,
+                end_date,
+            } = ethereum_operations_request;
+
+            let url = Query::new()
+                .list("validators", validators.iter().flatten())
+                .list("wallets", wallets.iter().flatten())
+                .list("proxies", proxies.iter().flatten())
+                .list("validators_indexes", validator_indexes.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .value("start_date", start_date)
+                .option("end_date", end_date.as_ref())
+                .url(&format!("{}/operations", self.base_url));
+
+            self.http.get("kiln.eth.get_operations", &url).await
+        }
+
+        pub async fn network_stats(&self) -> Result<ReturnedData<EthereumNetworkStats>, KilnError> {
+            let url = format!("{}/network-stats", self.base_url);
+
+            self.http.get("kiln.eth.network_stats", &url).await
+        }
+
+        pub async fn kiln_stats(&self) -> Result<ReturnedData<KilnStats>, KilnError> {
+            let url = format!("{}/kiln-stats", self.base_url);
+
+            self.http.get("kiln.eth.kiln_stats", &url).await
+        }
+
+        pub async fn post_keys(
+            &self,
+            post_validation_keys_request: &PostValidationKeysRequest,
+        ) -> Result<ReturnedData<Vec<ValidationKeys>>, KilnError> {
+            let url = format!("{}/keys", self.base_url);
+
+            self.http
+                .post("kiln.eth.post_keys", &url, post_validation_keys_request)
+                .await
+        }
+
+        pub async fn post_stake_tx(
+            &self,
+            post_eth_stake_tx: &PostETHStakesTx,
+        ) -> Result<ReturnedData<PostETHStakesTxResponse>, KilnError> {
+            let url = format!("{}/stake", self.base_url);
+
+            let response: ReturnedData<PostETHStakesTxResponse> = self
+                .http
+                .post_tx("kiln.eth.post_stake_tx", &url, post_eth_stake_tx)
+                .await?;
+
+            environment::warn_on_chain_mismatch(self.environment, &response.data.chain_id);
+
+            Ok(response)
+        }
+
+        pub async fn post_prepare_tx(
+            &self,
+            prepare_tx_request: &EthereumPrepareTxRequest,
+        ) -> Result<ReturnedData<EthereumPrepareTxResponse>, KilnError> {
+            let url = format!("{}/transaction/prepare", self.base_url);
+
+            self.http
+                .post_tx("kiln.eth.post_prepare_tx", &url, prepare_tx_request)
+                .await
+        }
+
+        pub async fn post_broadcast_tx(
+            &self,
+            tx_serialized: &BroadcastTxRequest,
+        ) -> Result<ReturnedData<BroadcastTxResponse>, KilnError> {
+            let url = format!("{}/transaction/broadcast", self.base_url);
+
+            self.http
+                .post_tx("kiln.eth.post_broadcast_tx", &url, tx_serialized)
+                .await
+        }
+
+        pub async fn get_status_tx(
+            &self,
+            tx_hash: &str,
+        ) -> Result<ReturnedData<EthereumTxStatusResponse>, KilnError> {
+            let url = Query::new()
+                .value("tx_hash", tx_hash)
+                .url(&format!("{}/transaction/status", self.base_url));
+
+            self.http.get("kiln.eth.get_status_tx", &url).await
+        }
+
+        pub async fn post_request_exit_tx(
+            &self,
+            request_exit_tx: &RequestExitTx,
+        ) -> Result<ReturnedData<RequestExitTxResponse>, KilnError> {
+            let url = format!("{}/transaction/exit-request", self.base_url);
+
+            let response: ReturnedData<RequestExitTxResponse> = self
+                .http
+                .post_tx("kiln.eth.post_request_exit_tx", &url, request_exit_tx)
+                .await?;
+
+            environment::warn_on_chain_mismatch(self.environment, &response.data.chain_id);
+
+            Ok(response)
+        }
+
+        pub async fn get_tx_decoding(
+            &self,
+            tx_decoding_request: &TxDecodingRequest,
+        ) -> Result<ReturnedData<EthereumTxDecodingResponse>, KilnError> {
+            let url = Query::new()
+                .value("tx_serialized", &tx_decoding_request.tx_serialized)
+                .url(&format!("{}/transaction/decode", self.base_url));
+
+            self.http.get("kiln.eth.get_tx_decoding", &url).await
+        }
+
+        pub async fn get_reports(
+            &self,
+            ethereum_reports_request: &EthereumReportsRequest,
+        ) -> Result<Vec<u8>, KilnError> {
+            let EthereumReportsRequest {
+                validators,
+                wallets,
+                accounts,
+            } = ethereum_reports_request;
+
+            let url = Query::new()
+                .list("validators", validators.iter().flatten())
+                .list("wallets", wallets.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .url(&format!("{}/reports", self.base_url));
+
+            self.http.get_bytes("kiln.eth.get_reports", &url).await
+        }
+
+        pub async fn get_exit_message(
+            &self,
+            validators: Vec<String>,
+        ) -> Result<ReturnedData<Vec<ExitMessageResponse>>, KilnError> {
+            let url = Query::new()
+                .list("validators", &validators)
+                .url(&format!("{}/transaction/exit-messages", self.base_url));
+
+            self.http.get("kiln.eth.get_exit_message", &url).await
         }
     }
 }
 
-impl KilnEthereumClient {
-    pub fn get_stakes(
-        &self,
+impl<M: Mode> KilnEthereumClient<M> {
+    pub fn stakes(&self) -> EthereumStakesBuilder<'_, M> {
+        EthereumStakesBuilder::new(self)
+    }
+
+    pub fn scoped_stakes(&self, scope: Scope) -> EthereumScopedStakesBuilder<'_, M> {
+        EthereumScopedStakesBuilder::new(self, scope)
+    }
+
+    pub fn rewards(&self) -> EthereumRewardsBuilder<'_, NoStartDate, M> {
+        EthereumRewardsBuilder::new(self)
+    }
+
+    pub fn operations(&self) -> EthereumOperationsBuilder<'_, NoStartDate, M> {
+        EthereumOperationsBuilder::new(self)
+    }
+}
+
+for_each_mode! { <M>
+    impl KilnEthereumClient<M> {
+        // every stake of the request, from its `current_page` on
+        pub fn iter_stakes(
+            &self,
+            ethereum_stake_request: EthereumStakesRequest,
+            options: PageOptions,
+        ) -> Pages<EthereumStakesResponse, M> {
+            let client = self.clone();
+            let first_page = ethereum_stake_request.current_page();
+
+            Pages::<_, M>::new(first_page, options, move |page| {
+                let request = ethereum_stake_request.clone().page(page, options.page_size);
+
+                fetch_stakes_page(client.clone(), request)
+            })
+        }
+    }
+
+    async fn fetch_stakes_page(
+        client: KilnEthereumClient<M>,
         ethereum_stake_request: EthereumStakesRequest,
-    ) -> Result<PaginatedData<Vec<EthereumStakesResponse>>, ureq::Error> {
-        let url: String = match ethereum_stake_request {
-            EthereumStakesRequest::Network(network_stakes_request) => {
-                let EthereumNetworkStakesRequest {
-                    validators,
-                    validator_indexes,
-                    accounts,
-                    current_page,
-                    page_size,
-                    filtered_states,
-                    include_eigenlayer,
-                    proxies,
-                    wallets,
-                    withdrawal_credentials,
-                } = network_stakes_request;
-
-                let validators = validators.join(",");
-
-                let validators_indexes = validator_indexes
-                    .iter()
-                    .map(|n| n.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",");
-
-                let wallets = wallets.join(",");
-
-                let accounts = accounts
-                    .iter()
-                    .map(|id| id.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",");
-
-                let filtered_states = filtered_states
-                    .iter()
-                    .map(|state| state.as_ref())
-                    .collect::<Vec<&str>>()
-                    .join(",");
-
-                let proxies = proxies.join(",");
-
-                let withdrawal_credentials = withdrawal_credentials.join(",");
-
-                let url = format!(
-                    "{}/stakes?validators={}&wallets={}&proxies={}&withdrawal_credentials={}&validator_indexes={}&include_eigenlayer={}&accounts={}&current_page={}&page_size={}&filtered_states={}",
-                    self.base_url,
-                    validators,
-                    wallets,
-                    proxies,
-                    withdrawal_credentials,
-                    validators_indexes,
-                    include_eigenlayer,
-                    accounts,
-                    current_page,
-                    page_size,
-                    filtered_states,
-                );
+    ) -> Result<Page<EthereumStakesResponse>, KilnError> {
+        let PaginatedData { data, pagination } = client.get_stakes(ethereum_stake_request).await?;
 
-                url
-            }
-            EthereumStakesRequest::Scoped(scoped_stakes_request) => {
-                let EthereumScopedStakesRequest {
-                    validators,
-                    scope,
-                    validator_indexes,
-                    accounts,
-                    current_page,
-                    page_size,
-                    include_eigenlayer,
-                    proxies,
-                    wallets,
-                    withdrawal_credentials,
-                } = scoped_stakes_request;
-
-                let validators = validators.join(",");
-
-                let validators_indexes = validator_indexes
-                    .iter()
-                    .map(|n| n.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",");
-
-                let wallets = wallets.join(",");
-
-                let accounts = accounts
-                    .iter()
-                    .map(|id| id.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",");
-
-                let proxies = proxies.join(",");
-
-                let withdrawal_credentials = withdrawal_credentials.join(",");
-
-                let url = format!(
-                    "{}/stakes?validators={}&scope={}&wallets={}&proxies={}&withdrawal_credentials={}&validator_indexes={}&include_eigenlayer={}&accounts={}&current_page={}&page_size={}",
-                    self.base_url,
-                    validators,
-                    scope.as_ref(),
-                    wallets,
-                    proxies,
-                    withdrawal_credentials,
-                    validators_indexes,
-                    include_eigenlayer,
-                    accounts,
-                    current_page,
-                    page_size
-                );
+        Ok(Page {
+            items: data,
+            last: pagination.current_page >= pagination.total_pages,
+        })
+    }
+}
 
-                url
-            }
-        };
+// `kiln.ethereum().stakes()` : the stakes of the whole network, a page at a time
+#[derive(Debug)]
+pub struct EthereumStakesBuilder<'a, M: Mode = Blocking> {
+    client: &'a KilnEthereumClient<M>,
+    validators: Option<Vec<String>>,
+    wallets: Option<Vec<String>>,
+    proxies: Option<Vec<String>>,
+    withdrawal_credentials: Option<Vec<String>>,
+    validator_indexes: Option<Vec<u64>>,
+    include_eigenlayer: bool,
+    accounts: Option<Vec<Uuid>>,
+    current_page: NonZeroU64,
+    page_size: NonZeroU64,
+    filtered_states: Option<Vec<EthereumFilterState>>,
+}
+
+impl<'a, M: Mode> EthereumStakesBuilder<'a, M> {
+    fn new(client: &'a KilnEthereumClient<M>) -> Self {
+        Self {
+            client,
+            validators: None,
+            wallets: None,
+            proxies: None,
+            withdrawal_credentials: None,
+            validator_indexes: None,
+            include_eigenlayer: false,
+            accounts: None,
+            current_page: NonZeroU64::MIN,
+            page_size: DEFAULT_PAGE_SIZE,
+            filtered_states: None,
+        }
+    }
+
+    pub fn validators(self, validators: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            validators: filter(validators),
+            ..self
+        }
+    }
+
+    pub fn wallets(self, wallets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            wallets: filter(wallets),
+            ..self
+        }
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<PaginatedData<Vec<EthereumStakesResponse>>>();
+    pub fn proxies(self, proxies: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            proxies: filter(proxies),
+            ..self
+        }
+    }
+
+    pub fn withdrawal_credentials(
+        self,
+        withdrawal_credentials: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            withdrawal_credentials: filter(withdrawal_credentials),
+            ..self
+        }
+    }
 
-        data
+    pub fn validator_indexes(
+        self,
+        validator_indexes: impl IntoIterator<Item = impl Into<u64>>,
+    ) -> Self {
+        Self {
+            validator_indexes: filter(validator_indexes),
+            ..self
+        }
     }
 
-    pub fn post_stakes(
-        &self,
-        ethereum_create_stake_request: &EthereumPostStakesRequest,
-    ) -> Result<PaginatedData<Vec<EthereumStakesResponse>>, ureq::Error> {
-        let data = ureq::post(&self.base_url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(ethereum_create_stake_request)?
-            .body_mut()
-            .read_json::<PaginatedData<Vec<EthereumStakesResponse>>>();
+    pub fn include_eigenlayer(self) -> Self {
+        Self {
+            include_eigenlayer: true,
+            ..self
+        }
+    }
 
-        data
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
     }
 
-    pub fn rewards(
-        &self,
-        ethereum_rewards_request: &EthereumRewardsRequest,
-    ) -> Result<ReturnedData<Vec<EthereumRewardsResponse>>, ureq::Error> {
-        let EthereumRewardsRequest {
-            validators,
+    pub fn current_page(self, current_page: NonZeroU64) -> Self {
+        Self {
+            current_page,
+            ..self
+        }
+    }
+
+    pub fn page_size(self, page_size: NonZeroU64) -> Self {
+        Self { page_size, ..self }
+    }
+
+    pub fn filtered_states(
+        self,
+        filtered_states: impl IntoIterator<Item = impl Into<EthereumFilterState>>,
+    ) -> Self {
+        Self {
+            filtered_states: filter(filtered_states),
+            ..self
+        }
+    }
+
+    pub fn into_request(self) -> EthereumStakesRequest {
+        EthereumStakesRequest::Network(EthereumNetworkStakesRequest {
+            validators: self.validators,
+            wallets: self.wallets,
+            proxies: self.proxies,
+            withdrawal_credentials: self.withdrawal_credentials,
+            validator_indexes: self.validator_indexes,
+            include_eigenlayer: self.include_eigenlayer,
+            accounts: self.accounts,
+            current_page: self.current_page,
+            page_size: self.page_size,
+            filtered_states: self.filtered_states,
+        })
+    }
+}
+
+for_each_mode! { <M>
+    impl EthereumStakesBuilder<'_, M> {
+        pub async fn send(self) -> Result<PaginatedData<Vec<EthereumStakesResponse>>, KilnError> {
+            let client = self.client;
+
+            client.get_stakes(self.into_request()).await
+        }
+
+        // the stakes of every page, from `current_page` on
+        pub fn pages(self, options: PageOptions) -> Pages<EthereumStakesResponse, M> {
+            let client = self.client;
+
+            client.iter_stakes(self.into_request(), options)
+        }
+    }
+}
+
+// `kiln.ethereum().scoped_stakes(scope)`
+#[derive(Debug)]
+pub struct EthereumScopedStakesBuilder<'a, M: Mode = Blocking> {
+    client: &'a KilnEthereumClient<M>,
+    validators: Option<Vec<String>>,
+    scope: Scope,
+    wallets: Option<Vec<String>>,
+    proxies: Option<Vec<String>>,
+    withdrawal_credentials: Option<Vec<String>>,
+    validator_indexes: Option<Vec<u64>>,
+    include_eigenlayer: bool,
+    accounts: Option<Vec<Uuid>>,
+    current_page: NonZeroU64,
+    page_size: NonZeroU64,
+}
+
+impl<'a, M: Mode> EthereumScopedStakesBuilder<'a, M> {
+    fn new(client: &'a KilnEthereumClient<M>, scope: Scope) -> Self {
+        Self {
+            client,
+            validators: None,
             scope,
-            wallets,
-            proxies,
-            validator_indexes,
-            accounts,
-            start_date,
-            end_date,
-            include_usd,
-        } = ethereum_rewards_request;
-
-        let validators = validators.join(",");
-
-        let wallets = wallets.join(",");
-
-        let proxies = proxies.join(",");
-
-        let validators_indexes = validator_indexes
-            .iter()
-            .map(|n| n.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let url = format!(
-            "{}/rewards?validators={}&scope={}&wallets={}&proxies={}&validators_indexes={}&accounts={}&start_date={}&end_date={}&include_usd={}",
-            self.base_url,
-            validators,
-            scope.as_ref(),
-            wallets,
-            proxies,
-            validators_indexes,
-            accounts,
-            start_date,
-            end_date,
-            include_usd
-        );
+            wallets: None,
+            proxies: None,
+            withdrawal_credentials: None,
+            validator_indexes: None,
+            include_eigenlayer: false,
+            accounts: None,
+            current_page: NonZeroU64::MIN,
+            page_size: DEFAULT_PAGE_SIZE,
+        }
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<EthereumRewardsResponse>>>();
-
-        data
-    }
-
-    pub fn get_operations(
-        &self,
-        ethereum_operations_request: &EthereumOperationsRequest,
-    ) -> Result<ReturnedData<Vec<EthereumOperationsResponse>>, ureq::Error> {
-        let EthereumOperationsRequest {
-            validators,
-            wallets,
-            proxies,
-            validator_indexes,
-            accounts,
-            start_date,
-            end_date,
-        } = ethereum_operations_request;
-
-        let validators = validators.join(",");
-
-        let wallets = wallets.join(",");
-
-        let proxies = proxies.join(",");
-
-        let validators_indexes = validator_indexes
-            .iter()
-            .map(|n| n.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let url = format!(
-            "{}/operations?validators={}&wallets={}&proxies={}&validators_indexes={}&accounts={}&start_date={}&end_date={}",
-            self.base_url,
-            validators,
-            wallets,
-            proxies,
-            validators_indexes,
-            accounts,
-            start_date,
-            end_date,
-        );
+    pub fn validators(self, validators: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            validators: filter(validators),
+            ..self
+        }
+    }
+
+    pub fn wallets(self, wallets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            wallets: filter(wallets),
+            ..self
+        }
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<EthereumOperationsResponse>>>();
+    pub fn proxies(self, proxies: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            proxies: filter(proxies),
+            ..self
+        }
+    }
 
-        data
+    pub fn withdrawal_credentials(
+        self,
+        withdrawal_credentials: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            withdrawal_credentials: filter(withdrawal_credentials),
+            ..self
+        }
     }
 
-    pub fn network_stats(&self) -> Result<ReturnedData<EthereumNetworkStats>, ureq::Error> {
-        let url = format!("{}/network-stats", self.base_url);
+    pub fn validator_indexes(
+        self,
+        validator_indexes: impl IntoIterator<Item = impl Into<u64>>,
+    ) -> Self {
+        Self {
+            validator_indexes: filter(validator_indexes),
+            ..self
+        }
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<EthereumNetworkStats>>();
+    pub fn include_eigenlayer(self) -> Self {
+        Self {
+            include_eigenlayer: true,
+            ..self
+        }
+    }
 
-        data
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
     }
 
-    pub fn kiln_stats(&self) -> Result<ReturnedData<KilnStats>, ureq::Error> {
-        let url = format!("{}/kiln-stats", self.base_url);
+    pub fn current_page(self, current_page: NonZeroU64) -> Self {
+        Self {
+            current_page,
+            ..self
+        }
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<KilnStats>>();
+    pub fn page_size(self, page_size: NonZeroU64) -> Self {
+        Self { page_size, ..self }
+    }
 
-        data
+    pub fn into_request(self) -> EthereumStakesRequest {
+        EthereumStakesRequest::Scoped(EthereumScopedStakesRequest {
+            validators: self.validators,
+            scope: self.scope,
+            wallets: self.wallets,
+            proxies: self.proxies,
+            withdrawal_credentials: self.withdrawal_credentials,
+            validator_indexes: self.validator_indexes,
+            include_eigenlayer: self.include_eigenlayer,
+            accounts: self.accounts,
+            current_page: self.current_page,
+            page_size: self.page_size,
+        })
     }
+}
+
+for_each_mode! { <M>
+    impl EthereumScopedStakesBuilder<'_, M> {
+        pub async fn send(self) -> Result<PaginatedData<Vec<EthereumStakesResponse>>, KilnError> {
+            let client = self.client;
 
-    pub fn post_keys(
-        &self,
-        post_validation_keys_request: &PostValidationKeysRequest,
-    ) -> Result<ReturnedData<Vec<ValidationKeys>>, ureq::Error> {
-        let url = format!("{}/keys", self.base_url);
+            client.get_stakes(self.into_request()).await
+        }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(post_validation_keys_request)?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<ValidationKeys>>>();
+        // the stakes of every page, from `current_page` on
+        pub fn pages(self, options: PageOptions) -> Pages<EthereumStakesResponse, M> {
+            let client = self.client;
 
-        data
+            client.iter_stakes(self.into_request(), options)
+        }
     }
+}
 
-    pub fn post_stake_tx(
-        &self,
-        post_eth_stake_tx: &PostETHStakesTx,
-    ) -> Result<ReturnedData<PostETHStakesTxResponse>, ureq::Error> {
-        let url = format!("{}/stake", self.base_url);
+// `kiln.ethereum().rewards()`, scoped to the Kiln validators unless `scope` says otherwise
+#[derive(Debug)]
+pub struct EthereumRewardsBuilder<'a, S, M: Mode = Blocking> {
+    client: &'a KilnEthereumClient<M>,
+    validators: Option<Vec<String>>,
+    scope: Scope,
+    wallets: Option<Vec<String>>,
+    proxies: Option<Vec<String>>,
+    validator_indexes: Option<Vec<u64>>,
+    accounts: Option<Vec<Uuid>>,
+    include_usd: bool,
+    start_date: S,
+    end_date: Option<chrono::DateTime<chrono::Utc>>,
+}
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(post_eth_stake_tx)?
-            .body_mut()
-            .read_json::<ReturnedData<PostETHStakesTxResponse>>();
+impl<'a, M: Mode> EthereumRewardsBuilder<'a, NoStartDate, M> {
+    fn new(client: &'a KilnEthereumClient<M>) -> Self {
+        Self {
+            client,
+            validators: None,
+            scope: Scope::Kiln,
+            wallets: None,
+            proxies: None,
+            validator_indexes: None,
+            accounts: None,
+            include_usd: false,
+            start_date: NoStartDate,
+            end_date: None,
+        }
+    }
+}
 
-        data
+impl<'a, S, M: Mode> EthereumRewardsBuilder<'a, S, M> {
+    pub fn validators(self, validators: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            validators: filter(validators),
+            ..self
+        }
     }
 
-    pub fn post_prepare_tx(
-        &self,
-        prepare_tx_request: &EthereumPrepareTxRequest,
-    ) -> Result<ReturnedData<EthereumPrepareTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/prepare", self.base_url);
+    pub fn scope(self, scope: Scope) -> Self {
+        Self { scope, ..self }
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(prepare_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<EthereumPrepareTxResponse>>();
+    pub fn wallets(self, wallets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            wallets: filter(wallets),
+            ..self
+        }
+    }
 
-        data
+    pub fn proxies(self, proxies: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            proxies: filter(proxies),
+            ..self
+        }
     }
 
-    pub fn post_broadcast_tx(
-        &self,
-        tx_serialized: &BroadcastTxRequest,
-    ) -> Result<ReturnedData<BroadcastTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/broadcast", self.base_url);
+    pub fn validator_indexes(
+        self,
+        validator_indexes: impl IntoIterator<Item = impl Into<u64>>,
+    ) -> Self {
+        Self {
+            validator_indexes: filter(validator_indexes),
+            ..self
+        }
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(tx_serialized)?
-            .body_mut()
-            .read_json::<ReturnedData<BroadcastTxResponse>>();
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
+    }
 
-        data
+    pub fn include_usd(self) -> Self {
+        Self {
+            include_usd: true,
+            ..self
+        }
     }
 
-    pub fn get_status_tx(
-        &self,
-        tx_hash: &str,
-    ) -> Result<ReturnedData<TxStatusResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/status?tx_hash={}", self.base_url, tx_hash);
+    pub fn until(self, end_date: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            end_date: Some(end_date),
+            ..self
+        }
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<TxStatusResponse>>();
+    pub fn since(
+        self,
+        start_date: chrono::DateTime<chrono::Utc>,
+    ) -> EthereumRewardsBuilder<'a, StartDate, M> {
+        EthereumRewardsBuilder {
+            client: self.client,
+            validators: self.validators,
+            scope: self.scope,
+            wallets: self.wallets,
+            proxies: self.proxies,
+            validator_indexes: self.validator_indexes,
+            accounts: self.accounts,
+            include_usd: self.include_usd,
+            start_date: StartDate(start_date),
+            end_date: self.end_date,
+        }
+    }
+}
 
-        data
+impl<M: Mode> EthereumRewardsBuilder<'_, StartDate, M> {
+    pub fn into_request(self) -> EthereumRewardsRequest {
+        EthereumRewardsRequest {
+            validators: self.validators,
+            scope: self.scope,
+            wallets: self.wallets,
+            proxies: self.proxies,
+            validator_indexes: self.validator_indexes,
+            accounts: self.accounts,
+            include_usd: self.include_usd,
+            start_date: self.start_date.0,
+            end_date: self.end_date,
+        }
     }
+}
 
-    pub fn post_request_exit_tx(
-        &self,
-        request_exit_tx: &RequestExitTx,
-    ) -> Result<ReturnedData<RequestExitTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/exit-request", self.base_url);
+for_each_mode! { <M>
+    impl EthereumRewardsBuilder<'_, StartDate, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<EthereumRewardsResponse>>, KilnError> {
+            let client = self.client;
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(request_exit_tx)?
-            .body_mut()
-            .read_json::<ReturnedData<RequestExitTxResponse>>();
+            client.get_rewards(&self.into_request()).await
+        }
+    }
+}
 
-        data
+// `kiln.ethereum().operations()`
+#[derive(Debug)]
+pub struct EthereumOperationsBuilder<'a, S, M: Mode = Blocking> {
+    client: &'a KilnEthereumClient<M>,
+    validators: Option<Vec<String>>,
+    wallets: Option<Vec<String>>,
+    proxies: Option<Vec<String>>,
+    validator_indexes: Option<Vec<String>>,
+    accounts: Option<Vec<Uuid>>,
+    start_date: S,
+    end_date: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl<'a, M: Mode> EthereumOperationsBuilder<'a, NoStartDate, M> {
+    fn new(client: &'a KilnEthereumClient<M>) -> Self {
+        Self {
+            client,
+            validators: None,
+            wallets: None,
+            proxies: None,
+            validator_indexes: None,
+            accounts: None,
+            start_date: NoStartDate,
+            end_date: None,
+        }
+    }
+}
+
+impl<'a, S, M: Mode> EthereumOperationsBuilder<'a, S, M> {
+    pub fn validators(self, validators: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            validators: filter(validators),
+            ..self
+        }
+    }
+
+    pub fn wallets(self, wallets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            wallets: filter(wallets),
+            ..self
+        }
+    }
+
+    pub fn proxies(self, proxies: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            proxies: filter(proxies),
+            ..self
+        }
+    }
+
+    pub fn validator_indexes(
+        self,
+        validator_indexes: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            validator_indexes: filter(validator_indexes),
+            ..self
+        }
     }
 
-    pub fn get_tx_decoding(
-        &self,
-        tx_decoding_request: &TxDecodingRequest,
-    ) -> Result<EthereumTxDecodingResponse, ureq::Error> {
-        let url: String = format!(
-            "{}/transaction/decode?tx_serialized={}",
-            self.base_url, tx_decoding_request.tx_serialized
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
+    }
+
+    pub fn until(self, end_date: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            end_date: Some(end_date),
+            ..self
+        }
+    }
+
+    pub fn since(
+        self,
+        start_date: chrono::DateTime<chrono::Utc>,
+    ) -> EthereumOperationsBuilder<'a, StartDate, M> {
+        EthereumOperationsBuilder {
+            client: self.client,
+            validators: self.validators,
+            wallets: self.wallets,
+            proxies: self.proxies,
+            validator_indexes: self.validator_indexes,
+            accounts: self.accounts,
+            start_date: StartDate(start_date),
+            end_date: self.end_date,
+        }
+    }
+}
+
+impl<M: Mode> EthereumOperationsBuilder<'_, StartDate, M> {
+    pub fn into_request(self) -> EthereumOperationsRequest {
+        EthereumOperationsRequest {
+            validators: self.validators,
+            wallets: self.wallets,
+            proxies: self.proxies,
+            validator_indexes: self.validator_indexes,
+            accounts: self.accounts,
+            start_date: self.start_date.0,
+            end_date: self.end_date,
+        }
+    }
+}
+
+for_each_mode! { <M>
+    impl EthereumOperationsBuilder<'_, StartDate, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<EthereumOperationsResponse>>, KilnError> {
+            let client = self.client;
+
+            client.get_operations(&self.into_request()).await
+        }
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod ethereum_test {
+
+    use super::*;
+    use crate::query::query_test_support::{date, fixture, requested_url};
+    use crate::transport::{HttpRequest, HttpResponse, Transport, TransportError};
+
+    #[test]
+    fn get_stakes_network() {
+        let url = requested_url(|kiln| {
+            kiln.ethereum().get_stakes(EthereumStakesRequest::Network(
+                EthereumNetworkStakesRequest {
+                    validators: Some(vec!["0xa".into(), "0xb".into()]),
+                    wallets: None,
+                    proxies: None,
+                    withdrawal_credentials: None,
+                    validator_indexes: Some(vec![1, 2]),
+                    include_eigenlayer: true,
+                    accounts: None,
+                    current_page: NonZeroU64::MIN,
+                    page_size: NonZeroU64::new(25).unwrap(),
+                    filtered_states: Some(vec![
+                        EthereumFilterState::ActiveOngoing,
+                        EthereumFilterState::ExitedSlashed,
+                    ]),
+                },
+            ))
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/stakes?validators=0xa,0xb&validator_indexes=1,2&include_eigenlayer=true&current_page=1&page_size=25&filtered_states=active_ongoing,exited_slashed"
         );
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<EthereumTxDecodingResponse>();
+    #[test]
+    fn get_stakes_scoped() {
+        let url = requested_url(|kiln| {
+            kiln.ethereum()
+                .get_stakes(EthereumStakesRequest::Scoped(EthereumScopedStakesRequest {
+                    validators: None,
+                    scope: Scope::Kiln,
+                    wallets: Some(vec!["0xwallet".into()]),
+                    proxies: None,
+                    withdrawal_credentials: None,
+                    validator_indexes: None,
+                    include_eigenlayer: false,
+                    accounts: Some(vec![Uuid::nil()]),
+                    current_page: NonZeroU64::MIN,
+                    page_size: NonZeroU64::MIN,
+                }))
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/stakes?scope=kiln&wallets=0xwallet&accounts=00000000-0000-0000-0000-000000000000&current_page=1&page_size=1"
+        );
+    }
+
+    #[test]
+    fn get_rewards() {
+        let url = requested_url(|kiln| {
+            kiln.ethereum().get_rewards(&EthereumRewardsRequest {
+                validators: None,
+                scope: Scope::Network,
+                wallets: Some(vec!["0xa".into()]),
+                proxies: None,
+                validator_indexes: Some(vec![7]),
+                accounts: None,
+                start_date: date("2024-01-01T00:00:00Z"),
+                end_date: Some(date("2024-01-31T12:30:00+01:00")),
+                include_usd: true,
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/rewards?scope=network&wallets=0xa&validators_indexes=7&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T11%3A30%3A00Z&include_usd=true"
+        );
+    }
+
+    #[test]
+    fn rewards_builder() {
+        let url = requested_url(|kiln| {
+            kiln.ethereum()
+                .rewards()
+                .wallets(["0xa"])
+                .since(date("2024-01-01T00:00:00Z"))
+                .until(date("2024-01-31T00:00:00Z"))
+                .include_usd()
+                .send()
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/rewards?scope=kiln&wallets=0xa&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z&include_usd=true"
+        );
+    }
+
+    #[test]
+    fn stakes_builder() {
+        let url = requested_url(|kiln| {
+            kiln.ethereum()
+                .stakes()
+                .validator_indexes([1u64, 2])
+                .filtered_states([EthereumFilterState::ActiveOngoing])
+                .send()
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/stakes?validator_indexes=1,2&current_page=1&page_size=25&filtered_states=active_ongoing"
+        );
+    }
 
-        data
+    #[test]
+    fn iter_stakes_first_page() {
+        let url = requested_url(|kiln| {
+            kiln.ethereum()
+                .iter_stakes(
+                    EthereumStakesRequest::Scoped(EthereumScopedStakesRequest {
+                        validators: None,
+                        scope: Scope::Kiln,
+                        wallets: Some(vec!["0xwallet".into()]),
+                        proxies: None,
+                        withdrawal_credentials: None,
+                        validator_indexes: None,
+                        include_eigenlayer: false,
+                        accounts: None,
+                        current_page: NonZeroU64::MIN,
+                        page_size: NonZeroU64::MIN,
+                    }),
+                    PageOptions::default().max_items(10),
+                )
+                .next()
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/stakes?scope=kiln&wallets=0xwallet&current_page=1&page_size=25"
+        );
+    }
+
+    #[test]
+    fn get_operations() {
+        let url = requested_url(|kiln| {
+            kiln.ethereum().get_operations(&EthereumOperationsRequest {
+                validators: Some(vec!["0xa".into()]),
+                wallets: None,
+                proxies: Some(vec!["0xp".into()]),
+                validator_indexes: None,
+                accounts: None,
+                start_date: date("2024-01-01T00:00:00Z"),
+                end_date: Some(date("2024-02-01T00:00:00Z")),
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/operations?validators=0xa&proxies=0xp&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-02-01T00%3A00%3A00Z"
+        );
     }
 
-    pub fn get_reports(
-        &self,
-        ethereum_reports_request: &EthereumReportsRequest,
-    ) -> Result<Vec<u8>, ureq::Error> {
-        let EthereumReportsRequest {
-            validators,
-            wallets,
-            accounts,
-        } = ethereum_reports_request;
+    #[test]
+    fn network_stats() {
+        let url = requested_url(|kiln| kiln.ethereum().network_stats());
 
-        let validators = validators.join(",");
+        assert_eq!(url, "https://api.kiln.fi/v1/eth/network-stats");
+    }
 
-        let wallets = wallets.join(",");
+    #[test]
+    fn kiln_stats() {
+        let url = requested_url(|kiln| kiln.ethereum().kiln_stats());
 
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
+        assert_eq!(url, "https://api.kiln.fi/v1/eth/kiln-stats");
+    }
 
-        let url: String = format!(
-            "{}/reports?validators={}&wallets={}&accounts={}",
-            self.base_url, validators, wallets, accounts
+    #[test]
+    fn get_status_tx() {
+        let url = requested_url(|kiln| kiln.ethereum().get_status_tx("0xhash"));
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/transaction/status?tx_hash=0xhash"
         );
+    }
 
-        let mut file_bytes: Vec<u8> = Vec::new();
+    #[test]
+    fn get_tx_decoding() {
+        let url = requested_url(|kiln| {
+            kiln.ethereum().get_tx_decoding(&TxDecodingRequest {
+                tx_serialized: "02f8+/=".into(),
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/transaction/decode?tx_serialized=02f8%2B%2F%3D"
+        );
+    }
 
-        ureq::get(url)
-            .header("accept", "application/octet-stream")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .as_reader()
-            .read_to_end(&mut file_bytes)?;
+    #[test]
+    fn get_reports() {
+        let url = requested_url(|kiln| {
+            kiln.ethereum().get_reports(&EthereumReportsRequest {
+                wallets: Some(vec!["0xa".into(), "0xb".into()]),
+                ..Default::default()
+            })
+        });
 
-        Ok(file_bytes)
+        assert_eq!(url, "https://api.kiln.fi/v1/eth/reports?wallets=0xa,0xb");
     }
 
-    pub fn get_exit_message(
-        &self,
-        validators: Vec<String>,
-    ) -> Result<ReturnedData<Vec<ExitMessageResponse>>, ureq::Error> {
-        let validators = validators.join(",");
+    #[test]
+    fn get_exit_message() {
+        let url = requested_url(|kiln| kiln.ethereum().get_exit_message(vec!["0xa".into()]));
 
-        let url: String = format!(
-            "{}/transaction/exit-messages?validators={}",
-            self.base_url, validators
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/transaction/exit-messages?validators=0xa"
         );
+    }
+
+    #[test]
+    fn responses_match_the_fixtures() {
+        let stakes: PaginatedData<Vec<EthereumStakesResponse>> = fixture("ethereum/stakes.json");
+        let keys: ReturnedData<Vec<ValidationKeys>> = fixture("ethereum/keys.json");
+        let status: ReturnedData<EthereumTxStatusResponse> = fixture("ethereum/tx_status.json");
+        let decoded: ReturnedData<EthereumTxDecodingResponse> =
+            fixture("ethereum/tx_decoding.json");
+
+        assert_eq!(stakes.data[1].state, EthereumFilterState::PendingQueued);
+        assert!(stakes.data[1].activated_at.is_none());
+        assert!(matches!(keys.data[0], ValidationKeys::BatchResponse { .. }));
+        assert!(matches!(keys.data[1], ValidationKeys::CliResponse { .. }));
+        assert_eq!(status.data.status, EthereumTxStatus::Success);
+        assert_eq!(decoded.data.chain_id, ChainID::EthereumMainnet);
+
+        fixture::<ReturnedData<Vec<EthereumRewardsResponse>>>("ethereum/rewards.json");
+        fixture::<ReturnedData<EthereumNetworkStats>>("ethereum/network_stats.json");
+        fixture::<ReturnedData<KilnStats>>("ethereum/kiln_stats.json");
+        fixture::<ReturnedData<PostETHStakesTxResponse>>("ethereum/stake_tx.json");
+        fixture::<ReturnedData<RequestExitTxResponse>>("ethereum/exit_request_tx.json");
+        fixture::<ReturnedData<EthereumPrepareTxResponse>>("ethereum/prepare_tx.json");
+        fixture::<ReturnedData<BroadcastTxResponse>>("ethereum/broadcast_tx.json");
+        fixture::<ReturnedData<Vec<ExitMessageResponse>>>("ethereum/exit_messages.json");
+    }
+
+    #[test]
+    fn operations_are_told_apart_by_their_type() {
+        let operations: ReturnedData<Vec<EthereumOperationsResponse>> =
+            fixture("ethereum/operations.json");
+
+        assert!(matches!(
+            operations.data[0],
+            EthereumOperationsResponse::Deposit { .. }
+        ));
+        assert!(operations.data.last().unwrap().is_unknown());
+        assert_eq!(
+            operations
+                .data
+                .iter()
+                .filter(|operation| operation.is_unknown())
+                .count(),
+            1
+        );
+    }
+
+    // answers the crafted stake transaction of the fixtures, a mainnet one (`chain_id: 1`), to
+    // any request
+    #[derive(Debug)]
+    struct StakeTxTransport;
+
+    impl Transport for StakeTxTransport {
+        fn send(&self, _: HttpRequest) -> Result<HttpResponse, TransportError> {
+            Ok(HttpResponse {
+                status: 200,
+                headers: Vec::new(),
+                body: std::fs::read(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/fixtures/ethereum/stake_tx.json"
+                ))?,
+            })
+        }
+    }
+
+    fn stake_tx() -> PostETHStakesTx {
+        PostETHStakesTx {
+            account_id: Uuid::nil(),
+            wallet: "0x0000000000000000000000000000000000000000"
+                .parse()
+                .unwrap(),
+            amount_wei: Wei::new(32_000_000_000_000_000_000),
+        }
+    }
+
+    #[test]
+    fn crafted_tx_on_another_chain_is_left_to_the_caller() {
+        let post_stake_tx = |environment: KilnEnvironment| {
+            Kiln::builder()
+                .api_token("kiln_test_token")
+                .environment(environment)
+                .transport(StakeTxTransport)
+                .seal()
+                .build()
+                .unwrap()
+                .ethereum()
+                .post_stake_tx(&stake_tx())
+                .unwrap()
+        };
+
+        assert_eq!(
+            post_stake_tx(KilnEnvironment::Mainnet).data.chain_id,
+            ChainID::EthereumMainnet
+        );
+
+        // a warning with the `tracing` feature
+        let crafted = post_stake_tx(KilnEnvironment::Testnet);
+
+        assert!(!KilnEnvironment::Testnet.supports(&crafted.data.chain_id));
+    }
+
+    #[test]
+    fn stake_tx_is_charged_to_the_transaction_budget() {
+        let kiln = Kiln::builder()
+            .api_token("kiln_test_token")
+            .base_url("https://api.kiln.fi/v1")
+            .transport(StakeTxTransport)
+            .rate_limit(crate::RateLimit::per_minute(1))
+            .seal()
+            .build()
+            .unwrap();
+
+        // the read budget is spent, the next read would wait a minute
+        let _ = kiln.ethereum().network_stats();
+
+        let started_at = std::time::Instant::now();
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<ExitMessageResponse>>>();
+        kiln.ethereum().post_stake_tx(&stake_tx()).unwrap();
 
-        data
+        assert!(started_at.elapsed() < std::time::Duration::from_secs(1));
     }
 }
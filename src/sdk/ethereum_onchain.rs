@@ -1,8 +1,15 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::address::{EthAddress, Lenient};
+use crate::amount::{Amount, FloatAmount, IntAmount};
+use crate::errors::KilnError;
+use crate::http::HttpClient;
+use crate::mode::{for_each_mode, Blocking, Mode};
+use crate::query::Query;
 use crate::{response_format::ReturnedData, Kiln};
 
+use super::builders::{filter, NoStartDate, StartDate};
 use super::ethereum::ValidationKeys;
 
 // use super::PostValidationKeys;
@@ -12,8 +19,8 @@ use super::ethereum::ValidationKeys;
 pub struct EthOnchainKeysRequest {
     pub account_id: Uuid,
     pub number: u64,
-    pub fee_recipient_contract_address: String,
-    pub staking_contract_address: String,
+    pub fee_recipient_contract_address: EthAddress,
+    pub staking_contract_address: EthAddress,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -22,7 +29,7 @@ pub struct EthSuiteKeysRequest {
     pub account_id: Uuid,
     pub number: u64,
     // Address
-    pub factory_contract_address: String,
+    pub factory_contract_address: EthAddress,
 }
 
 // --- Onchain V2 stakes --- start
@@ -32,7 +39,7 @@ pub struct EthSuiteKeysRequest {
 pub struct StakeStructure {
     pub pool: String,
     // Address
-    pub pool_address: String,
+    pub pool_address: Lenient<EthAddress>,
     pub share: f64,
 }
 
@@ -47,12 +54,12 @@ pub struct StakeStats {
 #[serde(rename_all = "snake_case")]
 pub struct OnchainV2StakesResponse {
     // Address
-    pub owner: String,
+    pub owner: Lenient<EthAddress>,
     pub integration: String,
     // Address
-    pub integration_address: String,
-    pub balance: String,
-    pub rewards: String,
+    pub integration_address: Lenient<EthAddress>,
+    pub balance: Amount,
+    pub rewards: Amount,
     pub nrr: f64,
     pub grr: f64,
     pub one_year: StakeStats,
@@ -71,75 +78,72 @@ pub struct OnchainV2StakesResponse {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct OnchainV2OperationsRequest {
-    pub wallets: Vec<String>,
-    pub ids: Vec<String>,
+    pub wallets: Option<Vec<String>>,
+    pub ids: Option<Vec<String>>,
     pub start_date: chrono::DateTime<chrono::Utc>,
-    pub end_date: chrono::DateTime<chrono::Utc>,
+    pub end_date: Option<chrono::DateTime<chrono::Utc>>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-#[serde(rename_all = "snake_case")]
-pub enum TicketStatus {
-    Unfulfillable,
-    Fulfillable,
-    PartiallyFulfillable,
+string_enum! {
+    pub enum TicketStatus {
+        Unfulfillable => "unfulfillable",
+        Fulfillable => "fulfillable",
+        PartiallyFulfillable => "partially_fulfillable",
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-#[serde(rename_all = "snake_case")]
-#[allow(clippy::large_enum_variant)]
-pub enum OnchainV2Operation {
-    Exit {
-        r#type: String,
-        ticket_id: String,
-        ticket_status: TicketStatus,
-        size: String,
-        size_shares: String,
-        claimable: String,
-        claimable_shares: String,
-        cask_ids: Vec<String>,
-        id: String,
-        // Address
-        owner: String,
-        time: String,
-        block: u64,
-        tx_hash: String,
-    },
-    Claim {
-        r#type: String,
-        ticket_id: String,
-        ticket_status: TicketStatus,
-        claimed: String,
-        claimable_shares: String,
-        remaining: String,
-        remaining_shares: String,
-        used_cask_ids: Vec<String>,
-        id: String,
-        // Address
-        owner: String,
-        time: String,
-        block: u64,
-        tx_hash: String,
-    },
-    Deposit {
-        r#type: String,
-        amount: String,
-        amount_shares: String,
-        id: String,
-        // Address
-        owner: String,
-        time: String,
-        block: u64,
-        tx_hash: String,
-    },
+operation_enum! {
+    #[allow(clippy::large_enum_variant)]
+    pub enum OnchainV2Operation {
+        Exit => "exit" {
+            ticket_id: String,
+            ticket_status: TicketStatus,
+            size: String,
+            size_shares: String,
+            claimable: String,
+            claimable_shares: String,
+            cask_ids: Vec<String>,
+            id: String,
+            // Address
+            owner: Lenient<EthAddress>,
+            time: String,
+            block: u64,
+            tx_hash: String,
+        },
+        Claim => "claim" {
+            ticket_id: String,
+            ticket_status: TicketStatus,
+            claimed: String,
+            claimable_shares: String,
+            remaining: String,
+            remaining_shares: String,
+            used_cask_ids: Vec<String>,
+            id: String,
+            // Address
+            owner: Lenient<EthAddress>,
+            time: String,
+            block: u64,
+            tx_hash: String,
+        },
+        Deposit => "deposit" {
+            amount: Amount,
+            amount_shares: Amount,
+            id: String,
+            // Address
+            owner: Lenient<EthAddress>,
+            time: String,
+            block: u64,
+            tx_hash: String,
+        },
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct OnchainV2RewardsRequest {
-    pub wallets: Vec<String>,
+    pub wallets: Option<Vec<String>>,
     pub start_date: chrono::DateTime<chrono::Utc>,
-    pub end_date: chrono::DateTime<chrono::Utc>,
+    pub end_date: Option<chrono::DateTime<chrono::Utc>>,
     pub include_usd: bool,
     // Address
     pub integration: String,
@@ -149,11 +153,11 @@ pub struct OnchainV2RewardsRequest {
 #[serde(rename_all = "snake_case")]
 pub struct OnchainV2Reward {
     pub date: chrono::DateTime<chrono::Utc>,
-    pub rewards: String,
-    pub balance: String,
+    pub rewards: Amount,
+    pub balance: Amount,
     pub nrr: f64,
-    pub rewards_usd: u64,
-    pub balance_usd: u64,
+    pub rewards_usd: FloatAmount,
+    pub balance_usd: FloatAmount,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -167,27 +171,27 @@ pub struct OnchainV2NetworkStatsRequest {
 #[serde(rename_all = "snake_case")]
 pub struct Pool {
     // Address
-    pub address: String,
+    pub address: Lenient<EthAddress>,
     pub name: String,
     pub ratio: u64,
     pub commission: u64,
-    pub total_deposited: String,
+    pub total_deposited: Amount,
     // Address
-    pub factory_address: String,
+    pub factory_address: Lenient<EthAddress>,
     // Address
-    pub operator_address: String,
+    pub operator_address: Lenient<EthAddress>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct OnchainV2NetworkStatsResponse {
     // Address
-    pub address: String,
+    pub address: Lenient<EthAddress>,
     pub name: String,
     pub symbol: String,
-    pub fee: u64,
-    pub total_supply: String,
-    pub total_underlying_supply: String,
+    pub fee: IntAmount,
+    pub total_supply: Amount,
+    pub total_underlying_supply: Amount,
     pub total_stakers: u64,
     pub nrr: f64,
     pub grr: f64,
@@ -203,181 +207,459 @@ pub struct OnchainV2NetworkStatsResponse {
 #[serde(rename_all = "snake_case")]
 pub struct OnchainV2ExitTicketsResponse {
     // Address
-    pub owner: String,
+    pub owner: Lenient<EthAddress>,
     pub state: TicketStatus,
-    pub retrievable_amount: String,
-    pub exiting_amount: String,
+    pub retrievable_amount: Amount,
+    pub exiting_amount: Amount,
     // Address
-    pub exit_queue_address: String,
+    pub exit_queue_address: Lenient<EthAddress>,
     // Address
-    pub integration_address: String,
+    pub integration_address: Lenient<EthAddress>,
     pub ticket_id: String,
     pub cask_id: Option<String>,
     pub estimated_claimable_at: Option<u64>,
 }
 
 #[derive(Clone, Debug)]
-pub struct KilnEthereumOnchainClient {
-    pub bearer_token: String,
+pub struct KilnEthereumOnchainClient<M: Mode = Blocking> {
     pub base_url: String,
+    http: HttpClient<M>,
 }
 
-impl From<&Kiln> for KilnEthereumOnchainClient {
-    fn from(kiln: &Kiln) -> Self {
+impl<M: Mode> From<&Kiln<M>> for KilnEthereumOnchainClient<M> {
+    fn from(kiln: &Kiln<M>) -> Self {
         let url: String = format!("{}/eth/onchain", kiln.base_url);
-        let bearer_token: String = format!("Bearer {}", kiln.api_token);
 
         Self {
-            bearer_token,
             base_url: url,
+            http: kiln.http.clone(),
         }
     }
 }
 
-impl KilnEthereumOnchainClient {
-    pub fn post_eth_onchain_keys(
-        &self,
-        eth_onchain_keys_request: &EthOnchainKeysRequest,
-    ) -> Result<ReturnedData<Vec<ValidationKeys>>, ureq::Error> {
-        let url = format!("{}/v1/keys", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(eth_onchain_keys_request)?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<ValidationKeys>>>();
-
-        data
+for_each_mode! { <M>
+    impl KilnEthereumOnchainClient<M> {
+        pub async fn post_eth_onchain_keys(
+            &self,
+            eth_onchain_keys_request: &EthOnchainKeysRequest,
+        ) -> Result<ReturnedData<Vec<ValidationKeys>>, KilnError> {
+            let url = format!("{}/v1/keys", self.base_url);
+
+            self.http
+                .post(
+                    "kiln.eth.onchain.post_eth_onchain_keys",
+                    &url,
+                    eth_onchain_keys_request,
+                )
+                .await
+        }
+
+        pub async fn post_eth_suite_keys(
+            &self,
+            eth_onchain_keys_request: &EthSuiteKeysRequest,
+        ) -> Result<ReturnedData<Vec<ValidationKeys>>, KilnError> {
+            let url = format!("{}/v2/keys", self.base_url);
+
+            self.http
+                .post(
+                    "kiln.eth.onchain.post_eth_suite_keys",
+                    &url,
+                    eth_onchain_keys_request,
+                )
+                .await
+        }
+
+        pub async fn get_onchain_v2_stakes(
+            &self,
+            wallets: Vec<String>,
+        ) -> Result<ReturnedData<Vec<OnchainV2StakesResponse>>, KilnError> {
+            let url = Query::new()
+                .list("wallets", wallets)
+                .url(&format!("{}/v2/stakes", self.base_url));
+
+            self.http
+                .get("kiln.eth.onchain.get_onchain_v2_stakes", &url)
+                .await
+        }
+
+        pub async fn get_onchain_v2_operations(
+            &self,
+            onchain_v2_operation_request: &OnchainV2OperationsRequest,
+        ) -> Result<ReturnedData<Vec<OnchainV2Operation>>, KilnError> {
+            let OnchainV2OperationsRequest {
+                wallets,
+                ids,
+                start_date,
+                end_date,
+            } = onchain_v2_operation_request;
+
+            let url = Query::new()
+                .list("wallets", wallets.iter().flatten())
+                .list("ids", ids.iter().flatten())
+                .value("start_date", start_date)
+                .option("end_date", end_date.as_ref())
+                .url(&format!("{}/v2/operations", self.base_url));
+
+            self.http
+                .get("kiln.eth.onchain.get_onchain_v2_operations", &url)
+                .await
+        }
+
+        pub async fn get_onchain_v2_rewards(
+            &self,
+            onchain_v2_rewards_request: &OnchainV2RewardsRequest,
+        ) -> Result<ReturnedData<Vec<OnchainV2Reward>>, KilnError> {
+            let OnchainV2RewardsRequest {
+                wallets,
+                start_date,
+                end_date,
+                integration,
+                include_usd,
+            } = onchain_v2_rewards_request;
+
+            let url = Query::new()
+                .list("wallets", wallets.iter().flatten())
+                .value("start_date", start_date)
+                .option("end_date", end_date.as_ref())
+                .value("include_usd", include_usd)
+                .value("integration", integration)
+                .url(&format!("{}/v2/rewards", self.base_url));
+
+            self.http
+                .get("kiln.eth.onchain.get_onchain_v2_rewards", &url)
+                .await
+        }
+
+        pub async fn get_onchain_v2_network_stats(
+            &self,
+            integration_request: &OnchainV2NetworkStatsRequest,
+        ) -> Result<ReturnedData<OnchainV2NetworkStatsResponse>, KilnError> {
+            let url = Query::new()
+                .value("integration", &integration_request.integration)
+                .url(&format!("{}/v2/network-stats", self.base_url));
+
+            self.http
+                .get("kiln.eth.onchain.get_onchain_v2_network_stats", &url)
+                .await
+        }
+
+        pub async fn get_onchain_v2_exit_tickets(
+            &self,
+            wallets: Vec<String>,
+        ) -> Result<ReturnedData<Vec<OnchainV2ExitTicketsResponse>>, KilnError> {
+            let url = Query::new()
+                .list("wallets", wallets)
+                .url(&format!("{}/v2/exit-tickets", self.base_url));
+
+            self.http
+                .get("kiln.eth.onchain.get_onchain_v2_exit_tickets", &url)
+                .await
+        }
     }
+}
 
-    pub fn post_eth_suite_keys(
-        &self,
-        eth_onchain_keys_request: &EthSuiteKeysRequest,
-    ) -> Result<ReturnedData<Vec<ValidationKeys>>, ureq::Error> {
-        let url = format!("{}/v2/keys", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(eth_onchain_keys_request)?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<ValidationKeys>>>();
-
-        data
+impl<M: Mode> KilnEthereumOnchainClient<M> {
+    pub fn v2_operations(&self) -> OnchainV2OperationsBuilder<'_, NoStartDate, M> {
+        OnchainV2OperationsBuilder::new(self)
     }
 
-    pub fn get_onchain_v2_stakes(
+    pub fn v2_rewards(
         &self,
-        wallets: Vec<String>,
-    ) -> Result<ReturnedData<OnchainV2StakesResponse>, ureq::Error> {
-        let wallets = wallets.join(",");
+        integration: impl Into<String>,
+    ) -> OnchainV2RewardsBuilder<'_, NoStartDate, M> {
+        OnchainV2RewardsBuilder::new(self, integration.into())
+    }
+}
 
-        let url: String = format!("{}/v2/stakes?&wallets={}", self.base_url, wallets);
+// `kiln.ethereum_onchain().v2_operations()`
+#[derive(Debug)]
+pub struct OnchainV2OperationsBuilder<'a, S, M: Mode = Blocking> {
+    client: &'a KilnEthereumOnchainClient<M>,
+    wallets: Option<Vec<String>>,
+    ids: Option<Vec<String>>,
+    start_date: S,
+    end_date: Option<chrono::DateTime<chrono::Utc>>,
+}
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<OnchainV2StakesResponse>>();
+impl<'a, M: Mode> OnchainV2OperationsBuilder<'a, NoStartDate, M> {
+    fn new(client: &'a KilnEthereumOnchainClient<M>) -> Self {
+        Self {
+            client,
+            wallets: None,
+            ids: None,
+            start_date: NoStartDate,
+            end_date: None,
+        }
+    }
+}
+
+impl<'a, S, M: Mode> OnchainV2OperationsBuilder<'a, S, M> {
+    pub fn wallets(self, wallets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            wallets: filter(wallets),
+            ..self
+        }
+    }
 
-        data
+    pub fn ids(self, ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            ids: filter(ids),
+            ..self
+        }
     }
 
-    pub fn get_onchain_v2_operations(
-        &self,
-        onchain_v2_operation_request: &OnchainV2OperationsRequest,
-    ) -> Result<ReturnedData<Vec<OnchainV2Operation>>, ureq::Error> {
-        let OnchainV2OperationsRequest {
-            wallets,
-            ids,
-            start_date,
-            end_date,
-        } = onchain_v2_operation_request;
-
-        let wallets = wallets.join(",");
-
-        let ids = ids.join(",");
-
-        let url: String = format!(
-            "{}/v2/operations?&wallets={}&ids={}&start_date={}&end_date={}",
-            self.base_url, wallets, ids, start_date, end_date
-        );
+    pub fn until(self, end_date: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            end_date: Some(end_date),
+            ..self
+        }
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<OnchainV2Operation>>>();
+    pub fn since(
+        self,
+        start_date: chrono::DateTime<chrono::Utc>,
+    ) -> OnchainV2OperationsBuilder<'a, StartDate, M> {
+        OnchainV2OperationsBuilder {
+            client: self.client,
+            wallets: self.wallets,
+            ids: self.ids,
+            start_date: StartDate(start_date),
+            end_date: self.end_date,
+        }
+    }
+}
 
-        data
+impl<M: Mode> OnchainV2OperationsBuilder<'_, StartDate, M> {
+    pub fn into_request(self) -> OnchainV2OperationsRequest {
+        OnchainV2OperationsRequest {
+            wallets: self.wallets,
+            ids: self.ids,
+            start_date: self.start_date.0,
+            end_date: self.end_date,
+        }
     }
+}
 
-    pub fn get_onchain_v2_rewards(
-        &self,
-        onchain_v2_rewards_request: &OnchainV2RewardsRequest,
-    ) -> Result<ReturnedData<Vec<OnchainV2Reward>>, ureq::Error> {
-        let OnchainV2RewardsRequest {
-            wallets,
-            start_date,
-            end_date,
+for_each_mode! { <M>
+    impl OnchainV2OperationsBuilder<'_, StartDate, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<OnchainV2Operation>>, KilnError> {
+            let client = self.client;
+
+            client.get_onchain_v2_operations(&self.into_request()).await
+        }
+    }
+}
+
+// `kiln.ethereum_onchain().v2_rewards(integration)`
+#[derive(Debug)]
+pub struct OnchainV2RewardsBuilder<'a, S, M: Mode = Blocking> {
+    client: &'a KilnEthereumOnchainClient<M>,
+    wallets: Option<Vec<String>>,
+    include_usd: bool,
+    integration: String,
+    start_date: S,
+    end_date: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl<'a, M: Mode> OnchainV2RewardsBuilder<'a, NoStartDate, M> {
+    fn new(client: &'a KilnEthereumOnchainClient<M>, integration: String) -> Self {
+        Self {
+            client,
+            wallets: None,
+            include_usd: false,
             integration,
-            include_usd,
-        } = onchain_v2_rewards_request;
+            start_date: NoStartDate,
+            end_date: None,
+        }
+    }
+}
+
+impl<'a, S, M: Mode> OnchainV2RewardsBuilder<'a, S, M> {
+    pub fn wallets(self, wallets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            wallets: filter(wallets),
+            ..self
+        }
+    }
+
+    pub fn include_usd(self) -> Self {
+        Self {
+            include_usd: true,
+            ..self
+        }
+    }
+
+    pub fn until(self, end_date: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            end_date: Some(end_date),
+            ..self
+        }
+    }
+
+    pub fn since(
+        self,
+        start_date: chrono::DateTime<chrono::Utc>,
+    ) -> OnchainV2RewardsBuilder<'a, StartDate, M> {
+        OnchainV2RewardsBuilder {
+            client: self.client,
+            wallets: self.wallets,
+            include_usd: self.include_usd,
+            integration: self.integration,
+            start_date: StartDate(start_date),
+            end_date: self.end_date,
+        }
+    }
+}
 
-        let wallets = wallets.join(",");
+impl<M: Mode> OnchainV2RewardsBuilder<'_, StartDate, M> {
+    pub fn into_request(self) -> OnchainV2RewardsRequest {
+        OnchainV2RewardsRequest {
+            wallets: self.wallets,
+            include_usd: self.include_usd,
+            integration: self.integration,
+            start_date: self.start_date.0,
+            end_date: self.end_date,
+        }
+    }
+}
+
+for_each_mode! { <M>
+    impl OnchainV2RewardsBuilder<'_, StartDate, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<OnchainV2Reward>>, KilnError> {
+            let client = self.client;
+
+            client.get_onchain_v2_rewards(&self.into_request()).await
+        }
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod ethereum_onchain_test {
+
+    use super::*;
+    use crate::query::query_test_support::{date, fixture, requested_url};
+
+    #[test]
+    fn get_onchain_v2_stakes() {
+        let url = requested_url(|kiln| {
+            kiln.ethereum_onchain()
+                .get_onchain_v2_stakes(vec!["0xa".into(), "0xb".into()])
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/onchain/v2/stakes?wallets=0xa,0xb"
+        );
+    }
 
-        let url: String = format!(
-            "{}/v2/rewards?&wallets={}&start_date={}&end_date={}&include_usd={}&integration={}",
-            self.base_url, wallets, start_date, end_date, include_usd, integration
+    #[test]
+    fn get_onchain_v2_rewards() {
+        let url = requested_url(|kiln| {
+            kiln.ethereum_onchain()
+                .get_onchain_v2_rewards(&OnchainV2RewardsRequest {
+                    wallets: Some(vec!["0xa".into()]),
+                    start_date: date("2024-01-01T00:00:00Z"),
+                    end_date: Some(date("2024-01-31T00:00:00Z")),
+                    include_usd: true,
+                    integration: "0xi".into(),
+                })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/onchain/v2/rewards?wallets=0xa&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z&include_usd=true&integration=0xi"
         );
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<OnchainV2Reward>>>();
+    #[test]
+    fn get_onchain_v2_operations() {
+        let url = requested_url(|kiln| {
+            kiln.ethereum_onchain()
+                .get_onchain_v2_operations(&OnchainV2OperationsRequest {
+                    wallets: Some(vec!["0xa".into()]),
+                    ids: Some(vec!["1".into(), "2".into()]),
+                    start_date: date("2024-01-01T00:00:00Z"),
+                    end_date: Some(date("2024-01-31T00:00:00Z")),
+                })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/onchain/v2/operations?wallets=0xa&ids=1,2&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z"
+        );
+    }
 
-        data
+    #[test]
+    fn get_onchain_v2_network_stats() {
+        let url = requested_url(|kiln| {
+            kiln.ethereum_onchain()
+                .get_onchain_v2_network_stats(&OnchainV2NetworkStatsRequest {
+                    integration: "0xi".into(),
+                })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/onchain/v2/network-stats?integration=0xi"
+        );
     }
 
-    pub fn get_onchain_v2_network_stats(
-        &self,
-        integration_request: &OnchainV2NetworkStatsRequest,
-    ) -> Result<ReturnedData<OnchainV2NetworkStatsResponse>, ureq::Error> {
-        let url: String = format!(
-            "{}/v2/network-stats?&integration={}",
-            self.base_url, integration_request.integration,
+    #[test]
+    fn v2_rewards_builder() {
+        let url = requested_url(|kiln| {
+            kiln.ethereum_onchain()
+                .v2_rewards("0xi")
+                .wallets(["0xa"])
+                .since(date("2024-01-01T00:00:00Z"))
+                .until(date("2024-01-31T00:00:00Z"))
+                .send()
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/eth/onchain/v2/rewards?wallets=0xa&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z&include_usd=false&integration=0xi"
         );
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<OnchainV2NetworkStatsResponse>>();
+    #[test]
+    fn get_onchain_v2_exit_tickets() {
+        let url = requested_url(|kiln| kiln.ethereum_onchain().get_onchain_v2_exit_tickets(vec![]));
 
-        data
+        assert_eq!(url, "https://api.kiln.fi/v1/eth/onchain/v2/exit-tickets");
     }
 
-    pub fn get_onchain_v2_exit_tickets(
-        &self,
-        wallets: Vec<String>,
-    ) -> Result<ReturnedData<Vec<OnchainV2ExitTicketsResponse>>, ureq::Error> {
-        let wallets = wallets.join(",");
+    #[test]
+    fn responses_match_the_fixtures() {
+        let tickets: ReturnedData<Vec<OnchainV2ExitTicketsResponse>> =
+            fixture("ethereum_onchain/exit_tickets.json");
 
-        let url: String = format!("{}/v2/exit-tickets?&wallets={}", self.base_url, wallets);
+        assert_eq!(tickets.data[0].state, TicketStatus::Fulfillable);
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<OnchainV2ExitTicketsResponse>>>();
+        fixture::<ReturnedData<Vec<ValidationKeys>>>("ethereum_onchain/keys.json");
+        fixture::<ReturnedData<Vec<OnchainV2StakesResponse>>>("ethereum_onchain/stakes.json");
+        fixture::<ReturnedData<Vec<OnchainV2Reward>>>("ethereum_onchain/rewards.json");
+        fixture::<ReturnedData<OnchainV2NetworkStatsResponse>>(
+            "ethereum_onchain/network_stats.json",
+        );
+    }
 
-        data
+    #[test]
+    fn operations_are_told_apart_by_their_type() {
+        let operations: ReturnedData<Vec<OnchainV2Operation>> =
+            fixture("ethereum_onchain/operations.json");
+
+        assert!(matches!(
+            operations.data[0],
+            OnchainV2Operation::Deposit { .. }
+        ));
+        assert!(operations.data.last().unwrap().is_unknown());
+        assert_eq!(
+            operations
+                .data
+                .iter()
+                .filter(|operation| operation.is_unknown())
+                .count(),
+            1
+        );
     }
 }
@@ -1,10 +1,18 @@
-use std::io::Read;
-
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::address::{AddressError, CosmosAddress};
+use crate::amount::FloatAmount;
+use crate::denomination::Inj;
+use crate::errors::KilnError;
+use crate::http::HttpClient;
+use crate::mode::{for_each_mode, Blocking, Mode};
+use crate::query::Query;
 use crate::{response_format::ReturnedData, Kiln};
 
+use super::builders::{
+    GetStakesBuilder, NoStartDate, RewardsBuilder, StartDate, ValidatorOperationsBuilder,
+};
 use super::{
     BroadcastTxRequest, BroadcastTxResponse, GetStakesRequest, GetStakesResponse,
     PostStakesRequest, PostStakesResponse, PrepareTxRequest, PrepareTxResponse, ReportsRequest,
@@ -12,10 +20,13 @@ use super::{
     ValidatorOperationsRequest, WithdrawRewardsTxRequest,
 };
 
+// bech32 prefix of the injective addresses : `inj1..`, `injvaloper1..`
+const PREFIX: &str = "inj";
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct InjectiveNetworkStatsResponse {
-    pub inj_price_usd: f64,
+    pub inj_price_usd: FloatAmount,
     pub nb_validators: u64,
     pub net_gross_apy: f64,
     pub supply_staked_percent: f64,
@@ -28,8 +39,14 @@ pub struct InjectiveNetworkStatsResponse {
 pub struct InjectiveStakeTxRequest {
     pub account_id: Uuid,
     pub pubkey: String,
-    pub validator: String,
-    pub amount_inj: String,
+    pub validator: CosmosAddress,
+    pub amount_inj: Inj,
+}
+
+impl InjectiveStakeTxRequest {
+    pub(crate) fn check_addresses(&self, prefix: &str) -> Result<(), AddressError> {
+        self.validator.check_validator(prefix)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -38,9 +55,15 @@ pub struct InjectiveUnstakeTxRequest {
     // Pubkey
     pub pubkey: String,
     // Address
-    pub validator: String,
+    pub validator: CosmosAddress,
     // Omit with Option::None to unstake all the delegated amount
-    pub amount_inj: Option<String>,
+    pub amount_inj: Option<Inj>,
+}
+
+impl InjectiveUnstakeTxRequest {
+    pub(crate) fn check_addresses(&self, prefix: &str) -> Result<(), AddressError> {
+        self.validator.check_validator(prefix)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -49,388 +72,435 @@ pub struct InjectiveRedelegateTxRequest {
     pub account_id: Uuid,
     // Pubkey
     pub pubkey: String,
-    pub validator_source: String,
-    pub validator_destination: String,
-    pub amount_inj: String,
+    pub validator_source: CosmosAddress,
+    pub validator_destination: CosmosAddress,
+    pub amount_inj: Inj,
+}
+
+impl InjectiveRedelegateTxRequest {
+    pub(crate) fn check_addresses(&self, prefix: &str) -> Result<(), AddressError> {
+        self.validator_source.check_validator(prefix)?;
+        self.validator_destination.check_validator(prefix)
+    }
 }
 
 #[derive(Clone, Debug)]
-pub struct KilnInjectiveClient {
-    pub bearer_token: String,
+pub struct KilnInjectiveClient<M: Mode = Blocking> {
     pub base_url: String,
+    http: HttpClient<M>,
 }
 
-impl From<&Kiln> for KilnInjectiveClient {
-    fn from(kiln: &Kiln) -> Self {
+impl<M: Mode> From<&Kiln<M>> for KilnInjectiveClient<M> {
+    fn from(kiln: &Kiln<M>) -> Self {
         let url: String = format!("{}/inj", kiln.base_url);
-        let bearer_token: String = format!("Bearer {}", kiln.api_token);
 
         Self {
-            bearer_token,
             base_url: url,
+            http: kiln.http.clone(),
         }
     }
 }
 
-impl KilnInjectiveClient {
-    pub fn get_stakes(
-        &self,
-        stakes_request: &GetStakesRequest,
-    ) -> Result<ReturnedData<Vec<GetStakesResponse>>, ureq::Error> {
-        let GetStakesRequest {
-            validators,
-            delegators,
-            accounts,
-        } = stakes_request;
-
-        let validators = validators.join(",");
-
-        let delegators = delegators.join(",");
-
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let url: String = format!(
-            "{}/stakes?validators={}&delegators={}&accounts={}",
-            self.base_url, validators, delegators, accounts
-        );
-
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<GetStakesResponse>>>();
+for_each_mode! { <M>
+    impl KilnInjectiveClient<M> {
+        pub async fn get_stakes(
+            &self,
+            stakes_request: &GetStakesRequest,
+        ) -> Result<ReturnedData<Vec<GetStakesResponse>>, KilnError> {
+            let GetStakesRequest {
+                validators,
+                delegators,
+                accounts,
+            } = stakes_request;
 
-        data
-    }
+            let url = Query::new()
+                .list("validators", validators.iter().flatten())
+                .list("delegators", delegators.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .url(&format!("{}/stakes", self.base_url));
 
-    pub fn post_stakes(
-        &self,
-        post_stakes_request: &PostStakesRequest,
-    ) -> Result<ReturnedData<PostStakesResponse>, ureq::Error> {
-        let url = format!("{}/stakes", self.base_url);
+            self.http.get("kiln.inj.get_stakes", &url).await
+        }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(post_stakes_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PostStakesResponse>>();
+        pub async fn post_stakes(
+            &self,
+            post_stakes_request: &PostStakesRequest,
+        ) -> Result<ReturnedData<PostStakesResponse>, KilnError> {
+            let url = format!("{}/stakes", self.base_url);
 
-        data
-    }
+            self.http
+                .post("kiln.inj.post_stakes", &url, post_stakes_request)
+                .await
+        }
 
-    pub fn get_rewards(
-        &self,
-        reward_request: RewardRequest,
-    ) -> Result<ReturnedData<Vec<Reward>>, ureq::Error> {
-        let url: String = match reward_request {
-            RewardRequest::Epoch {
-                stakes_addresses,
-                wallets,
-                pool_ids,
-                accounts,
-                format,
-                start_date,
-                end_date,
-                start_epoch,
-                end_epoch,
-            } => {
-                let stakes_addresses = stakes_addresses.join(",");
-
-                let pool_ids = pool_ids.join(",");
-
-                let wallets = wallets.join(",");
-
-                let accounts = accounts
-                    .iter()
-                    .map(|id| id.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",");
-
-                let url = format!(
-            "{}/rewards?stake_addresses={}&pool_ids={}&wallets={}&accounts={}&format={}&start_date={}&end_date={}&start_epoch={}&end_epoch={}",
-            self.base_url,
-            stakes_addresses,
-            pool_ids,
-            wallets,
-            accounts,
-            format.as_ref(),
-            start_date,
-            end_date,
-            start_epoch,
-            end_epoch
-        );
+        pub async fn get_rewards(
+            &self,
+            reward_request: RewardRequest,
+        ) -> Result<ReturnedData<Vec<Reward>>, KilnError> {
+            let url: String = match reward_request {
+                RewardRequest::Epoch {
+                    stakes_addresses,
+                    wallets,
+                    pool_ids,
+                    accounts,
+                    format,
+                    start_date,
+                    end_date,
+                    start_epoch,
+                    end_epoch,
+                } => Query::new()
+                    .list("stake_addresses", stakes_addresses.iter().flatten())
+                    .list("pool_ids", pool_ids.iter().flatten())
+                    .list("wallets", wallets.iter().flatten())
+                    .list("accounts", accounts.iter().flatten())
+                    .value("format", format.as_ref())
+                    .value("start_date", start_date)
+                    .option("end_date", end_date.as_ref())
+                    .value("start_epoch", start_epoch)
+                    .value("end_epoch", end_epoch)
+                    .url(&format!("{}/rewards", self.base_url)),
+                RewardRequest::Daily {
+                    stakes_addresses,
+                    wallets,
+                    pool_ids,
+                    accounts,
+                    format,
+                    start_date,
+                    end_date,
+                    include_usd,
+                } => Query::new()
+                    .list("stake_addresses", stakes_addresses.iter().flatten())
+                    .list("pool_ids", pool_ids.iter().flatten())
+                    .list("wallets", wallets.iter().flatten())
+                    .list("accounts", accounts.iter().flatten())
+                    .value("format", format.as_ref())
+                    .value("start_date", start_date)
+                    .option("end_date", end_date.as_ref())
+                    .value("include_usd", include_usd)
+                    .url(&format!("{}/rewards", self.base_url)),
+            };
+
+            self.http.get("kiln.inj.get_rewards", &url).await
+        }
 
-                url
-            }
-            RewardRequest::Daily {
-                stakes_addresses,
-                wallets,
-                pool_ids,
+        pub async fn get_operations(
+            &self,
+            validator_operations_request: &ValidatorOperationsRequest,
+        ) -> Result<ReturnedData<Vec<StakingOperation>>, KilnError> {
+            let ValidatorOperationsRequest {
+                validators,
+                delegators,
                 accounts,
-                format,
+                authz,
                 start_date,
                 end_date,
-                include_usd,
-            } => {
-                let stakes_addresses = stakes_addresses.join(",");
-
-                let pool_ids = pool_ids.join(",");
-
-                let wallets = wallets.join(",");
-
-                let accounts = accounts
-                    .iter()
-                    .map(|id| id.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",");
-
-                let url = format!(
-            "{}/rewards?stake_addresses={}&pool_ids={}&wallets={}&accounts={}&format={}&start_date={}&end_date={}&include_usd={}",
-            self.base_url,
-            stakes_addresses,
-            pool_ids,
-            wallets,
-            accounts,
-            format.as_ref(),
-            start_date,
-            end_date,
-            include_usd
-        );
+            } = validator_operations_request;
+
+            let url = Query::new()
+                .list("validators", validators.iter().flatten())
+                .value("authz", authz)
+                .list("delegators", delegators.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .value("start_date", start_date)
+                .option("end_date", end_date.as_ref())
+                .url(&format!("{}/operations", self.base_url));
+
+            self.http.get("kiln.inj.get_operations", &url).await
+        }
 
-                url
-            }
-        };
+        pub async fn get_network_stats(
+            &self,
+        ) -> Result<ReturnedData<InjectiveNetworkStatsResponse>, KilnError> {
+            let url: String = format!("{}/network-stats", self.base_url);
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<Reward>>>();
+            self.http.get("kiln.inj.get_network_stats", &url).await
+        }
 
-        data
-    }
+        pub async fn get_reports(
+            &self,
+            reports_request: &ReportsRequest,
+        ) -> Result<Vec<u8>, KilnError> {
+            let ReportsRequest {
+                delegators,
+                validators,
+                accounts,
+            } = reports_request;
 
-    pub fn get_operations(
-        &self,
-        validator_operations_request: &ValidatorOperationsRequest,
-    ) -> Result<ReturnedData<Vec<StakingOperation>>, ureq::Error> {
-        let ValidatorOperationsRequest {
-            validators,
-            delegators,
-            accounts,
-            authz,
-            start_date,
-            end_date,
-        } = validator_operations_request;
-
-        let validators = validators.join(",");
-
-        let delegators = delegators.join(",");
-
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let url: String = format!(
-            "{}/operations?validators={}&authz={}&delegators={}&accounts={}&start_date={}&end_date={}",
-            self.base_url, validators, authz, delegators, accounts, start_date, end_date
-        );
+            let url = Query::new()
+                .list("validators", validators.iter().flatten())
+                .list("delegators", delegators.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .url(&format!("{}/reports", self.base_url));
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<StakingOperation>>>();
+            self.http.get_bytes("kiln.inj.get_reports", &url).await
+        }
 
-        data
-    }
+        pub async fn post_stake_tx(
+            &self,
+            transaction_stake_request: &InjectiveStakeTxRequest,
+        ) -> Result<ReturnedData<TxResponse>, KilnError> {
+            transaction_stake_request.check_addresses(PREFIX)?;
 
-    pub fn get_network_stats(
-        &self,
-    ) -> Result<ReturnedData<InjectiveNetworkStatsResponse>, ureq::Error> {
-        let url: String = format!("{}/network-stats", self.base_url);
+            let url: String = format!("{}/transaction/stake", self.base_url,);
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<InjectiveNetworkStatsResponse>>();
+            self.http
+                .post_tx("kiln.inj.post_stake_tx", &url, transaction_stake_request)
+                .await
+        }
 
-        data
-    }
+        pub async fn post_withdraw_rewards_tx(
+            &self,
+            withdraw_rewards_transaction_request: &WithdrawRewardsTxRequest,
+        ) -> Result<ReturnedData<TxResponse>, KilnError> {
+            withdraw_rewards_transaction_request.check_addresses(PREFIX)?;
+
+            let url: String = format!("{}/transaction/withdraw-rewards", self.base_url,);
+
+            self.http
+                .post_tx(
+                    "kiln.inj.post_withdraw_rewards_tx",
+                    &url,
+                    withdraw_rewards_transaction_request,
+                )
+                .await
+        }
 
-    pub fn get_reports(&self, reports_request: &ReportsRequest) -> Result<Vec<u8>, ureq::Error> {
-        let ReportsRequest {
-            delegators,
-            validators,
-            accounts,
-        } = reports_request;
+        pub async fn post_unstake_tx(
+            &self,
+            unstake_transaction_request: &InjectiveUnstakeTxRequest,
+        ) -> Result<ReturnedData<TxResponse>, KilnError> {
+            unstake_transaction_request.check_addresses(PREFIX)?;
+
+            let url: String = format!("{}/transaction/unstake", self.base_url,);
+
+            self.http
+                .post_tx(
+                    "kiln.inj.post_unstake_tx",
+                    &url,
+                    unstake_transaction_request,
+                )
+                .await
+        }
 
-        let delegators = delegators.join(",");
+        pub async fn post_redelegate_tx(
+            &self,
+            redelegate_transaction_request: &InjectiveRedelegateTxRequest,
+        ) -> Result<ReturnedData<TxResponse>, KilnError> {
+            redelegate_transaction_request.check_addresses(PREFIX)?;
+
+            let url: String = format!("{}/transaction/redelegate", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.inj.post_redelegate_tx",
+                    &url,
+                    redelegate_transaction_request,
+                )
+                .await
+        }
 
-        let validators = validators.join(",");
+        pub async fn post_prepare_tx(
+            &self,
+            prepare_transaction_request: &PrepareTxRequest,
+        ) -> Result<ReturnedData<PrepareTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/prepare", self.base_url,);
+
+            self.http
+                .post_tx(
+                    "kiln.inj.post_prepare_tx",
+                    &url,
+                    prepare_transaction_request,
+                )
+                .await
+        }
 
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
+        pub async fn post_broadcast_tx(
+            &self,
+            broadcast_transaction_request: &BroadcastTxRequest,
+        ) -> Result<ReturnedData<BroadcastTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/broadcast", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.inj.post_broadcast_tx",
+                    &url,
+                    broadcast_transaction_request,
+                )
+                .await
+        }
 
-        let url: String = format!(
-            "{}/reports?validators={}&delegators={}&accounts={}",
-            self.base_url, validators, delegators, accounts
-        );
+        pub async fn get_tx_status(
+            &self,
+            tx_hash: &str,
+        ) -> Result<ReturnedData<TxStatusResponse>, KilnError> {
+            let url = Query::new()
+                .value("tx_hash", tx_hash)
+                .url(&format!("{}/transaction/status", self.base_url));
 
-        let mut file_bytes: Vec<u8> = Vec::new();
+            self.http.get("kiln.inj.get_tx_status", &url).await
+        }
 
-        ureq::get(url)
-            .header("accept", "application/octet-stream")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .as_reader()
-            .read_to_end(&mut file_bytes)?;
+        pub async fn get_tx_decoding(
+            &self,
+            tx_serialized: &str,
+        ) -> Result<ReturnedData<TxDecodingResponse>, KilnError> {
+            let url = Query::new()
+                .value("tx_serialized", tx_serialized)
+                .url(&format!("{}/transaction/decode", self.base_url));
 
-        Ok(file_bytes)
+            self.http.get("kiln.inj.get_tx_decoding", &url).await
+        }
     }
+}
 
-    pub fn post_stake_tx(
-        &self,
-        transaction_stake_request: &InjectiveStakeTxRequest,
-    ) -> Result<ReturnedData<TxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/stake", self.base_url,);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(transaction_stake_request)?
-            .body_mut()
-            .read_json::<ReturnedData<TxResponse>>();
+impl<M: Mode> KilnInjectiveClient<M> {
+    pub fn stakes(&self) -> GetStakesBuilder<'_, Self> {
+        GetStakesBuilder::new(self)
+    }
 
-        data
+    pub fn rewards(&self) -> RewardsBuilder<'_, Self, NoStartDate> {
+        RewardsBuilder::new(self)
     }
 
-    pub fn post_withdraw_rewards_tx(
-        &self,
-        withdraw_rewards_transaction_request: &WithdrawRewardsTxRequest,
-    ) -> Result<ReturnedData<TxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/withdraw-rewards", self.base_url,);
+    pub fn operations(&self) -> ValidatorOperationsBuilder<'_, Self, NoStartDate> {
+        ValidatorOperationsBuilder::new(self)
+    }
+}
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(withdraw_rewards_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<TxResponse>>();
+for_each_mode! { <M>
+    impl GetStakesBuilder<'_, KilnInjectiveClient<M>> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<GetStakesResponse>>, KilnError> {
+            let client = self.client;
 
-        data
+            client.get_stakes(&self.into_request()).await
+        }
     }
+}
 
-    pub fn post_unstake_tx(
-        &self,
-        unstake_transaction_request: &InjectiveUnstakeTxRequest,
-    ) -> Result<ReturnedData<TxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/unstake", self.base_url,);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(unstake_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<TxResponse>>();
-        data
-    }
+for_each_mode! { <M>
+    impl RewardsBuilder<'_, KilnInjectiveClient<M>, StartDate> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<Reward>>, KilnError> {
+            let client = self.client;
 
-    pub fn post_redelegate_tx(
-        &self,
-        redelegate_transaction_request: &InjectiveRedelegateTxRequest,
-    ) -> Result<ReturnedData<TxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/redelegate", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(redelegate_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<TxResponse>>();
-        data
+            client.get_rewards(self.into_request()?).await
+        }
     }
+}
 
-    pub fn post_prepare_tx(
-        &self,
-        prepare_transaction_request: &PrepareTxRequest,
-    ) -> Result<ReturnedData<PrepareTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/prepare", self.base_url,);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(prepare_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PrepareTxResponse>>();
+for_each_mode! { <M>
+    impl ValidatorOperationsBuilder<'_, KilnInjectiveClient<M>, StartDate> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<StakingOperation>>, KilnError> {
+            let client = self.client;
 
-        data
+            client.get_operations(&self.into_request()).await
+        }
     }
+}
 
-    pub fn post_broadcast_tx(
-        &self,
-        broadcast_transaction_request: &BroadcastTxRequest,
-    ) -> Result<ReturnedData<BroadcastTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/broadcast", self.base_url);
+#[cfg(all(test, feature = "blocking"))]
+mod injective_test {
+
+    use super::*;
+    use crate::query::query_test_support::{date, fixture, requested_url};
+    use crate::sdk::ResponseFormat;
+
+    #[test]
+    fn get_stakes() {
+        let url = requested_url(|kiln| {
+            kiln.injective().get_stakes(&GetStakesRequest {
+                validators: Some(vec!["injvaloper1a".into(), "injvaloper1b".into()]),
+                accounts: Some(vec![Uuid::nil()]),
+                ..Default::default()
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/inj/stakes?validators=injvaloper1a,injvaloper1b&accounts=00000000-0000-0000-0000-000000000000"
+        );
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(broadcast_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<BroadcastTxResponse>>();
+    #[test]
+    fn get_rewards() {
+        let url = requested_url(|kiln| {
+            kiln.injective().get_rewards(RewardRequest::Daily {
+                stakes_addresses: None,
+                wallets: Some(vec!["inj1a".into()]),
+                pool_ids: None,
+                accounts: None,
+                format: ResponseFormat::Daily,
+                start_date: date("2024-01-01T00:00:00Z"),
+                end_date: Some(date("2024-01-31T00:00:00Z")),
+                include_usd: true,
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/inj/rewards?wallets=inj1a&format=daily&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z&include_usd=true"
+        );
+    }
 
-        data
+    #[test]
+    fn get_operations() {
+        let url = requested_url(|kiln| {
+            kiln.injective()
+                .get_operations(&ValidatorOperationsRequest {
+                    validators: None,
+                    delegators: Some(vec!["inj1a".into()]),
+                    authz: false,
+                    accounts: None,
+                    start_date: date("2024-01-01T00:00:00Z"),
+                    end_date: Some(date("2024-01-31T00:00:00Z")),
+                })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/inj/operations?authz=false&delegators=inj1a&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z"
+        );
     }
 
-    pub fn get_tx_status(
-        &self,
-        tx_hash: &str,
-    ) -> Result<ReturnedData<TxStatusResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/status?tx_hash={}", self.base_url, tx_hash);
+    #[test]
+    fn get_reports() {
+        let url = requested_url(|kiln| {
+            kiln.injective().get_reports(&ReportsRequest {
+                delegators: Some(vec!["inj1a".into(), "inj1b".into()]),
+                ..Default::default()
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/inj/reports?delegators=inj1a,inj1b"
+        );
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<TxStatusResponse>>();
+    #[test]
+    fn get_tx_status() {
+        let url = requested_url(|kiln| kiln.injective().get_tx_status("A1B2C3"));
 
-        data
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/inj/transaction/status?tx_hash=A1B2C3"
+        );
     }
 
-    pub fn get_tx_decoding(
-        &self,
-        tx_serialized: &str,
-    ) -> Result<ReturnedData<TxDecodingResponse>, ureq::Error> {
-        let url: String = format!(
-            "{}/transaction/decode?tx_serialized={}",
-            self.base_url, tx_serialized
+    #[test]
+    fn get_tx_decoding() {
+        let url = requested_url(|kiln| kiln.injective().get_tx_decoding("Cp8B+/=="));
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/inj/transaction/decode?tx_serialized=Cp8B%2B%2F%3D%3D"
         );
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<TxDecodingResponse>>();
+    #[test]
+    fn responses_match_the_fixtures() {
+        let stats: ReturnedData<InjectiveNetworkStatsResponse> =
+            fixture("injective/network_stats.json");
 
-        data
+        assert_eq!(stats.data.nb_validators, 109);
     }
 }
@@ -1,67 +1,78 @@
-use std::io::Read;
-
 use serde::{Deserialize, Serialize};
 use strum_macros::AsRefStr;
 use uuid::Uuid;
 
+use crate::address::{AddressError, Lenient, Ss58Address};
+use crate::amount::{Amount, FloatAmount, IntAmount};
+use crate::denomination::Planck;
+use crate::errors::KilnError;
+use crate::http::HttpClient;
+use crate::mode::{for_each_mode, Blocking, Mode};
+use crate::query::Query;
 use crate::{response_format::ReturnedData, Kiln};
 
+use super::builders::{filter, NoStartDate, StartDate};
 use super::{BroadcastTxRequest, BroadcastTxResponse, PrepareTxResponse};
 
-#[derive(Serialize, Deserialize, Debug)]
+// ss58 network of the kusama addresses
+const NETWORK: u16 = 2;
+
+#[derive(Serialize, Deserialize, Debug, Default)]
 #[serde(rename_all = "snake_case")]
 pub struct KusamaGetStakesRequest {
-    pub addresses: Vec<String>,
-    pub accounts: Vec<Uuid>,
+    pub addresses: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct KusamaStakesResponse {
     // Address
-    pub address: String,
+    pub address: Lenient<Ss58Address>,
     pub pool_id: u64,
-    pub active_balance: String,
-    pub unbonding_balance: String,
-    pub withdrawable_balance: String,
-    pub net_rewards: String,
-    pub gross_rewards: String,
-    pub withdrawable_rewards: String,
+    pub active_balance: Amount,
+    pub unbonding_balance: Amount,
+    pub withdrawable_balance: Amount,
+    pub net_rewards: Amount,
+    pub gross_rewards: Amount,
+    pub withdrawable_rewards: Amount,
     pub state: String,
     pub net_apy: f64,
     pub delegated_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
-    pub undelegated_at: chrono::DateTime<chrono::Utc>,
+    pub undelegated_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+// daily rewards have a `date`, era rewards an `era`
 #[derive(Serialize, Deserialize, Debug)]
-#[serde(rename_all = "snake_case")]
+#[serde(untagged)]
 pub enum KusamaReward {
     ByDay {
-        net_rewards: String,
-        gross_rewards: String,
+        net_rewards: Amount,
+        gross_rewards: Amount,
         date: chrono::DateTime<chrono::Utc>,
-        active_balance: String,
+        active_balance: Amount,
         net_apy: f64,
         gross_apy: f64,
-        active_balance_usd: u64,
-        net_rewards_usd: u64,
-        gross_rewards_usd: u64,
+        active_balance_usd: FloatAmount,
+        net_rewards_usd: FloatAmount,
+        gross_rewards_usd: FloatAmount,
     },
     ByEra {
-        net_rewards: String,
-        gross_rewards: String,
+        net_rewards: Amount,
+        gross_rewards: Amount,
         era: u64,
-        active_balance: String,
+        active_balance: Amount,
         net_apy: f64,
-        active_balance_usd: u64,
-        net_rewards_usd: u64,
-        gross_rewards_usd: u64,
+        active_balance_usd: FloatAmount,
+        net_rewards_usd: FloatAmount,
+        gross_rewards_usd: FloatAmount,
     },
 }
 
 #[derive(Serialize, Deserialize, AsRefStr, Default, Debug)]
 #[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
 #[non_exhaustive]
 pub enum KusamaResponseFormat {
     #[default]
@@ -72,12 +83,12 @@ pub enum KusamaResponseFormat {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct KusamaRewardRequest {
-    pub addresses: Vec<String>,
-    pub pool_ids: Vec<String>,
-    pub accounts: Vec<Uuid>,
+    pub addresses: Option<Vec<String>>,
+    pub pool_ids: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
     pub format: KusamaResponseFormat,
     pub start_date: chrono::DateTime<chrono::Utc>,
-    pub end_date: chrono::DateTime<chrono::Utc>,
+    pub end_date: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -89,35 +100,36 @@ pub struct KusamaOperation {
     pub tx_hash: String,
     pub extrinsic_seq_id: u64,
     pub event_seq_id: u64,
-    pub amount: String,
+    pub amount: Amount,
     // Address
-    pub address: String,
-    pub validator: String,
-    pub pool_id: String,
-    pub earned_era: String,
-    pub era: String,
+    pub address: Lenient<Ss58Address>,
+    // depending on the operation type
+    pub validator: Option<String>,
+    pub pool_id: Option<String>,
+    pub earned_era: Option<String>,
+    pub era: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct KusamaOperationsRequest {
-    pub addresses: Vec<String>,
-    pub accounts: Vec<Uuid>,
+    pub addresses: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
     pub start_date: chrono::DateTime<chrono::Utc>,
-    pub end_date: chrono::DateTime<chrono::Utc>,
+    pub end_date: Option<chrono::DateTime<chrono::Utc>>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 #[serde(rename_all = "snake_case")]
 pub struct KusamaReportsRequest {
-    pub addresses: Vec<String>,
-    pub accounts: Vec<Uuid>,
+    pub addresses: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct KusamaNetworkStatsResponse {
-    pub ksm_price_usd: f64,
+    pub ksm_price_usd: FloatAmount,
     pub nb_validators: u64,
     pub net_gross_apy: f64,
     pub supply_staked_percent: f64,
@@ -131,11 +143,12 @@ pub struct Payee {
     pub account: String,
 }
 
+// the arguments of the call : a `chill` has none
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct KusamaTxMethodArgs {
-    pub value: String,
-    pub payee: Payee,
+    pub value: Option<String>,
+    pub payee: Option<Payee>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -173,93 +186,169 @@ pub struct KusamaTxResponse {
 pub struct KusamaBondTxRequest {
     pub account_id: Uuid,
     // Address
-    pub stash_account: String,
-    pub amount_planck: String,
+    pub stash_account: Ss58Address,
+    pub amount_planck: Planck,
     pub reward_destination: String,
 }
 
+impl KusamaBondTxRequest {
+    pub(crate) fn check_addresses(&self, network: u16) -> Result<(), AddressError> {
+        self.stash_account.check_network(network)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct KusamaBondExtraTxRequest {
     // Address
-    pub stash_account: String,
-    pub amount_planck: String,
+    pub stash_account: Ss58Address,
+    pub amount_planck: Planck,
+}
+
+impl KusamaBondExtraTxRequest {
+    pub(crate) fn check_addresses(&self, network: u16) -> Result<(), AddressError> {
+        self.stash_account.check_network(network)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct KusamaRebondTxRequest {
     // Address
-    pub stash_account: String,
-    pub amount_planck: String,
+    pub stash_account: Ss58Address,
+    pub amount_planck: Planck,
+}
+
+impl KusamaRebondTxRequest {
+    pub(crate) fn check_addresses(&self, network: u16) -> Result<(), AddressError> {
+        self.stash_account.check_network(network)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct KusamaUnbondTxRequest {
     // Address
-    pub stash_account: String,
-    pub amount_planck: String,
+    pub stash_account: Ss58Address,
+    pub amount_planck: Planck,
+}
+
+impl KusamaUnbondTxRequest {
+    pub(crate) fn check_addresses(&self, network: u16) -> Result<(), AddressError> {
+        self.stash_account.check_network(network)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct KusamaNominateTxRequest {
     // Address
-    pub stash_account: String,
-    pub validator_addresses: Vec<String>,
+    pub stash_account: Ss58Address,
+    pub validator_addresses: Vec<Ss58Address>,
+}
+
+impl KusamaNominateTxRequest {
+    pub(crate) fn check_addresses(&self, network: u16) -> Result<(), AddressError> {
+        self.stash_account.check_network(network)?;
+        self.validator_addresses
+            .iter()
+            .try_for_each(|address| address.check_network(network))
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct KusamaWithdrawUnbondedTxRequest {
     // Address
-    pub stash_account: String,
+    pub stash_account: Ss58Address,
+}
+
+impl KusamaWithdrawUnbondedTxRequest {
+    pub(crate) fn check_addresses(&self, network: u16) -> Result<(), AddressError> {
+        self.stash_account.check_network(network)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct KusamaChillTxRequest {
     // Address
-    pub stash_account: String,
+    pub stash_account: Ss58Address,
+}
+
+impl KusamaChillTxRequest {
+    pub(crate) fn check_addresses(&self, network: u16) -> Result<(), AddressError> {
+        self.stash_account.check_network(network)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct KusamaSetPayeeTxRequest {
     // Address
-    pub stash_account: String,
+    pub stash_account: Ss58Address,
     // Address
-    pub reward_destination: String,
+    pub reward_destination: Ss58Address,
+}
+
+impl KusamaSetPayeeTxRequest {
+    pub(crate) fn check_addresses(&self, network: u16) -> Result<(), AddressError> {
+        self.stash_account.check_network(network)?;
+        self.reward_destination.check_network(network)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct KusamaJoinPoolTxRequest {
     pub account_id: Uuid,
-    pub member_account: String,
-    pub amount_planck: String,
+    pub member_account: Ss58Address,
+    pub amount_planck: Planck,
     pub pool_id: String,
 }
 
+impl KusamaJoinPoolTxRequest {
+    pub(crate) fn check_addresses(&self, network: u16) -> Result<(), AddressError> {
+        self.member_account.check_network(network)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct KusamaBondExtraPoolTxRequest {
-    pub member_account: String,
-    pub amount_planck: String,
+    pub member_account: Ss58Address,
+    pub amount_planck: Planck,
+}
+
+impl KusamaBondExtraPoolTxRequest {
+    pub(crate) fn check_addresses(&self, network: u16) -> Result<(), AddressError> {
+        self.member_account.check_network(network)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct KusamaPoolTxRequest {
-    pub member_account: String,
+    pub member_account: Ss58Address,
+}
+
+impl KusamaPoolTxRequest {
+    pub(crate) fn check_addresses(&self, network: u16) -> Result<(), AddressError> {
+        self.member_account.check_network(network)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct KusamaUnbondFromPoolTxRequest {
-    pub member_account: String,
-    pub amount_planck: String,
+    pub member_account: Ss58Address,
+    pub amount_planck: Planck,
+}
+
+impl KusamaUnbondFromPoolTxRequest {
+    pub(crate) fn check_addresses(&self, network: u16) -> Result<(), AddressError> {
+        self.member_account.check_network(network)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -308,7 +397,7 @@ pub struct KusamaTxStatusResponseDataLifetime {
 #[serde(rename_all = "snake_case")]
 pub struct KusamaTxStatusResponseDataAccountDisplay {
     // Address
-    pub address: String,
+    pub address: Lenient<Ss58Address>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -329,12 +418,12 @@ pub struct KusamaTxStatusResponseData {
     pub transfer: Option<String>,
     pub event: Vec<KusamaTxStatusResponseDataEvent>,
     pub event_count: u64,
-    pub fee: String,
-    pub fee_used: String,
-    pub error: KusamaTxStatusResponseDataError,
+    pub fee: Amount,
+    pub fee_used: Amount,
+    pub error: Option<KusamaTxStatusResponseDataError>,
     pub finalized: bool,
     pub lifetime: KusamaTxStatusResponseDataLifetime,
-    pub tip: u64,
+    pub tip: IntAmount,
     pub account_display: KusamaTxStatusResponseDataAccountDisplay,
     pub block_hash: String,
     pub pending: bool,
@@ -353,7 +442,7 @@ pub struct KusamaTxStatusResponse {
 #[serde(rename_all = "camelCase")]
 pub struct KusamaTxDecodingResponse {
     // Address
-    pub address: String,
+    pub address: Lenient<Ss58Address>,
     pub asset_id: u64,
     pub block_hash: String,
     pub block_number: u64,
@@ -364,7 +453,7 @@ pub struct KusamaTxDecodingResponse {
     pub nonce: String,
     pub signed_extensions: Vec<String>,
     pub spec_version: String,
-    pub tip: String,
+    pub tip: Amount,
     pub transaction_version: String,
     pub version: u64,
 }
@@ -377,472 +466,717 @@ pub struct KusamaPrepareTxRequest {
 }
 
 #[derive(Clone, Debug)]
-pub struct KilnKusamaClient {
-    pub bearer_token: String,
+pub struct KilnKusamaClient<M: Mode = Blocking> {
     pub base_url: String,
+    http: HttpClient<M>,
 }
 
-impl From<&Kiln> for KilnKusamaClient {
-    fn from(kiln: &Kiln) -> Self {
+impl<M: Mode> From<&Kiln<M>> for KilnKusamaClient<M> {
+    fn from(kiln: &Kiln<M>) -> Self {
         let url: String = format!("{}/ksm", kiln.base_url);
-        let bearer_token: String = format!("Bearer {}", kiln.api_token);
 
         Self {
-            bearer_token,
             base_url: url,
+            http: kiln.http.clone(),
         }
     }
 }
 
-impl KilnKusamaClient {
-    pub fn get_stakes(
-        &self,
-        stakes_request: &KusamaGetStakesRequest,
-    ) -> Result<ReturnedData<Vec<KusamaStakesResponse>>, ureq::Error> {
-        let KusamaGetStakesRequest {
-            addresses,
-            accounts,
-        } = stakes_request;
+for_each_mode! { <M>
+    impl KilnKusamaClient<M> {
+        pub async fn get_stakes(
+            &self,
+            stakes_request: &KusamaGetStakesRequest,
+        ) -> Result<ReturnedData<Vec<KusamaStakesResponse>>, KilnError> {
+            let KusamaGetStakesRequest {
+                addresses,
+                accounts,
+            } = stakes_request;
+
+            let url = Query::new()
+                .list("addresses", addresses.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .url(&format!("{}/stakes", self.base_url));
+
+            self.http.get("kiln.ksm.get_stakes", &url).await
+        }
 
-        let addresses = addresses.join(",");
+        pub async fn get_rewards(
+            &self,
+            reward_request: &KusamaRewardRequest,
+        ) -> Result<ReturnedData<Vec<KusamaReward>>, KilnError> {
+            let KusamaRewardRequest {
+                addresses,
+                accounts,
+                pool_ids,
+                format,
+                start_date,
+                end_date,
+            } = reward_request;
+
+            let url = Query::new()
+                .list("addresses", addresses.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .list("pool_ids", pool_ids.iter().flatten())
+                .value("format", format.as_ref())
+                .value("start_date", start_date)
+                .option("end_date", end_date.as_ref())
+                .url(&format!("{}/rewards", self.base_url));
+
+            self.http.get("kiln.ksm.get_rewards", &url).await
+        }
 
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
+        pub async fn get_operations(
+            &self,
+            operations_request: &KusamaOperationsRequest,
+        ) -> Result<ReturnedData<Vec<KusamaOperation>>, KilnError> {
+            let KusamaOperationsRequest {
+                addresses,
+                accounts,
+                start_date,
+                end_date,
+            } = operations_request;
+
+            let url = Query::new()
+                .list("addresses", addresses.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .value("start_date", start_date)
+                .option("end_date", end_date.as_ref())
+                .url(&format!("{}/operations", self.base_url));
+
+            self.http.get("kiln.ksm.get_operations", &url).await
+        }
 
-        let url: String = format!(
-            "{}/stakes?addresses={}&accounts={}",
-            self.base_url, addresses, accounts
-        );
+        pub async fn get_reports(
+            &self,
+            reports_request: &KusamaReportsRequest,
+        ) -> Result<Vec<u8>, KilnError> {
+            let KusamaReportsRequest {
+                addresses,
+                accounts,
+            } = reports_request;
+
+            let url = Query::new()
+                .list("addresses", addresses.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .url(&format!("{}/reports", self.base_url));
+
+            self.http.get_bytes("kiln.ksm.get_reports", &url).await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<KusamaStakesResponse>>>();
+        pub async fn get_network_stats(
+            &self,
+        ) -> Result<ReturnedData<KusamaNetworkStatsResponse>, KilnError> {
+            let url: String = format!("{}/network-stats", self.base_url);
 
-        data
-    }
+            self.http.get("kiln.ksm.get_network_stats", &url).await
+        }
 
-    pub fn get_rewards(
-        &self,
-        reward_request: &KusamaRewardRequest,
-    ) -> Result<ReturnedData<Vec<KusamaReward>>, ureq::Error> {
-        let KusamaRewardRequest {
-            addresses,
-            accounts,
-            pool_ids,
-            format,
-            start_date,
-            end_date,
-        } = reward_request;
+        pub async fn post_bond_tx(
+            &self,
+            bond_tx_request: &KusamaBondTxRequest,
+        ) -> Result<ReturnedData<KusamaTxResponse>, KilnError> {
+            bond_tx_request.check_addresses(NETWORK)?;
 
-        let addresses = addresses.join(",");
+            let url = format!("{}/transaction/bond", self.base_url);
 
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let pool_ids = pool_ids.join(",");
-
-        let url = format!(
-            "{}/rewards?addresses={}&accounts={}&pool_ids={}&format={}&start_date={}&end_date={}",
-            self.base_url,
-            addresses,
-            accounts,
-            pool_ids,
-            format.as_ref(),
-            start_date,
-            end_date
-        );
+            self.http
+                .post_tx("kiln.ksm.post_bond_tx", &url, bond_tx_request)
+                .await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<KusamaReward>>>();
+        pub async fn post_bond_extra_tx(
+            &self,
+            bond_extra_tx_request: &KusamaBondExtraTxRequest,
+        ) -> Result<ReturnedData<KusamaTxResponse>, KilnError> {
+            bond_extra_tx_request.check_addresses(NETWORK)?;
 
-        data
-    }
+            let url = format!("{}/transaction/bond-extra", self.base_url);
 
-    pub fn get_operations(
-        &self,
-        operations_request: &KusamaOperationsRequest,
-    ) -> Result<ReturnedData<Vec<KusamaOperation>>, ureq::Error> {
-        let KusamaOperationsRequest {
-            addresses,
-            accounts,
-            start_date,
-            end_date,
-        } = operations_request;
+            self.http
+                .post_tx("kiln.ksm.post_bond_extra_tx", &url, bond_extra_tx_request)
+                .await
+        }
 
-        let addresses = addresses.join(",");
+        pub async fn post_rebond_tx(
+            &self,
+            rebond_tx_request: &KusamaRebondTxRequest,
+        ) -> Result<ReturnedData<KusamaTxResponse>, KilnError> {
+            rebond_tx_request.check_addresses(NETWORK)?;
 
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
+            let url = format!("{}/transaction/rebond", self.base_url);
 
-        let url = format!(
-            "{}/operations?addresses={}&accounts={}&start_date={}&end_date={}",
-            self.base_url, addresses, accounts, start_date, end_date
-        );
+            self.http
+                .post_tx("kiln.ksm.post_rebond_tx", &url, rebond_tx_request)
+                .await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<KusamaOperation>>>();
+        pub async fn post_unbond_tx(
+            &self,
+            unbond_tx_request: &KusamaUnbondTxRequest,
+        ) -> Result<ReturnedData<KusamaTxResponse>, KilnError> {
+            unbond_tx_request.check_addresses(NETWORK)?;
 
-        data
-    }
+            let url = format!("{}/transaction/unbond", self.base_url);
 
-    pub fn get_reports(
-        &self,
-        reports_request: &KusamaReportsRequest,
-    ) -> Result<Vec<u8>, ureq::Error> {
-        let KusamaReportsRequest {
-            addresses,
-            accounts,
-        } = reports_request;
+            self.http
+                .post_tx("kiln.ksm.post_unbond_tx", &url, unbond_tx_request)
+                .await
+        }
 
-        let addresses = addresses.join(",");
+        pub async fn post_nominate_tx(
+            &self,
+            nominate_tx_request: &KusamaNominateTxRequest,
+        ) -> Result<ReturnedData<KusamaTxResponse>, KilnError> {
+            nominate_tx_request.check_addresses(NETWORK)?;
 
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
+            let url = format!("{}/transaction/nominate", self.base_url);
 
-        let url: String = format!(
-            "{}/reports?addresses={}&accounts={}",
-            self.base_url, addresses, accounts
-        );
+            self.http
+                .post_tx("kiln.ksm.post_nominate_tx", &url, nominate_tx_request)
+                .await
+        }
 
-        let mut file_bytes: Vec<u8> = Vec::new();
+        pub async fn post_withdraw_unbonded_tx(
+            &self,
+            withdraw_unbonded_tx_request: &KusamaWithdrawUnbondedTxRequest,
+        ) -> Result<ReturnedData<KusamaTxResponse>, KilnError> {
+            withdraw_unbonded_tx_request.check_addresses(NETWORK)?;
+
+            let url = format!("{}/transaction/withdraw-unbonded", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.ksm.post_withdraw_unbonded_tx",
+                    &url,
+                    withdraw_unbonded_tx_request,
+                )
+                .await
+        }
 
-        ureq::get(url)
-            .header("accept", "application/octet-stream")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .as_reader()
-            .read_to_end(&mut file_bytes)?;
+        pub async fn post_chill_tx(
+            &self,
+            chill_tx_request: &KusamaChillTxRequest,
+        ) -> Result<ReturnedData<KusamaTxResponse>, KilnError> {
+            chill_tx_request.check_addresses(NETWORK)?;
 
-        Ok(file_bytes)
-    }
+            let url = format!("{}/transaction/chill", self.base_url);
 
-    pub fn get_network_stats(
-        &self,
-    ) -> Result<ReturnedData<KusamaNetworkStatsResponse>, ureq::Error> {
-        let url: String = format!("{}/network-stats", self.base_url);
+            self.http
+                .post_tx("kiln.ksm.post_chill_tx", &url, chill_tx_request)
+                .await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<KusamaNetworkStatsResponse>>();
+        pub async fn post_set_payee_tx(
+            &self,
+            join_pool_tx_request: &KusamaSetPayeeTxRequest,
+        ) -> Result<ReturnedData<KusamaTxResponse>, KilnError> {
+            join_pool_tx_request.check_addresses(NETWORK)?;
 
-        data
-    }
+            let url = format!("{}/transaction/set-payee", self.base_url);
+
+            self.http
+                .post_tx("kiln.ksm.post_set_payee_tx", &url, join_pool_tx_request)
+                .await
+        }
 
-    pub fn post_bond_tx(
-        &self,
-        bond_tx_request: &KusamaBondTxRequest,
-    ) -> Result<ReturnedData<KusamaTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/bond", self.base_url);
+        pub async fn post_join_pool_tx(
+            &self,
+            join_pool_tx_request: &KusamaJoinPoolTxRequest,
+        ) -> Result<ReturnedData<KusamaTxResponse>, KilnError> {
+            join_pool_tx_request.check_addresses(NETWORK)?;
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(bond_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<KusamaTxResponse>>();
+            let url = format!("{}/transaction/join-pool", self.base_url);
 
-        data
-    }
+            self.http
+                .post_tx("kiln.ksm.post_join_pool_tx", &url, join_pool_tx_request)
+                .await
+        }
+
+        pub async fn post_bond_extra_pool_tx(
+            &self,
+            bond_extra_pool_tx_request: &KusamaBondExtraPoolTxRequest,
+        ) -> Result<ReturnedData<KusamaTxResponse>, KilnError> {
+            bond_extra_pool_tx_request.check_addresses(NETWORK)?;
+
+            let url = format!("{}/transaction/bond-extra-pool", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.ksm.post_bond_extra_pool_tx",
+                    &url,
+                    bond_extra_pool_tx_request,
+                )
+                .await
+        }
 
-    pub fn post_bond_extra_tx(
-        &self,
-        bond_extra_tx_request: &KusamaBondExtraTxRequest,
-    ) -> Result<ReturnedData<KusamaTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/bond-extra", self.base_url);
+        pub async fn post_bond_rewards_to_pool_tx(
+            &self,
+            bond_rewards_to_pool_tx_request: &KusamaPoolTxRequest,
+        ) -> Result<ReturnedData<KusamaTxResponse>, KilnError> {
+            bond_rewards_to_pool_tx_request.check_addresses(NETWORK)?;
+
+            let url = format!("{}/transaction/bond-rewards-pool", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.ksm.post_bond_rewards_to_pool_tx",
+                    &url,
+                    bond_rewards_to_pool_tx_request,
+                )
+                .await
+        }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(bond_extra_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<KusamaTxResponse>>();
+        pub async fn post_claim_payout_from_pool_tx(
+            &self,
+            claim_tx_request: &KusamaPoolTxRequest,
+        ) -> Result<ReturnedData<KusamaTxResponse>, KilnError> {
+            claim_tx_request.check_addresses(NETWORK)?;
+
+            let url = format!("{}/transaction/claim-payout-pool", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.ksm.post_claim_payout_from_pool_tx",
+                    &url,
+                    claim_tx_request,
+                )
+                .await
+        }
 
-        data
+        pub async fn post_unbond_from_pool_tx(
+            &self,
+            unbond_from_pool_tx_request: &KusamaUnbondFromPoolTxRequest,
+        ) -> Result<ReturnedData<KusamaTxResponse>, KilnError> {
+            unbond_from_pool_tx_request.check_addresses(NETWORK)?;
+
+            let url = format!("{}/transaction/unbond-pool", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.ksm.post_unbond_from_pool_tx",
+                    &url,
+                    unbond_from_pool_tx_request,
+                )
+                .await
+        }
+
+        pub async fn post_withdraw_unbonded_from_pool_tx(
+            &self,
+            pool_tx_request: &KusamaPoolTxRequest,
+        ) -> Result<ReturnedData<KusamaTxResponse>, KilnError> {
+            pool_tx_request.check_addresses(NETWORK)?;
+
+            let url = format!("{}/transaction/withdraw-unbonded-pool", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.ksm.post_withdraw_unbonded_from_pool_tx",
+                    &url,
+                    pool_tx_request,
+                )
+                .await
+        }
+
+        pub async fn get_tx_status(
+            &self,
+            tx_hash: &str,
+        ) -> Result<ReturnedData<KusamaTxStatusResponse>, KilnError> {
+            let url = Query::new()
+                .value("tx_hash", tx_hash)
+                .url(&format!("{}/transaction/status", self.base_url));
+
+            self.http.get("kiln.ksm.get_tx_status", &url).await
+        }
+
+        pub async fn get_tx_decoding(
+            &self,
+            tx_serialized: &str,
+        ) -> Result<ReturnedData<KusamaTxDecodingResponse>, KilnError> {
+            let url = Query::new()
+                .value("tx_serialized", tx_serialized)
+                .url(&format!("{}/transaction/decode", self.base_url));
+
+            self.http.get("kiln.ksm.get_tx_decoding", &url).await
+        }
+
+        pub async fn post_prepare_tx(
+            &self,
+            prepare_transaction_request: &KusamaPrepareTxRequest,
+        ) -> Result<ReturnedData<PrepareTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/prepare", self.base_url,);
+
+            self.http
+                .post_tx(
+                    "kiln.ksm.post_prepare_tx",
+                    &url,
+                    prepare_transaction_request,
+                )
+                .await
+        }
+
+        pub async fn post_broadcast_tx(
+            &self,
+            broadcast_transaction_request: &BroadcastTxRequest,
+        ) -> Result<ReturnedData<BroadcastTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/broadcast", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.ksm.post_broadcast_tx",
+                    &url,
+                    broadcast_transaction_request,
+                )
+                .await
+        }
     }
+}
 
-    pub fn post_rebond_tx(
-        &self,
-        rebond_tx_request: &KusamaRebondTxRequest,
-    ) -> Result<ReturnedData<KusamaTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/rebond", self.base_url);
+impl<M: Mode> KilnKusamaClient<M> {
+    pub fn stakes(&self) -> KusamaStakesBuilder<'_, M> {
+        KusamaStakesBuilder::new(self)
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(rebond_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<KusamaTxResponse>>();
+    pub fn rewards(&self) -> KusamaRewardsBuilder<'_, NoStartDate, M> {
+        KusamaRewardsBuilder::new(self)
+    }
 
-        data
+    pub fn operations(&self) -> KusamaOperationsBuilder<'_, NoStartDate, M> {
+        KusamaOperationsBuilder::new(self)
     }
+}
 
-    pub fn post_unbond_tx(
-        &self,
-        unbond_tx_request: &KusamaUnbondTxRequest,
-    ) -> Result<ReturnedData<KusamaTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/unbond", self.base_url);
+// `kiln.kusama().stakes()`
+#[derive(Debug)]
+pub struct KusamaStakesBuilder<'a, M: Mode = Blocking> {
+    client: &'a KilnKusamaClient<M>,
+    addresses: Option<Vec<String>>,
+    accounts: Option<Vec<Uuid>>,
+}
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(unbond_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<KusamaTxResponse>>();
+impl<'a, M: Mode> KusamaStakesBuilder<'a, M> {
+    fn new(client: &'a KilnKusamaClient<M>) -> Self {
+        Self {
+            client,
+            addresses: None,
+            accounts: None,
+        }
+    }
 
-        data
+    pub fn addresses(self, addresses: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            addresses: filter(addresses),
+            ..self
+        }
     }
 
-    pub fn post_nominate_tx(
-        &self,
-        nominate_tx_request: &KusamaNominateTxRequest,
-    ) -> Result<ReturnedData<KusamaTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/nominate", self.base_url);
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
+    }
+
+    pub fn into_request(self) -> KusamaGetStakesRequest {
+        KusamaGetStakesRequest {
+            addresses: self.addresses,
+            accounts: self.accounts,
+        }
+    }
+}
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(nominate_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<KusamaTxResponse>>();
+for_each_mode! { <M>
+    impl KusamaStakesBuilder<'_, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<KusamaStakesResponse>>, KilnError> {
+            let client = self.client;
 
-        data
+            client.get_stakes(&self.into_request()).await
+        }
     }
+}
+
+// `kiln.kusama().rewards()`
+#[derive(Debug)]
+pub struct KusamaRewardsBuilder<'a, S, M: Mode = Blocking> {
+    client: &'a KilnKusamaClient<M>,
+    addresses: Option<Vec<String>>,
+    pool_ids: Option<Vec<String>>,
+    accounts: Option<Vec<Uuid>>,
+    format: KusamaResponseFormat,
+    start_date: S,
+    end_date: Option<chrono::DateTime<chrono::Utc>>,
+}
 
-    pub fn post_withdraw_unbonded_tx(
-        &self,
-        withdraw_unbonded_tx_request: &KusamaWithdrawUnbondedTxRequest,
-    ) -> Result<ReturnedData<KusamaTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/withdraw-unbonded", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(withdraw_unbonded_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<KusamaTxResponse>>();
+impl<'a, M: Mode> KusamaRewardsBuilder<'a, NoStartDate, M> {
+    fn new(client: &'a KilnKusamaClient<M>) -> Self {
+        Self {
+            client,
+            addresses: None,
+            pool_ids: None,
+            accounts: None,
+            format: KusamaResponseFormat::default(),
+            start_date: NoStartDate,
+            end_date: None,
+        }
+    }
+}
 
-        data
+impl<'a, S, M: Mode> KusamaRewardsBuilder<'a, S, M> {
+    pub fn addresses(self, addresses: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            addresses: filter(addresses),
+            ..self
+        }
     }
 
-    pub fn post_chill_tx(
-        &self,
-        chill_tx_request: &KusamaChillTxRequest,
-    ) -> Result<ReturnedData<KusamaTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/chill", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(chill_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<KusamaTxResponse>>();
+    pub fn pool_ids(self, pool_ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            pool_ids: filter(pool_ids),
+            ..self
+        }
+    }
 
-        data
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
     }
 
-    pub fn post_set_payee_tx(
-        &self,
-        join_pool_tx_request: &KusamaSetPayeeTxRequest,
-    ) -> Result<ReturnedData<KusamaTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/set-payee", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(join_pool_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<KusamaTxResponse>>();
+    pub fn format(self, format: KusamaResponseFormat) -> Self {
+        Self { format, ..self }
+    }
 
-        data
+    pub fn until(self, end_date: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            end_date: Some(end_date),
+            ..self
+        }
     }
 
-    pub fn post_join_pool_tx(
-        &self,
-        join_pool_tx_request: &KusamaJoinPoolTxRequest,
-    ) -> Result<ReturnedData<KusamaTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/join-pool", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(join_pool_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<KusamaTxResponse>>();
+    pub fn since(
+        self,
+        start_date: chrono::DateTime<chrono::Utc>,
+    ) -> KusamaRewardsBuilder<'a, StartDate, M> {
+        KusamaRewardsBuilder {
+            client: self.client,
+            addresses: self.addresses,
+            pool_ids: self.pool_ids,
+            accounts: self.accounts,
+            format: self.format,
+            start_date: StartDate(start_date),
+            end_date: self.end_date,
+        }
+    }
+}
 
-        data
+impl<M: Mode> KusamaRewardsBuilder<'_, StartDate, M> {
+    pub fn into_request(self) -> KusamaRewardRequest {
+        KusamaRewardRequest {
+            addresses: self.addresses,
+            pool_ids: self.pool_ids,
+            accounts: self.accounts,
+            format: self.format,
+            start_date: self.start_date.0,
+            end_date: self.end_date,
+        }
     }
+}
 
-    pub fn post_bond_extra_pool_tx(
-        &self,
-        bond_extra_pool_tx_request: &KusamaBondExtraPoolTxRequest,
-    ) -> Result<ReturnedData<KusamaTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/bond-extra-pool", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(bond_extra_pool_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<KusamaTxResponse>>();
+for_each_mode! { <M>
+    impl KusamaRewardsBuilder<'_, StartDate, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<KusamaReward>>, KilnError> {
+            let client = self.client;
 
-        data
+            client.get_rewards(&self.into_request()).await
+        }
     }
+}
 
-    pub fn post_bond_rewards_to_pool_tx(
-        &self,
-        bond_rewards_to_pool_tx_request: &KusamaPoolTxRequest,
-    ) -> Result<ReturnedData<KusamaTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/bond-rewards-pool", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(bond_rewards_to_pool_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<KusamaTxResponse>>();
+// `kiln.kusama().operations()`
+#[derive(Debug)]
+pub struct KusamaOperationsBuilder<'a, S, M: Mode = Blocking> {
+    client: &'a KilnKusamaClient<M>,
+    addresses: Option<Vec<String>>,
+    accounts: Option<Vec<Uuid>>,
+    start_date: S,
+    end_date: Option<chrono::DateTime<chrono::Utc>>,
+}
 
-        data
+impl<'a, M: Mode> KusamaOperationsBuilder<'a, NoStartDate, M> {
+    fn new(client: &'a KilnKusamaClient<M>) -> Self {
+        Self {
+            client,
+            addresses: None,
+            accounts: None,
+            start_date: NoStartDate,
+            end_date: None,
+        }
     }
+}
 
-    pub fn post_claim_payout_from_pool_tx(
-        &self,
-        claim_tx_request: &KusamaPoolTxRequest,
-    ) -> Result<ReturnedData<KusamaTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/claim-payout-pool", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(claim_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<KusamaTxResponse>>();
+impl<'a, S, M: Mode> KusamaOperationsBuilder<'a, S, M> {
+    pub fn addresses(self, addresses: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            addresses: filter(addresses),
+            ..self
+        }
+    }
 
-        data
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
     }
 
-    pub fn post_unbond_from_pool_tx(
-        &self,
-        unbond_from_pool_tx_request: &KusamaUnbondFromPoolTxRequest,
-    ) -> Result<ReturnedData<KusamaTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/unbond-pool", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(unbond_from_pool_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<KusamaTxResponse>>();
+    pub fn until(self, end_date: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            end_date: Some(end_date),
+            ..self
+        }
+    }
+
+    pub fn since(
+        self,
+        start_date: chrono::DateTime<chrono::Utc>,
+    ) -> KusamaOperationsBuilder<'a, StartDate, M> {
+        KusamaOperationsBuilder {
+            client: self.client,
+            addresses: self.addresses,
+            accounts: self.accounts,
+            start_date: StartDate(start_date),
+            end_date: self.end_date,
+        }
+    }
+}
 
-        data
+impl<M: Mode> KusamaOperationsBuilder<'_, StartDate, M> {
+    pub fn into_request(self) -> KusamaOperationsRequest {
+        KusamaOperationsRequest {
+            addresses: self.addresses,
+            accounts: self.accounts,
+            start_date: self.start_date.0,
+            end_date: self.end_date,
+        }
     }
+}
 
-    pub fn post_withdraw_unbonded_from_pool_tx(
-        &self,
-        pool_tx_request: &KusamaPoolTxRequest,
-    ) -> Result<ReturnedData<KusamaTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/withdraw-unbonded-pool", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(pool_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<KusamaTxResponse>>();
+for_each_mode! { <M>
+    impl KusamaOperationsBuilder<'_, StartDate, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<KusamaOperation>>, KilnError> {
+            let client = self.client;
 
-        data
+            client.get_operations(&self.into_request()).await
+        }
     }
+}
 
-    pub fn get_tx_status(
-        &self,
-        tx_hash: &str,
-    ) -> Result<ReturnedData<KusamaTxStatusResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/status?tx_hash={}", self.base_url, tx_hash);
-
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<KusamaTxStatusResponse>>();
+#[cfg(all(test, feature = "blocking"))]
+mod kusama_test {
 
-        data
+    use super::*;
+    use crate::query::query_test_support::{date, fixture, requested_url};
+
+    #[test]
+    fn get_stakes() {
+        let url = requested_url(|kiln| {
+            kiln.kusama().get_stakes(&KusamaGetStakesRequest {
+                addresses: Some(vec!["Ha1".into(), "Ha1b".into()]),
+                ..Default::default()
+            })
+        });
+
+        assert_eq!(url, "https://api.kiln.fi/v1/ksm/stakes?addresses=Ha1,Ha1b");
     }
 
-    pub fn get_tx_decoding(
-        &self,
-        tx_serialized: &str,
-    ) -> Result<KusamaTxDecodingResponse, ureq::Error> {
-        let url: String = format!(
-            "{}/transaction/decode?tx_serialized={}",
-            self.base_url, tx_serialized
+    #[test]
+    fn get_rewards() {
+        let url = requested_url(|kiln| {
+            kiln.kusama().get_rewards(&KusamaRewardRequest {
+                addresses: Some(vec!["Ha1".into()]),
+                pool_ids: None,
+                accounts: None,
+                format: KusamaResponseFormat::Era,
+                start_date: date("2024-01-01T00:00:00Z"),
+                end_date: Some(date("2024-01-31T00:00:00Z")),
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/ksm/rewards?addresses=Ha1&format=era&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z"
         );
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<KusamaTxDecodingResponse>();
-
-        data
+    #[test]
+    fn get_operations() {
+        let url = requested_url(|kiln| {
+            kiln.kusama().get_operations(&KusamaOperationsRequest {
+                addresses: None,
+                accounts: Some(vec![Uuid::nil()]),
+                start_date: date("2024-01-01T00:00:00Z"),
+                end_date: Some(date("2024-01-31T00:00:00Z")),
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/ksm/operations?accounts=00000000-0000-0000-0000-000000000000&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z"
+        );
     }
 
-    pub fn post_prepare_tx(
-        &self,
-        prepare_transaction_request: &KusamaPrepareTxRequest,
-    ) -> Result<ReturnedData<PrepareTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/prepare", self.base_url,);
+    #[test]
+    fn get_reports() {
+        let url = requested_url(|kiln| {
+            kiln.kusama().get_reports(&KusamaReportsRequest {
+                addresses: Some(vec!["Ha1".into()]),
+                ..Default::default()
+            })
+        });
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(prepare_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PrepareTxResponse>>();
+        assert_eq!(url, "https://api.kiln.fi/v1/ksm/reports?addresses=Ha1");
+    }
+
+    #[test]
+    fn get_tx_status() {
+        let url = requested_url(|kiln| kiln.kusama().get_tx_status("0xhash"));
 
-        data
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/ksm/transaction/status?tx_hash=0xhash"
+        );
     }
 
-    pub fn post_broadcast_tx(
-        &self,
-        broadcast_transaction_request: &BroadcastTxRequest,
-    ) -> Result<ReturnedData<BroadcastTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/broadcast", self.base_url);
+    #[test]
+    fn get_tx_decoding() {
+        let url = requested_url(|kiln| kiln.kusama().get_tx_decoding("ab+/="));
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(broadcast_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<BroadcastTxResponse>>();
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/ksm/transaction/decode?tx_serialized=ab%2B%2F%3D"
+        );
+    }
 
-        data
+    #[test]
+    fn responses_match_the_fixtures() {
+        let rewards: ReturnedData<Vec<KusamaReward>> = fixture("kusama/rewards.json");
+        let chill: ReturnedData<KusamaTxResponse> = fixture("kusama/chill_tx.json");
+
+        assert!(matches!(rewards.data[0], KusamaReward::ByDay { .. }));
+        assert!(matches!(
+            rewards.data[1],
+            KusamaReward::ByEra { era: 1412, .. }
+        ));
+        assert!(chill.data.unsigned_tx.method.args.payee.is_none());
+
+        fixture::<ReturnedData<Vec<KusamaStakesResponse>>>("kusama/stakes.json");
+        fixture::<ReturnedData<Vec<KusamaOperation>>>("kusama/operations.json");
+        fixture::<ReturnedData<KusamaNetworkStatsResponse>>("kusama/network_stats.json");
+        fixture::<ReturnedData<KusamaTxResponse>>("kusama/tx.json");
+        fixture::<ReturnedData<KusamaTxStatusResponse>>("kusama/tx_status.json");
+        fixture::<ReturnedData<KusamaTxDecodingResponse>>("kusama/tx_decoding.json");
+        fixture::<ReturnedData<PrepareTxResponse>>("kusama/prepare_tx.json");
+        fixture::<ReturnedData<BroadcastTxResponse>>("kusama/broadcast_tx.json");
     }
 }
@@ -1,4 +1,11 @@
+#[macro_use]
+mod string_enum;
+
+#[macro_use]
+mod operation_enum;
+
 pub mod account;
+mod builders;
 pub mod cardano;
 pub mod celestia;
 pub mod cosmos;
@@ -24,4 +31,5 @@ pub mod tezos;
 mod types;
 pub mod zetachain;
 
+pub use builders::*;
 pub use types::*;
@@ -1,19 +1,23 @@
-use std::io::Read;
-
 use serde::{Deserialize, Serialize};
-use strum_macros::EnumString;
 use uuid::Uuid;
 
+use crate::address::{ErdAddress, Lenient};
+use crate::amount::{Amount, FloatAmount};
+use crate::errors::KilnError;
+use crate::http::HttpClient;
+use crate::mode::{for_each_mode, Blocking, Mode};
+use crate::query::Query;
 use crate::{response_format::ReturnedData, Kiln};
 
+use super::builders::{filter, NoStartDate, StartDate};
 use super::ReportsRequest;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 #[serde(rename_all = "snake_case")]
 pub struct MultiversxGetStakesRequest {
-    pub wallets: Vec<String>,
-    pub accounts: Vec<Uuid>,
-    pub validators: Vec<String>,
+    pub wallets: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
+    pub validators: Option<Vec<String>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -24,10 +28,11 @@ pub struct MultiversxStakesResponse {
     pub state: String,
     pub delegated_at: chrono::DateTime<chrono::Utc>,
     pub delegated_epoch: u64,
-    pub undelegated_at: chrono::DateTime<chrono::Utc>,
-    pub undelegated_epoch: u64,
-    pub balance: String,
-    pub rewards: String,
+    // `None` while the stake is delegated
+    pub undelegated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub undelegated_epoch: Option<u64>,
+    pub balance: Amount,
+    pub rewards: Amount,
     pub grr: f64,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
@@ -35,28 +40,28 @@ pub struct MultiversxStakesResponse {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct MultiversxRewardRequest {
-    pub wallets: Vec<String>,
-    pub accounts: Vec<Uuid>,
-    pub validators: Vec<String>,
+    pub wallets: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
+    pub validators: Option<Vec<String>>,
     pub start_date: chrono::DateTime<chrono::Utc>,
-    pub end_date: chrono::DateTime<chrono::Utc>,
+    pub end_date: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct MultiversxReward {
     pub date: chrono::DateTime<chrono::Utc>,
-    pub rewards: String,
-    pub active_balance: String,
+    pub rewards: Amount,
+    pub active_balance: Amount,
     pub grr: f64,
-    pub rewards_usd: u64,
-    pub active_balance_usd: u64,
+    pub rewards_usd: FloatAmount,
+    pub active_balance_usd: FloatAmount,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct MultiversxNetworkStatsResponse {
-    pub egld_price_usd: f64,
+    pub egld_price_usd: FloatAmount,
     pub nb_validators: u64,
     pub net_gross_apy: f64,
     pub supply_staked_percent: f64,
@@ -67,250 +72,536 @@ pub struct MultiversxNetworkStatsResponse {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct MultiversxOperationsRequest {
-    pub wallets: Vec<String>,
-    pub accounts: Vec<Uuid>,
-    pub validators: Vec<String>,
+    pub wallets: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
+    pub validators: Option<Vec<String>>,
     pub start_date: chrono::DateTime<chrono::Utc>,
-    pub end_date: chrono::DateTime<chrono::Utc>,
+    pub end_date: Option<chrono::DateTime<chrono::Utc>>,
 }
 
-#[derive(Serialize, Deserialize, EnumString, Debug)]
-pub enum MultiversxOperationType {
-    #[strum(serialize = "addNodes")]
-    AddNodes,
-    #[strum(serialize = "changeOwner")]
-    ChangeOwner,
-    #[strum(serialize = "changeServiceFee")]
-    ChangeServiceFee,
-    #[strum(serialize = "getAllNodeStates")]
-    GetAllNodeStates,
-    #[strum(serialize = "modifyTotalDelegationCap")]
-    ModifyTotalDelegationCap,
-    #[strum(serialize = "removeNodes")]
-    RemoveNodes,
-    #[strum(serialize = "reStakeUnstakedNodes")]
-    RestakeUnstakedNodes,
-    #[strum(serialize = "setAutomaticActivation")]
-    SetAutomaticActivation,
-    #[strum(serialize = "setCheckCapOnReDelegateRewards")]
-    SetCheckCapOnReDelegateRewards,
-    #[strum(serialize = "setMetaData")]
-    SetMetaData,
-    #[strum(serialize = "stakeNodes")]
-    StakeNodes,
-    #[strum(serialize = "synchronizeOwner")]
-    SynchronizeOwner,
-    #[strum(serialize = "unBondNodes")]
-    UnBondNodes,
-    #[strum(serialize = "unJailNodes")]
-    UnJailNodes,
-    #[strum(serialize = "delegate")]
-    Delegate,
-    #[strum(serialize = "unStakeNodes")]
-    UnStakeNodes,
-    #[strum(serialize = "whitelistForMerge")]
-    WhitelistForMerge,
-    #[strum(serialize = "undelegate")]
-    UnDelegate,
-    #[strum(serialize = "claimRewards")]
-    ClaimRewards,
-    #[strum(serialize = "reDelegateRewards")]
-    RedelegateRewards,
-    #[strum(serialize = "reward")]
-    Reward,
-    #[strum(serialize = "withdraw")]
-    Withdraw,
+string_enum! {
+    pub enum MultiversxOperationType {
+        AddNodes => "addNodes",
+        ChangeOwner => "changeOwner",
+        ChangeServiceFee => "changeServiceFee",
+        GetAllNodeStates => "getAllNodeStates",
+        ModifyTotalDelegationCap => "modifyTotalDelegationCap",
+        RemoveNodes => "removeNodes",
+        RestakeUnstakedNodes => "reStakeUnstakedNodes",
+        SetAutomaticActivation => "setAutomaticActivation",
+        SetCheckCapOnReDelegateRewards => "setCheckCapOnReDelegateRewards",
+        SetMetaData => "setMetaData",
+        StakeNodes => "stakeNodes",
+        SynchronizeOwner => "synchronizeOwner",
+        UnBondNodes => "unBondNodes",
+        UnJailNodes => "unJailNodes",
+        Delegate => "delegate",
+        UnStakeNodes => "unStakeNodes",
+        WhitelistForMerge => "whitelistForMerge",
+        UnDelegate => "undelegate",
+        ClaimRewards => "claimRewards",
+        RedelegateRewards => "reDelegateRewards",
+        Reward => "reward",
+        Withdraw => "withdraw",
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct MultiversxOperation {
     // Address (EGLD address)
-    pub sender: String,
+    pub sender: Lenient<ErdAddress>,
     // Address (EGLD address)
-    pub receiver: String,
+    pub receiver: Lenient<ErdAddress>,
     pub tx_hash: String,
     pub tx_timestamp: chrono::DateTime<chrono::Utc>,
-    pub amount: String,
+    pub amount: Amount,
     pub gas_used: String,
     pub r#type: MultiversxOperationType,
 }
 
 #[derive(Clone, Debug)]
-pub struct KilnMultiversxClient {
-    pub bearer_token: String,
+pub struct KilnMultiversxClient<M: Mode = Blocking> {
     pub base_url: String,
+    http: HttpClient<M>,
 }
 
-impl From<&Kiln> for KilnMultiversxClient {
-    fn from(kiln: &Kiln) -> Self {
+impl<M: Mode> From<&Kiln<M>> for KilnMultiversxClient<M> {
+    fn from(kiln: &Kiln<M>) -> Self {
         let url: String = format!("{}/egld", kiln.base_url);
-        let bearer_token: String = format!("Bearer {}", kiln.api_token);
 
         Self {
-            bearer_token,
             base_url: url,
+            http: kiln.http.clone(),
         }
     }
 }
 
-impl KilnMultiversxClient {
-    pub fn get_stakes(
-        &self,
-        stakes_request: &MultiversxGetStakesRequest,
-    ) -> Result<ReturnedData<Vec<MultiversxStakesResponse>>, ureq::Error> {
-        let MultiversxGetStakesRequest {
-            wallets,
-            accounts,
-            validators,
-        } = stakes_request;
-
-        let validators = validators.join(",");
-
-        let wallets = wallets.join(",");
-
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let url: String = format!(
-            "{}/stakes?validators={}&delegators={}&accounts={}",
-            self.base_url, validators, wallets, accounts
-        );
+for_each_mode! { <M>
+    impl KilnMultiversxClient<M> {
+        pub async fn get_stakes(
+            &self,
+            stakes_request: &MultiversxGetStakesRequest,
+        ) -> Result<ReturnedData<Vec<MultiversxStakesResponse>>, KilnError> {
+            let MultiversxGetStakesRequest {
+                wallets,
+                accounts,
+                validators,
+            } = stakes_request;
+
+            let url = Query::new()
+                .list("validators", validators.iter().flatten())
+                .list("delegators", wallets.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .url(&format!("{}/stakes", self.base_url));
+
+            self.http.get("kiln.egld.get_stakes", &url).await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<MultiversxStakesResponse>>>();
-
-        data
-    }
-
-    pub fn get_rewards(
-        &self,
-        reward_request: &MultiversxRewardRequest,
-    ) -> Result<ReturnedData<Vec<MultiversxReward>>, ureq::Error> {
-        let MultiversxRewardRequest {
-            validators,
-            wallets,
-            accounts,
-            start_date,
-            end_date,
-        } = reward_request;
-
-        let validators = validators.join(",");
-
-        let wallets = wallets.join(",");
-
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let url: String = format!(
-            "{}/rewards?validators={}&delegators={}&accounts={}&start_date={}&end_date={}",
-            self.base_url, validators, wallets, accounts, start_date, end_date
-        );
+        pub async fn get_rewards(
+            &self,
+            reward_request: &MultiversxRewardRequest,
+        ) -> Result<ReturnedData<Vec<MultiversxReward>>, KilnError> {
+            let MultiversxRewardRequest {
+                validators,
+                wallets,
+                accounts,
+                start_date,
+                end_date,
+            } = reward_request;
+
+            let url = Query::new()
+                .list("validators", validators.iter().flatten())
+                .list("delegators", wallets.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .value("start_date", start_date)
+                .option("end_date", end_date.as_ref())
+                .url(&format!("{}/rewards", self.base_url));
+
+            self.http.get("kiln.egld.get_rewards", &url).await
+        }
+
+        pub async fn get_network_stats(
+            &self,
+        ) -> Result<ReturnedData<MultiversxNetworkStatsResponse>, KilnError> {
+            let url: String = format!("{}/network-stats", self.base_url);
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<MultiversxReward>>>();
+            self.http.get("kiln.egld.get_network_stats", &url).await
+        }
+
+        pub async fn get_operations(
+            &self,
+            validator_operations_request: &MultiversxOperationsRequest,
+        ) -> Result<ReturnedData<Vec<MultiversxOperation>>, KilnError> {
+            let MultiversxOperationsRequest {
+                wallets,
+                validators,
+                accounts,
+                start_date,
+                end_date,
+            } = validator_operations_request;
+
+            let url = Query::new()
+                .list("wallets", wallets.iter().flatten())
+                .list("validators", validators.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .value("start_date", start_date)
+                .option("end_date", end_date.as_ref())
+                .url(&format!("{}/operations", self.base_url));
+
+            self.http.get("kiln.egld.get_operations", &url).await
+        }
 
-        data
+        pub async fn get_reports(
+            &self,
+            reports_request: &ReportsRequest,
+        ) -> Result<Vec<u8>, KilnError> {
+            let ReportsRequest {
+                delegators,
+                validators,
+                accounts,
+            } = reports_request;
+
+            let url = Query::new()
+                .list("validators", validators.iter().flatten())
+                .list("delegators", delegators.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .url(&format!("{}/reports", self.base_url));
+
+            self.http.get_bytes("kiln.egld.get_reports", &url).await
+        }
     }
+}
 
-    pub fn get_network_stats(
-        &self,
-    ) -> Result<ReturnedData<MultiversxNetworkStatsResponse>, ureq::Error> {
-        let url: String = format!("{}/network-stats", self.base_url);
+impl<M: Mode> KilnMultiversxClient<M> {
+    pub fn stakes(&self) -> MultiversxStakesBuilder<'_, M> {
+        MultiversxStakesBuilder::new(self)
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<MultiversxNetworkStatsResponse>>();
+    pub fn rewards(&self) -> MultiversxRewardsBuilder<'_, NoStartDate, M> {
+        MultiversxRewardsBuilder::new(self)
+    }
 
-        data
+    pub fn operations(&self) -> MultiversxOperationsBuilder<'_, NoStartDate, M> {
+        MultiversxOperationsBuilder::new(self)
     }
+}
 
-    pub fn get_operations(
-        &self,
-        validator_operations_request: &MultiversxOperationsRequest,
-    ) -> Result<ReturnedData<Vec<MultiversxOperation>>, ureq::Error> {
-        let MultiversxOperationsRequest {
-            wallets,
-            validators,
-            accounts,
-            start_date,
-            end_date,
-        } = validator_operations_request;
+// `kiln.multiversx().stakes()`
+#[derive(Debug)]
+pub struct MultiversxStakesBuilder<'a, M: Mode = Blocking> {
+    client: &'a KilnMultiversxClient<M>,
+    wallets: Option<Vec<String>>,
+    accounts: Option<Vec<Uuid>>,
+    validators: Option<Vec<String>>,
+}
 
-        let wallets = wallets.join(",");
+impl<'a, M: Mode> MultiversxStakesBuilder<'a, M> {
+    fn new(client: &'a KilnMultiversxClient<M>) -> Self {
+        Self {
+            client,
+            wallets: None,
+            accounts: None,
+            validators: None,
+        }
+    }
 
-        let validators = validators.join(",");
+    pub fn wallets(self, wallets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            wallets: filter(wallets),
+            ..self
+        }
+    }
 
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
+    }
 
-        let url: String = format!(
-            "{}/operations?wallets={}&validators={}&accounts={}&start_date={}&end_date={}",
-            self.base_url, wallets, validators, accounts, start_date, end_date
-        );
+    pub fn validators(self, validators: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            validators: filter(validators),
+            ..self
+        }
+    }
+
+    pub fn into_request(self) -> MultiversxGetStakesRequest {
+        MultiversxGetStakesRequest {
+            wallets: self.wallets,
+            accounts: self.accounts,
+            validators: self.validators,
+        }
+    }
+}
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<MultiversxOperation>>>();
+for_each_mode! { <M>
+    impl MultiversxStakesBuilder<'_, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<MultiversxStakesResponse>>, KilnError> {
+            let client = self.client;
 
-        data
+            client.get_stakes(&self.into_request()).await
+        }
     }
+}
 
-    pub fn get_reports(&self, reports_request: &ReportsRequest) -> Result<Vec<u8>, ureq::Error> {
-        let ReportsRequest {
-            delegators,
-            validators,
-            accounts,
-        } = reports_request;
+// `kiln.multiversx().rewards()`
+#[derive(Debug)]
+pub struct MultiversxRewardsBuilder<'a, S, M: Mode = Blocking> {
+    client: &'a KilnMultiversxClient<M>,
+    wallets: Option<Vec<String>>,
+    accounts: Option<Vec<Uuid>>,
+    validators: Option<Vec<String>>,
+    start_date: S,
+    end_date: Option<chrono::DateTime<chrono::Utc>>,
+}
 
-        let delegators = delegators.join(",");
+impl<'a, M: Mode> MultiversxRewardsBuilder<'a, NoStartDate, M> {
+    fn new(client: &'a KilnMultiversxClient<M>) -> Self {
+        Self {
+            client,
+            wallets: None,
+            accounts: None,
+            validators: None,
+            start_date: NoStartDate,
+            end_date: None,
+        }
+    }
+}
 
-        let validators = validators.join(",");
+impl<'a, S, M: Mode> MultiversxRewardsBuilder<'a, S, M> {
+    pub fn wallets(self, wallets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            wallets: filter(wallets),
+            ..self
+        }
+    }
 
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
+    }
 
-        let url: String = format!(
-            "{}/reports?validators={}&delegators={}&accounts={}",
-            self.base_url, validators, delegators, accounts
+    pub fn validators(self, validators: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            validators: filter(validators),
+            ..self
+        }
+    }
+
+    pub fn until(self, end_date: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            end_date: Some(end_date),
+            ..self
+        }
+    }
+
+    pub fn since(
+        self,
+        start_date: chrono::DateTime<chrono::Utc>,
+    ) -> MultiversxRewardsBuilder<'a, StartDate, M> {
+        MultiversxRewardsBuilder {
+            client: self.client,
+            wallets: self.wallets,
+            accounts: self.accounts,
+            validators: self.validators,
+            start_date: StartDate(start_date),
+            end_date: self.end_date,
+        }
+    }
+}
+
+impl<M: Mode> MultiversxRewardsBuilder<'_, StartDate, M> {
+    pub fn into_request(self) -> MultiversxRewardRequest {
+        MultiversxRewardRequest {
+            wallets: self.wallets,
+            accounts: self.accounts,
+            validators: self.validators,
+            start_date: self.start_date.0,
+            end_date: self.end_date,
+        }
+    }
+}
+
+for_each_mode! { <M>
+    impl MultiversxRewardsBuilder<'_, StartDate, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<MultiversxReward>>, KilnError> {
+            let client = self.client;
+
+            client.get_rewards(&self.into_request()).await
+        }
+    }
+}
+
+// `kiln.multiversx().operations()`
+#[derive(Debug)]
+pub struct MultiversxOperationsBuilder<'a, S, M: Mode = Blocking> {
+    client: &'a KilnMultiversxClient<M>,
+    wallets: Option<Vec<String>>,
+    accounts: Option<Vec<Uuid>>,
+    validators: Option<Vec<String>>,
+    start_date: S,
+    end_date: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl<'a, M: Mode> MultiversxOperationsBuilder<'a, NoStartDate, M> {
+    fn new(client: &'a KilnMultiversxClient<M>) -> Self {
+        Self {
+            client,
+            wallets: None,
+            accounts: None,
+            validators: None,
+            start_date: NoStartDate,
+            end_date: None,
+        }
+    }
+}
+
+impl<'a, S, M: Mode> MultiversxOperationsBuilder<'a, S, M> {
+    pub fn wallets(self, wallets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            wallets: filter(wallets),
+            ..self
+        }
+    }
+
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
+    }
+
+    pub fn validators(self, validators: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            validators: filter(validators),
+            ..self
+        }
+    }
+
+    pub fn until(self, end_date: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            end_date: Some(end_date),
+            ..self
+        }
+    }
+
+    pub fn since(
+        self,
+        start_date: chrono::DateTime<chrono::Utc>,
+    ) -> MultiversxOperationsBuilder<'a, StartDate, M> {
+        MultiversxOperationsBuilder {
+            client: self.client,
+            wallets: self.wallets,
+            accounts: self.accounts,
+            validators: self.validators,
+            start_date: StartDate(start_date),
+            end_date: self.end_date,
+        }
+    }
+}
+
+impl<M: Mode> MultiversxOperationsBuilder<'_, StartDate, M> {
+    pub fn into_request(self) -> MultiversxOperationsRequest {
+        MultiversxOperationsRequest {
+            wallets: self.wallets,
+            accounts: self.accounts,
+            validators: self.validators,
+            start_date: self.start_date.0,
+            end_date: self.end_date,
+        }
+    }
+}
+
+for_each_mode! { <M>
+    impl MultiversxOperationsBuilder<'_, StartDate, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<MultiversxOperation>>, KilnError> {
+            let client = self.client;
+
+            client.get_operations(&self.into_request()).await
+        }
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod multiversx_test {
+
+    use super::*;
+    use crate::query::query_test_support::{date, fixture, requested_url};
+
+    #[test]
+    fn unknown_operation_type_still_deserializes() {
+        let operation: MultiversxOperation = serde_json::from_str(
+            r#"{
+                "sender": "erd1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5z5tpwxqergd3c8g7rusq4707q5",
+                "receiver": "erd1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5z5tpwxqergd3c8g7rusq4707q5",
+                "tx_hash": "0xhash",
+                "tx_timestamp": "2024-01-01T00:00:00Z",
+                "amount": "1000",
+                "gas_used": "50000",
+                "type": "stakeAndDelegate"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            operation.r#type,
+            MultiversxOperationType::Unknown("stakeAndDelegate".into())
+        );
+    }
+
+    #[test]
+    fn get_stakes() {
+        let url = requested_url(|kiln| {
+            kiln.multiversx().get_stakes(&MultiversxGetStakesRequest {
+                wallets: Some(vec!["erd1a".into()]),
+                ..Default::default()
+            })
+        });
+
+        assert_eq!(url, "https://api.kiln.fi/v1/egld/stakes?delegators=erd1a");
+    }
+
+    #[test]
+    fn get_rewards() {
+        let url = requested_url(|kiln| {
+            kiln.multiversx().get_rewards(&MultiversxRewardRequest {
+                wallets: None,
+                accounts: None,
+                validators: Some(vec!["erd1v".into()]),
+                start_date: date("2024-01-01T00:00:00Z"),
+                end_date: Some(date("2024-01-31T00:00:00Z")),
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/egld/rewards?validators=erd1v&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z"
+        );
+    }
+
+    #[test]
+    fn get_operations() {
+        let url = requested_url(|kiln| {
+            kiln.multiversx()
+                .get_operations(&MultiversxOperationsRequest {
+                    wallets: Some(vec!["erd1a".into()]),
+                    accounts: None,
+                    validators: None,
+                    start_date: date("2024-01-01T00:00:00Z"),
+                    end_date: Some(date("2024-01-31T00:00:00Z")),
+                })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/egld/operations?wallets=erd1a&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z"
         );
+    }
 
-        let mut file_bytes: Vec<u8> = Vec::new();
+    #[test]
+    fn get_reports() {
+        let url = requested_url(|kiln| {
+            kiln.multiversx().get_reports(&ReportsRequest {
+                delegators: Some(vec!["erd1a".into()]),
+                ..Default::default()
+            })
+        });
+
+        assert_eq!(url, "https://api.kiln.fi/v1/egld/reports?delegators=erd1a");
+    }
 
-        ureq::get(url)
-            .header("accept", "application/octet-stream")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .as_reader()
-            .read_to_end(&mut file_bytes)?;
+    #[test]
+    fn responses_match_the_fixtures() {
+        let stakes: ReturnedData<Vec<MultiversxStakesResponse>> = fixture("multiversx/stakes.json");
+        let operations: ReturnedData<Vec<MultiversxOperation>> =
+            fixture("multiversx/operations.json");
 
-        Ok(file_bytes)
+        assert!(stakes.data[0].undelegated_at.is_none());
+        assert_eq!(stakes.data[1].undelegated_epoch, Some(1412));
+        assert_eq!(operations.data[0].r#type, MultiversxOperationType::Delegate);
+
+        fixture::<ReturnedData<Vec<MultiversxReward>>>("multiversx/rewards.json");
+        fixture::<ReturnedData<MultiversxNetworkStatsResponse>>("multiversx/network_stats.json");
+    }
+
+    #[test]
+    fn operations_keep_the_addresses_as_the_api_sends_them() {
+        let mut operations: serde_json::Value = fixture("multiversx/operations.json");
+
+        // the protocol itself (rewards, fees...) has no erd1 address
+        operations["data"][0]["sender"] = "metachain".into();
+
+        let operations: ReturnedData<Vec<MultiversxOperation>> =
+            serde_json::from_value(operations).unwrap();
+
+        assert_eq!(
+            operations.data[0].sender,
+            Lenient::Unchecked("metachain".into())
+        );
+        assert!(operations.data[0].receiver.valid().is_some());
     }
 }
@@ -1,22 +1,28 @@
-use std::io::Read;
-
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::address::NearAccountId;
+use crate::amount::{Amount, FloatAmount};
+use crate::denomination::YoctoNear;
+use crate::errors::KilnError;
+use crate::http::HttpClient;
+use crate::mode::{for_each_mode, Blocking, Mode};
+use crate::query::Query;
 use crate::{response_format::ReturnedData, Kiln};
 
+use super::builders::{filter, NoStartDate, RewardsBuilder, StartDate};
 use super::{
     BroadcastTxResponse, PostStakesRequest, PostStakesResponse, PrepareTxResponse, ResponseFormat,
     Reward, RewardRequest,
 };
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 #[serde(rename_all = "snake_case")]
 pub struct NearGetStakesRequest {
-    pub stake_accounts: Vec<Uuid>,
-    pub validators: Vec<String>,
-    pub wallets: Vec<String>,
-    pub accounts: Vec<Uuid>,
+    pub stake_accounts: Option<Vec<Uuid>>,
+    pub validators: Option<Vec<String>>,
+    pub wallets: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -26,9 +32,9 @@ pub struct NearStakesResponse {
     // NEAR account ID
     pub account: String,
     pub validator: String,
-    pub balance: String,
-    pub rewards: String,
-    pub unstaked_balance: String,
+    pub balance: Amount,
+    pub rewards: Amount,
+    pub unstaked_balance: Amount,
     pub can_withdraw: bool,
     pub activated_at: chrono::DateTime<chrono::Utc>,
     pub activated_epoch: u64,
@@ -40,11 +46,11 @@ pub struct NearStakesResponse {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct NearOperationsRequest {
-    pub stake_accounts: Vec<Uuid>,
-    pub wallets: Vec<String>,
-    pub accounts: Vec<Uuid>,
+    pub stake_accounts: Option<Vec<Uuid>>,
+    pub wallets: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
     pub start_date: chrono::DateTime<chrono::Utc>,
-    pub end_date: chrono::DateTime<chrono::Utc>,
+    pub end_date: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -55,25 +61,25 @@ pub struct NearOperationsResponse {
     pub time: chrono::DateTime<chrono::Utc>,
     pub block: u64,
     pub tx_hash: String,
-    pub tx_fees: String,
+    pub tx_fees: Amount,
     pub validator: String,
     pub account: String,
-    pub amount: String,
+    pub amount: Amount,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct NearReportsRequest {
-    pub stake_accounts: Vec<Uuid>,
-    pub accounts: Vec<Uuid>,
-    pub wallets: Vec<String>,
+    pub stake_accounts: Option<Vec<Uuid>>,
+    pub accounts: Option<Vec<Uuid>>,
+    pub wallets: Option<Vec<String>>,
     pub format: ResponseFormat,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct NearNetworkStatsResponse {
-    pub near_price_usd: f64,
+    pub near_price_usd: FloatAmount,
     pub nb_validators: u64,
     pub net_gross_apy: f64,
     pub supply_staked_percent: f64,
@@ -116,7 +122,7 @@ pub struct NearFunctionCall {
     pub method_name: String,
     pub args: NearFunctionCallArgs,
     pub gas: String,
-    pub deposit: String,
+    pub deposit: Amount,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -144,27 +150,27 @@ pub struct NearTxResponse {
 #[serde(rename_all = "snake_case")]
 pub struct NearStakeTxRequest {
     pub account_id: Uuid,
-    pub wallet: String,
-    pub pool_id: String,
-    pub amount_yocto: String,
+    pub wallet: NearAccountId,
+    pub pool_id: NearAccountId,
+    pub amount_yocto: YoctoNear,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct NearUnstakeTxRequest {
-    pub wallet: String,
-    pub pool_id: String,
+    pub wallet: NearAccountId,
+    pub pool_id: NearAccountId,
     // if omitted with Option::None, all is unstaked
-    pub amount_yocto: Option<String>,
+    pub amount_yocto: Option<YoctoNear>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct NearWithdrawRewardsTxRequest {
-    pub wallet: String,
-    pub pool_id: String,
+    pub wallet: NearAccountId,
+    pub pool_id: NearAccountId,
     // if omitted with Option::None, all is withdrawn
-    pub amount_yocto: Option<String>,
+    pub amount_yocto: Option<YoctoNear>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -200,371 +206,529 @@ pub struct NearBroadcastTxRequest {
 }
 
 #[derive(Clone, Debug)]
-pub struct KilnNearClient {
-    pub bearer_token: String,
+pub struct KilnNearClient<M: Mode = Blocking> {
     pub base_url: String,
+    http: HttpClient<M>,
 }
 
-impl From<&Kiln> for KilnNearClient {
-    fn from(kiln: &Kiln) -> Self {
+impl<M: Mode> From<&Kiln<M>> for KilnNearClient<M> {
+    fn from(kiln: &Kiln<M>) -> Self {
         let url: String = format!("{}/near", kiln.base_url);
-        let bearer_token: String = format!("Bearer {}", kiln.api_token);
 
         Self {
-            bearer_token,
             base_url: url,
+            http: kiln.http.clone(),
         }
     }
 }
 
-impl KilnNearClient {
-    pub fn get_stakes(
-        &self,
-        stakes_request: &NearGetStakesRequest,
-    ) -> Result<ReturnedData<Vec<NearStakesResponse>>, ureq::Error> {
-        let NearGetStakesRequest {
-            stake_accounts,
-            accounts,
-            validators,
-            wallets,
-        } = stakes_request;
-
-        let validators = validators.join(",");
-
-        let stake_accounts = stake_accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let wallets = wallets.join(",");
-
-        let url: String = format!(
-            "{}/stakes?validators={}&stake_accounts={}&accounts={}&wallets={}",
-            self.base_url, validators, stake_accounts, accounts, wallets
-        );
+for_each_mode! { <M>
+    impl KilnNearClient<M> {
+        pub async fn get_stakes(
+            &self,
+            stakes_request: &NearGetStakesRequest,
+        ) -> Result<ReturnedData<Vec<NearStakesResponse>>, KilnError> {
+            let NearGetStakesRequest {
+                stake_accounts,
+                accounts,
+                validators,
+                wallets,
+            } = stakes_request;
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<NearStakesResponse>>>();
+            let url = Query::new()
+                .list("validators", validators.iter().flatten())
+                .list("stake_accounts", stake_accounts.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .list("wallets", wallets.iter().flatten())
+                .url(&format!("{}/stakes", self.base_url));
 
-        data
-    }
+            self.http.get("kiln.near.get_stakes", &url).await
+        }
 
-    pub fn post_stakes(
-        &self,
-        post_stakes_request: &PostStakesRequest,
-    ) -> Result<ReturnedData<PostStakesResponse>, ureq::Error> {
-        let url = format!("{}/stakes", self.base_url);
+        pub async fn post_stakes(
+            &self,
+            post_stakes_request: &PostStakesRequest,
+        ) -> Result<ReturnedData<PostStakesResponse>, KilnError> {
+            let url = format!("{}/stakes", self.base_url);
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(post_stakes_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PostStakesResponse>>();
+            self.http
+                .post("kiln.near.post_stakes", &url, post_stakes_request)
+                .await
+        }
 
-        data
-    }
+        pub async fn get_rewards(
+            &self,
+            reward_request: RewardRequest,
+        ) -> Result<ReturnedData<Vec<Reward>>, KilnError> {
+            let url: String = match reward_request {
+                RewardRequest::Epoch {
+                    stakes_addresses,
+                    wallets,
+                    pool_ids,
+                    accounts,
+                    format,
+                    start_date,
+                    end_date,
+                    start_epoch,
+                    end_epoch,
+                } => Query::new()
+                    .list("stake_addresses", stakes_addresses.iter().flatten())
+                    .list("pool_ids", pool_ids.iter().flatten())
+                    .list("wallets", wallets.iter().flatten())
+                    .list("accounts", accounts.iter().flatten())
+                    .value("format", format.as_ref())
+                    .value("start_date", start_date)
+                    .option("end_date", end_date.as_ref())
+                    .value("start_epoch", start_epoch)
+                    .value("end_epoch", end_epoch)
+                    .url(&format!("{}/rewards", self.base_url)),
+                RewardRequest::Daily {
+                    stakes_addresses,
+                    wallets,
+                    pool_ids,
+                    accounts,
+                    format,
+                    start_date,
+                    end_date,
+                    include_usd,
+                } => Query::new()
+                    .list("stake_addresses", stakes_addresses.iter().flatten())
+                    .list("pool_ids", pool_ids.iter().flatten())
+                    .list("wallets", wallets.iter().flatten())
+                    .list("accounts", accounts.iter().flatten())
+                    .value("format", format.as_ref())
+                    .value("start_date", start_date)
+                    .option("end_date", end_date.as_ref())
+                    .value("include_usd", include_usd)
+                    .url(&format!("{}/rewards", self.base_url)),
+            };
+
+            self.http.get("kiln.near.get_rewards", &url).await
+        }
 
-    pub fn get_rewards(
-        &self,
-        reward_request: RewardRequest,
-    ) -> Result<ReturnedData<Vec<Reward>>, ureq::Error> {
-        let url: String = match reward_request {
-            RewardRequest::Epoch {
-                stakes_addresses,
-                wallets,
-                pool_ids,
+        pub async fn get_operations(
+            &self,
+            operations_request: &NearOperationsRequest,
+        ) -> Result<ReturnedData<Vec<NearOperationsResponse>>, KilnError> {
+            let NearOperationsRequest {
+                stake_accounts,
                 accounts,
-                format,
+                wallets,
                 start_date,
                 end_date,
-                start_epoch,
-                end_epoch,
-            } => {
-                let stakes_addresses = stakes_addresses.join(",");
-
-                let pool_ids = pool_ids.join(",");
-
-                let wallets = wallets.join(",");
-
-                let accounts = accounts
-                    .iter()
-                    .map(|id| id.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",");
-
-                let url = format!(
-            "{}/rewards?stake_addresses={}&pool_ids={}&wallets={}&accounts={}&format={}&start_date={}&end_date={}&start_epoch={}&end_epoch={}",
-            self.base_url,
-            stakes_addresses,
-            pool_ids,
-            wallets,
-            accounts,
-            format.as_ref(),
-            start_date,
-            end_date,
-            start_epoch,
-            end_epoch
-        );
+            } = operations_request;
 
-                url
-            }
-            RewardRequest::Daily {
-                stakes_addresses,
-                wallets,
-                pool_ids,
+            let url = Query::new()
+                .list("stake_accounts", stake_accounts.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .list("wallets", wallets.iter().flatten())
+                .value("start_date", start_date)
+                .option("end_date", end_date.as_ref())
+                .url(&format!("{}/operations", self.base_url));
+
+            self.http.get("kiln.near.get_operations", &url).await
+        }
+
+        pub async fn get_reports(
+            &self,
+            reports_request: &NearReportsRequest,
+        ) -> Result<Vec<u8>, KilnError> {
+            let NearReportsRequest {
+                stake_accounts,
                 accounts,
+                wallets,
                 format,
-                start_date,
-                end_date,
-                include_usd,
-            } => {
-                let stakes_addresses = stakes_addresses.join(",");
-
-                let pool_ids = pool_ids.join(",");
-
-                let wallets = wallets.join(",");
-
-                let accounts = accounts
-                    .iter()
-                    .map(|id| id.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",");
-
-                let url = format!(
-            "{}/rewards?stake_addresses={}&pool_ids={}&wallets={}&accounts={}&format={}&start_date={}&end_date={}&include_usd={}",
-            self.base_url,
-            stakes_addresses,
-            pool_ids,
-            wallets,
-            accounts,
-            format.as_ref(),
-            start_date,
-            end_date,
-            include_usd
-        );
+            } = reports_request;
+
+            let url = Query::new()
+                .list("stake_accounts", stake_accounts.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .list("wallets", wallets.iter().flatten())
+                .value("format", format.as_ref())
+                .url(&format!("{}/reports", self.base_url));
+
+            self.http.get_bytes("kiln.near.get_reports", &url).await
+        }
+
+        pub async fn get_network_stats(
+            &self,
+        ) -> Result<ReturnedData<NearNetworkStatsResponse>, KilnError> {
+            let url: String = format!("{}/network-stats", self.base_url);
+
+            self.http.get("kiln.near.get_network_stats", &url).await
+        }
+
+        pub async fn post_stake_tx(
+            &self,
+            transaction_stake_request: &NearStakeTxRequest,
+        ) -> Result<ReturnedData<NearTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/stake", self.base_url,);
+
+            self.http
+                .post_tx("kiln.near.post_stake_tx", &url, transaction_stake_request)
+                .await
+        }
+
+        pub async fn post_unstake_tx(
+            &self,
+            unstake_transaction_request: &NearUnstakeTxRequest,
+        ) -> Result<ReturnedData<NearTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/unstake", self.base_url,);
+
+            self.http
+                .post_tx(
+                    "kiln.near.post_unstake_tx",
+                    &url,
+                    unstake_transaction_request,
+                )
+                .await
+        }
+
+        pub async fn post_withdraw_rewards_tx(
+            &self,
+            withdraw_rewards_transaction_request: &NearWithdrawRewardsTxRequest,
+        ) -> Result<ReturnedData<NearTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/withdraw-rewards", self.base_url,);
+
+            self.http
+                .post_tx(
+                    "kiln.near.post_withdraw_rewards_tx",
+                    &url,
+                    withdraw_rewards_transaction_request,
+                )
+                .await
+        }
+
+        pub async fn post_prepare_tx(
+            &self,
+            prepare_transaction_request: &NearPrepareTxRequest,
+        ) -> Result<ReturnedData<PrepareTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/prepare", self.base_url,);
+
+            self.http
+                .post_tx(
+                    "kiln.near.post_prepare_tx",
+                    &url,
+                    prepare_transaction_request,
+                )
+                .await
+        }
 
-                url
-            }
-        };
+        pub async fn post_broadcast_tx(
+            &self,
+            broadcast_transaction_request: &NearBroadcastTxRequest,
+        ) -> Result<ReturnedData<BroadcastTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/broadcast", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.near.post_broadcast_tx",
+                    &url,
+                    broadcast_transaction_request,
+                )
+                .await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<Reward>>>();
+        pub async fn get_tx_decoding(
+            &self,
+            tx_serialized: &str,
+        ) -> Result<ReturnedData<NearTxDecodingResponse>, KilnError> {
+            let url = Query::new()
+                .value("tx_serialized", tx_serialized)
+                .url(&format!("{}/transaction/decode", self.base_url));
 
-        data
+            self.http.get("kiln.near.get_tx_decoding", &url).await
+        }
     }
+}
 
-    pub fn get_operations(
-        &self,
-        operations_request: &NearOperationsRequest,
-    ) -> Result<ReturnedData<Vec<NearOperationsResponse>>, ureq::Error> {
-        let NearOperationsRequest {
-            stake_accounts,
-            accounts,
-            wallets,
-            start_date,
-            end_date,
-        } = operations_request;
-
-        let stake_accounts = stake_accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let wallets = wallets.join(",");
-
-        let url = format!(
-            "{}/operations?stake_accounts={}&accounts={}&wallets={}&start_date={}&end_date={}",
-            self.base_url, stake_accounts, accounts, wallets, start_date, end_date
-        );
+impl<M: Mode> KilnNearClient<M> {
+    pub fn stakes(&self) -> NearStakesBuilder<'_, M> {
+        NearStakesBuilder::new(self)
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<NearOperationsResponse>>>();
+    pub fn rewards(&self) -> RewardsBuilder<'_, Self, NoStartDate> {
+        RewardsBuilder::new(self)
+    }
 
-        data
+    pub fn operations(&self) -> NearOperationsBuilder<'_, NoStartDate, M> {
+        NearOperationsBuilder::new(self)
     }
+}
 
-    pub fn get_reports(
-        &self,
-        reports_request: &NearReportsRequest,
-    ) -> Result<Vec<u8>, ureq::Error> {
-        let NearReportsRequest {
-            stake_accounts,
-            accounts,
-            wallets,
-            format,
-        } = reports_request;
-
-        let stake_accounts = stake_accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let wallets = wallets.join(",");
-
-        let url: String = format!(
-            "{}/reports?stake_accounts={}&accounts={}&wallets={}&format={}",
-            self.base_url,
-            stake_accounts,
-            accounts,
-            wallets,
-            format.as_ref()
-        );
+// `kiln.near().stakes()`
+#[derive(Debug)]
+pub struct NearStakesBuilder<'a, M: Mode = Blocking> {
+    client: &'a KilnNearClient<M>,
+    stake_accounts: Option<Vec<Uuid>>,
+    validators: Option<Vec<String>>,
+    wallets: Option<Vec<String>>,
+    accounts: Option<Vec<Uuid>>,
+}
+
+impl<'a, M: Mode> NearStakesBuilder<'a, M> {
+    fn new(client: &'a KilnNearClient<M>) -> Self {
+        Self {
+            client,
+            stake_accounts: None,
+            validators: None,
+            wallets: None,
+            accounts: None,
+        }
+    }
+
+    pub fn stake_accounts(self, stake_accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            stake_accounts: filter(stake_accounts),
+            ..self
+        }
+    }
 
-        let mut file_bytes: Vec<u8> = Vec::new();
+    pub fn validators(self, validators: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            validators: filter(validators),
+            ..self
+        }
+    }
+
+    pub fn wallets(self, wallets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            wallets: filter(wallets),
+            ..self
+        }
+    }
 
-        ureq::get(url)
-            .header("accept", "application/octet-stream")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .as_reader()
-            .read_to_end(&mut file_bytes)?;
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
+    }
 
-        Ok(file_bytes)
+    pub fn into_request(self) -> NearGetStakesRequest {
+        NearGetStakesRequest {
+            stake_accounts: self.stake_accounts,
+            validators: self.validators,
+            wallets: self.wallets,
+            accounts: self.accounts,
+        }
     }
+}
 
-    pub fn get_network_stats(&self) -> Result<ReturnedData<NearNetworkStatsResponse>, ureq::Error> {
-        let url: String = format!("{}/network-stats", self.base_url);
+for_each_mode! { <M>
+    impl NearStakesBuilder<'_, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<NearStakesResponse>>, KilnError> {
+            let client = self.client;
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<NearNetworkStatsResponse>>();
+            client.get_stakes(&self.into_request()).await
+        }
+    }
+}
+
+for_each_mode! { <M>
+    impl RewardsBuilder<'_, KilnNearClient<M>, StartDate> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<Reward>>, KilnError> {
+            let client = self.client;
+
+            client.get_rewards(self.into_request()?).await
+        }
+    }
+}
+
+// `kiln.near().operations()`
+#[derive(Debug)]
+pub struct NearOperationsBuilder<'a, S, M: Mode = Blocking> {
+    client: &'a KilnNearClient<M>,
+    stake_accounts: Option<Vec<Uuid>>,
+    wallets: Option<Vec<String>>,
+    accounts: Option<Vec<Uuid>>,
+    start_date: S,
+    end_date: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl<'a, M: Mode> NearOperationsBuilder<'a, NoStartDate, M> {
+    fn new(client: &'a KilnNearClient<M>) -> Self {
+        Self {
+            client,
+            stake_accounts: None,
+            wallets: None,
+            accounts: None,
+            start_date: NoStartDate,
+            end_date: None,
+        }
+    }
+}
 
-        data
+impl<'a, S, M: Mode> NearOperationsBuilder<'a, S, M> {
+    pub fn stake_accounts(self, stake_accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            stake_accounts: filter(stake_accounts),
+            ..self
+        }
     }
 
-    pub fn post_stake_tx(
-        &self,
-        transaction_stake_request: &NearStakeTxRequest,
-    ) -> Result<ReturnedData<NearTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/stake", self.base_url,);
+    pub fn wallets(self, wallets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            wallets: filter(wallets),
+            ..self
+        }
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(transaction_stake_request)?
-            .body_mut()
-            .read_json::<ReturnedData<NearTxResponse>>();
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
+    }
 
-        data
+    pub fn until(self, end_date: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            end_date: Some(end_date),
+            ..self
+        }
     }
 
-    pub fn post_unstake_tx(
-        &self,
-        unstake_transaction_request: &NearUnstakeTxRequest,
-    ) -> Result<ReturnedData<NearTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/unstake", self.base_url,);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(unstake_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<NearTxResponse>>();
-        data
+    pub fn since(
+        self,
+        start_date: chrono::DateTime<chrono::Utc>,
+    ) -> NearOperationsBuilder<'a, StartDate, M> {
+        NearOperationsBuilder {
+            client: self.client,
+            stake_accounts: self.stake_accounts,
+            wallets: self.wallets,
+            accounts: self.accounts,
+            start_date: StartDate(start_date),
+            end_date: self.end_date,
+        }
     }
+}
 
-    pub fn post_withdraw_rewards_tx(
-        &self,
-        withdraw_rewards_transaction_request: &NearWithdrawRewardsTxRequest,
-    ) -> Result<ReturnedData<NearTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/withdraw-rewards", self.base_url,);
+impl<M: Mode> NearOperationsBuilder<'_, StartDate, M> {
+    pub fn into_request(self) -> NearOperationsRequest {
+        NearOperationsRequest {
+            stake_accounts: self.stake_accounts,
+            wallets: self.wallets,
+            accounts: self.accounts,
+            start_date: self.start_date.0,
+            end_date: self.end_date,
+        }
+    }
+}
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(withdraw_rewards_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<NearTxResponse>>();
+for_each_mode! { <M>
+    impl NearOperationsBuilder<'_, StartDate, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<NearOperationsResponse>>, KilnError> {
+            let client = self.client;
 
-        data
+            client.get_operations(&self.into_request()).await
+        }
     }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod near_test {
 
-    pub fn post_prepare_tx(
-        &self,
-        prepare_transaction_request: &NearPrepareTxRequest,
-    ) -> Result<ReturnedData<PrepareTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/prepare", self.base_url,);
+    use super::*;
+    use crate::query::query_test_support::{date, fixture, requested_url};
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(prepare_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PrepareTxResponse>>();
+    #[test]
+    fn get_stakes() {
+        let url = requested_url(|kiln| {
+            kiln.near().get_stakes(&NearGetStakesRequest {
+                validators: Some(vec!["kiln.poolv1.near".into()]),
+                ..Default::default()
+            })
+        });
 
-        data
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/near/stakes?validators=kiln.poolv1.near"
+        );
     }
 
-    pub fn post_broadcast_tx(
-        &self,
-        broadcast_transaction_request: &NearBroadcastTxRequest,
-    ) -> Result<ReturnedData<BroadcastTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/broadcast", self.base_url);
+    #[test]
+    fn get_rewards() {
+        let url = requested_url(|kiln| {
+            kiln.near().get_rewards(RewardRequest::Daily {
+                stakes_addresses: None,
+                wallets: Some(vec!["alice.near".into()]),
+                pool_ids: None,
+                accounts: None,
+                format: ResponseFormat::Daily,
+                start_date: date("2024-01-01T00:00:00Z"),
+                end_date: Some(date("2024-01-31T00:00:00Z")),
+                include_usd: false,
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/near/rewards?wallets=alice.near&format=daily&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z&include_usd=false"
+        );
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(broadcast_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<BroadcastTxResponse>>();
+    #[test]
+    fn get_operations() {
+        let url = requested_url(|kiln| {
+            kiln.near().get_operations(&NearOperationsRequest {
+                stake_accounts: None,
+                wallets: Some(vec!["alice.near".into()]),
+                accounts: None,
+                start_date: date("2024-01-01T00:00:00Z"),
+                end_date: Some(date("2024-01-31T00:00:00Z")),
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/near/operations?wallets=alice.near&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z"
+        );
+    }
 
-        data
+    #[test]
+    fn get_reports() {
+        let url = requested_url(|kiln| {
+            kiln.near().get_reports(&NearReportsRequest {
+                stake_accounts: None,
+                accounts: None,
+                wallets: Some(vec!["alice.near".into()]),
+                format: ResponseFormat::Epoch,
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/near/reports?wallets=alice.near&format=epoch"
+        );
     }
 
-    pub fn get_tx_decoding(
-        &self,
-        tx_serialized: &str,
-    ) -> Result<NearTxDecodingResponse, ureq::Error> {
-        let url: String = format!(
-            "{}/transaction/decode?tx_serialized={}",
-            self.base_url, tx_serialized
+    #[test]
+    fn get_tx_decoding() {
+        let url = requested_url(|kiln| kiln.near().get_tx_decoding("ab+/="));
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/near/transaction/decode?tx_serialized=ab%2B%2F%3D"
         );
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<NearTxDecodingResponse>();
+    #[test]
+    fn responses_match_the_fixtures() {
+        let tx: ReturnedData<NearTxResponse> = fixture("near/tx.json");
+        let decoded: ReturnedData<NearTxDecodingResponse> = fixture("near/tx_decoding.json");
 
-        data
+        assert_eq!(
+            tx.data.actions[0].function_call.method_name,
+            "deposit_and_stake"
+        );
+        assert_eq!(decoded.data.receiver_id, "kiln.poolv1.near");
+
+        fixture::<ReturnedData<Vec<NearStakesResponse>>>("near/stakes.json");
+        fixture::<ReturnedData<PostStakesResponse>>("near/post_stakes.json");
+        fixture::<ReturnedData<Vec<Reward>>>("near/rewards.json");
+        fixture::<ReturnedData<Vec<NearOperationsResponse>>>("near/operations.json");
+        fixture::<ReturnedData<NearNetworkStatsResponse>>("near/network_stats.json");
+        fixture::<ReturnedData<PrepareTxResponse>>("near/prepare_tx.json");
+        fixture::<ReturnedData<BroadcastTxResponse>>("near/broadcast_tx.json");
     }
 }
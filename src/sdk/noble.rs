@@ -1,5 +1,10 @@
 use serde::{Deserialize, Serialize};
 
+use crate::denomination::Uusdc;
+use crate::errors::KilnError;
+use crate::http::HttpClient;
+use crate::mode::{for_each_mode, Blocking, Mode};
+use crate::query::Query;
 use crate::{response_format::ReturnedData, Kiln};
 
 use super::{
@@ -12,7 +17,7 @@ use super::{
 pub struct BurnTxRequest {
     pub pubkey: String,
     pub recipient: String,
-    pub amount_uusdc: String,
+    pub amount_uusdc: Uusdc,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -20,137 +25,155 @@ pub struct BurnTxRequest {
 pub struct OsmoIBCTransferTxRequest {
     pub pubkey: String,
     pub recipient: String,
-    pub amount_uusdc: String,
+    pub amount_uusdc: Uusdc,
 }
 
 #[derive(Clone, Debug)]
-pub struct KilnNobleClient {
-    pub bearer_token: String,
+pub struct KilnNobleClient<M: Mode = Blocking> {
     pub base_url: String,
+    http: HttpClient<M>,
 }
 
-impl From<&Kiln> for KilnNobleClient {
-    fn from(kiln: &Kiln) -> Self {
+impl<M: Mode> From<&Kiln<M>> for KilnNobleClient<M> {
+    fn from(kiln: &Kiln<M>) -> Self {
         let url: String = format!("{}/noble", kiln.base_url);
-        let bearer_token: String = format!("Bearer {}", kiln.api_token);
 
         Self {
-            bearer_token,
             base_url: url,
+            http: kiln.http.clone(),
         }
     }
 }
 
-impl KilnNobleClient {
-    pub fn post_get_balance(
-        &self,
-        get_balance_request: &GetBalanceRequest,
-    ) -> Result<ReturnedData<TxStakeCoin>, ureq::Error> {
-        let url: String = format!("{}/balance", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(get_balance_request)?
-            .body_mut()
-            .read_json::<ReturnedData<TxStakeCoin>>();
-
-        data
-    }
+for_each_mode! { <M>
+    impl KilnNobleClient<M> {
+        pub async fn post_get_balance(
+            &self,
+            get_balance_request: &GetBalanceRequest,
+        ) -> Result<ReturnedData<TxStakeCoin>, KilnError> {
+            let url: String = format!("{}/balance", self.base_url);
+
+            self.http
+                .post("kiln.noble.post_get_balance", &url, get_balance_request)
+                .await
+        }
 
-    pub fn post_burn_usdc_tx(
-        &self,
-        burn_tx_request: &BurnTxRequest,
-    ) -> Result<ReturnedData<TxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/burn-usdc", self.base_url,);
+        pub async fn post_burn_usdc_tx(
+            &self,
+            burn_tx_request: &BurnTxRequest,
+        ) -> Result<ReturnedData<TxResponse>, KilnError> {
+            let url = format!("{}/transaction/burn-usdc", self.base_url,);
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(burn_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<TxResponse>>();
+            self.http
+                .post_tx("kiln.noble.post_burn_usdc_tx", &url, burn_tx_request)
+                .await
+        }
 
-        data
-    }
+        pub async fn post_osmo_ibc_transfer_tx(
+            &self,
+            osmo_ibc_transfer_tx_request: &OsmoIBCTransferTxRequest,
+        ) -> Result<ReturnedData<TxResponse>, KilnError> {
+            let url = format!("{}/transaction/osmo-ibc-transfer", self.base_url,);
+
+            self.http
+                .post_tx(
+                    "kiln.noble.post_osmo_ibc_transfer_tx",
+                    &url,
+                    osmo_ibc_transfer_tx_request,
+                )
+                .await
+        }
 
-    pub fn post_osmo_ibc_transfer_tx(
-        &self,
-        osmo_ibc_transfer_tx_request: &OsmoIBCTransferTxRequest,
-    ) -> Result<ReturnedData<TxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/osmo-ibc-transfer", self.base_url,);
+        pub async fn post_prepare_tx(
+            &self,
+            prepare_transaction_request: &PrepareTxRequest,
+        ) -> Result<ReturnedData<PrepareTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/prepare", self.base_url,);
+
+            self.http
+                .post_tx(
+                    "kiln.noble.post_prepare_tx",
+                    &url,
+                    prepare_transaction_request,
+                )
+                .await
+        }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(osmo_ibc_transfer_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<TxResponse>>();
+        pub async fn get_tx_status(
+            &self,
+            tx_hash: &str,
+        ) -> Result<ReturnedData<TxStatusResponse>, KilnError> {
+            let url = Query::new()
+                .value("tx_hash", tx_hash)
+                .url(&format!("{}/transaction/status", self.base_url));
 
-        data
-    }
+            self.http.get("kiln.noble.get_tx_status", &url).await
+        }
 
-    pub fn post_prepare_tx(
-        &self,
-        prepare_transaction_request: &PrepareTxRequest,
-    ) -> Result<ReturnedData<PrepareTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/prepare", self.base_url,);
+        pub async fn post_broadcast_tx(
+            &self,
+            broadcast_transaction_request: &BroadcastTxRequest,
+        ) -> Result<ReturnedData<BroadcastTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/broadcast", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.noble.post_broadcast_tx",
+                    &url,
+                    broadcast_transaction_request,
+                )
+                .await
+        }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(prepare_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PrepareTxResponse>>();
+        pub async fn get_tx_decoding(
+            &self,
+            tx_serialized: &str,
+        ) -> Result<ReturnedData<TxDecodingResponse>, KilnError> {
+            let url = Query::new()
+                .value("tx_serialized", tx_serialized)
+                .url(&format!("{}/transaction/decode", self.base_url));
 
-        data
+            self.http.get("kiln.noble.get_tx_decoding", &url).await
+        }
     }
+}
 
-    pub fn get_tx_status(
-        &self,
-        tx_hash: &str,
-    ) -> Result<ReturnedData<TxStatusResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/status?tx_hash={}", self.base_url, tx_hash);
-
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<TxStatusResponse>>();
-
-        data
-    }
+#[cfg(all(test, feature = "blocking"))]
+mod noble_test {
 
-    pub fn post_broadcast_tx(
-        &self,
-        broadcast_transaction_request: &BroadcastTxRequest,
-    ) -> Result<ReturnedData<BroadcastTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/broadcast", self.base_url);
+    use super::*;
+    use crate::query::query_test_support::{fixture, requested_url};
+    use crate::sdk::TxStakeMessage;
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(broadcast_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<BroadcastTxResponse>>();
+    #[test]
+    fn get_tx_status() {
+        let url = requested_url(|kiln| kiln.noble().get_tx_status("A1B2"));
 
-        data
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/noble/transaction/status?tx_hash=A1B2"
+        );
     }
 
-    pub fn get_tx_decoding(&self, tx_serialized: &str) -> Result<TxDecodingResponse, ureq::Error> {
-        let url: String = format!(
-            "{}/transaction/decode?tx_serialized={}",
-            self.base_url, tx_serialized
+    #[test]
+    fn get_tx_decoding() {
+        let url = requested_url(|kiln| kiln.noble().get_tx_decoding("ab+/="));
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/noble/transaction/decode?tx_serialized=ab%2B%2F%3D"
         );
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<TxDecodingResponse>();
+    #[test]
+    fn responses_match_the_fixtures() {
+        let balance: ReturnedData<TxStakeCoin> = fixture("noble/balance.json");
+        let burn: ReturnedData<TxResponse> = fixture("noble/burn_usdc_tx.json");
 
-        data
+        assert_eq!(
+            balance.data.amount_in::<Uusdc>(),
+            Some(Uusdc::new(25_000_000))
+        );
+        assert!(matches!(burn.data.message, TxStakeMessage::Other { .. }));
     }
 }
@@ -0,0 +1,106 @@
+// The operations the Kiln API sends : flat json objects told apart by their `"type"`, each type
+// spelled once. An operation type this version of the SDK doesn't know yet ends up in `Unknown`,
+// the json object as is, instead of failing the deserialization of the whole response.
+// A known type with a field that doesn't match is still an error, naming the type and the field.
+macro_rules! operation_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $(
+                $variant:ident => $value:literal $(| $alias:literal)* {
+                    $($field:ident : $ty:ty,)*
+                },
+            )*
+        }
+    ) => {
+        #[derive(Debug)]
+        $(#[$meta])*
+        pub enum $name {
+            $($variant { $($field: $ty,)* },)*
+            // the whole json object, its `"type"` included
+            Unknown(serde_json::Map<String, serde_json::Value>),
+        }
+
+        impl $name {
+            pub fn operation_type(&self) -> &str {
+                match self {
+                    $(Self::$variant { .. } => $value,)*
+                    Self::Unknown(object) => object
+                        .get("type")
+                        .and_then(serde_json::Value::as_str)
+                        .unwrap_or_default(),
+                }
+            }
+
+            pub fn is_unknown(&self) -> bool {
+                matches!(self, Self::Unknown(_))
+            }
+        }
+
+        const _: () = {
+            // the same variants, internally tagged, for serde to do the serialization
+            #[derive(serde::Serialize)]
+            #[serde(tag = "type")]
+            enum Borrowed<'a> {
+                $(
+                    #[serde(rename = $value)]
+                    $variant { $($field: &'a $ty,)* },
+                )*
+            }
+
+            impl serde::Serialize for $name {
+                fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    match self {
+                        $(Self::$variant { $($field,)* } => {
+                            Borrowed::$variant { $($field,)* }.serialize(serializer)
+                        })*
+                        Self::Unknown(object) => object.serialize(serializer),
+                    }
+                }
+            }
+
+            impl<'de> serde::Deserialize<'de> for $name {
+                fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let object = serde_json::Map::deserialize(deserializer)?;
+
+                    let operation_type = object
+                        .get("type")
+                        .and_then(serde_json::Value::as_str)
+                        .unwrap_or_default()
+                        .to_owned();
+
+                    // the path to the operation is tracked by the caller, the one inside it is
+                    // lost with the error type : it goes to the message, with the operation type
+                    let custom = |error: serde_path_to_error::Error<serde_json::Error>| {
+                        serde::de::Error::custom(format!(
+                            "`{operation_type}` operation, at `{}`: {}",
+                            error.path(),
+                            error.inner()
+                        ))
+                    };
+
+                    match operation_type.as_str() {
+                        $(
+                            $value $(| $alias)* => {
+                                // not an internally tagged enum : serde buffers its content,
+                                // which loses the path
+                                #[derive(serde::Deserialize)]
+                                struct Fields {
+                                    $($field: $ty,)*
+                                }
+
+                                let Fields { $($field,)* } = serde_path_to_error::deserialize(
+                                    serde_json::Value::Object(object),
+                                )
+                                .map_err(custom)?;
+
+                                Ok(Self::$variant { $($field,)* })
+                            }
+                        )*
+                        _ => Ok(Self::Unknown(object)),
+                    }
+                }
+            }
+        };
+    };
+}
@@ -1,68 +1,77 @@
-use std::io::Read;
-
+use crate::errors::KilnError;
+use crate::http::HttpClient;
+use crate::mode::{for_each_mode, Blocking, Mode};
 use crate::{response_format::ReturnedData, Kiln};
 
 use super::Portofolio;
 
 #[derive(Clone, Debug)]
-pub struct KilnOrganisationClient {
-    bearer_token: String,
+pub struct KilnOrganisationClient<M: Mode = Blocking> {
     base_url: String,
+    http: HttpClient<M>,
 }
 
-impl From<&Kiln> for KilnOrganisationClient {
-    fn from(kiln: &Kiln) -> Self {
+impl<M: Mode> From<&Kiln<M>> for KilnOrganisationClient<M> {
+    fn from(kiln: &Kiln<M>) -> Self {
         let url: String = format!("{}/organisations", kiln.base_url);
-        let bearer_token: String = format!("Bearer {}", kiln.api_token);
 
         Self {
-            bearer_token,
             base_url: url,
+            http: kiln.http.clone(),
         }
     }
 }
 
-impl KilnOrganisationClient {
-    pub fn get_by_uuid(&self, id: uuid::Uuid) -> Result<ReturnedData<Portofolio>, ureq::Error> {
-        let id_param = id.to_string();
-
-        let url: String = format!("{}/{}", self.base_url, id_param);
-
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Portofolio>>();
-
-        data
-    }
+for_each_mode! { <M>
+    impl KilnOrganisationClient<M> {
+        pub async fn get_by_uuid(&self, id: uuid::Uuid) -> Result<ReturnedData<Portofolio>, KilnError> {
+            let id_param = id.to_string();
 
-    pub fn get_reports(&self, id: uuid::Uuid) -> Result<Vec<u8>, ureq::Error> {
-        let id_param: String = id.to_string();
+            let url: String = format!("{}/{}", self.base_url, id_param);
 
-        let url: String = format!("{}/{}/reports", self.base_url, id_param);
+            self.http.get("kiln.organisations.get_by_uuid", &url).await
+        }
 
-        let mut file_bytes: Vec<u8> = Vec::new();
+        pub async fn get_reports(&self, id: uuid::Uuid) -> Result<Vec<u8>, KilnError> {
+            let id_param: String = id.to_string();
 
-        ureq::get(url)
-            .header("accept", "application/octet-stream")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .as_reader()
-            .read_to_end(&mut file_bytes)?;
+            let url: String = format!("{}/{}/reports", self.base_url, id_param);
 
-        Ok(file_bytes)
+            self.http
+                .get_bytes("kiln.organisations.get_reports", &url)
+                .await
+        }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "blocking"))]
 mod organisations_test {
 
     use std::str::FromStr;
 
     use super::*;
+    use crate::query::query_test_support::{fixture, requested_url};
+    use crate::sdk::Protocol;
+
+    #[test]
+    fn get_by_uuid() {
+        let url = requested_url(|kiln| kiln.organisations().get_by_uuid(uuid::Uuid::nil()));
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/organisations/00000000-0000-0000-0000-000000000000"
+        );
+    }
+
+    #[test]
+    fn get_reports() {
+        let url = requested_url(|kiln| kiln.organisations().get_reports(uuid::Uuid::nil()));
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/organisations/00000000-0000-0000-0000-000000000000/reports"
+        );
+    }
 
     // the #[ignore] are here because the temporary free api token isn't valid anymore
 
@@ -74,7 +83,6 @@ mod organisations_test {
 
         let kiln: Kiln = Kiln::builder()
             .api_token(api_token)
-            // no trailing slash, or else it will break
             .base_url("https://api.kiln.fi/v1")
             .seal()
             .build()
@@ -86,4 +94,15 @@ mod organisations_test {
 
         dbg!(data);
     }
+
+    #[test]
+    fn responses_match_the_fixtures() {
+        let portofolio: ReturnedData<Portofolio> = fixture("organisation/portofolio.json");
+
+        let balances = portofolio.data.balance_usd_by_protocol();
+
+        assert_eq!(balances.len(), 2);
+        assert!(balances.contains_key(&Protocol::Cosmos));
+        assert_eq!(portofolio.data.eigenlayer().count(), 1);
+    }
 }
@@ -1,67 +1,78 @@
-use std::io::Read;
-
 use serde::{Deserialize, Serialize};
 use strum_macros::AsRefStr;
 use uuid::Uuid;
 
+use crate::address::{AddressError, Lenient, Ss58Address};
+use crate::amount::{Amount, FloatAmount, IntAmount};
+use crate::denomination::Planck;
+use crate::errors::KilnError;
+use crate::http::HttpClient;
+use crate::mode::{for_each_mode, Blocking, Mode};
+use crate::query::Query;
 use crate::{response_format::ReturnedData, Kiln};
 
+use super::builders::{filter, NoStartDate, StartDate};
 use super::{BroadcastTxRequest, BroadcastTxResponse, PrepareTxResponse};
 
-#[derive(Serialize, Deserialize, Debug)]
+// ss58 network of the polkadot addresses
+const NETWORK: u16 = 0;
+
+#[derive(Serialize, Deserialize, Debug, Default)]
 #[serde(rename_all = "snake_case")]
 pub struct PolkadotGetStakesRequest {
-    pub addresses: Vec<String>,
-    pub accounts: Vec<Uuid>,
+    pub addresses: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct PolkadotStakesResponse {
     // Address
-    pub address: String,
+    pub address: Lenient<Ss58Address>,
     pub pool_id: u64,
-    pub active_balance: String,
-    pub unbonding_balance: String,
-    pub withdrawable_balance: String,
-    pub net_rewards: String,
-    pub gross_rewards: String,
-    pub withdrawable_rewards: String,
+    pub active_balance: Amount,
+    pub unbonding_balance: Amount,
+    pub withdrawable_balance: Amount,
+    pub net_rewards: Amount,
+    pub gross_rewards: Amount,
+    pub withdrawable_rewards: Amount,
     pub state: String,
     pub net_apy: f64,
     pub delegated_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
-    pub undelegated_at: chrono::DateTime<chrono::Utc>,
+    pub undelegated_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+// daily rewards have a `date`, era rewards an `era`
 #[derive(Serialize, Deserialize, Debug)]
-#[serde(rename_all = "snake_case")]
+#[serde(untagged)]
 pub enum PolkadotReward {
     ByDay {
-        net_rewards: String,
-        gross_rewards: String,
+        net_rewards: Amount,
+        gross_rewards: Amount,
         date: chrono::DateTime<chrono::Utc>,
-        active_balance: String,
+        active_balance: Amount,
         net_apy: f64,
         gross_apy: f64,
-        active_balance_usd: u64,
-        net_rewards_usd: u64,
-        gross_rewards_usd: u64,
+        active_balance_usd: FloatAmount,
+        net_rewards_usd: FloatAmount,
+        gross_rewards_usd: FloatAmount,
     },
     ByEra {
-        net_rewards: String,
-        gross_rewards: String,
+        net_rewards: Amount,
+        gross_rewards: Amount,
         era: u64,
-        active_balance: String,
+        active_balance: Amount,
         net_apy: f64,
-        active_balance_usd: u64,
-        net_rewards_usd: u64,
-        gross_rewards_usd: u64,
+        active_balance_usd: FloatAmount,
+        net_rewards_usd: FloatAmount,
+        gross_rewards_usd: FloatAmount,
     },
 }
 
 #[derive(Serialize, Deserialize, AsRefStr, Default, Debug)]
 #[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
 #[non_exhaustive]
 pub enum PolkadotResponseFormat {
     #[default]
@@ -72,11 +83,11 @@ pub enum PolkadotResponseFormat {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct PolkadotRewardRequest {
-    pub addresses: Vec<String>,
-    pub pool_ids: Vec<String>,
-    pub accounts: Vec<Uuid>,
+    pub addresses: Option<Vec<String>>,
+    pub pool_ids: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
     pub start_date: chrono::DateTime<chrono::Utc>,
-    pub end_date: chrono::DateTime<chrono::Utc>,
+    pub end_date: Option<chrono::DateTime<chrono::Utc>>,
     pub format: PolkadotResponseFormat,
 }
 
@@ -89,35 +100,36 @@ pub struct PolkadotOperation {
     pub tx_hash: String,
     pub extrinsic_seq_id: u64,
     pub event_seq_id: u64,
-    pub amount: String,
+    pub amount: Amount,
     // Address
-    pub address: String,
-    pub validator: String,
-    pub pool_id: String,
-    pub earned_era: String,
-    pub era: String,
+    pub address: Lenient<Ss58Address>,
+    // depending on the operation type
+    pub validator: Option<String>,
+    pub pool_id: Option<String>,
+    pub earned_era: Option<String>,
+    pub era: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct PolkadotOperationsRequest {
-    pub addresses: Vec<String>,
-    pub accounts: Vec<Uuid>,
+    pub addresses: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
     pub start_date: chrono::DateTime<chrono::Utc>,
-    pub end_date: chrono::DateTime<chrono::Utc>,
+    pub end_date: Option<chrono::DateTime<chrono::Utc>>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 #[serde(rename_all = "snake_case")]
 pub struct PolkadotReportsRequest {
-    pub addresses: Vec<String>,
-    pub accounts: Vec<Uuid>,
+    pub addresses: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct PolkadotNetworkStatsResponse {
-    pub dot_price_usd: f64,
+    pub dot_price_usd: FloatAmount,
     pub nb_validators: u64,
     pub net_gross_apy: f64,
     pub supply_staked_percent: f64,
@@ -131,11 +143,12 @@ pub struct Payee {
     pub account: String,
 }
 
+// the arguments of the call : a `chill` has none
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct PolkadotTxMethodArgs {
-    pub value: String,
-    pub payee: Payee,
+    pub value: Option<String>,
+    pub payee: Option<Payee>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -173,93 +186,169 @@ pub struct PolkadotTxResponse {
 pub struct PolkadotBondTxRequest {
     pub account_id: Uuid,
     // Address
-    pub stash_account: String,
-    pub amount_planck: String,
+    pub stash_account: Ss58Address,
+    pub amount_planck: Planck,
     pub reward_destination: String,
 }
 
+impl PolkadotBondTxRequest {
+    pub(crate) fn check_addresses(&self, network: u16) -> Result<(), AddressError> {
+        self.stash_account.check_network(network)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct PolkadotBondExtraTxRequest {
     // Address
-    pub stash_account: String,
-    pub amount_planck: String,
+    pub stash_account: Ss58Address,
+    pub amount_planck: Planck,
+}
+
+impl PolkadotBondExtraTxRequest {
+    pub(crate) fn check_addresses(&self, network: u16) -> Result<(), AddressError> {
+        self.stash_account.check_network(network)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct PolkadotRebondTxRequest {
     // Address
-    pub stash_account: String,
-    pub amount_planck: String,
+    pub stash_account: Ss58Address,
+    pub amount_planck: Planck,
+}
+
+impl PolkadotRebondTxRequest {
+    pub(crate) fn check_addresses(&self, network: u16) -> Result<(), AddressError> {
+        self.stash_account.check_network(network)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct PolkadotUnbondTxRequest {
     // Address
-    pub stash_account: String,
-    pub amount_planck: String,
+    pub stash_account: Ss58Address,
+    pub amount_planck: Planck,
+}
+
+impl PolkadotUnbondTxRequest {
+    pub(crate) fn check_addresses(&self, network: u16) -> Result<(), AddressError> {
+        self.stash_account.check_network(network)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct PolkadotNominateTxRequest {
     // Address
-    pub stash_account: String,
-    pub validator_addresses: Vec<String>,
+    pub stash_account: Ss58Address,
+    pub validator_addresses: Vec<Ss58Address>,
+}
+
+impl PolkadotNominateTxRequest {
+    pub(crate) fn check_addresses(&self, network: u16) -> Result<(), AddressError> {
+        self.stash_account.check_network(network)?;
+        self.validator_addresses
+            .iter()
+            .try_for_each(|address| address.check_network(network))
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct PolkadotWithdrawUnbondedTxRequest {
     // Address
-    pub stash_account: String,
+    pub stash_account: Ss58Address,
+}
+
+impl PolkadotWithdrawUnbondedTxRequest {
+    pub(crate) fn check_addresses(&self, network: u16) -> Result<(), AddressError> {
+        self.stash_account.check_network(network)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct PolkadotChillTxRequest {
     // Address
-    pub stash_account: String,
+    pub stash_account: Ss58Address,
+}
+
+impl PolkadotChillTxRequest {
+    pub(crate) fn check_addresses(&self, network: u16) -> Result<(), AddressError> {
+        self.stash_account.check_network(network)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct PolkadotSetPayeeTxRequest {
     // Address
-    pub stash_account: String,
+    pub stash_account: Ss58Address,
     // Address
-    pub reward_destination: String,
+    pub reward_destination: Ss58Address,
+}
+
+impl PolkadotSetPayeeTxRequest {
+    pub(crate) fn check_addresses(&self, network: u16) -> Result<(), AddressError> {
+        self.stash_account.check_network(network)?;
+        self.reward_destination.check_network(network)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct PolkadotJoinPoolTxRequest {
     pub account_id: Uuid,
-    pub member_account: String,
-    pub amount_planck: String,
+    pub member_account: Ss58Address,
+    pub amount_planck: Planck,
     pub pool_id: String,
 }
 
+impl PolkadotJoinPoolTxRequest {
+    pub(crate) fn check_addresses(&self, network: u16) -> Result<(), AddressError> {
+        self.member_account.check_network(network)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct PolkadotBondExtraPoolTxRequest {
-    pub member_account: String,
-    pub amount_planck: String,
+    pub member_account: Ss58Address,
+    pub amount_planck: Planck,
+}
+
+impl PolkadotBondExtraPoolTxRequest {
+    pub(crate) fn check_addresses(&self, network: u16) -> Result<(), AddressError> {
+        self.member_account.check_network(network)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct PolkadotPoolTxRequest {
-    pub member_account: String,
+    pub member_account: Ss58Address,
+}
+
+impl PolkadotPoolTxRequest {
+    pub(crate) fn check_addresses(&self, network: u16) -> Result<(), AddressError> {
+        self.member_account.check_network(network)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct PolkadotUnbondFromPoolTxRequest {
-    pub member_account: String,
-    pub amount_planck: String,
+    pub member_account: Ss58Address,
+    pub amount_planck: Planck,
+}
+
+impl PolkadotUnbondFromPoolTxRequest {
+    pub(crate) fn check_addresses(&self, network: u16) -> Result<(), AddressError> {
+        self.member_account.check_network(network)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -308,7 +397,7 @@ pub struct PolkadotTxStatusResponseDataLifetime {
 #[serde(rename_all = "snake_case")]
 pub struct PolkadotTxStatusResponseDataAccountDisplay {
     // Address
-    pub address: String,
+    pub address: Lenient<Ss58Address>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -329,12 +418,12 @@ pub struct PolkadotTxStatusResponseData {
     pub transfer: Option<String>,
     pub event: Vec<PolkadotTxStatusResponseDataEvent>,
     pub event_count: u64,
-    pub fee: String,
-    pub fee_used: String,
-    pub error: PolkadotTxStatusResponseDataError,
+    pub fee: Amount,
+    pub fee_used: Amount,
+    pub error: Option<PolkadotTxStatusResponseDataError>,
     pub finalized: bool,
     pub lifetime: PolkadotTxStatusResponseDataLifetime,
-    pub tip: u64,
+    pub tip: IntAmount,
     pub account_display: PolkadotTxStatusResponseDataAccountDisplay,
     pub block_hash: String,
     pub pending: bool,
@@ -353,7 +442,7 @@ pub struct PolkadotTxStatusResponse {
 #[serde(rename_all = "camelCase")]
 pub struct PolkadotTxDecodingResponse {
     // Address
-    pub address: String,
+    pub address: Lenient<Ss58Address>,
     pub asset_id: u64,
     pub block_hash: String,
     pub block_number: u64,
@@ -364,7 +453,7 @@ pub struct PolkadotTxDecodingResponse {
     pub nonce: String,
     pub signed_extensions: Vec<String>,
     pub spec_version: String,
-    pub tip: String,
+    pub tip: Amount,
     pub transaction_version: String,
     pub version: u64,
 }
@@ -377,472 +466,739 @@ pub struct PolkadotPrepareTxRequest {
 }
 
 #[derive(Clone, Debug)]
-pub struct KilnPolkadotClient {
-    pub bearer_token: String,
+pub struct KilnPolkadotClient<M: Mode = Blocking> {
     pub base_url: String,
+    http: HttpClient<M>,
 }
 
-impl From<&Kiln> for KilnPolkadotClient {
-    fn from(kiln: &Kiln) -> Self {
+impl<M: Mode> From<&Kiln<M>> for KilnPolkadotClient<M> {
+    fn from(kiln: &Kiln<M>) -> Self {
         let url: String = format!("{}/dot", kiln.base_url);
-        let bearer_token: String = format!("Bearer {}", kiln.api_token);
 
         Self {
-            bearer_token,
             base_url: url,
+            http: kiln.http.clone(),
         }
     }
 }
 
-impl KilnPolkadotClient {
-    pub fn get_stakes(
-        &self,
-        stakes_request: &PolkadotGetStakesRequest,
-    ) -> Result<ReturnedData<Vec<PolkadotStakesResponse>>, ureq::Error> {
-        let PolkadotGetStakesRequest {
-            addresses,
-            accounts,
-        } = stakes_request;
+for_each_mode! { <M>
+    impl KilnPolkadotClient<M> {
+        pub async fn get_stakes(
+            &self,
+            stakes_request: &PolkadotGetStakesRequest,
+        ) -> Result<ReturnedData<Vec<PolkadotStakesResponse>>, KilnError> {
+            let PolkadotGetStakesRequest {
+                addresses,
+                accounts,
+            } = stakes_request;
+
+            let url = Query::new()
+                .list("addresses", addresses.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .url(&format!("{}/stakes", self.base_url));
+
+            self.http.get("kiln.dot.get_stakes", &url).await
+        }
 
-        let addresses = addresses.join(",");
+        pub async fn get_rewards(
+            &self,
+            reward_request: &PolkadotRewardRequest,
+        ) -> Result<ReturnedData<Vec<PolkadotReward>>, KilnError> {
+            let PolkadotRewardRequest {
+                addresses,
+                accounts,
+                pool_ids,
+                format,
+                start_date,
+                end_date,
+            } = reward_request;
+
+            let url = Query::new()
+                .list("addresses", addresses.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .list("pool_ids", pool_ids.iter().flatten())
+                .value("format", format.as_ref())
+                .value("start_date", start_date)
+                .option("end_date", end_date.as_ref())
+                .url(&format!("{}/rewards", self.base_url));
+
+            self.http.get("kiln.dot.get_rewards", &url).await
+        }
 
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
+        pub async fn get_operations(
+            &self,
+            operations_request: &PolkadotOperationsRequest,
+        ) -> Result<ReturnedData<Vec<PolkadotOperation>>, KilnError> {
+            let PolkadotOperationsRequest {
+                addresses,
+                accounts,
+                start_date,
+                end_date,
+            } = operations_request;
+
+            let url = Query::new()
+                .list("addresses", addresses.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .value("start_date", start_date)
+                .option("end_date", end_date.as_ref())
+                .url(&format!("{}/operations", self.base_url));
+
+            self.http.get("kiln.dot.get_operations", &url).await
+        }
 
-        let url: String = format!(
-            "{}/stakes?addresses={}&accounts={}",
-            self.base_url, addresses, accounts
-        );
+        pub async fn get_reports(
+            &self,
+            reports_request: &PolkadotReportsRequest,
+        ) -> Result<Vec<u8>, KilnError> {
+            let PolkadotReportsRequest {
+                addresses,
+                accounts,
+            } = reports_request;
+
+            let url = Query::new()
+                .list("addresses", addresses.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .url(&format!("{}/reports", self.base_url));
+
+            self.http.get_bytes("kiln.dot.get_reports", &url).await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<PolkadotStakesResponse>>>();
+        pub async fn get_network_stats(
+            &self,
+        ) -> Result<ReturnedData<PolkadotNetworkStatsResponse>, KilnError> {
+            let url: String = format!("{}/network-stats", self.base_url);
 
-        data
-    }
+            self.http.get("kiln.dot.get_network_stats", &url).await
+        }
 
-    pub fn get_rewards(
-        &self,
-        reward_request: &PolkadotRewardRequest,
-    ) -> Result<ReturnedData<Vec<PolkadotReward>>, ureq::Error> {
-        let PolkadotRewardRequest {
-            addresses,
-            accounts,
-            pool_ids,
-            format,
-            start_date,
-            end_date,
-        } = reward_request;
+        pub async fn post_bond_tx(
+            &self,
+            bond_tx_request: &PolkadotBondTxRequest,
+        ) -> Result<ReturnedData<PolkadotTxResponse>, KilnError> {
+            bond_tx_request.check_addresses(NETWORK)?;
 
-        let addresses = addresses.join(",");
+            let url = format!("{}/transaction/bond", self.base_url);
 
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let pool_ids = pool_ids.join(",");
-
-        let url = format!(
-            "{}/rewards?addresses={}&accounts={}&pool_ids={}&format={}&start_date={}&end_date={}",
-            self.base_url,
-            addresses,
-            accounts,
-            pool_ids,
-            format.as_ref(),
-            start_date,
-            end_date
-        );
+            self.http
+                .post_tx("kiln.dot.post_bond_tx", &url, bond_tx_request)
+                .await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<PolkadotReward>>>();
+        pub async fn post_bond_extra_tx(
+            &self,
+            bond_extra_tx_request: &PolkadotBondExtraTxRequest,
+        ) -> Result<ReturnedData<PolkadotTxResponse>, KilnError> {
+            bond_extra_tx_request.check_addresses(NETWORK)?;
 
-        data
-    }
+            let url = format!("{}/transaction/bond-extra", self.base_url);
 
-    pub fn get_operations(
-        &self,
-        operations_request: &PolkadotOperationsRequest,
-    ) -> Result<ReturnedData<Vec<PolkadotOperation>>, ureq::Error> {
-        let PolkadotOperationsRequest {
-            addresses,
-            accounts,
-            start_date,
-            end_date,
-        } = operations_request;
+            self.http
+                .post_tx("kiln.dot.post_bond_extra_tx", &url, bond_extra_tx_request)
+                .await
+        }
 
-        let addresses = addresses.join(",");
+        pub async fn post_rebond_tx(
+            &self,
+            rebond_tx_request: &PolkadotRebondTxRequest,
+        ) -> Result<ReturnedData<PolkadotTxResponse>, KilnError> {
+            rebond_tx_request.check_addresses(NETWORK)?;
 
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
+            let url = format!("{}/transaction/rebond", self.base_url);
 
-        let url = format!(
-            "{}/operations?addresses={}&accounts={}&start_date={}&end_date={}",
-            self.base_url, addresses, accounts, start_date, end_date
-        );
+            self.http
+                .post_tx("kiln.dot.post_rebond_tx", &url, rebond_tx_request)
+                .await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<PolkadotOperation>>>();
+        pub async fn post_unbond_tx(
+            &self,
+            unbond_tx_request: &PolkadotUnbondTxRequest,
+        ) -> Result<ReturnedData<PolkadotTxResponse>, KilnError> {
+            unbond_tx_request.check_addresses(NETWORK)?;
 
-        data
-    }
+            let url = format!("{}/transaction/unbond", self.base_url);
 
-    pub fn get_reports(
-        &self,
-        reports_request: &PolkadotReportsRequest,
-    ) -> Result<Vec<u8>, ureq::Error> {
-        let PolkadotReportsRequest {
-            addresses,
-            accounts,
-        } = reports_request;
+            self.http
+                .post_tx("kiln.dot.post_unbond_tx", &url, unbond_tx_request)
+                .await
+        }
 
-        let addresses = addresses.join(",");
+        pub async fn post_nominate_tx(
+            &self,
+            nominate_tx_request: &PolkadotNominateTxRequest,
+        ) -> Result<ReturnedData<PolkadotTxResponse>, KilnError> {
+            nominate_tx_request.check_addresses(NETWORK)?;
 
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
+            let url = format!("{}/transaction/nominate", self.base_url);
 
-        let url: String = format!(
-            "{}/reports?addresses={}&accounts={}",
-            self.base_url, addresses, accounts
-        );
+            self.http
+                .post_tx("kiln.dot.post_nominate_tx", &url, nominate_tx_request)
+                .await
+        }
 
-        let mut file_bytes: Vec<u8> = Vec::new();
+        pub async fn post_withdraw_unbonded_tx(
+            &self,
+            withdraw_unbonded_tx_request: &PolkadotWithdrawUnbondedTxRequest,
+        ) -> Result<ReturnedData<PolkadotTxResponse>, KilnError> {
+            withdraw_unbonded_tx_request.check_addresses(NETWORK)?;
+
+            let url = format!("{}/transaction/withdraw-unbonded", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.dot.post_withdraw_unbonded_tx",
+                    &url,
+                    withdraw_unbonded_tx_request,
+                )
+                .await
+        }
 
-        ureq::get(url)
-            .header("accept", "application/octet-stream")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .as_reader()
-            .read_to_end(&mut file_bytes)?;
+        pub async fn post_chill_tx(
+            &self,
+            chill_tx_request: &PolkadotChillTxRequest,
+        ) -> Result<ReturnedData<PolkadotTxResponse>, KilnError> {
+            chill_tx_request.check_addresses(NETWORK)?;
 
-        Ok(file_bytes)
-    }
+            let url = format!("{}/transaction/chill", self.base_url);
 
-    pub fn get_network_stats(
-        &self,
-    ) -> Result<ReturnedData<PolkadotNetworkStatsResponse>, ureq::Error> {
-        let url: String = format!("{}/network-stats", self.base_url);
+            self.http
+                .post_tx("kiln.dot.post_chill_tx", &url, chill_tx_request)
+                .await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<PolkadotNetworkStatsResponse>>();
+        pub async fn post_set_payee_tx(
+            &self,
+            join_pool_tx_request: &PolkadotSetPayeeTxRequest,
+        ) -> Result<ReturnedData<PolkadotTxResponse>, KilnError> {
+            join_pool_tx_request.check_addresses(NETWORK)?;
 
-        data
-    }
+            let url = format!("{}/transaction/set-payee", self.base_url);
 
-    pub fn post_bond_tx(
-        &self,
-        bond_tx_request: &PolkadotBondTxRequest,
-    ) -> Result<ReturnedData<PolkadotTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/bond", self.base_url);
+            self.http
+                .post_tx("kiln.dot.post_set_payee_tx", &url, join_pool_tx_request)
+                .await
+        }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(bond_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PolkadotTxResponse>>();
+        pub async fn post_join_pool_tx(
+            &self,
+            join_pool_tx_request: &PolkadotJoinPoolTxRequest,
+        ) -> Result<ReturnedData<PolkadotTxResponse>, KilnError> {
+            join_pool_tx_request.check_addresses(NETWORK)?;
 
-        data
-    }
+            let url = format!("{}/transaction/join-pool", self.base_url);
 
-    pub fn post_bond_extra_tx(
-        &self,
-        bond_extra_tx_request: &PolkadotBondExtraTxRequest,
-    ) -> Result<ReturnedData<PolkadotTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/bond-extra", self.base_url);
+            self.http
+                .post_tx("kiln.dot.post_join_pool_tx", &url, join_pool_tx_request)
+                .await
+        }
+
+        pub async fn post_bond_extra_pool_tx(
+            &self,
+            bond_extra_pool_tx_request: &PolkadotBondExtraPoolTxRequest,
+        ) -> Result<ReturnedData<PolkadotTxResponse>, KilnError> {
+            bond_extra_pool_tx_request.check_addresses(NETWORK)?;
+
+            let url = format!("{}/transaction/bond-extra-pool", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.dot.post_bond_extra_pool_tx",
+                    &url,
+                    bond_extra_pool_tx_request,
+                )
+                .await
+        }
+
+        pub async fn post_bond_rewards_to_pool_tx(
+            &self,
+            bond_rewards_to_pool_tx_request: &PolkadotPoolTxRequest,
+        ) -> Result<ReturnedData<PolkadotTxResponse>, KilnError> {
+            bond_rewards_to_pool_tx_request.check_addresses(NETWORK)?;
+
+            let url = format!("{}/transaction/bond-rewards-pool", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.dot.post_bond_rewards_to_pool_tx",
+                    &url,
+                    bond_rewards_to_pool_tx_request,
+                )
+                .await
+        }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(bond_extra_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PolkadotTxResponse>>();
+        pub async fn post_claim_payout_from_pool_tx(
+            &self,
+            claim_tx_request: &PolkadotPoolTxRequest,
+        ) -> Result<ReturnedData<PolkadotTxResponse>, KilnError> {
+            claim_tx_request.check_addresses(NETWORK)?;
+
+            let url = format!("{}/transaction/claim-payout-pool", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.dot.post_claim_payout_from_pool_tx",
+                    &url,
+                    claim_tx_request,
+                )
+                .await
+        }
+
+        pub async fn post_unbond_from_pool_tx(
+            &self,
+            unbond_from_pool_tx_request: &PolkadotUnbondFromPoolTxRequest,
+        ) -> Result<ReturnedData<PolkadotTxResponse>, KilnError> {
+            unbond_from_pool_tx_request.check_addresses(NETWORK)?;
+
+            let url = format!("{}/transaction/unbond-pool", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.dot.post_unbond_from_pool_tx",
+                    &url,
+                    unbond_from_pool_tx_request,
+                )
+                .await
+        }
+
+        pub async fn post_withdraw_unbonded_from_pool_tx(
+            &self,
+            pool_tx_request: &PolkadotPoolTxRequest,
+        ) -> Result<ReturnedData<PolkadotTxResponse>, KilnError> {
+            pool_tx_request.check_addresses(NETWORK)?;
+
+            let url = format!("{}/transaction/withdraw-unbonded-pool", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.dot.post_withdraw_unbonded_from_pool_tx",
+                    &url,
+                    pool_tx_request,
+                )
+                .await
+        }
 
-        data
+        pub async fn get_tx_status(
+            &self,
+            tx_hash: &str,
+        ) -> Result<ReturnedData<PolkadotTxStatusResponse>, KilnError> {
+            let url = Query::new()
+                .value("tx_hash", tx_hash)
+                .url(&format!("{}/transaction/status", self.base_url));
+
+            self.http.get("kiln.dot.get_tx_status", &url).await
+        }
+
+        pub async fn get_tx_decoding(
+            &self,
+            tx_serialized: &str,
+        ) -> Result<ReturnedData<PolkadotTxDecodingResponse>, KilnError> {
+            let url = Query::new()
+                .value("tx_serialized", tx_serialized)
+                .url(&format!("{}/transaction/decode", self.base_url));
+
+            self.http.get("kiln.dot.get_tx_decoding", &url).await
+        }
+
+        pub async fn post_prepare_tx(
+            &self,
+            prepare_transaction_request: &PolkadotPrepareTxRequest,
+        ) -> Result<ReturnedData<PrepareTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/prepare", self.base_url,);
+
+            self.http
+                .post_tx(
+                    "kiln.dot.post_prepare_tx",
+                    &url,
+                    prepare_transaction_request,
+                )
+                .await
+        }
+
+        pub async fn post_broadcast_tx(
+            &self,
+            broadcast_transaction_request: &BroadcastTxRequest,
+        ) -> Result<ReturnedData<BroadcastTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/broadcast", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.dot.post_broadcast_tx",
+                    &url,
+                    broadcast_transaction_request,
+                )
+                .await
+        }
     }
+}
 
-    pub fn post_rebond_tx(
-        &self,
-        rebond_tx_request: &PolkadotRebondTxRequest,
-    ) -> Result<ReturnedData<PolkadotTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/rebond", self.base_url);
+impl<M: Mode> KilnPolkadotClient<M> {
+    pub fn stakes(&self) -> PolkadotStakesBuilder<'_, M> {
+        PolkadotStakesBuilder::new(self)
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(rebond_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PolkadotTxResponse>>();
+    pub fn rewards(&self) -> PolkadotRewardsBuilder<'_, NoStartDate, M> {
+        PolkadotRewardsBuilder::new(self)
+    }
 
-        data
+    pub fn operations(&self) -> PolkadotOperationsBuilder<'_, NoStartDate, M> {
+        PolkadotOperationsBuilder::new(self)
     }
+}
 
-    pub fn post_unbond_tx(
-        &self,
-        unbond_tx_request: &PolkadotUnbondTxRequest,
-    ) -> Result<ReturnedData<PolkadotTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/unbond", self.base_url);
+// `kiln.polkadot().stakes()`
+#[derive(Debug)]
+pub struct PolkadotStakesBuilder<'a, M: Mode = Blocking> {
+    client: &'a KilnPolkadotClient<M>,
+    addresses: Option<Vec<String>>,
+    accounts: Option<Vec<Uuid>>,
+}
+
+impl<'a, M: Mode> PolkadotStakesBuilder<'a, M> {
+    fn new(client: &'a KilnPolkadotClient<M>) -> Self {
+        Self {
+            client,
+            addresses: None,
+            accounts: None,
+        }
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(unbond_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PolkadotTxResponse>>();
+    pub fn addresses(self, addresses: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            addresses: filter(addresses),
+            ..self
+        }
+    }
 
-        data
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
     }
 
-    pub fn post_nominate_tx(
-        &self,
-        nominate_tx_request: &PolkadotNominateTxRequest,
-    ) -> Result<ReturnedData<PolkadotTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/nominate", self.base_url);
+    pub fn into_request(self) -> PolkadotGetStakesRequest {
+        PolkadotGetStakesRequest {
+            addresses: self.addresses,
+            accounts: self.accounts,
+        }
+    }
+}
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(nominate_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PolkadotTxResponse>>();
+for_each_mode! { <M>
+    impl PolkadotStakesBuilder<'_, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<PolkadotStakesResponse>>, KilnError> {
+            let client = self.client;
 
-        data
+            client.get_stakes(&self.into_request()).await
+        }
     }
+}
 
-    pub fn post_withdraw_unbonded_tx(
-        &self,
-        withdraw_unbonded_tx_request: &PolkadotWithdrawUnbondedTxRequest,
-    ) -> Result<ReturnedData<PolkadotTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/withdraw-unbonded", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(withdraw_unbonded_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PolkadotTxResponse>>();
+// `kiln.polkadot().rewards()`
+#[derive(Debug)]
+pub struct PolkadotRewardsBuilder<'a, S, M: Mode = Blocking> {
+    client: &'a KilnPolkadotClient<M>,
+    addresses: Option<Vec<String>>,
+    pool_ids: Option<Vec<String>>,
+    accounts: Option<Vec<Uuid>>,
+    format: PolkadotResponseFormat,
+    start_date: S,
+    end_date: Option<chrono::DateTime<chrono::Utc>>,
+}
 
-        data
+impl<'a, M: Mode> PolkadotRewardsBuilder<'a, NoStartDate, M> {
+    fn new(client: &'a KilnPolkadotClient<M>) -> Self {
+        Self {
+            client,
+            addresses: None,
+            pool_ids: None,
+            accounts: None,
+            format: PolkadotResponseFormat::default(),
+            start_date: NoStartDate,
+            end_date: None,
+        }
     }
+}
 
-    pub fn post_chill_tx(
-        &self,
-        chill_tx_request: &PolkadotChillTxRequest,
-    ) -> Result<ReturnedData<PolkadotTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/chill", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(chill_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PolkadotTxResponse>>();
+impl<'a, S, M: Mode> PolkadotRewardsBuilder<'a, S, M> {
+    pub fn addresses(self, addresses: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            addresses: filter(addresses),
+            ..self
+        }
+    }
 
-        data
+    pub fn pool_ids(self, pool_ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            pool_ids: filter(pool_ids),
+            ..self
+        }
     }
 
-    pub fn post_set_payee_tx(
-        &self,
-        join_pool_tx_request: &PolkadotSetPayeeTxRequest,
-    ) -> Result<ReturnedData<PolkadotTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/set-payee", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(join_pool_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PolkadotTxResponse>>();
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
+    }
 
-        data
+    pub fn format(self, format: PolkadotResponseFormat) -> Self {
+        Self { format, ..self }
     }
 
-    pub fn post_join_pool_tx(
-        &self,
-        join_pool_tx_request: &PolkadotJoinPoolTxRequest,
-    ) -> Result<ReturnedData<PolkadotTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/join-pool", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(join_pool_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PolkadotTxResponse>>();
+    pub fn until(self, end_date: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            end_date: Some(end_date),
+            ..self
+        }
+    }
 
-        data
+    pub fn since(
+        self,
+        start_date: chrono::DateTime<chrono::Utc>,
+    ) -> PolkadotRewardsBuilder<'a, StartDate, M> {
+        PolkadotRewardsBuilder {
+            client: self.client,
+            addresses: self.addresses,
+            pool_ids: self.pool_ids,
+            accounts: self.accounts,
+            format: self.format,
+            start_date: StartDate(start_date),
+            end_date: self.end_date,
+        }
     }
+}
 
-    pub fn post_bond_extra_pool_tx(
-        &self,
-        bond_extra_pool_tx_request: &PolkadotBondExtraPoolTxRequest,
-    ) -> Result<ReturnedData<PolkadotTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/bond-extra-pool", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(bond_extra_pool_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PolkadotTxResponse>>();
+impl<M: Mode> PolkadotRewardsBuilder<'_, StartDate, M> {
+    pub fn into_request(self) -> PolkadotRewardRequest {
+        PolkadotRewardRequest {
+            addresses: self.addresses,
+            pool_ids: self.pool_ids,
+            accounts: self.accounts,
+            format: self.format,
+            start_date: self.start_date.0,
+            end_date: self.end_date,
+        }
+    }
+}
 
-        data
+for_each_mode! { <M>
+    impl PolkadotRewardsBuilder<'_, StartDate, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<PolkadotReward>>, KilnError> {
+            let client = self.client;
+
+            client.get_rewards(&self.into_request()).await
+        }
     }
+}
 
-    pub fn post_bond_rewards_to_pool_tx(
-        &self,
-        bond_rewards_to_pool_tx_request: &PolkadotPoolTxRequest,
-    ) -> Result<ReturnedData<PolkadotTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/bond-rewards-pool", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(bond_rewards_to_pool_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PolkadotTxResponse>>();
+// `kiln.polkadot().operations()`
+#[derive(Debug)]
+pub struct PolkadotOperationsBuilder<'a, S, M: Mode = Blocking> {
+    client: &'a KilnPolkadotClient<M>,
+    addresses: Option<Vec<String>>,
+    accounts: Option<Vec<Uuid>>,
+    start_date: S,
+    end_date: Option<chrono::DateTime<chrono::Utc>>,
+}
 
-        data
+impl<'a, M: Mode> PolkadotOperationsBuilder<'a, NoStartDate, M> {
+    fn new(client: &'a KilnPolkadotClient<M>) -> Self {
+        Self {
+            client,
+            addresses: None,
+            accounts: None,
+            start_date: NoStartDate,
+            end_date: None,
+        }
     }
+}
 
-    pub fn post_claim_payout_from_pool_tx(
-        &self,
-        claim_tx_request: &PolkadotPoolTxRequest,
-    ) -> Result<ReturnedData<PolkadotTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/claim-payout-pool", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(claim_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PolkadotTxResponse>>();
+impl<'a, S, M: Mode> PolkadotOperationsBuilder<'a, S, M> {
+    pub fn addresses(self, addresses: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            addresses: filter(addresses),
+            ..self
+        }
+    }
 
-        data
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
     }
 
-    pub fn post_unbond_from_pool_tx(
-        &self,
-        unbond_from_pool_tx_request: &PolkadotUnbondFromPoolTxRequest,
-    ) -> Result<ReturnedData<PolkadotTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/unbond-pool", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(unbond_from_pool_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PolkadotTxResponse>>();
+    pub fn until(self, end_date: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            end_date: Some(end_date),
+            ..self
+        }
+    }
 
-        data
+    pub fn since(
+        self,
+        start_date: chrono::DateTime<chrono::Utc>,
+    ) -> PolkadotOperationsBuilder<'a, StartDate, M> {
+        PolkadotOperationsBuilder {
+            client: self.client,
+            addresses: self.addresses,
+            accounts: self.accounts,
+            start_date: StartDate(start_date),
+            end_date: self.end_date,
+        }
+    }
+}
+
+impl<M: Mode> PolkadotOperationsBuilder<'_, StartDate, M> {
+    pub fn into_request(self) -> PolkadotOperationsRequest {
+        PolkadotOperationsRequest {
+            addresses: self.addresses,
+            accounts: self.accounts,
+            start_date: self.start_date.0,
+            end_date: self.end_date,
+        }
     }
+}
 
-    pub fn post_withdraw_unbonded_from_pool_tx(
-        &self,
-        pool_tx_request: &PolkadotPoolTxRequest,
-    ) -> Result<ReturnedData<PolkadotTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/withdraw-unbonded-pool", self.base_url);
-
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(pool_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PolkadotTxResponse>>();
+for_each_mode! { <M>
+    impl PolkadotOperationsBuilder<'_, StartDate, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<PolkadotOperation>>, KilnError> {
+            let client = self.client;
 
-        data
+            client.get_operations(&self.into_request()).await
+        }
     }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod polkadot_test {
+
+    use super::*;
+    use crate::query::query_test_support::{date, fixture, requested_url};
 
-    pub fn get_tx_status(
-        &self,
-        tx_hash: &str,
-    ) -> Result<ReturnedData<PolkadotTxStatusResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/status?tx_hash={}", self.base_url, tx_hash);
-
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<PolkadotTxStatusResponse>>();
+    #[test]
+    fn get_stakes() {
+        let url = requested_url(|kiln| {
+            kiln.polkadot().get_stakes(&PolkadotGetStakesRequest {
+                addresses: Some(vec!["1a".into(), "1ab".into()]),
+                ..Default::default()
+            })
+        });
 
-        data
+        assert_eq!(url, "https://api.kiln.fi/v1/dot/stakes?addresses=1a,1ab");
     }
 
-    pub fn get_tx_decoding(
-        &self,
-        tx_serialized: &str,
-    ) -> Result<PolkadotTxDecodingResponse, ureq::Error> {
-        let url: String = format!(
-            "{}/transaction/decode?tx_serialized={}",
-            self.base_url, tx_serialized
+    #[test]
+    fn get_rewards() {
+        let url = requested_url(|kiln| {
+            kiln.polkadot().get_rewards(&PolkadotRewardRequest {
+                addresses: Some(vec!["1a".into()]),
+                pool_ids: None,
+                accounts: None,
+                format: PolkadotResponseFormat::Era,
+                start_date: date("2024-01-01T00:00:00Z"),
+                end_date: Some(date("2024-01-31T00:00:00Z")),
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/dot/rewards?addresses=1a&format=era&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z"
         );
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<PolkadotTxDecodingResponse>();
-
-        data
+    #[test]
+    fn get_operations() {
+        let url = requested_url(|kiln| {
+            kiln.polkadot().get_operations(&PolkadotOperationsRequest {
+                addresses: None,
+                accounts: Some(vec![Uuid::nil()]),
+                start_date: date("2024-01-01T00:00:00Z"),
+                end_date: Some(date("2024-01-31T00:00:00Z")),
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/dot/operations?accounts=00000000-0000-0000-0000-000000000000&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z"
+        );
     }
 
-    pub fn post_prepare_tx(
-        &self,
-        prepare_transaction_request: &PolkadotPrepareTxRequest,
-    ) -> Result<ReturnedData<PrepareTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/prepare", self.base_url,);
+    #[test]
+    fn get_reports() {
+        let url = requested_url(|kiln| {
+            kiln.polkadot().get_reports(&PolkadotReportsRequest {
+                addresses: Some(vec!["1a".into()]),
+                ..Default::default()
+            })
+        });
+
+        assert_eq!(url, "https://api.kiln.fi/v1/dot/reports?addresses=1a");
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(prepare_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PrepareTxResponse>>();
+    #[test]
+    fn get_tx_status() {
+        let url = requested_url(|kiln| kiln.polkadot().get_tx_status("0xhash"));
 
-        data
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/dot/transaction/status?tx_hash=0xhash"
+        );
     }
 
-    pub fn post_broadcast_tx(
-        &self,
-        broadcast_transaction_request: &BroadcastTxRequest,
-    ) -> Result<ReturnedData<BroadcastTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/broadcast", self.base_url);
+    #[test]
+    fn get_tx_decoding() {
+        let url = requested_url(|kiln| kiln.polkadot().get_tx_decoding("ab+/="));
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(broadcast_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<BroadcastTxResponse>>();
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/dot/transaction/decode?tx_serialized=ab%2B%2F%3D"
+        );
+    }
+
+    #[test]
+    fn responses_match_the_fixtures() {
+        let rewards: ReturnedData<Vec<PolkadotReward>> = fixture("polkadot/rewards.json");
+        let chill: ReturnedData<PolkadotTxResponse> = fixture("polkadot/chill_tx.json");
+
+        assert!(matches!(rewards.data[0], PolkadotReward::ByDay { .. }));
+        assert!(matches!(
+            rewards.data[1],
+            PolkadotReward::ByEra { era: 1412, .. }
+        ));
+        assert!(chill.data.unsigned_tx.method.args.payee.is_none());
+
+        fixture::<ReturnedData<Vec<PolkadotStakesResponse>>>("polkadot/stakes.json");
+        fixture::<ReturnedData<Vec<PolkadotOperation>>>("polkadot/operations.json");
+        fixture::<ReturnedData<PolkadotNetworkStatsResponse>>("polkadot/network_stats.json");
+        fixture::<ReturnedData<PolkadotTxResponse>>("polkadot/tx.json");
+        fixture::<ReturnedData<PolkadotTxStatusResponse>>("polkadot/tx_status.json");
+        fixture::<ReturnedData<PolkadotTxDecodingResponse>>("polkadot/tx_decoding.json");
+        fixture::<ReturnedData<PrepareTxResponse>>("polkadot/prepare_tx.json");
+        fixture::<ReturnedData<BroadcastTxResponse>>("polkadot/broadcast_tx.json");
+    }
 
-        data
+    #[test]
+    fn kusama_accounts_are_rejected_before_sending() {
+        // nothing listens there : an answer would be a transport error
+        let kiln = Kiln::builder()
+            .api_token("kiln_test_token")
+            .base_url("http://127.0.0.1:9/v1")
+            .seal()
+            .build()
+            .unwrap();
+
+        let chill = PolkadotChillTxRequest {
+            stash_account: "CbeARaCxXBbUrE5xArpY7Lkj9611oLe8Q1tgQNiBtRFnrrh"
+                .parse()
+                .unwrap(),
+        };
+
+        assert!(matches!(
+            kiln.polkadot().post_chill_tx(&chill),
+            Err(KilnError::InvalidInput(_))
+        ));
     }
 }
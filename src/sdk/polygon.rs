@@ -1,30 +1,36 @@
-use std::io::Read;
-
 use serde::{Deserialize, Serialize};
-use strum_macros::{AsRefStr, EnumString};
 use uuid::Uuid;
 
+use crate::address::EthAddress;
+use crate::amount::{Amount, FloatAmount};
+use crate::denomination::Wei;
+use crate::environment::{self, KilnEnvironment};
+use crate::errors::KilnError;
+use crate::http::HttpClient;
+use crate::mode::{for_each_mode, Blocking, Mode};
+use crate::query::Query;
 use crate::{response_format::ReturnedData, Kiln};
 
+use super::builders::{filter, NoStartDate, RewardsBuilder, StartDate};
 use super::{
     BroadcastTxRequest, BroadcastTxResponse, ChainID, PostStakesRequest, PostStakesResponse,
     PrepareTxResponse, Reward, RewardRequest,
 };
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 #[serde(rename_all = "snake_case")]
 pub struct PolygonGetStakesRequest {
     // Addresses
-    pub wallets: Vec<String>,
-    pub accounts: Vec<Uuid>,
-    pub validators: Vec<String>,
+    pub wallets: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
+    pub validators: Option<Vec<String>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct PolygonUnboundWithID {
     pub nonce: u64,
-    pub balance: String,
+    pub balance: Amount,
     pub epoch: u64,
     pub is_complete: bool,
 }
@@ -36,14 +42,14 @@ pub struct PolygonStakesResponse {
     pub validator_index: String,
     pub state: String,
     pub delegated_block: u64,
-    pub balance: String,
+    pub balance: Amount,
     pub shares: String,
-    pub unbounded_balance: String,
+    pub unbounded_balance: Amount,
     pub unbounded_epoch: u64,
     pub is_unbounding_complete: bool,
-    pub rewards: String,
+    pub rewards: Amount,
     pub net_apy: f64,
-    pub available_rewards: String,
+    pub available_rewards: Amount,
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub unbounds_with_ids: Vec<PolygonUnboundWithID>,
 }
@@ -52,149 +58,141 @@ pub struct PolygonStakesResponse {
 #[serde(rename_all = "snake_case")]
 pub struct PolygonOperationsRequest {
     // Addresses
-    pub wallets: Vec<String>,
-    pub validator_indexes: Vec<String>,
-    pub accounts: Vec<Uuid>,
+    pub wallets: Option<Vec<String>>,
+    pub validator_indexes: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
     pub start_date: chrono::DateTime<chrono::Utc>,
-    pub end_date: chrono::DateTime<chrono::Utc>,
+    pub end_date: Option<chrono::DateTime<chrono::Utc>>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-#[serde(rename_all = "snake_case")]
-pub enum PolygonOperation {
-    ShareMinted {
-        r#type: String,
-        block: u64,
-        time: chrono::DateTime<chrono::Utc>,
-        tx_hash: String,
-        tx_index: u64,
-        tx_sender: String,
-        tx_method_name: String,
-        tx_gas_used: String,
-        tx_effective_gas_price: String,
-        tx_cumulative_gas_used: String,
-        validator_id: u64,
-        user: String,
-        amount: String,
-        tokens: String,
-    },
-    ShareBurned {
-        r#type: String,
-        block: u64,
-        time: chrono::DateTime<chrono::Utc>,
-        tx_hash: String,
-        tx_index: u64,
-        tx_sender: String,
-        tx_method_name: String,
-        tx_gas_used: String,
-        tx_effective_gas_price: String,
-        tx_cumulative_gas_used: String,
-        validator_id: u64,
-        user: String,
-        amount: String,
-        tokens: String,
-    },
-    ShareBurnedWithID {
-        r#type: String,
-        block: u64,
-        time: chrono::DateTime<chrono::Utc>,
-        tx_hash: String,
-        tx_index: u64,
-        tx_sender: String,
-        tx_method_name: String,
-        tx_gas_used: String,
-        tx_effective_gas_price: String,
-        tx_cumulative_gas_used: String,
-        validator_id: u64,
-        user: String,
-        amount: String,
-        tokens: String,
-        nonce: String,
-    },
-    DelegatorRestaked {
-        r#type: String,
-        block: u64,
-        time: chrono::DateTime<chrono::Utc>,
-        tx_hash: String,
-        tx_index: u64,
-        tx_sender: String,
-        tx_method_name: String,
-        tx_gas_used: String,
-        tx_effective_gas_price: String,
-        tx_cumulative_gas_used: String,
-        validator_id: u64,
-        user: String,
-        total_staked: String,
-    },
-    DelegatorUnstaked {
-        r#type: String,
-        block: u64,
-        time: chrono::DateTime<chrono::Utc>,
-        tx_hash: String,
-        tx_index: u64,
-        tx_sender: String,
-        tx_method_name: String,
-        tx_gas_used: String,
-        tx_effective_gas_price: String,
-        tx_cumulative_gas_used: String,
-        validator_id: u64,
-        user: String,
-        amount: String,
-    },
-    DelegatorUnstakedWithID {
-        r#type: String,
-        block: u64,
-        time: chrono::DateTime<chrono::Utc>,
-        tx_hash: String,
-        tx_index: u64,
-        tx_sender: String,
-        tx_method_name: String,
-        tx_gas_used: String,
-        tx_effective_gas_price: String,
-        tx_cumulative_gas_used: String,
-        validator_id: u64,
-        user: String,
-        amount: String,
-        nonce: String,
-    },
-    DelegatorClaimedRewards {
-        r#type: String,
-        block: u64,
-        time: chrono::DateTime<chrono::Utc>,
-        tx_hash: String,
-        tx_index: u64,
-        tx_sender: String,
-        tx_method_name: String,
-        tx_gas_used: String,
-        tx_effective_gas_price: String,
-        tx_cumulative_gas_used: String,
-        validator_id: u64,
-        user: String,
-        amount: String,
-        rewards: String,
-    },
-    SharesTransfer {
-        r#type: String,
-        block: u64,
-        time: chrono::DateTime<chrono::Utc>,
-        tx_hash: String,
-        tx_index: u64,
-        tx_sender: String,
-        tx_method_name: String,
-        tx_gas_used: String,
-        tx_effective_gas_price: String,
-        tx_cumulative_gas_used: String,
-        validator_id: u64,
-        from: String,
-        to: String,
-        value: String,
-    },
+operation_enum! {
+    pub enum PolygonOperation {
+        ShareMinted => "ShareMinted" {
+            block: u64,
+            time: chrono::DateTime<chrono::Utc>,
+            tx_hash: String,
+            tx_index: u64,
+            tx_sender: String,
+            tx_method_name: String,
+            tx_gas_used: String,
+            tx_effective_gas_price: String,
+            tx_cumulative_gas_used: String,
+            validator_id: u64,
+            user: String,
+            amount: Amount,
+            tokens: String,
+        },
+        ShareBurned => "ShareBurned" {
+            block: u64,
+            time: chrono::DateTime<chrono::Utc>,
+            tx_hash: String,
+            tx_index: u64,
+            tx_sender: String,
+            tx_method_name: String,
+            tx_gas_used: String,
+            tx_effective_gas_price: String,
+            tx_cumulative_gas_used: String,
+            validator_id: u64,
+            user: String,
+            amount: Amount,
+            tokens: String,
+        },
+        ShareBurnedWithID => "ShareBurnedWithId" {
+            block: u64,
+            time: chrono::DateTime<chrono::Utc>,
+            tx_hash: String,
+            tx_index: u64,
+            tx_sender: String,
+            tx_method_name: String,
+            tx_gas_used: String,
+            tx_effective_gas_price: String,
+            tx_cumulative_gas_used: String,
+            validator_id: u64,
+            user: String,
+            amount: Amount,
+            tokens: String,
+            nonce: String,
+        },
+        DelegatorRestaked => "DelegatorRestaked" {
+            block: u64,
+            time: chrono::DateTime<chrono::Utc>,
+            tx_hash: String,
+            tx_index: u64,
+            tx_sender: String,
+            tx_method_name: String,
+            tx_gas_used: String,
+            tx_effective_gas_price: String,
+            tx_cumulative_gas_used: String,
+            validator_id: u64,
+            user: String,
+            total_staked: String,
+        },
+        DelegatorUnstaked => "DelegatorUnstaked" {
+            block: u64,
+            time: chrono::DateTime<chrono::Utc>,
+            tx_hash: String,
+            tx_index: u64,
+            tx_sender: String,
+            tx_method_name: String,
+            tx_gas_used: String,
+            tx_effective_gas_price: String,
+            tx_cumulative_gas_used: String,
+            validator_id: u64,
+            user: String,
+            amount: Amount,
+        },
+        DelegatorUnstakedWithID => "DelegatorUnstakeWithId" {
+            block: u64,
+            time: chrono::DateTime<chrono::Utc>,
+            tx_hash: String,
+            tx_index: u64,
+            tx_sender: String,
+            tx_method_name: String,
+            tx_gas_used: String,
+            tx_effective_gas_price: String,
+            tx_cumulative_gas_used: String,
+            validator_id: u64,
+            user: String,
+            amount: Amount,
+            nonce: String,
+        },
+        DelegatorClaimedRewards => "DelegatorClaimedRewards" {
+            block: u64,
+            time: chrono::DateTime<chrono::Utc>,
+            tx_hash: String,
+            tx_index: u64,
+            tx_sender: String,
+            tx_method_name: String,
+            tx_gas_used: String,
+            tx_effective_gas_price: String,
+            tx_cumulative_gas_used: String,
+            validator_id: u64,
+            user: String,
+            amount: Amount,
+            rewards: Amount,
+        },
+        SharesTransfer => "SharesTransfer" {
+            block: u64,
+            time: chrono::DateTime<chrono::Utc>,
+            tx_hash: String,
+            tx_index: u64,
+            tx_sender: String,
+            tx_method_name: String,
+            tx_gas_used: String,
+            tx_effective_gas_price: String,
+            tx_cumulative_gas_used: String,
+            validator_id: u64,
+            from: String,
+            to: String,
+            value: Amount,
+        },
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct PolygonNetworkStatsResponse {
-    pub pol_price_usd: f64,
+    pub pol_price_usd: FloatAmount,
     pub nb_validators: u64,
     pub net_gross_apy: f64,
     pub supply_staked_percent: f64,
@@ -202,45 +200,45 @@ pub struct PolygonNetworkStatsResponse {
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 #[serde(rename_all = "snake_case")]
 pub struct PolygonReportsRequest {
-    pub validator_indexes: Vec<String>,
-    pub wallets: Vec<String>,
-    pub accounts: Vec<Uuid>,
+    pub validator_indexes: Option<Vec<String>>,
+    pub wallets: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct PolygonApproveTxRequest {
     // Address
-    pub wallet: String,
-    pub contract: String,
-    pub amount_wei: String,
+    pub wallet: EthAddress,
+    pub contract: EthAddress,
+    pub amount_wei: Wei,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct PolygonBuyVoucherTxRequest {
     pub account_id: Uuid,
-    pub wallet: String,
-    pub amount_wei: String,
-    pub validator_share_proxy_address: String,
+    pub wallet: EthAddress,
+    pub amount_wei: Wei,
+    pub validator_share_proxy_address: EthAddress,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct PolygonSellVoucherTxRequest {
-    pub wallet: String,
-    pub amount_wei: String,
-    pub validator_share_proxy_address: String,
+    pub wallet: EthAddress,
+    pub amount_wei: Wei,
+    pub validator_share_proxy_address: EthAddress,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct PolygonTxRequest {
-    pub wallet: String,
-    pub validator_share_proxy_address: String,
+    pub wallet: EthAddress,
+    pub validator_share_proxy_address: EthAddress,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -259,7 +257,7 @@ pub struct PolygonTxResponse {
     pub unsigned_tx_serialized: String,
     pub to: String,
     pub contract_call_data: String,
-    pub amount_wei: String,
+    pub amount_wei: Amount,
     pub nonce: u64,
     pub gas_limit: u64,
     pub max_priority_fee_per_gas_wei: String,
@@ -301,22 +299,20 @@ pub struct PolygonTxStatusReceipt {
     pub r#type: String,
 }
 
-#[derive(Serialize, Deserialize, Debug, EnumString, AsRefStr)]
-#[serde(rename_all = "snake_case")]
-pub enum PolygonTxStatus {
-    #[strum(serialize = "success")]
-    Success,
-    #[strum(serialize = "error")]
-    Error,
-    #[strum(serialize = "pending_confirmation")]
-    PendingConfirmation,
+string_enum! {
+    pub enum PolygonTxStatus {
+        Success => "success",
+        Error => "error",
+        PendingConfirmation => "pending_confirmation",
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct PolygonTxStatusResponse {
     pub status: PolygonTxStatus,
-    pub receipt: PolygonTxStatusReceipt,
+    // `None` while the transaction is pending
+    pub receipt: Option<PolygonTxStatusReceipt>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -332,7 +328,7 @@ pub struct PolygonTxDecodingResponse {
     pub gas: String,
     pub data: String,
     pub nonce: u64,
-    pub value: String,
+    pub value: Amount,
     pub max_fee_per_gas: String,
     pub max_priority_fee_per_gas: String,
     pub function_name: String,
@@ -340,419 +336,624 @@ pub struct PolygonTxDecodingResponse {
 }
 
 #[derive(Clone, Debug)]
-pub struct KilnPolygonClient {
-    pub bearer_token: String,
+pub struct KilnPolygonClient<M: Mode = Blocking> {
     pub base_url: String,
+    environment: Option<KilnEnvironment>,
+    http: HttpClient<M>,
 }
 
-impl From<&Kiln> for KilnPolygonClient {
-    fn from(kiln: &Kiln) -> Self {
+impl<M: Mode> From<&Kiln<M>> for KilnPolygonClient<M> {
+    fn from(kiln: &Kiln<M>) -> Self {
         let url: String = format!("{}/pol", kiln.base_url);
 
-        let bearer_token = format!("Bearer {}", kiln.api_token);
-
         Self {
             base_url: url,
-            bearer_token,
+            environment: kiln.environment,
+            http: kiln.http.clone(),
         }
     }
 }
 
-impl KilnPolygonClient {
-    pub fn get_stakes(
-        &self,
-        stakes_request: &PolygonGetStakesRequest,
-    ) -> Result<ReturnedData<Vec<PolygonStakesResponse>>, ureq::Error> {
-        let PolygonGetStakesRequest {
-            validators,
-            wallets,
-            accounts,
-        } = stakes_request;
-
-        let validators = validators.join(",");
-
-        let wallets = wallets.join(",");
-
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
+for_each_mode! { <M>
+    impl KilnPolygonClient<M> {
+        pub fn environment(&self) -> Option<KilnEnvironment> {
+            self.environment
+        }
 
-        let url: String = format!(
-            "{}/stakes?validators={}&wallets={}&accounts={}",
-            self.base_url, validators, wallets, accounts
-        );
+        pub async fn get_stakes(
+            &self,
+            stakes_request: &PolygonGetStakesRequest,
+        ) -> Result<ReturnedData<Vec<PolygonStakesResponse>>, KilnError> {
+            let PolygonGetStakesRequest {
+                validators,
+                wallets,
+                accounts,
+            } = stakes_request;
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<PolygonStakesResponse>>>();
+            let url = Query::new()
+                .list("validators", validators.iter().flatten())
+                .list("wallets", wallets.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .url(&format!("{}/stakes", self.base_url));
 
-        data
-    }
+            self.http.get("kiln.pol.get_stakes", &url).await
+        }
 
-    pub fn post_stakes(
-        &self,
-        post_stakes_request: &PostStakesRequest,
-    ) -> Result<ReturnedData<PostStakesResponse>, ureq::Error> {
-        let url = format!("{}/stakes", self.base_url);
+        pub async fn post_stakes(
+            &self,
+            post_stakes_request: &PostStakesRequest,
+        ) -> Result<ReturnedData<PostStakesResponse>, KilnError> {
+            let url = format!("{}/stakes", self.base_url);
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(post_stakes_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PostStakesResponse>>();
+            self.http
+                .post("kiln.pol.post_stakes", &url, post_stakes_request)
+                .await
+        }
 
-        data
-    }
+        pub async fn get_rewards(
+            &self,
+            reward_request: &RewardRequest,
+        ) -> Result<ReturnedData<Vec<Reward>>, KilnError> {
+            let url: String = match reward_request {
+                RewardRequest::Epoch {
+                    stakes_addresses,
+                    wallets,
+                    pool_ids,
+                    accounts,
+                    format,
+                    start_date,
+                    end_date,
+                    start_epoch,
+                    end_epoch,
+                } => Query::new()
+                    .list("stake_addresses", stakes_addresses.iter().flatten())
+                    .list("pool_ids", pool_ids.iter().flatten())
+                    .list("wallets", wallets.iter().flatten())
+                    .list("accounts", accounts.iter().flatten())
+                    .value("format", format.as_ref())
+                    .value("start_date", start_date)
+                    .option("end_date", end_date.as_ref())
+                    .value("start_epoch", start_epoch)
+                    .value("end_epoch", end_epoch)
+                    .url(&format!("{}/rewards", self.base_url)),
+                RewardRequest::Daily {
+                    stakes_addresses,
+                    wallets,
+                    pool_ids,
+                    accounts,
+                    format,
+                    start_date,
+                    end_date,
+                    include_usd,
+                } => Query::new()
+                    .list("stake_addresses", stakes_addresses.iter().flatten())
+                    .list("pool_ids", pool_ids.iter().flatten())
+                    .list("wallets", wallets.iter().flatten())
+                    .list("accounts", accounts.iter().flatten())
+                    .value("format", format.as_ref())
+                    .value("start_date", start_date)
+                    .option("end_date", end_date.as_ref())
+                    .value("include_usd", include_usd)
+                    .url(&format!("{}/rewards", self.base_url)),
+            };
+
+            self.http.get("kiln.pol.get_rewards", &url).await
+        }
 
-    pub fn get_rewards(
-        &self,
-        reward_request: &RewardRequest,
-    ) -> Result<ReturnedData<Vec<Reward>>, ureq::Error> {
-        let url: String = match reward_request {
-            RewardRequest::Epoch {
-                stakes_addresses,
+        pub async fn get_operations(
+            &self,
+            operations_request: &PolygonOperationsRequest,
+        ) -> Result<ReturnedData<Vec<PolygonOperation>>, KilnError> {
+            let PolygonOperationsRequest {
                 wallets,
-                pool_ids,
+                validator_indexes,
                 accounts,
-                format,
                 start_date,
                 end_date,
-                start_epoch,
-                end_epoch,
-            } => {
-                let stakes_addresses = stakes_addresses.join(",");
-
-                let pool_ids = pool_ids.join(",");
-
-                let wallets = wallets.join(",");
-
-                let accounts = accounts
-                    .iter()
-                    .map(|id| id.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",");
-
-                let url = format!(
-            "{}/rewards?stake_addresses={}&pool_ids={}&wallets={}&accounts={}&format={}&start_date={}&end_date={}&start_epoch={}&end_epoch={}",
-            self.base_url,
-            stakes_addresses,
-            pool_ids,
-            wallets,
-            accounts,
-            format.as_ref(),
-            start_date,
-            end_date,
-            start_epoch,
-            end_epoch
-        );
+            } = operations_request;
+
+            let url = Query::new()
+                .list("wallets", wallets.iter().flatten())
+                .list("validator_indexes", validator_indexes.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .value("start_date", start_date)
+                .option("end_date", end_date.as_ref())
+                .url(&format!("{}/operations", self.base_url));
+
+            self.http.get("kiln.pol.get_operations", &url).await
+        }
+
+        pub async fn get_network_stats(
+            &self,
+        ) -> Result<ReturnedData<PolygonNetworkStatsResponse>, KilnError> {
+            let url: String = format!("{}/network-stats", self.base_url);
+
+            self.http.get("kiln.pol.get_network_stats", &url).await
+        }
 
-                url
-            }
-            RewardRequest::Daily {
-                stakes_addresses,
+        pub async fn get_reports(
+            &self,
+            reports_request: &PolygonReportsRequest,
+        ) -> Result<Vec<u8>, KilnError> {
+            let PolygonReportsRequest {
+                validator_indexes,
                 wallets,
-                pool_ids,
                 accounts,
-                format,
-                start_date,
-                end_date,
-                include_usd,
-            } => {
-                let stakes_addresses = stakes_addresses.join(",");
-
-                let pool_ids = pool_ids.join(",");
-
-                let wallets = wallets.join(",");
-
-                let accounts = accounts
-                    .iter()
-                    .map(|id| id.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",");
-
-                let url = format!(
-            "{}/rewards?stake_addresses={}&pool_ids={}&wallets={}&accounts={}&format={}&start_date={}&end_date={}&include_usd={}",
-            self.base_url,
-            stakes_addresses,
-            pool_ids,
-            wallets,
-            accounts,
-            format.as_ref(),
-            start_date,
-            end_date,
-            include_usd
-        );
+            } = reports_request;
+
+            let url = Query::new()
+                .list("validator_indexes", validator_indexes.iter().flatten())
+                .list("wallets", wallets.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .url(&format!("{}/reports", self.base_url));
 
-                url
-            }
-        };
+            self.http.get_bytes("kiln.pol.get_reports", &url).await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<Reward>>>();
+        pub async fn post_approve_tx(
+            &self,
+            approve_tx_request: &PolygonApproveTxRequest,
+        ) -> Result<ReturnedData<PolygonTxResponse>, KilnError> {
+            let url = format!("{}/transaction/approve", self.base_url);
 
-        data
-    }
+            let response: ReturnedData<PolygonTxResponse> = self
+                .http
+                .post_tx("kiln.pol.post_approve_tx", &url, approve_tx_request)
+                .await?;
 
-    pub fn get_operations(
-        &self,
-        operations_request: &PolygonOperationsRequest,
-    ) -> Result<ReturnedData<Vec<PolygonOperation>>, ureq::Error> {
-        let PolygonOperationsRequest {
-            wallets,
-            validator_indexes,
-            accounts,
-            start_date,
-            end_date,
-        } = operations_request;
-
-        let wallets = wallets.join(",");
-
-        let validator_indexes = validator_indexes.join(",");
-
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let url: String = format!(
-            "{}/operations?wallets={}&validator_indexes={}&accounts={}&start_date={}&end_date={}",
-            self.base_url, wallets, validator_indexes, accounts, start_date, end_date
-        );
+            environment::warn_on_chain_mismatch(self.environment, &response.data.chain_id);
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<PolygonOperation>>>();
+            Ok(response)
+        }
 
-        data
-    }
+        pub async fn post_buy_voucher_tx(
+            &self,
+            buy_voucher_tx_request: &PolygonBuyVoucherTxRequest,
+        ) -> Result<ReturnedData<PolygonTxResponse>, KilnError> {
+            let url = format!("{}/transaction/buy-voucher", self.base_url);
 
-    pub fn get_network_stats(
-        &self,
-    ) -> Result<ReturnedData<PolygonNetworkStatsResponse>, ureq::Error> {
-        let url: String = format!("{}/network-stats", self.base_url);
+            let response: ReturnedData<PolygonTxResponse> = self
+                .http
+                .post_tx("kiln.pol.post_buy_voucher_tx", &url, buy_voucher_tx_request)
+                .await?;
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<PolygonNetworkStatsResponse>>();
+            environment::warn_on_chain_mismatch(self.environment, &response.data.chain_id);
 
-        data
-    }
+            Ok(response)
+        }
 
-    pub fn get_reports(
-        &self,
-        reports_request: &PolygonReportsRequest,
-    ) -> Result<Vec<u8>, ureq::Error> {
-        let PolygonReportsRequest {
-            validator_indexes,
-            wallets,
-            accounts,
-        } = reports_request;
-
-        let validator_indexes = validator_indexes.join(",");
-
-        let wallets = wallets.join(",");
-
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let url: String = format!(
-            "{}/reports?validator_indexes={}&wallets={}&accounts={}",
-            self.base_url, validator_indexes, wallets, accounts
-        );
+        pub async fn post_sell_voucher_tx(
+            &self,
+            sell_voucher_tx_request: &PolygonSellVoucherTxRequest,
+        ) -> Result<ReturnedData<PolygonTxResponse>, KilnError> {
+            let url = format!("{}/transaction/sell-voucher", self.base_url);
+
+            let response: ReturnedData<PolygonTxResponse> = self
+                .http
+                .post_tx(
+                    "kiln.pol.post_sell_voucher_tx",
+                    &url,
+                    sell_voucher_tx_request,
+                )
+                .await?;
+
+            environment::warn_on_chain_mismatch(self.environment, &response.data.chain_id);
+
+            Ok(response)
+        }
+
+        pub async fn post_unstake_claim_tokens_tx(
+            &self,
+            unstake_claim_tokens_tx_request: &PolygonTxRequest,
+        ) -> Result<ReturnedData<PolygonTxResponse>, KilnError> {
+            let url = format!("{}/transaction/unstake-claim_tokens", self.base_url);
+
+            let response: ReturnedData<PolygonTxResponse> = self
+                .http
+                .post_tx(
+                    "kiln.pol.post_unstake_claim_tokens_tx",
+                    &url,
+                    unstake_claim_tokens_tx_request,
+                )
+                .await?;
+
+            environment::warn_on_chain_mismatch(self.environment, &response.data.chain_id);
+
+            Ok(response)
+        }
+
+        pub async fn post_withdraw_rewards_tx(
+            &self,
+            withdraw_rewards_tx_request: &PolygonTxRequest,
+        ) -> Result<ReturnedData<PolygonTxResponse>, KilnError> {
+            let url = format!("{}/transaction/withdraw-rewards", self.base_url);
+
+            let response: ReturnedData<PolygonTxResponse> = self
+                .http
+                .post_tx(
+                    "kiln.pol.post_withdraw_rewards_tx",
+                    &url,
+                    withdraw_rewards_tx_request,
+                )
+                .await?;
+
+            environment::warn_on_chain_mismatch(self.environment, &response.data.chain_id);
+
+            Ok(response)
+        }
+
+        pub async fn post_restake_rewards_tx(
+            &self,
+            restake_tx_request: &PolygonTxRequest,
+        ) -> Result<ReturnedData<PolygonTxResponse>, KilnError> {
+            let url = format!("{}/transaction/restake-rewards", self.base_url);
+
+            let response: ReturnedData<PolygonTxResponse> = self
+                .http
+                .post_tx("kiln.pol.post_restake_rewards_tx", &url, restake_tx_request)
+                .await?;
+
+            environment::warn_on_chain_mismatch(self.environment, &response.data.chain_id);
+
+            Ok(response)
+        }
+
+        pub async fn post_prepare_tx(
+            &self,
+            prepare_transaction_request: &PolygonPrepareTxRequest,
+        ) -> Result<ReturnedData<PrepareTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/prepare", self.base_url,);
+
+            self.http
+                .post_tx(
+                    "kiln.pol.post_prepare_tx",
+                    &url,
+                    prepare_transaction_request,
+                )
+                .await
+        }
+
+        pub async fn post_broadcast_tx(
+            &self,
+            broadcast_transaction_request: &BroadcastTxRequest,
+        ) -> Result<ReturnedData<BroadcastTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/broadcast", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.pol.post_broadcast_tx",
+                    &url,
+                    broadcast_transaction_request,
+                )
+                .await
+        }
+
+        pub async fn transaction_status(
+            &self,
+            tx_hash: &str,
+        ) -> Result<ReturnedData<PolygonTxStatusResponse>, KilnError> {
+            let url = Query::new()
+                .value("tx_hash", tx_hash)
+                .url(&format!("{}/transaction/status", self.base_url));
 
-        let mut file_bytes: Vec<u8> = Vec::new();
+            self.http.get("kiln.pol.transaction_status", &url).await
+        }
 
-        ureq::get(url)
-            .header("accept", "application/octet-stream")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .as_reader()
-            .read_to_end(&mut file_bytes)?;
+        pub async fn get_tx_decoding(
+            &self,
+            tx_serialized: &str,
+        ) -> Result<ReturnedData<PolygonTxDecodingResponse>, KilnError> {
+            let url = Query::new()
+                .value("tx_serialized", tx_serialized)
+                .url(&format!("{}/transaction/decode", self.base_url));
 
-        Ok(file_bytes)
+            self.http.get("kiln.pol.get_tx_decoding", &url).await
+        }
     }
+}
 
-    pub fn post_approve_tx(
-        &self,
-        approve_tx_request: &PolygonApproveTxRequest,
-    ) -> Result<ReturnedData<PolygonTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/approve", self.base_url);
+impl<M: Mode> KilnPolygonClient<M> {
+    pub fn stakes(&self) -> PolygonStakesBuilder<'_, M> {
+        PolygonStakesBuilder::new(self)
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(approve_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PolygonTxResponse>>();
+    pub fn rewards(&self) -> RewardsBuilder<'_, Self, NoStartDate> {
+        RewardsBuilder::new(self)
+    }
 
-        data
+    pub fn operations(&self) -> PolygonOperationsBuilder<'_, NoStartDate, M> {
+        PolygonOperationsBuilder::new(self)
     }
+}
 
-    pub fn post_buy_voucher_tx(
-        &self,
-        buy_voucher_tx_request: &PolygonBuyVoucherTxRequest,
-    ) -> Result<ReturnedData<PolygonTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/buy-voucher", self.base_url);
+// `kiln.polygon().stakes()`
+#[derive(Debug)]
+pub struct PolygonStakesBuilder<'a, M: Mode = Blocking> {
+    client: &'a KilnPolygonClient<M>,
+    wallets: Option<Vec<String>>,
+    accounts: Option<Vec<Uuid>>,
+    validators: Option<Vec<String>>,
+}
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(buy_voucher_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PolygonTxResponse>>();
+impl<'a, M: Mode> PolygonStakesBuilder<'a, M> {
+    fn new(client: &'a KilnPolygonClient<M>) -> Self {
+        Self {
+            client,
+            wallets: None,
+            accounts: None,
+            validators: None,
+        }
+    }
 
-        data
+    pub fn wallets(self, wallets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            wallets: filter(wallets),
+            ..self
+        }
     }
 
-    pub fn post_sell_voucher_tx(
-        &self,
-        sell_voucher_tx_request: &PolygonSellVoucherTxRequest,
-    ) -> Result<ReturnedData<PolygonTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/sell-voucher", self.base_url);
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(sell_voucher_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PolygonTxResponse>>();
+    pub fn validators(self, validators: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            validators: filter(validators),
+            ..self
+        }
+    }
 
-        data
+    pub fn into_request(self) -> PolygonGetStakesRequest {
+        PolygonGetStakesRequest {
+            wallets: self.wallets,
+            accounts: self.accounts,
+            validators: self.validators,
+        }
     }
+}
 
-    pub fn post_unstake_claim_tokens_tx(
-        &self,
-        unstake_claim_tokens_tx_request: &PolygonTxRequest,
-    ) -> Result<ReturnedData<PolygonTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/unstake-claim_tokens", self.base_url);
+for_each_mode! { <M>
+    impl PolygonStakesBuilder<'_, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<PolygonStakesResponse>>, KilnError> {
+            let client = self.client;
+
+            client.get_stakes(&self.into_request()).await
+        }
+    }
+}
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(unstake_claim_tokens_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PolygonTxResponse>>();
+for_each_mode! { <M>
+    impl RewardsBuilder<'_, KilnPolygonClient<M>, StartDate> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<Reward>>, KilnError> {
+            let client = self.client;
 
-        data
+            client.get_rewards(&self.into_request()?).await
+        }
     }
+}
+
+// `kiln.polygon().operations()`
+#[derive(Debug)]
+pub struct PolygonOperationsBuilder<'a, S, M: Mode = Blocking> {
+    client: &'a KilnPolygonClient<M>,
+    wallets: Option<Vec<String>>,
+    validator_indexes: Option<Vec<String>>,
+    accounts: Option<Vec<Uuid>>,
+    start_date: S,
+    end_date: Option<chrono::DateTime<chrono::Utc>>,
+}
 
-    pub fn post_withdraw_rewards_tx(
-        &self,
-        withdraw_rewards_tx_request: &PolygonTxRequest,
-    ) -> Result<ReturnedData<PolygonTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/withdraw-rewards", self.base_url);
+impl<'a, M: Mode> PolygonOperationsBuilder<'a, NoStartDate, M> {
+    fn new(client: &'a KilnPolygonClient<M>) -> Self {
+        Self {
+            client,
+            wallets: None,
+            validator_indexes: None,
+            accounts: None,
+            start_date: NoStartDate,
+            end_date: None,
+        }
+    }
+}
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(withdraw_rewards_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PolygonTxResponse>>();
+impl<'a, S, M: Mode> PolygonOperationsBuilder<'a, S, M> {
+    pub fn wallets(self, wallets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            wallets: filter(wallets),
+            ..self
+        }
+    }
 
-        data
+    pub fn validator_indexes(
+        self,
+        validator_indexes: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            validator_indexes: filter(validator_indexes),
+            ..self
+        }
     }
 
-    pub fn post_restake_rewards_tx(
-        &self,
-        restake_tx_request: &PolygonTxRequest,
-    ) -> Result<ReturnedData<PolygonTxResponse>, ureq::Error> {
-        let url = format!("{}/transaction/restake-rewards", self.base_url);
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(restake_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PolygonTxResponse>>();
+    pub fn until(self, end_date: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            end_date: Some(end_date),
+            ..self
+        }
+    }
 
-        data
+    pub fn since(
+        self,
+        start_date: chrono::DateTime<chrono::Utc>,
+    ) -> PolygonOperationsBuilder<'a, StartDate, M> {
+        PolygonOperationsBuilder {
+            client: self.client,
+            wallets: self.wallets,
+            validator_indexes: self.validator_indexes,
+            accounts: self.accounts,
+            start_date: StartDate(start_date),
+            end_date: self.end_date,
+        }
     }
+}
 
-    pub fn post_prepare_tx(
-        &self,
-        prepare_transaction_request: &PolygonPrepareTxRequest,
-    ) -> Result<ReturnedData<PrepareTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/prepare", self.base_url,);
+impl<M: Mode> PolygonOperationsBuilder<'_, StartDate, M> {
+    pub fn into_request(self) -> PolygonOperationsRequest {
+        PolygonOperationsRequest {
+            wallets: self.wallets,
+            validator_indexes: self.validator_indexes,
+            accounts: self.accounts,
+            start_date: self.start_date.0,
+            end_date: self.end_date,
+        }
+    }
+}
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(prepare_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PrepareTxResponse>>();
+for_each_mode! { <M>
+    impl PolygonOperationsBuilder<'_, StartDate, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<PolygonOperation>>, KilnError> {
+            let client = self.client;
 
-        data
+            client.get_operations(&self.into_request()).await
+        }
     }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod polygon_test {
+
+    use super::*;
+    use crate::query::query_test_support::{date, fixture, requested_url};
+    use crate::sdk::ResponseFormat;
 
-    pub fn post_broadcast_tx(
-        &self,
-        broadcast_transaction_request: &BroadcastTxRequest,
-    ) -> Result<ReturnedData<BroadcastTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/broadcast", self.base_url);
+    #[test]
+    fn get_stakes() {
+        let url = requested_url(|kiln| {
+            kiln.polygon().get_stakes(&PolygonGetStakesRequest {
+                validators: Some(vec!["0xa".into(), "0xb".into()]),
+                ..Default::default()
+            })
+        });
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(broadcast_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<BroadcastTxResponse>>();
+        assert_eq!(url, "https://api.kiln.fi/v1/pol/stakes?validators=0xa,0xb");
+    }
+
+    #[test]
+    fn get_rewards() {
+        let url = requested_url(|kiln| {
+            kiln.polygon().get_rewards(&RewardRequest::Daily {
+                stakes_addresses: None,
+                wallets: Some(vec!["0xa".into()]),
+                pool_ids: None,
+                accounts: None,
+                format: ResponseFormat::Daily,
+                start_date: date("2024-01-01T00:00:00Z"),
+                end_date: Some(date("2024-01-31T00:00:00Z")),
+                include_usd: false,
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/pol/rewards?wallets=0xa&format=daily&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z&include_usd=false"
+        );
+    }
 
-        data
+    #[test]
+    fn get_operations() {
+        let url = requested_url(|kiln| {
+            kiln.polygon().get_operations(&PolygonOperationsRequest {
+                wallets: Some(vec!["0xa".into()]),
+                validator_indexes: Some(vec!["7".into()]),
+                accounts: None,
+                start_date: date("2024-01-01T00:00:00Z"),
+                end_date: Some(date("2024-01-31T00:00:00Z")),
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/pol/operations?wallets=0xa&validator_indexes=7&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z"
+        );
     }
 
-    pub fn transaction_status(
-        &self,
-        tx_hash: &str,
-    ) -> Result<ReturnedData<PolygonTxStatusResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/status?tx_hash={}", self.base_url, tx_hash);
+    #[test]
+    fn get_reports() {
+        let url = requested_url(|kiln| {
+            kiln.polygon().get_reports(&PolygonReportsRequest {
+                wallets: Some(vec!["0xa".into()]),
+                ..Default::default()
+            })
+        });
+
+        assert_eq!(url, "https://api.kiln.fi/v1/pol/reports?wallets=0xa");
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<PolygonTxStatusResponse>>();
+    #[test]
+    fn transaction_status() {
+        let url = requested_url(|kiln| kiln.polygon().transaction_status("0xhash"));
 
-        data
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/pol/transaction/status?tx_hash=0xhash"
+        );
     }
 
-    pub fn get_tx_decoding(
-        &self,
-        tx_serialized: &str,
-    ) -> Result<ReturnedData<PolygonTxDecodingResponse>, ureq::Error> {
-        let url: String = format!(
-            "{}/transaction/decode?tx_serialized={}",
-            self.base_url, tx_serialized
+    #[test]
+    fn get_tx_decoding() {
+        let url = requested_url(|kiln| kiln.polygon().get_tx_decoding("ab+/="));
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/pol/transaction/decode?tx_serialized=ab%2B%2F%3D"
         );
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<PolygonTxDecodingResponse>>();
+    #[test]
+    fn responses_match_the_fixtures() {
+        let stakes: ReturnedData<Vec<PolygonStakesResponse>> = fixture("polygon/stakes.json");
+        let pending: ReturnedData<PolygonTxStatusResponse> =
+            fixture("polygon/tx_status_pending.json");
+
+        assert_eq!(stakes.data[0].unbounds_with_ids.len(), 1);
+        assert_eq!(pending.data.status, PolygonTxStatus::PendingConfirmation);
+        assert!(pending.data.receipt.is_none());
+
+        fixture::<ReturnedData<PostStakesResponse>>("polygon/post_stakes.json");
+        fixture::<ReturnedData<Vec<Reward>>>("polygon/rewards.json");
+        fixture::<ReturnedData<PolygonNetworkStatsResponse>>("polygon/network_stats.json");
+        fixture::<ReturnedData<PolygonTxResponse>>("polygon/tx.json");
+        fixture::<ReturnedData<PrepareTxResponse>>("polygon/prepare_tx.json");
+        fixture::<ReturnedData<BroadcastTxResponse>>("polygon/broadcast_tx.json");
+        fixture::<ReturnedData<PolygonTxStatusResponse>>("polygon/tx_status.json");
+        fixture::<ReturnedData<PolygonTxDecodingResponse>>("polygon/tx_decoding.json");
+    }
 
-        data
+    #[test]
+    fn operations_are_told_apart_by_their_type() {
+        let operations: ReturnedData<Vec<PolygonOperation>> = fixture("polygon/operations.json");
+
+        assert!(matches!(
+            operations.data[0],
+            PolygonOperation::ShareMinted { .. }
+        ));
+        assert!(operations.data.last().unwrap().is_unknown());
+        assert_eq!(
+            operations
+                .data
+                .iter()
+                .filter(|operation| operation.is_unknown())
+                .count(),
+            1
+        );
     }
 }
@@ -1,23 +1,29 @@
-use std::io::Read;
-
 use serde::{Deserialize, Serialize};
 use strum_macros::AsRefStr;
 use uuid::Uuid;
 
+use crate::address::{Lenient, SolanaPubkey};
+use crate::amount::{Amount, FloatAmount, IntAmount};
+use crate::denomination::Lamports;
+use crate::errors::KilnError;
+use crate::http::HttpClient;
+use crate::mode::{for_each_mode, Blocking, Mode};
+use crate::query::Query;
 use crate::{response_format::ReturnedData, Kiln};
 
+use super::builders::{filter, NoStartDate, StartDate};
 use super::{
     BroadcastTxRequest, BroadcastTxResponse, PostStakesResponse, PrepareTxResponse, ResponseFormat,
     StakeState,
 };
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 #[serde(rename_all = "snake_case")]
 pub struct SolanaGetStakesRequest {
-    pub stake_accounts: Vec<Uuid>,
-    pub validators: Vec<String>,
-    pub wallets: Vec<String>,
-    pub accounts: Vec<Uuid>,
+    pub stake_accounts: Option<Vec<Uuid>>,
+    pub validators: Option<Vec<String>>,
+    pub wallets: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -29,10 +35,11 @@ pub struct SolanaStakesResponse {
     pub state: StakeState,
     pub activated_at: chrono::DateTime<chrono::Utc>,
     pub activated_epoch: u64,
-    pub deactivated_at: chrono::DateTime<chrono::Utc>,
-    pub deactivated_epoch: u64,
-    pub balance: String,
-    pub rewards: String,
+    // `None` until the stake account is deactivated
+    pub deactivated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub deactivated_epoch: Option<u64>,
+    pub balance: Amount,
+    pub rewards: Amount,
     pub net_apy: f64,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
@@ -56,183 +63,176 @@ pub struct SolanaPostStakesRequest {
 #[non_exhaustive]
 pub enum SolanaRewardRequest {
     Daily {
-        stakes_accounts: Vec<String>,
-        validators: Vec<String>,
-        wallets: Vec<String>,
-        accounts: Vec<Uuid>,
+        stakes_accounts: Option<Vec<String>>,
+        validators: Option<Vec<String>>,
+        wallets: Option<Vec<String>>,
+        accounts: Option<Vec<Uuid>>,
         format: ResponseFormat,
         start_date: chrono::DateTime<chrono::Utc>,
-        end_date: chrono::DateTime<chrono::Utc>,
+        end_date: Option<chrono::DateTime<chrono::Utc>>,
         include_usd: bool,
     },
     Epoch {
-        stakes_addresses: Vec<String>,
-        wallets: Vec<String>,
-        pool_ids: Vec<String>,
-        accounts: Vec<Uuid>,
+        stakes_addresses: Option<Vec<String>>,
+        wallets: Option<Vec<String>>,
+        pool_ids: Option<Vec<String>>,
+        accounts: Option<Vec<Uuid>>,
         format: ResponseFormat,
         start_date: chrono::DateTime<chrono::Utc>,
-        end_date: chrono::DateTime<chrono::Utc>,
+        end_date: Option<chrono::DateTime<chrono::Utc>>,
         start_epoch: u64,
         end_epoch: u64,
     },
 }
 
+// epoch rewards have an `epoch`, daily rewards a `date`
 #[derive(Serialize, Deserialize, Debug)]
-#[serde(rename_all = "snake_case")]
+#[serde(untagged)]
 pub enum SolanaReward {
     Epoch {
         epoch: u64,
         epoch_ts: chrono::DateTime<chrono::Utc>,
-        rewards: String,
-        gross_mev_rewards: String,
+        rewards: Amount,
+        gross_mev_rewards: Amount,
         mev_comission: String,
-        active_balance: String,
+        active_balance: Amount,
         net_apy: f64,
     },
     Daily {
         date: chrono::DateTime<chrono::Utc>,
-        rewards: String,
-        gross_mev_rewards: String,
+        rewards: Amount,
+        gross_mev_rewards: Amount,
         mev_comission: String,
-        active_balance: String,
+        active_balance: Amount,
         net_apy: f64,
-        rewards_usd: String,
-        rewards_balance_usd: Option<u64>,
-        active_balance_usd: Option<u64>,
+        rewards_usd: Amount,
+        rewards_balance_usd: Option<FloatAmount>,
+        active_balance_usd: Option<FloatAmount>,
     },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct SolanaOperationsRequest {
-    pub stake_accounts: Vec<Uuid>,
-    pub wallets: Vec<String>,
-    pub accounts: Vec<Uuid>,
+    pub stake_accounts: Option<Vec<Uuid>>,
+    pub wallets: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
     pub start_date: chrono::DateTime<chrono::Utc>,
-    pub end_date: chrono::DateTime<chrono::Utc>,
+    pub end_date: Option<chrono::DateTime<chrono::Utc>>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-#[serde(rename_all = "snake_case")]
-pub enum SolanaOperation {
-    CreateAccountWithSeed {
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        tx_hash: String,
-        tx_fee: String,
-        tx_memo: Option<String>,
-        block: u64,
-        // Address
-        stake_account: String,
-        amount: String,
-        // Address
-        stake_authority: String,
-    },
-    CreateAccount {
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        tx_hash: String,
-        tx_fee: String,
-        tx_memo: Option<String>,
-        block: u64,
-        // Solana Address
-        stake_account: String,
-        amount: String,
-        // Solana Address
-        stake_authority: String,
-    },
-    Delegate {
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        tx_hash: String,
-        tx_fee: String,
-        tx_memo: Option<String>,
-        block: u64,
-        // Solana Address
-        stake_account: String,
-        // Solana Address
-        vote_account: String,
-        stake_authority: String,
-    },
-    Deactivate {
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        tx_hash: String,
-        tx_fee: String,
-        tx_memo: Option<String>,
-        block: u64,
-        // Solana Address
-        stake_account: String,
-        // Solana Address
-        stake_authority: String,
-    },
-    Redelegate {
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        tx_hash: String,
-        tx_fee: String,
-        tx_memo: Option<String>,
-        block: u64,
-        // Solana Address
-        stake_account: String,
-        // Solana Address
-        new_stake_account: String,
-        // Solana Address
-        vote_account: String,
-        // Solana Address
-        stake_authority: String,
-    },
-    Split {
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        tx_hash: String,
-        tx_fee: String,
-        tx_memo: Option<String>,
-        block: u64,
-        // Solana Address
-        stake_account: Option<String>,
-        // Solana Address
-        new_stake_account: String,
-        amount: String,
-        // Solana Address
-        stake_authority: String,
-    },
-    Withdraw {
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        tx_hash: String,
-        tx_fee: String,
-        tx_memo: Option<String>,
-        block: u64,
-        // Solana Address
-        stake_account: String,
-        // Solana Address
-        destination_account: String,
-        amount: String,
-        // Solana Address
-        stake_authority: String,
-    },
-    Merge {
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        tx_hash: String,
-        tx_fee: String,
-        tx_memo: Option<String>,
-        block: u64,
-        // Solana Address
-        stake_account: String,
-        // Solana Address
-        source_stake_account: String,
-        // Solana Address
-        stake_authority: String,
-    },
+operation_enum! {
+    pub enum SolanaOperation {
+        CreateAccountWithSeed => "create_account_with_seed" {
+            time: chrono::DateTime<chrono::Utc>,
+            tx_hash: String,
+            tx_fee: String,
+            tx_memo: Option<String>,
+            block: u64,
+            // Address
+            stake_account: Lenient<SolanaPubkey>,
+            amount: Amount,
+            // Address
+            stake_authority: Lenient<SolanaPubkey>,
+        },
+        CreateAccount => "create_account" {
+            time: chrono::DateTime<chrono::Utc>,
+            tx_hash: String,
+            tx_fee: String,
+            tx_memo: Option<String>,
+            block: u64,
+            // Solana Address
+            stake_account: String,
+            amount: Amount,
+            // Solana Address
+            stake_authority: String,
+        },
+        Delegate => "delegate" {
+            time: chrono::DateTime<chrono::Utc>,
+            tx_hash: String,
+            tx_fee: String,
+            tx_memo: Option<String>,
+            block: u64,
+            // Solana Address
+            stake_account: String,
+            // Solana Address
+            vote_account: String,
+            stake_authority: String,
+        },
+        Deactivate => "deactivate" {
+            time: chrono::DateTime<chrono::Utc>,
+            tx_hash: String,
+            tx_fee: String,
+            tx_memo: Option<String>,
+            block: u64,
+            // Solana Address
+            stake_account: String,
+            // Solana Address
+            stake_authority: String,
+        },
+        Redelegate => "redelegate" {
+            time: chrono::DateTime<chrono::Utc>,
+            tx_hash: String,
+            tx_fee: String,
+            tx_memo: Option<String>,
+            block: u64,
+            // Solana Address
+            stake_account: String,
+            // Solana Address
+            new_stake_account: String,
+            // Solana Address
+            vote_account: String,
+            // Solana Address
+            stake_authority: String,
+        },
+        Split => "split" {
+            time: chrono::DateTime<chrono::Utc>,
+            tx_hash: String,
+            tx_fee: String,
+            tx_memo: Option<String>,
+            block: u64,
+            // Solana Address
+            stake_account: Option<String>,
+            // Solana Address
+            new_stake_account: String,
+            amount: Amount,
+            // Solana Address
+            stake_authority: String,
+        },
+        Withdraw => "withdraw" {
+            time: chrono::DateTime<chrono::Utc>,
+            tx_hash: String,
+            tx_fee: String,
+            tx_memo: Option<String>,
+            block: u64,
+            // Solana Address
+            stake_account: String,
+            // Solana Address
+            destination_account: String,
+            amount: Amount,
+            // Solana Address
+            stake_authority: String,
+        },
+        Merge => "merge" {
+            time: chrono::DateTime<chrono::Utc>,
+            tx_hash: String,
+            tx_fee: String,
+            tx_memo: Option<String>,
+            block: u64,
+            // Solana Address
+            stake_account: String,
+            // Solana Address
+            source_stake_account: String,
+            // Solana Address
+            stake_authority: String,
+        },
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct SolanaNetworkStatsResponse {
-    pub sol_price_usd: f64,
+    pub sol_price_usd: FloatAmount,
     pub nb_validators: u64,
     pub net_gross_apy: f64,
     pub supply_staked_percent: f64,
@@ -240,12 +240,12 @@ pub struct SolanaNetworkStatsResponse {
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 #[serde(rename_all = "snake_case")]
 pub struct SolanaReportsRequest {
-    pub stake_accounts: Vec<Uuid>,
-    pub accounts: Vec<Uuid>,
-    pub wallets: Vec<String>,
+    pub stake_accounts: Option<Vec<Uuid>>,
+    pub accounts: Option<Vec<Uuid>>,
+    pub wallets: Option<Vec<String>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -280,6 +280,8 @@ pub struct SolanaUnsignedTx {
     pub account_keys: Vec<String>,
     pub recent_blockhash: String,
     pub instructions: Vec<SolanaUnsignedTxInstruction>,
+    // only in the transactions built by Kiln, not in the rpc receipts
+    #[serde(default)]
     pub index_to_program_ids: serde_json::Value,
 }
 
@@ -296,10 +298,10 @@ pub struct SolanaTxResponse {
 pub struct SolanaStakeTxRequest {
     pub account_id: Uuid,
     // Solana Address
-    pub wallet: String,
-    pub amount_lamports: String,
+    pub wallet: SolanaPubkey,
+    pub amount_lamports: Lamports,
     // Solana Address
-    pub vote_account_address: String,
+    pub vote_account_address: SolanaPubkey,
     pub memo: String,
 }
 
@@ -307,29 +309,29 @@ pub struct SolanaStakeTxRequest {
 #[serde(rename_all = "snake_case")]
 pub struct SolanaDeactivateStakeTxRequest {
     // Solana Address
-    pub stake_account: String,
-    pub wallet: String,
+    pub stake_account: SolanaPubkey,
+    pub wallet: SolanaPubkey,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct SolanaWithdrawStakeTxRequest {
     // Solana Address
-    pub stake_account: String,
+    pub stake_account: SolanaPubkey,
     // Solana Address
-    pub wallet: String,
-    pub amount_lamports: String,
+    pub wallet: SolanaPubkey,
+    pub amount_lamports: Lamports,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct SolanaMergeStakeTxRequest {
     // Solana Address
-    pub stake_account_source: String,
+    pub stake_account_source: SolanaPubkey,
     // Solana Address
-    pub stake_account_destination: String,
+    pub stake_account_destination: SolanaPubkey,
     // Solana Address
-    pub wallet: String,
+    pub wallet: SolanaPubkey,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -337,10 +339,10 @@ pub struct SolanaMergeStakeTxRequest {
 pub struct SolanaSplitStakeTxRequest {
     pub account_id: Uuid,
     // Solana Address
-    pub stake_account: String,
+    pub stake_account: SolanaPubkey,
     // Solana Address
-    pub wallet: String,
-    pub amount_lamports: String,
+    pub wallet: SolanaPubkey,
+    pub amount_lamports: Lamports,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -353,10 +355,10 @@ pub struct SolanaPrepareTxRequest {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct SolanaTokenAmount {
-    pub amount: String,
+    pub amount: Amount,
     pub decimals: String,
-    pub ui_amount: Option<u64>,
-    pub ui_amount_string: Option<String>,
+    pub ui_amount: Option<IntAmount>,
+    pub ui_amount_string: Option<Amount>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -380,14 +382,16 @@ pub struct LoadedAddress {
 #[serde(rename_all = "camelCase")]
 pub struct SolanaTxReceiptMeta {
     pub compute_units_consumed: u64,
-    pub err: Option<String>,
-    pub fee: u64,
+    // `{ "InstructionError": [0, ..] }` when the transaction failed
+    pub err: Option<serde_json::Value>,
+    pub fee: IntAmount,
     pub pre_balances: Vec<u64>,
     pub post_balances: Vec<u64>,
     pub log_messages: Option<Vec<String>>,
     pub pre_token_balances: Option<Vec<SolanaTokenBalance>>,
     pub post_token_balances: Option<Vec<SolanaTokenBalance>>,
-    pub loaded_addresses: Vec<LoadedAddress>,
+    // only for versioned transactions
+    pub loaded_addresses: Option<LoadedAddress>,
     // Unclear what type it should be, so I'll just leave it like that until it blows up
     pub rewards: Vec<SolanaTxReceiptReward>,
     // Unclear what type it should be, so I'll just leave it like that until it blows up
@@ -398,8 +402,8 @@ pub struct SolanaTxReceiptMeta {
 #[serde(rename_all = "camelCase")]
 pub struct SolanaTxReceiptReward {
     pub pubkey: String,
-    pub lamports: u64,
-    pub post_balance: Option<u64>,
+    pub lamports: IntAmount,
+    pub post_balance: Option<IntAmount>,
     pub reward_type: Option<u64>,
     pub commission: Option<u64>,
 }
@@ -411,7 +415,7 @@ pub struct SolanaTxReceiptStatus {
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-#[serde(rename_all = "PascalCase")]
+#[serde(rename_all = "camelCase")]
 pub struct SolanaTxCore {
     pub message: SolanaUnsignedTx,
     pub signatures: Vec<String>,
@@ -421,6 +425,7 @@ pub struct SolanaTxCore {
 #[serde(rename_all = "camelCase")]
 pub struct SolanaTxReceipt {
     pub slot: u64,
+    #[serde(rename = "blockTime")]
     pub blocktime: Option<u64>,
     pub meta: Option<SolanaTxReceiptMeta>,
     pub transaction: SolanaTxCore,
@@ -430,7 +435,8 @@ pub struct SolanaTxReceipt {
 #[serde(rename_all = "snake_case")]
 pub struct SolanaTxStatusResponse {
     pub status: String,
-    pub receipt: SolanaTxReceipt,
+    // `None` while the transaction is pending
+    pub receipt: Option<SolanaTxReceipt>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -462,408 +468,702 @@ pub struct SolanaTxDecodeResponse {
 }
 
 #[derive(Clone, Debug)]
-pub struct KilnSolanaClient {
-    pub bearer_token: String,
+pub struct KilnSolanaClient<M: Mode = Blocking> {
     pub base_url: String,
+    http: HttpClient<M>,
 }
 
-impl From<&Kiln> for KilnSolanaClient {
-    fn from(kiln: &Kiln) -> Self {
+impl<M: Mode> From<&Kiln<M>> for KilnSolanaClient<M> {
+    fn from(kiln: &Kiln<M>) -> Self {
         let url: String = format!("{}/sol", kiln.base_url);
-        let bearer_token: String = format!("Bearer {}", kiln.api_token);
 
         Self {
-            bearer_token,
             base_url: url,
+            http: kiln.http.clone(),
         }
     }
 }
 
-impl KilnSolanaClient {
-    pub fn get_stakes(
-        &self,
-        stakes_request: &SolanaGetStakesRequest,
-    ) -> Result<ReturnedData<Vec<SolanaStakesResponse>>, ureq::Error> {
-        let SolanaGetStakesRequest {
-            validators,
-            stake_accounts,
-            accounts,
-            wallets,
-        } = stakes_request;
+for_each_mode! { <M>
+    impl KilnSolanaClient<M> {
+        pub async fn get_stakes(
+            &self,
+            stakes_request: &SolanaGetStakesRequest,
+        ) -> Result<ReturnedData<Vec<SolanaStakesResponse>>, KilnError> {
+            let SolanaGetStakesRequest {
+                validators,
+                stake_accounts,
+                accounts,
+                wallets,
+            } = stakes_request;
 
-        let validators = validators.join(",");
+            let url = Query::new()
+                .list("validators", validators.iter().flatten())
+                .list("stake_accounts", stake_accounts.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .list("wallets", wallets.iter().flatten())
+                .url(&format!("{}/stakes", self.base_url));
 
-        let stake_accounts = stake_accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
+            self.http.get("kiln.sol.get_stakes", &url).await
+        }
 
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
+        pub async fn post_stakes(
+            &self,
+            post_stakes_request: &SolanaPostStakesRequest,
+        ) -> Result<ReturnedData<PostStakesResponse>, KilnError> {
+            let url = format!("{}/stakes", self.base_url);
 
-        let wallets = wallets.join(",");
+            self.http
+                .post("kiln.sol.post_stakes", &url, post_stakes_request)
+                .await
+        }
 
-        let url: String = format!(
-            "{}/stakes?validators={}&stake_accounts={}&accounts={}&wallets={}",
-            self.base_url, validators, stake_accounts, accounts, wallets
-        );
+        pub async fn get_rewards(
+            &self,
+            reward_request: SolanaRewardRequest,
+        ) -> Result<ReturnedData<Vec<SolanaReward>>, KilnError> {
+            let url: String = match reward_request {
+                SolanaRewardRequest::Daily {
+                    stakes_accounts,
+                    validators,
+                    wallets,
+                    accounts,
+                    format,
+                    start_date,
+                    end_date,
+                    include_usd,
+                } => Query::new()
+                    .list("stake_accounts", stakes_accounts.iter().flatten())
+                    .list("validators", validators.iter().flatten())
+                    .list("wallets", wallets.iter().flatten())
+                    .list("accounts", accounts.iter().flatten())
+                    .value("format", format.as_ref())
+                    .value("start_date", start_date)
+                    .option("end_date", end_date.as_ref())
+                    .value("include_usd", include_usd)
+                    .url(&format!("{}/rewards", self.base_url)),
+                SolanaRewardRequest::Epoch {
+                    stakes_addresses,
+                    wallets,
+                    pool_ids,
+                    accounts,
+                    format,
+                    start_date,
+                    end_date,
+                    start_epoch,
+                    end_epoch,
+                } => Query::new()
+                    .list("stakes_addresses", stakes_addresses.iter().flatten())
+                    .list("wallets", wallets.iter().flatten())
+                    .list("pool_ids", pool_ids.iter().flatten())
+                    .list("accounts", accounts.iter().flatten())
+                    .value("format", format.as_ref())
+                    .value("start_date", start_date)
+                    .option("end_date", end_date.as_ref())
+                    .value("start_epoch", start_epoch)
+                    .value("end_epoch", end_epoch)
+                    .url(&format!("{}/rewards", self.base_url)),
+            };
+
+            self.http.get("kiln.sol.get_rewards", &url).await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<SolanaStakesResponse>>>();
+        pub async fn get_operations(
+            &self,
+            validator_operations_request: &SolanaOperationsRequest,
+        ) -> Result<ReturnedData<Vec<SolanaOperation>>, KilnError> {
+            let SolanaOperationsRequest {
+                stake_accounts,
+                wallets,
+                accounts,
+                start_date,
+                end_date,
+            } = validator_operations_request;
 
-        data
-    }
+            let url = Query::new()
+                .list("stake_accounts", stake_accounts.iter().flatten())
+                .list("wallets", wallets.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .value("start_date", start_date)
+                .option("end_date", end_date.as_ref())
+                .url(&format!("{}/operations", self.base_url));
 
-    pub fn post_stakes(
-        &self,
-        post_stakes_request: &SolanaPostStakesRequest,
-    ) -> Result<ReturnedData<PostStakesResponse>, ureq::Error> {
-        let url = format!("{}/stakes", self.base_url);
+            self.http.get("kiln.sol.get_operations", &url).await
+        }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(post_stakes_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PostStakesResponse>>();
+        pub async fn get_network_stats(
+            &self,
+        ) -> Result<ReturnedData<SolanaNetworkStatsResponse>, KilnError> {
+            let url: String = format!("{}/network-stats", self.base_url);
 
-        data
-    }
+            self.http.get("kiln.sol.get_network_stats", &url).await
+        }
 
-    pub fn get_rewards(
-        &self,
-        reward_request: SolanaRewardRequest,
-    ) -> Result<ReturnedData<Vec<SolanaReward>>, ureq::Error> {
-        let url: String = match reward_request {
-            SolanaRewardRequest::Daily {
-                stakes_accounts,
-                validators,
-                wallets,
+        pub async fn get_reports(
+            &self,
+            reports_request: &SolanaReportsRequest,
+        ) -> Result<Vec<u8>, KilnError> {
+            let SolanaReportsRequest {
+                stake_accounts,
                 accounts,
-                format,
-                start_date,
-                end_date,
-                include_usd,
-            } => {
-                let stakes_accounts = stakes_accounts
-                    .iter()
-                    .map(|id| id.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",");
-
-                let validators = validators.join(",");
-
-                let wallets = wallets.join(",");
-
-                let accounts = accounts
-                    .iter()
-                    .map(|id| id.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",");
-
-                let url = format!("{}/rewards?stake_accounts={}&validators={}&wallets={}&accounts={}&format={}&start_date={}&end_date={}&include_usd={}", self.base_url, stakes_accounts, validators, wallets, accounts, format.as_ref(), start_date, end_date, include_usd);
-
-                url
-            }
-            SolanaRewardRequest::Epoch {
-                stakes_addresses,
                 wallets,
-                pool_ids,
-                accounts,
-                format,
-                start_date,
-                end_date,
-                start_epoch,
-                end_epoch,
-            } => {
-                let stakes_addresses = stakes_addresses.join(",");
-
-                let wallets = wallets.join(",");
-
-                let pool_ids = pool_ids.join(",");
-
-                let accounts = accounts
-                    .iter()
-                    .map(|id| id.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",");
-
-                let url = format!("{}/rewards?stakes_addresses={}&wallets={}&pool_ids={}&accounts={}&format={}&start_date={}&end_date={}&start_epoch={}&end_epoch={}", self.base_url, stakes_addresses, wallets, pool_ids, accounts, format.as_ref(), start_date, end_date, start_epoch, end_epoch);
-
-                url
-            }
-        };
-
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<SolanaReward>>>();
-
-        data
-    }
-
-    pub fn get_operations(
-        &self,
-        validator_operations_request: &SolanaOperationsRequest,
-    ) -> Result<ReturnedData<Vec<SolanaOperation>>, ureq::Error> {
-        let SolanaOperationsRequest {
-            stake_accounts,
-            wallets,
-            accounts,
-            start_date,
-            end_date,
-        } = validator_operations_request;
-
-        let stake_accounts = stake_accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let wallets = wallets.join(",");
-
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let url: String = format!(
-            "{}/operations?stake_accounts={}&wallets={}&accounts={}&start_date={}&end_date={}",
-            self.base_url, stake_accounts, wallets, accounts, start_date, end_date
-        );
+            } = reports_request;
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<SolanaOperation>>>();
-
-        data
-    }
-
-    pub fn get_network_stats(
-        &self,
-    ) -> Result<ReturnedData<SolanaNetworkStatsResponse>, ureq::Error> {
-        let url: String = format!("{}/network-stats", self.base_url);
-
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<SolanaNetworkStatsResponse>>();
-
-        data
-    }
-
-    pub fn get_reports(
-        &self,
-        reports_request: &SolanaReportsRequest,
-    ) -> Result<Vec<u8>, ureq::Error> {
-        let SolanaReportsRequest {
-            stake_accounts,
-            accounts,
-            wallets,
-        } = reports_request;
-
-        let stake_accounts = stake_accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let wallets = wallets.join(",");
-
-        let url: String = format!(
-            "{}/reports?stake_accounts={}&accounts={}&wallets={}",
-            self.base_url, stake_accounts, accounts, wallets,
-        );
+            let url = Query::new()
+                .list("stake_accounts", stake_accounts.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .list("wallets", wallets.iter().flatten())
+                .url(&format!("{}/reports", self.base_url));
+
+            self.http.get_bytes("kiln.sol.get_reports", &url).await
+        }
+
+        pub async fn get_nonce_account(&self) -> Result<ReturnedData<NonceAccount>, KilnError> {
+            let url = format!("{}/nonce-account", self.base_url);
+
+            self.http.get("kiln.sol.get_nonce_account", &url).await
+        }
+
+        pub async fn post_stake_tx(
+            &self,
+            transaction_stake_request: &SolanaStakeTxRequest,
+        ) -> Result<ReturnedData<SolanaTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/stake", self.base_url,);
+
+            self.http
+                .post_tx("kiln.sol.post_stake_tx", &url, transaction_stake_request)
+                .await
+        }
+
+        pub async fn post_deactivate_stake_tx(
+            &self,
+            deactivate_stake_tx: &SolanaDeactivateStakeTxRequest,
+        ) -> Result<ReturnedData<SolanaTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/deactivate-stake", self.base_url,);
+
+            self.http
+                .post_tx(
+                    "kiln.sol.post_deactivate_stake_tx",
+                    &url,
+                    deactivate_stake_tx,
+                )
+                .await
+        }
+
+        pub async fn post_withdraw_stake_tx(
+            &self,
+            withdraw_stake_tx: &SolanaWithdrawStakeTxRequest,
+        ) -> Result<ReturnedData<SolanaTxResponse>, KilnError> {
+            let url = format!("{}/withdraw-stake", self.base_url);
+
+            self.http
+                .post_tx("kiln.sol.post_withdraw_stake_tx", &url, withdraw_stake_tx)
+                .await
+        }
+
+        pub async fn post_merge_stake_tx(
+            &self,
+            merge_stake_tx: &SolanaMergeStakeTxRequest,
+        ) -> Result<ReturnedData<SolanaTxResponse>, KilnError> {
+            let url = format!("{}/merge-stake", self.base_url);
+
+            self.http
+                .post_tx("kiln.sol.post_merge_stake_tx", &url, merge_stake_tx)
+                .await
+        }
+
+        pub async fn post_split_stake_tx(
+            &self,
+            split_stake_tx: &SolanaSplitStakeTxRequest,
+        ) -> Result<ReturnedData<SolanaTxResponse>, KilnError> {
+            let url = format!("{}/split-stake", self.base_url);
+
+            self.http
+                .post_tx("kiln.sol.post_split_stake_tx", &url, split_stake_tx)
+                .await
+        }
+
+        pub async fn post_prepare_tx(
+            &self,
+            prepare_transaction_request: &SolanaPrepareTxRequest,
+        ) -> Result<ReturnedData<PrepareTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/prepare", self.base_url,);
+
+            self.http
+                .post_tx(
+                    "kiln.sol.post_prepare_tx",
+                    &url,
+                    prepare_transaction_request,
+                )
+                .await
+        }
+
+        pub async fn post_broadcast_tx(
+            &self,
+            broadcast_transaction_request: &BroadcastTxRequest,
+        ) -> Result<ReturnedData<BroadcastTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/broadcast", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.sol.post_broadcast_tx",
+                    &url,
+                    broadcast_transaction_request,
+                )
+                .await
+        }
+
+        pub async fn get_tx_status(
+            &self,
+            tx_hash: &str,
+        ) -> Result<ReturnedData<SolanaTxStatusResponse>, KilnError> {
+            let url = Query::new()
+                .value("tx_hash", tx_hash)
+                .url(&format!("{}/transaction/status", self.base_url));
+
+            self.http.get("kiln.sol.get_tx_status", &url).await
+        }
+
+        pub async fn get_tx_decoding(
+            &self,
+            tx_serialized: &str,
+        ) -> Result<ReturnedData<SolanaTxDecodeResponse>, KilnError> {
+            let url = Query::new()
+                .value("tx_serialized", tx_serialized)
+                .url(&format!("{}/transaction/decode", self.base_url));
+
+            self.http.get("kiln.sol.get_tx_decoding", &url).await
+        }
+    }
+}
+
+impl<M: Mode> KilnSolanaClient<M> {
+    pub fn stakes(&self) -> SolanaStakesBuilder<'_, M> {
+        SolanaStakesBuilder::new(self)
+    }
+
+    pub fn rewards(&self) -> SolanaRewardsBuilder<'_, NoStartDate, M> {
+        SolanaRewardsBuilder::new(self)
+    }
+
+    pub fn operations(&self) -> SolanaOperationsBuilder<'_, NoStartDate, M> {
+        SolanaOperationsBuilder::new(self)
+    }
+}
+
+// `kiln.solana().stakes()`
+#[derive(Debug)]
+pub struct SolanaStakesBuilder<'a, M: Mode = Blocking> {
+    client: &'a KilnSolanaClient<M>,
+    stake_accounts: Option<Vec<Uuid>>,
+    validators: Option<Vec<String>>,
+    wallets: Option<Vec<String>>,
+    accounts: Option<Vec<Uuid>>,
+}
+
+impl<'a, M: Mode> SolanaStakesBuilder<'a, M> {
+    fn new(client: &'a KilnSolanaClient<M>) -> Self {
+        Self {
+            client,
+            stake_accounts: None,
+            validators: None,
+            wallets: None,
+            accounts: None,
+        }
+    }
+
+    pub fn stake_accounts(self, stake_accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            stake_accounts: filter(stake_accounts),
+            ..self
+        }
+    }
 
-        let mut file_bytes: Vec<u8> = Vec::new();
+    pub fn validators(self, validators: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            validators: filter(validators),
+            ..self
+        }
+    }
 
-        ureq::get(url)
-            .header("accept", "application/octet-stream")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .as_reader()
-            .read_to_end(&mut file_bytes)?;
+    pub fn wallets(self, wallets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            wallets: filter(wallets),
+            ..self
+        }
+    }
 
-        Ok(file_bytes)
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
     }
 
-    pub fn get_nonce_account(&self) -> Result<ReturnedData<NonceAccount>, ureq::Error> {
-        let url = format!("{}/nonce-account", self.base_url);
+    pub fn into_request(self) -> SolanaGetStakesRequest {
+        SolanaGetStakesRequest {
+            stake_accounts: self.stake_accounts,
+            validators: self.validators,
+            wallets: self.wallets,
+            accounts: self.accounts,
+        }
+    }
+}
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<NonceAccount>>();
+for_each_mode! { <M>
+    impl SolanaStakesBuilder<'_, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<SolanaStakesResponse>>, KilnError> {
+            let client = self.client;
 
-        data
+            client.get_stakes(&self.into_request()).await
+        }
     }
+}
 
-    pub fn post_stake_tx(
-        &self,
-        transaction_stake_request: &SolanaStakeTxRequest,
-    ) -> Result<ReturnedData<SolanaTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/stake", self.base_url,);
+// `kiln.solana().rewards()` : daily rewards, the per epoch ones go through `get_rewards(SolanaRewardRequest::Epoch { .. })`
+#[derive(Debug)]
+pub struct SolanaRewardsBuilder<'a, S, M: Mode = Blocking> {
+    client: &'a KilnSolanaClient<M>,
+    stakes_accounts: Option<Vec<String>>,
+    validators: Option<Vec<String>>,
+    wallets: Option<Vec<String>>,
+    accounts: Option<Vec<Uuid>>,
+    format: ResponseFormat,
+    include_usd: bool,
+    start_date: S,
+    end_date: Option<chrono::DateTime<chrono::Utc>>,
+}
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(transaction_stake_request)?
-            .body_mut()
-            .read_json::<ReturnedData<SolanaTxResponse>>();
+impl<'a, M: Mode> SolanaRewardsBuilder<'a, NoStartDate, M> {
+    fn new(client: &'a KilnSolanaClient<M>) -> Self {
+        Self {
+            client,
+            stakes_accounts: None,
+            validators: None,
+            wallets: None,
+            accounts: None,
+            format: ResponseFormat::default(),
+            include_usd: false,
+            start_date: NoStartDate,
+            end_date: None,
+        }
+    }
+}
 
-        data
+impl<'a, S, M: Mode> SolanaRewardsBuilder<'a, S, M> {
+    pub fn stakes_accounts(
+        self,
+        stakes_accounts: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            stakes_accounts: filter(stakes_accounts),
+            ..self
+        }
     }
 
-    pub fn post_deactivate_stake_tx(
-        &self,
-        deactivate_stake_tx: &SolanaDeactivateStakeTxRequest,
-    ) -> Result<ReturnedData<SolanaTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/deactivate-stake", self.base_url,);
+    pub fn validators(self, validators: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            validators: filter(validators),
+            ..self
+        }
+    }
+
+    pub fn wallets(self, wallets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            wallets: filter(wallets),
+            ..self
+        }
+    }
+
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(deactivate_stake_tx)?
-            .body_mut()
-            .read_json::<ReturnedData<SolanaTxResponse>>();
+    pub fn format(self, format: ResponseFormat) -> Self {
+        Self { format, ..self }
+    }
+
+    pub fn include_usd(self) -> Self {
+        Self {
+            include_usd: true,
+            ..self
+        }
+    }
 
-        data
+    pub fn until(self, end_date: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            end_date: Some(end_date),
+            ..self
+        }
+    }
+
+    pub fn since(
+        self,
+        start_date: chrono::DateTime<chrono::Utc>,
+    ) -> SolanaRewardsBuilder<'a, StartDate, M> {
+        SolanaRewardsBuilder {
+            client: self.client,
+            stakes_accounts: self.stakes_accounts,
+            validators: self.validators,
+            wallets: self.wallets,
+            accounts: self.accounts,
+            format: self.format,
+            include_usd: self.include_usd,
+            start_date: StartDate(start_date),
+            end_date: self.end_date,
+        }
     }
+}
 
-    pub fn post_withdraw_stake_tx(
-        &self,
-        withdraw_stake_tx: &SolanaWithdrawStakeTxRequest,
-    ) -> Result<ReturnedData<SolanaTxResponse>, ureq::Error> {
-        let url = format!("{}/withdraw-stake", self.base_url);
+impl<M: Mode> SolanaRewardsBuilder<'_, StartDate, M> {
+    pub fn into_request(self) -> SolanaRewardRequest {
+        SolanaRewardRequest::Daily {
+            stakes_accounts: self.stakes_accounts,
+            validators: self.validators,
+            wallets: self.wallets,
+            accounts: self.accounts,
+            format: self.format,
+            start_date: self.start_date.0,
+            end_date: self.end_date,
+            include_usd: self.include_usd,
+        }
+    }
+}
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(withdraw_stake_tx)?
-            .body_mut()
-            .read_json::<ReturnedData<SolanaTxResponse>>();
+for_each_mode! { <M>
+    impl SolanaRewardsBuilder<'_, StartDate, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<SolanaReward>>, KilnError> {
+            let client = self.client;
 
-        data
+            client.get_rewards(self.into_request()).await
+        }
     }
+}
 
-    pub fn post_merge_stake_tx(
-        &self,
-        merge_stake_tx: &SolanaMergeStakeTxRequest,
-    ) -> Result<ReturnedData<SolanaTxResponse>, ureq::Error> {
-        let url = format!("{}/merge-stake", self.base_url);
+// `kiln.solana().operations()`
+#[derive(Debug)]
+pub struct SolanaOperationsBuilder<'a, S, M: Mode = Blocking> {
+    client: &'a KilnSolanaClient<M>,
+    stake_accounts: Option<Vec<Uuid>>,
+    wallets: Option<Vec<String>>,
+    accounts: Option<Vec<Uuid>>,
+    start_date: S,
+    end_date: Option<chrono::DateTime<chrono::Utc>>,
+}
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(merge_stake_tx)?
-            .body_mut()
-            .read_json::<ReturnedData<SolanaTxResponse>>();
+impl<'a, M: Mode> SolanaOperationsBuilder<'a, NoStartDate, M> {
+    fn new(client: &'a KilnSolanaClient<M>) -> Self {
+        Self {
+            client,
+            stake_accounts: None,
+            wallets: None,
+            accounts: None,
+            start_date: NoStartDate,
+            end_date: None,
+        }
+    }
+}
 
-        data
+impl<'a, S, M: Mode> SolanaOperationsBuilder<'a, S, M> {
+    pub fn stake_accounts(self, stake_accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            stake_accounts: filter(stake_accounts),
+            ..self
+        }
     }
 
-    pub fn post_split_stake_tx(
-        &self,
-        split_stake_tx: &SolanaSplitStakeTxRequest,
-    ) -> Result<ReturnedData<SolanaTxResponse>, ureq::Error> {
-        let url = format!("{}/split-stake", self.base_url);
+    pub fn wallets(self, wallets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            wallets: filter(wallets),
+            ..self
+        }
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(split_stake_tx)?
-            .body_mut()
-            .read_json::<ReturnedData<SolanaTxResponse>>();
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
+    }
 
-        data
+    pub fn until(self, end_date: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            end_date: Some(end_date),
+            ..self
+        }
     }
 
-    pub fn post_prepare_tx(
-        &self,
-        prepare_transaction_request: &SolanaPrepareTxRequest,
-    ) -> Result<ReturnedData<PrepareTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/prepare", self.base_url,);
+    pub fn since(
+        self,
+        start_date: chrono::DateTime<chrono::Utc>,
+    ) -> SolanaOperationsBuilder<'a, StartDate, M> {
+        SolanaOperationsBuilder {
+            client: self.client,
+            stake_accounts: self.stake_accounts,
+            wallets: self.wallets,
+            accounts: self.accounts,
+            start_date: StartDate(start_date),
+            end_date: self.end_date,
+        }
+    }
+}
+
+impl<M: Mode> SolanaOperationsBuilder<'_, StartDate, M> {
+    pub fn into_request(self) -> SolanaOperationsRequest {
+        SolanaOperationsRequest {
+            stake_accounts: self.stake_accounts,
+            wallets: self.wallets,
+            accounts: self.accounts,
+            start_date: self.start_date.0,
+            end_date: self.end_date,
+        }
+    }
+}
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(prepare_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PrepareTxResponse>>();
+for_each_mode! { <M>
+    impl SolanaOperationsBuilder<'_, StartDate, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<SolanaOperation>>, KilnError> {
+            let client = self.client;
 
-        data
+            client.get_operations(&self.into_request()).await
+        }
     }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod solana_test {
+
+    use super::*;
+    use crate::query::query_test_support::{date, fixture, requested_url};
+
+    #[test]
+    fn get_stakes() {
+        let url = requested_url(|kiln| {
+            kiln.solana().get_stakes(&SolanaGetStakesRequest {
+                wallets: Some(vec!["So1a".into(), "So1b".into()]),
+                ..Default::default()
+            })
+        });
 
-    pub fn post_broadcast_tx(
-        &self,
-        broadcast_transaction_request: &BroadcastTxRequest,
-    ) -> Result<ReturnedData<BroadcastTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/broadcast", self.base_url);
+        assert_eq!(url, "https://api.kiln.fi/v1/sol/stakes?wallets=So1a,So1b");
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(broadcast_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<BroadcastTxResponse>>();
+    #[test]
+    fn get_rewards() {
+        let url = requested_url(|kiln| {
+            kiln.solana().get_rewards(SolanaRewardRequest::Daily {
+                stakes_accounts: None,
+                validators: Some(vec!["So1v".into()]),
+                wallets: None,
+                accounts: None,
+                format: ResponseFormat::Daily,
+                start_date: date("2024-01-01T00:00:00Z"),
+                end_date: Some(date("2024-01-31T00:00:00Z")),
+                include_usd: true,
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/sol/rewards?validators=So1v&format=daily&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z&include_usd=true"
+        );
+    }
 
-        data
+    #[test]
+    fn get_operations() {
+        let url = requested_url(|kiln| {
+            kiln.solana().get_operations(&SolanaOperationsRequest {
+                stake_accounts: None,
+                wallets: Some(vec!["So1a".into()]),
+                accounts: None,
+                start_date: date("2024-01-01T00:00:00Z"),
+                end_date: Some(date("2024-01-31T00:00:00Z")),
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/sol/operations?wallets=So1a&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z"
+        );
     }
 
-    pub fn get_tx_status(&self, tx_hash: &str) -> Result<SolanaTxStatusResponse, ureq::Error> {
-        let url: String = format!("{}/transaction/status?tx_hash={}", self.base_url, tx_hash);
+    #[test]
+    fn get_reports() {
+        let url = requested_url(|kiln| {
+            kiln.solana().get_reports(&SolanaReportsRequest {
+                accounts: Some(vec![Uuid::nil()]),
+                ..Default::default()
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/sol/reports?accounts=00000000-0000-0000-0000-000000000000"
+        );
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<SolanaTxStatusResponse>();
+    #[test]
+    fn get_tx_status() {
+        let url = requested_url(|kiln| kiln.solana().get_tx_status("0xhash"));
 
-        data
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/sol/transaction/status?tx_hash=0xhash"
+        );
     }
 
-    pub fn get_tx_decoding(
-        &self,
-        tx_serialized: &str,
-    ) -> Result<ReturnedData<SolanaTxDecodeResponse>, ureq::Error> {
-        let url: String = format!(
-            "{}/transaction/decode?tx_serialized={}",
-            self.base_url, tx_serialized
+    #[test]
+    fn get_tx_decoding() {
+        let url = requested_url(|kiln| kiln.solana().get_tx_decoding("ab+/="));
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/sol/transaction/decode?tx_serialized=ab%2B%2F%3D"
         );
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<SolanaTxDecodeResponse>>();
+    #[test]
+    fn responses_match_the_fixtures() {
+        let rewards: ReturnedData<Vec<SolanaReward>> = fixture("solana/rewards.json");
+        let status: ReturnedData<SolanaTxStatusResponse> = fixture("solana/tx_status.json");
+        let stakes: ReturnedData<Vec<SolanaStakesResponse>> = fixture("solana/stakes.json");
+
+        assert!(matches!(
+            rewards.data[0],
+            SolanaReward::Epoch { epoch: 580, .. }
+        ));
+        assert!(matches!(rewards.data[1], SolanaReward::Daily { .. }));
+        assert!(status.data.receipt.unwrap().meta.unwrap().err.is_none());
+        assert!(stakes.data[0].deactivated_at.is_none());
+
+        fixture::<ReturnedData<PostStakesResponse>>("solana/post_stakes.json");
+        fixture::<ReturnedData<SolanaNetworkStatsResponse>>("solana/network_stats.json");
+        fixture::<ReturnedData<NonceAccount>>("solana/nonce_account.json");
+        fixture::<ReturnedData<SolanaTxResponse>>("solana/tx.json");
+        fixture::<ReturnedData<PrepareTxResponse>>("solana/prepare_tx.json");
+        fixture::<ReturnedData<BroadcastTxResponse>>("solana/broadcast_tx.json");
+        fixture::<ReturnedData<SolanaTxDecodeResponse>>("solana/tx_decoding.json");
+    }
 
-        data
+    #[test]
+    fn operations_are_told_apart_by_their_type() {
+        let operations: ReturnedData<Vec<SolanaOperation>> = fixture("solana/operations.json");
+
+        assert!(matches!(
+            operations.data[0],
+            SolanaOperation::CreateAccountWithSeed { .. }
+        ));
+        assert!(operations.data.last().unwrap().is_unknown());
+        assert_eq!(
+            operations
+                .data
+                .iter()
+                .filter(|operation| operation.is_unknown())
+                .count(),
+            1
+        );
     }
 }
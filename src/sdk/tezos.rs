@@ -1,22 +1,27 @@
-use std::io::Read;
-
 use serde::{Deserialize, Serialize};
-use strum_macros::{AsRefStr, EnumString};
 use uuid::Uuid;
 
+use crate::address::{Lenient, TezosAddress};
+use crate::amount::{Amount, FloatAmount};
+use crate::denomination::Mutez;
+use crate::errors::KilnError;
+use crate::http::HttpClient;
+use crate::mode::{for_each_mode, Blocking, Mode};
+use crate::query::Query;
 use crate::{response_format::ReturnedData, Kiln};
 
+use super::builders::{filter, NoStartDate, RewardsBuilder, StartDate};
 use super::{
     BroadcastTxRequest, BroadcastTxResponse, PrepareTxResponse, RewardRequest, StakeState,
 };
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 #[serde(rename_all = "snake_case")]
 pub struct TezosGetStakesRequest {
     // Addresses
-    pub wallets: Vec<String>,
-    pub accounts: Vec<Uuid>,
-    pub validators: Vec<String>,
+    pub wallets: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
+    pub validators: Option<Vec<String>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -32,39 +37,41 @@ pub struct TezosStakesResponse {
     pub delegated_at: chrono::DateTime<chrono::Utc>,
     pub delegated_cycle: u64,
     pub delegated_block: String,
-    pub undelegated_at: chrono::DateTime<chrono::Utc>,
-    pub undelegated_cycle: u64,
-    pub balance: String,
-    pub staked_balance: String,
-    pub wallet_balance: String,
-    pub rewards: String,
-    pub staked_rewards: String,
+    // `None` while the wallet is delegated
+    pub undelegated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub undelegated_cycle: Option<u64>,
+    pub balance: Amount,
+    pub staked_balance: Amount,
+    pub wallet_balance: Amount,
+    pub rewards: Amount,
+    pub staked_rewards: Amount,
     pub gross_apy: f64,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+// daily rewards have a `date`, cycle rewards a `cycle`
 #[derive(Serialize, Deserialize, Debug)]
-#[serde(rename_all = "snake_case")]
+#[serde(untagged)]
 pub enum TezosXTZReward {
     ByDay {
         date: chrono::DateTime<chrono::Utc>,
-        rewards: String,
-        active_balance: String,
-        staked_rewards: String,
-        staked_balance: String,
+        rewards: Amount,
+        active_balance: Amount,
+        staked_rewards: Amount,
+        staked_balance: Amount,
         gross_apy: f64,
-        rewards_usd: String,
-        stake_balance_usd: String,
-        active_balance_usd: Option<u64>,
-        staked_balance_usd: u64,
+        rewards_usd: Amount,
+        stake_balance_usd: Amount,
+        active_balance_usd: Option<FloatAmount>,
+        staked_balance_usd: FloatAmount,
     },
     ByCycle {
         cycle: u64,
         cycle_begins_at: chrono::DateTime<chrono::Utc>,
-        rewards: String,
-        active_balance: String,
-        staked_rewards: String,
-        staked_balance: String,
+        rewards: Amount,
+        active_balance: Amount,
+        staked_rewards: Amount,
+        staked_balance: Amount,
         gross_apy: f64,
     },
 }
@@ -72,110 +79,103 @@ pub enum TezosXTZReward {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct TezosOperationsRequest {
-    pub wallets: Vec<String>,
-    pub accounts: Vec<Uuid>,
-    pub validators: Vec<String>,
+    pub wallets: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
+    pub validators: Option<Vec<String>>,
     pub start_date: chrono::DateTime<chrono::Utc>,
-    pub end_date: chrono::DateTime<chrono::Utc>,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-#[serde(rename_all = "snake_case")]
-pub enum TezosOperation {
-    Delegate {
-        r#type: String,
-        date: chrono::DateTime<chrono::Utc>,
-        // Address
-        staker_address: String,
-        // Address
-        baker_address: String,
-        operation: String,
-        operation_gas_used: String,
-        baker_fee: String,
-        block: u64,
-        amount: String,
-    },
-    Undelegate {
-        r#type: String,
-        date: chrono::DateTime<chrono::Utc>,
-        // Address
-        staker_address: String,
-        // Address
-        baker_address: String,
-        operation: String,
-        operation_gas_used: String,
-        baker_fee: String,
-        block: u64,
-    },
-    Activation {
-        r#type: String,
-        date: chrono::DateTime<chrono::Utc>,
-        // Address
-        staker_address: String,
-        // Address
-        baker_address: String,
-        cycle: u64,
-    },
-    Payment {
-        r#type: String,
-        date: chrono::DateTime<chrono::Utc>,
-        // Address
-        staker_address: String,
-        // Address
-        baker_address: String,
-        // Address
-        sender_address: String,
-        operation: String,
-        operation_gas_used: String,
-        baker_fee: String,
-        block: u64,
-        amount: String,
-    },
-    Stake {
-        r#type: String,
-        date: chrono::DateTime<chrono::Utc>,
-        // Address
-        staker_address: String,
-        // Address
-        baker_address: String,
-        operation: String,
-        operation_gas_used: String,
-        baker_fee: String,
-        block: u64,
-        amount: String,
-    },
-    Unstake {
-        r#type: String,
-        date: chrono::DateTime<chrono::Utc>,
-        // Address
-        staker_address: String,
-        // Address
-        baker_address: String,
-        operation: String,
-        operation_gas_used: String,
-        baker_fee: String,
-        block: u64,
-        amount: String,
-    },
-    Finalize {
-        r#type: String,
-        date: chrono::DateTime<chrono::Utc>,
-        // Address
-        staker_address: String,
-        // Address
-        baker_address: String,
-        operation: String,
-        operation_gas_used: String,
-        baker_fee: String,
-        block: u64,
-        amount: String,
-    },
+    pub end_date: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+operation_enum! {
+    pub enum TezosOperation {
+        Delegate => "delegate" {
+            date: chrono::DateTime<chrono::Utc>,
+            // Address
+            staker_address: Lenient<TezosAddress>,
+            // Address
+            baker_address: Lenient<TezosAddress>,
+            operation: String,
+            operation_gas_used: String,
+            baker_fee: String,
+            block: u64,
+            amount: Amount,
+        },
+        Undelegate => "undelegate" {
+            date: chrono::DateTime<chrono::Utc>,
+            // Address
+            staker_address: Lenient<TezosAddress>,
+            // Address
+            baker_address: Lenient<TezosAddress>,
+            operation: String,
+            operation_gas_used: String,
+            baker_fee: String,
+            block: u64,
+        },
+        Activation => "activation" {
+            date: chrono::DateTime<chrono::Utc>,
+            // Address
+            staker_address: Lenient<TezosAddress>,
+            // Address
+            baker_address: Lenient<TezosAddress>,
+            cycle: u64,
+        },
+        Payment => "payment" {
+            date: chrono::DateTime<chrono::Utc>,
+            // Address
+            staker_address: Lenient<TezosAddress>,
+            // Address
+            baker_address: Lenient<TezosAddress>,
+            // Address
+            sender_address: Lenient<TezosAddress>,
+            operation: String,
+            operation_gas_used: String,
+            baker_fee: String,
+            block: u64,
+            amount: Amount,
+        },
+        Stake => "stake" {
+            date: chrono::DateTime<chrono::Utc>,
+            // Address
+            staker_address: Lenient<TezosAddress>,
+            // Address
+            baker_address: Lenient<TezosAddress>,
+            operation: String,
+            operation_gas_used: String,
+            baker_fee: String,
+            block: u64,
+            amount: Amount,
+        },
+        Unstake => "unstake" {
+            date: chrono::DateTime<chrono::Utc>,
+            // Address
+            staker_address: Lenient<TezosAddress>,
+            // Address
+            baker_address: Lenient<TezosAddress>,
+            operation: String,
+            operation_gas_used: String,
+            baker_fee: String,
+            block: u64,
+            amount: Amount,
+        },
+        Finalize => "finalize" {
+            date: chrono::DateTime<chrono::Utc>,
+            // Address
+            staker_address: Lenient<TezosAddress>,
+            // Address
+            baker_address: Lenient<TezosAddress>,
+            operation: String,
+            operation_gas_used: String,
+            baker_fee: String,
+            block: u64,
+            amount: Amount,
+        },
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct TezosNetworkStatsResponse {
-    pub xtz_price_usd: f64,
+    pub xtz_price_usd: FloatAmount,
     pub nb_validators: u64,
     pub net_gross_apy: f64,
     pub supply_staked_percent: f64,
@@ -183,11 +183,11 @@ pub struct TezosNetworkStatsResponse {
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 #[serde(rename_all = "snake_case")]
 pub struct TezosReportsRequest {
-    pub wallets: Vec<String>,
-    pub accounts: Vec<Uuid>,
+    pub wallets: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -195,9 +195,9 @@ pub struct TezosReportsRequest {
 pub struct TezosDelegateTxRequest {
     pub account_id: Uuid,
     // Address
-    pub wallet: String,
+    pub wallet: TezosAddress,
     // Address
-    pub baker_address: String,
+    pub baker_address: TezosAddress,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -217,7 +217,8 @@ pub struct TxContentParamaters {
 #[serde(rename_all = "snake_case")]
 pub struct TezosBalanceUpdate {
     pub kind: String,
-    pub contract: String,
+    // `None` for the frozen deposits and the fees accumulator
+    pub contract: Option<String>,
     pub change: String,
     pub origin: String,
 }
@@ -271,11 +272,13 @@ pub struct TezosLazyStorageDiff {
 #[serde(rename_all = "snake_case")]
 pub struct TezosOperationResult {
     pub status: TezosTxStatus,
-    pub storage: TezosTxStorage,
-    pub balance_updates: Vec<TezosBalanceUpdate>,
     pub consumed_milligas: String,
-    pub storage_size: String,
-    pub paid_storage_size_diff: String,
+    // only for the calls to a contract
+    pub storage: Option<TezosTxStorage>,
+    #[serde(default)]
+    pub balance_updates: Vec<TezosBalanceUpdate>,
+    pub storage_size: Option<String>,
+    pub paid_storage_size_diff: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -290,15 +293,18 @@ pub struct TezosTxMetadata {
 pub struct TezosTxContent {
     pub kind: String,
     pub source: String,
-    pub fee: String,
+    pub fee: Amount,
     pub counter: String,
     pub gas_limit: String,
     pub storage_limit: String,
-    // Address
-    pub delegate: String,
-    pub destination: String,
-    pub parameters: TxContentParamaters,
-    pub metadata: TezosTxMetadata,
+    // depending on the operation kind : a `delegation` has a `delegate` address, a `transaction`
+    // a `destination`, an `amount` and the `parameters` of the call
+    pub delegate: Option<String>,
+    pub destination: Option<String>,
+    pub amount: Option<Amount>,
+    pub parameters: Option<TxContentParamaters>,
+    // only in the receipts
+    pub metadata: Option<TezosTxMetadata>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -320,30 +326,30 @@ pub struct TezosTxResponse {
 #[serde(rename_all = "snake_case")]
 pub struct TezosUndelegateTxRequest {
     // Address
-    pub wallet: String,
+    pub wallet: TezosAddress,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct TezosFinalizeUnstakeTxRequest {
     // Address
-    pub wallet: String,
+    pub wallet: TezosAddress,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct TezosStakeTxRequest {
     // Address
-    pub wallet: String,
-    pub amount_umutez: String,
+    pub wallet: TezosAddress,
+    pub amount_umutez: Mutez,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct TezosUnstakeTxRequest {
     // Address
-    pub wallet: String,
-    pub amount_umutez: String,
+    pub wallet: TezosAddress,
+    pub amount_umutez: Mutez,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -360,13 +366,13 @@ pub struct TezosTxStatusRequest {
     pub block: u64,
 }
 
-#[derive(Serialize, Deserialize, Debug, EnumString, AsRefStr)]
-#[serde(rename_all = "snake_case")]
-pub enum TezosTxStatus {
-    Applied,
-    Failed,
-    Skipped,
-    Backtracked,
+string_enum! {
+    pub enum TezosTxStatus {
+        Applied => "applied",
+        Failed => "failed",
+        Skipped => "skipped",
+        Backtracked => "backtracked",
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -396,385 +402,575 @@ pub struct TezosTxDecodingResponse {
 }
 
 #[derive(Clone, Debug)]
-pub struct KilnTezosClient {
-    pub bearer_token: String,
+pub struct KilnTezosClient<M: Mode = Blocking> {
     pub base_url: String,
+    http: HttpClient<M>,
 }
 
-impl From<&Kiln> for KilnTezosClient {
-    fn from(kiln: &Kiln) -> Self {
+impl<M: Mode> From<&Kiln<M>> for KilnTezosClient<M> {
+    fn from(kiln: &Kiln<M>) -> Self {
         let url: String = format!("{}/xtz", kiln.base_url);
-        let bearer_token: String = format!("Bearer {}", kiln.api_token);
 
         Self {
-            bearer_token,
             base_url: url,
+            http: kiln.http.clone(),
         }
     }
 }
 
-impl KilnTezosClient {
-    pub fn get_stakes(
-        &self,
-        stakes_request: &TezosGetStakesRequest,
-    ) -> Result<ReturnedData<Vec<TezosStakesResponse>>, ureq::Error> {
-        let TezosGetStakesRequest {
-            validators,
-            wallets,
-            accounts,
-        } = stakes_request;
-
-        let validators = validators.join(",");
-
-        let wallets = wallets.join(",");
-
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
+for_each_mode! { <M>
+    impl KilnTezosClient<M> {
+        pub async fn get_stakes(
+            &self,
+            stakes_request: &TezosGetStakesRequest,
+        ) -> Result<ReturnedData<Vec<TezosStakesResponse>>, KilnError> {
+            let TezosGetStakesRequest {
+                validators,
+                wallets,
+                accounts,
+            } = stakes_request;
 
-        let url: String = format!(
-            "{}/stakes?validators={}&wallets={}&accounts={}",
-            self.base_url, validators, wallets, accounts
-        );
+            let url = Query::new()
+                .list("validators", validators.iter().flatten())
+                .list("wallets", wallets.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .url(&format!("{}/stakes", self.base_url));
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<TezosStakesResponse>>>();
+            self.http.get("kiln.xtz.get_stakes", &url).await
+        }
 
-        data
-    }
+        pub async fn get_rewards(
+            &self,
+            reward_request: RewardRequest,
+        ) -> Result<ReturnedData<Vec<TezosXTZReward>>, KilnError> {
+            let url: String = match reward_request {
+                RewardRequest::Epoch {
+                    stakes_addresses,
+                    wallets,
+                    pool_ids,
+                    accounts,
+                    format,
+                    start_date,
+                    end_date,
+                    start_epoch,
+                    end_epoch,
+                } => Query::new()
+                    .list("stake_addresses", stakes_addresses.iter().flatten())
+                    .list("pool_ids", pool_ids.iter().flatten())
+                    .list("wallets", wallets.iter().flatten())
+                    .list("accounts", accounts.iter().flatten())
+                    .value("format", format.as_ref())
+                    .value("start_date", start_date)
+                    .option("end_date", end_date.as_ref())
+                    .value("start_epoch", start_epoch)
+                    .value("end_epoch", end_epoch)
+                    .url(&format!("{}/rewards", self.base_url)),
+                RewardRequest::Daily {
+                    stakes_addresses,
+                    wallets,
+                    pool_ids,
+                    accounts,
+                    format,
+                    start_date,
+                    end_date,
+                    include_usd,
+                } => Query::new()
+                    .list("stake_addresses", stakes_addresses.iter().flatten())
+                    .list("pool_ids", pool_ids.iter().flatten())
+                    .list("wallets", wallets.iter().flatten())
+                    .list("accounts", accounts.iter().flatten())
+                    .value("format", format.as_ref())
+                    .value("start_date", start_date)
+                    .option("end_date", end_date.as_ref())
+                    .value("include_usd", include_usd)
+                    .url(&format!("{}/rewards", self.base_url)),
+            };
+
+            self.http.get("kiln.xtz.get_rewards", &url).await
+        }
 
-    pub fn get_rewards(
-        &self,
-        reward_request: RewardRequest,
-    ) -> Result<ReturnedData<Vec<TezosXTZReward>>, ureq::Error> {
-        let url: String = match reward_request {
-            RewardRequest::Epoch {
-                stakes_addresses,
+        pub async fn get_operations(
+            &self,
+            validator_operations_request: &TezosOperationsRequest,
+        ) -> Result<ReturnedData<Vec<TezosOperation>>, KilnError> {
+            let TezosOperationsRequest {
+                validators,
                 wallets,
-                pool_ids,
                 accounts,
-                format,
                 start_date,
                 end_date,
-                start_epoch,
-                end_epoch,
-            } => {
-                let stakes_addresses = stakes_addresses.join(",");
-
-                let pool_ids = pool_ids.join(",");
-
-                let wallets = wallets.join(",");
-
-                let accounts = accounts
-                    .iter()
-                    .map(|id| id.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",");
-
-                let url = format!(
-            "{}/rewards?stake_addresses={}&pool_ids={}&wallets={}&accounts={}&format={}&start_date={}&end_date={}&start_epoch={}&end_epoch={}",
-            self.base_url,
-            stakes_addresses,
-            pool_ids,
-            wallets,
-            accounts,
-            format.as_ref(),
-            start_date,
-            end_date,
-            start_epoch,
-            end_epoch
-        );
+            } = validator_operations_request;
 
-                url
-            }
-            RewardRequest::Daily {
-                stakes_addresses,
-                wallets,
-                pool_ids,
-                accounts,
-                format,
-                start_date,
-                end_date,
-                include_usd,
-            } => {
-                let stakes_addresses = stakes_addresses.join(",");
-
-                let pool_ids = pool_ids.join(",");
-
-                let wallets = wallets.join(",");
-
-                let accounts = accounts
-                    .iter()
-                    .map(|id| id.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",");
-
-                let url = format!(
-            "{}/rewards?stake_addresses={}&pool_ids={}&wallets={}&accounts={}&format={}&start_date={}&end_date={}&include_usd={}",
-            self.base_url,
-            stakes_addresses,
-            pool_ids,
-            wallets,
-            accounts,
-            format.as_ref(),
-            start_date,
-            end_date,
-            include_usd
-        );
+            let url = Query::new()
+                .list("validators", validators.iter().flatten())
+                .list("wallets", wallets.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .value("start_date", start_date)
+                .option("end_date", end_date.as_ref())
+                .url(&format!("{}/operations", self.base_url));
 
-                url
-            }
-        };
+            self.http.get("kiln.xtz.get_operations", &url).await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<TezosXTZReward>>>();
+        pub async fn get_network_stats(
+            &self,
+        ) -> Result<ReturnedData<TezosNetworkStatsResponse>, KilnError> {
+            let url: String = format!("{}/network-stats", self.base_url);
 
-        data
-    }
+            self.http.get("kiln.xtz.get_network_stats", &url).await
+        }
 
-    pub fn get_operations(
-        &self,
-        validator_operations_request: &TezosOperationsRequest,
-    ) -> Result<ReturnedData<Vec<TezosOperation>>, ureq::Error> {
-        let TezosOperationsRequest {
-            validators,
-            wallets,
-            accounts,
-            start_date,
-            end_date,
-        } = validator_operations_request;
-
-        let validators = validators.join(",");
-
-        let wallets = wallets.join(",");
-
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        let url: String = format!(
-            "{}/operations?validators={}&wallets={}&accounts={}&start_date={}&end_date={}",
-            self.base_url, validators, wallets, accounts, start_date, end_date
-        );
+        pub async fn get_reports(
+            &self,
+            reports_request: &TezosReportsRequest,
+        ) -> Result<Vec<u8>, KilnError> {
+            let TezosReportsRequest { wallets, accounts } = reports_request;
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<Vec<TezosOperation>>>();
+            let url = Query::new()
+                .list("wallets", wallets.iter().flatten())
+                .list("accounts", accounts.iter().flatten())
+                .url(&format!("{}/reports", self.base_url));
 
-        data
-    }
+            self.http.get_bytes("kiln.xtz.get_reports", &url).await
+        }
+
+        pub async fn post_delegate_tx(
+            &self,
+            delegate_tx_request: &TezosDelegateTxRequest,
+        ) -> Result<ReturnedData<TezosTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/delegate", self.base_url);
 
-    pub fn get_network_stats(
-        &self,
-    ) -> Result<ReturnedData<TezosNetworkStatsResponse>, ureq::Error> {
-        let url: String = format!("{}/network-stats", self.base_url);
+            self.http
+                .post_tx("kiln.xtz.post_delegate_tx", &url, delegate_tx_request)
+                .await
+        }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<TezosNetworkStatsResponse>>();
+        pub async fn post_undelegate_tx(
+            &self,
+            undelegate_tx_request: &TezosUndelegateTxRequest,
+        ) -> Result<ReturnedData<TezosTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/undelegate", self.base_url);
 
-        data
-    }
+            self.http
+                .post_tx("kiln.xtz.post_undelegate_tx", &url, undelegate_tx_request)
+                .await
+        }
 
-    pub fn get_reports(
-        &self,
-        reports_request: &TezosReportsRequest,
-    ) -> Result<Vec<u8>, ureq::Error> {
-        let TezosReportsRequest { wallets, accounts } = reports_request;
+        pub async fn post_stake_tx(
+            &self,
+            transaction_stake_request: &TezosStakeTxRequest,
+        ) -> Result<ReturnedData<TezosTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/stake", self.base_url,);
 
-        let wallets = wallets.join(",");
+            self.http
+                .post_tx("kiln.xtz.post_stake_tx", &url, transaction_stake_request)
+                .await
+        }
 
-        let accounts = accounts
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
+        pub async fn post_unstake_tx(
+            &self,
+            transaction_unstake_request: &TezosUnstakeTxRequest,
+        ) -> Result<ReturnedData<TezosTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/unstake", self.base_url,);
+
+            self.http
+                .post_tx(
+                    "kiln.xtz.post_unstake_tx",
+                    &url,
+                    transaction_unstake_request,
+                )
+                .await
+        }
 
-        let url: String = format!(
-            "{}/reports?wallets={}&accounts={}",
-            self.base_url, wallets, accounts
-        );
+        pub async fn post_finalize_unstake_tx(
+            &self,
+            finalize_unstake_tx_request: &TezosFinalizeUnstakeTxRequest,
+        ) -> Result<ReturnedData<TezosTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/finalize-unstake", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.xtz.post_finalize_unstake_tx",
+                    &url,
+                    finalize_unstake_tx_request,
+                )
+                .await
+        }
+
+        pub async fn post_prepare_tx(
+            &self,
+            prepare_transaction_request: &TezosPrepareTxRequest,
+        ) -> Result<ReturnedData<PrepareTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/prepare", self.base_url,);
+
+            self.http
+                .post_tx(
+                    "kiln.xtz.post_prepare_tx",
+                    &url,
+                    prepare_transaction_request,
+                )
+                .await
+        }
+
+        pub async fn post_broadcast_tx(
+            &self,
+            broadcast_transaction_request: &BroadcastTxRequest,
+        ) -> Result<ReturnedData<BroadcastTxResponse>, KilnError> {
+            let url: String = format!("{}/transaction/broadcast", self.base_url);
+
+            self.http
+                .post_tx(
+                    "kiln.xtz.post_broadcast_tx",
+                    &url,
+                    broadcast_transaction_request,
+                )
+                .await
+        }
+
+        pub async fn get_tx_status(
+            &self,
+            tx_status_request: &TezosTxStatusRequest,
+        ) -> Result<ReturnedData<TezosTxStatusResponse>, KilnError> {
+            let TezosTxStatusRequest { block, tx_hash } = tx_status_request;
 
-        let mut file_bytes: Vec<u8> = Vec::new();
+            let url = Query::new()
+                .value("tx_hash", tx_hash)
+                .value("block", block)
+                .url(&format!("{}/transaction/status", self.base_url));
+
+            self.http.get("kiln.xtz.get_tx_status", &url).await
+        }
 
-        ureq::get(url)
-            .header("accept", "application/octet-stream")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .as_reader()
-            .read_to_end(&mut file_bytes)?;
+        pub async fn get_tx_decoding(
+            &self,
+            tx_serialized: &str,
+        ) -> Result<ReturnedData<TezosTxDecodingResponse>, KilnError> {
+            let url = Query::new()
+                .value("tx_serialized", tx_serialized)
+                .url(&format!("{}/transaction/decode", self.base_url));
 
-        Ok(file_bytes)
+            self.http.get("kiln.xtz.get_tx_decoding", &url).await
+        }
     }
+}
 
-    pub fn post_delegate_tx(
-        &self,
-        delegate_tx_request: &TezosDelegateTxRequest,
-    ) -> Result<ReturnedData<TezosTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/delegate", self.base_url);
+impl<M: Mode> KilnTezosClient<M> {
+    pub fn stakes(&self) -> TezosStakesBuilder<'_, M> {
+        TezosStakesBuilder::new(self)
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(delegate_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<TezosTxResponse>>();
+    pub fn rewards(&self) -> RewardsBuilder<'_, Self, NoStartDate> {
+        RewardsBuilder::new(self)
+    }
 
-        data
+    pub fn operations(&self) -> TezosOperationsBuilder<'_, NoStartDate, M> {
+        TezosOperationsBuilder::new(self)
     }
+}
 
-    pub fn post_undelegate_tx(
-        &self,
-        undelegate_tx_request: &TezosUndelegateTxRequest,
-    ) -> Result<ReturnedData<TezosTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/undelegate", self.base_url);
+// `kiln.tezos().stakes()`
+#[derive(Debug)]
+pub struct TezosStakesBuilder<'a, M: Mode = Blocking> {
+    client: &'a KilnTezosClient<M>,
+    wallets: Option<Vec<String>>,
+    accounts: Option<Vec<Uuid>>,
+    validators: Option<Vec<String>>,
+}
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(undelegate_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<TezosTxResponse>>();
+impl<'a, M: Mode> TezosStakesBuilder<'a, M> {
+    fn new(client: &'a KilnTezosClient<M>) -> Self {
+        Self {
+            client,
+            wallets: None,
+            accounts: None,
+            validators: None,
+        }
+    }
 
-        data
+    pub fn wallets(self, wallets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            wallets: filter(wallets),
+            ..self
+        }
     }
 
-    pub fn post_stake_tx(
-        &self,
-        transaction_stake_request: &TezosStakeTxRequest,
-    ) -> Result<ReturnedData<TezosTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/stake", self.base_url,);
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
+    }
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(transaction_stake_request)?
-            .body_mut()
-            .read_json::<ReturnedData<TezosTxResponse>>();
+    pub fn validators(self, validators: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            validators: filter(validators),
+            ..self
+        }
+    }
 
-        data
+    pub fn into_request(self) -> TezosGetStakesRequest {
+        TezosGetStakesRequest {
+            wallets: self.wallets,
+            accounts: self.accounts,
+            validators: self.validators,
+        }
     }
+}
 
-    pub fn post_unstake_tx(
-        &self,
-        transaction_unstake_request: &TezosUnstakeTxRequest,
-    ) -> Result<ReturnedData<TezosTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/unstake", self.base_url,);
+for_each_mode! { <M>
+    impl TezosStakesBuilder<'_, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<TezosStakesResponse>>, KilnError> {
+            let client = self.client;
+
+            client.get_stakes(&self.into_request()).await
+        }
+    }
+}
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(transaction_unstake_request)?
-            .body_mut()
-            .read_json::<ReturnedData<TezosTxResponse>>();
+for_each_mode! { <M>
+    impl RewardsBuilder<'_, KilnTezosClient<M>, StartDate> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<TezosXTZReward>>, KilnError> {
+            let client = self.client;
 
-        data
+            client.get_rewards(self.into_request()?).await
+        }
     }
+}
 
-    pub fn post_finalize_unstake_tx(
-        &self,
-        finalize_unstake_tx_request: &TezosFinalizeUnstakeTxRequest,
-    ) -> Result<ReturnedData<TezosTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/finalize-unstake", self.base_url);
+// `kiln.tezos().operations()`
+#[derive(Debug)]
+pub struct TezosOperationsBuilder<'a, S, M: Mode = Blocking> {
+    client: &'a KilnTezosClient<M>,
+    wallets: Option<Vec<String>>,
+    accounts: Option<Vec<Uuid>>,
+    validators: Option<Vec<String>>,
+    start_date: S,
+    end_date: Option<chrono::DateTime<chrono::Utc>>,
+}
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(finalize_unstake_tx_request)?
-            .body_mut()
-            .read_json::<ReturnedData<TezosTxResponse>>();
+impl<'a, M: Mode> TezosOperationsBuilder<'a, NoStartDate, M> {
+    fn new(client: &'a KilnTezosClient<M>) -> Self {
+        Self {
+            client,
+            wallets: None,
+            accounts: None,
+            validators: None,
+            start_date: NoStartDate,
+            end_date: None,
+        }
+    }
+}
 
-        data
+impl<'a, S, M: Mode> TezosOperationsBuilder<'a, S, M> {
+    pub fn wallets(self, wallets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            wallets: filter(wallets),
+            ..self
+        }
     }
 
-    pub fn post_prepare_tx(
-        &self,
-        prepare_transaction_request: &TezosPrepareTxRequest,
-    ) -> Result<ReturnedData<PrepareTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/prepare", self.base_url,);
+    pub fn accounts(self, accounts: impl IntoIterator<Item = impl Into<Uuid>>) -> Self {
+        Self {
+            accounts: filter(accounts),
+            ..self
+        }
+    }
+
+    pub fn validators(self, validators: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            validators: filter(validators),
+            ..self
+        }
+    }
+
+    pub fn until(self, end_date: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            end_date: Some(end_date),
+            ..self
+        }
+    }
+
+    pub fn since(
+        self,
+        start_date: chrono::DateTime<chrono::Utc>,
+    ) -> TezosOperationsBuilder<'a, StartDate, M> {
+        TezosOperationsBuilder {
+            client: self.client,
+            wallets: self.wallets,
+            accounts: self.accounts,
+            validators: self.validators,
+            start_date: StartDate(start_date),
+            end_date: self.end_date,
+        }
+    }
+}
+
+impl<M: Mode> TezosOperationsBuilder<'_, StartDate, M> {
+    pub fn into_request(self) -> TezosOperationsRequest {
+        TezosOperationsRequest {
+            wallets: self.wallets,
+            accounts: self.accounts,
+            validators: self.validators,
+            start_date: self.start_date.0,
+            end_date: self.end_date,
+        }
+    }
+}
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(prepare_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<PrepareTxResponse>>();
+for_each_mode! { <M>
+    impl TezosOperationsBuilder<'_, StartDate, M> {
+        pub async fn send(self) -> Result<ReturnedData<Vec<TezosOperation>>, KilnError> {
+            let client = self.client;
 
-        data
+            client.get_operations(&self.into_request()).await
+        }
     }
+}
 
-    pub fn post_broadcast_tx(
-        &self,
-        broadcast_transaction_request: &BroadcastTxRequest,
-    ) -> Result<ReturnedData<BroadcastTxResponse>, ureq::Error> {
-        let url: String = format!("{}/transaction/broadcast", self.base_url);
+#[cfg(all(test, feature = "blocking"))]
+mod tezos_test {
 
-        let data = ureq::post(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .send_json(broadcast_transaction_request)?
-            .body_mut()
-            .read_json::<ReturnedData<BroadcastTxResponse>>();
+    use super::*;
+    use crate::query::query_test_support::{date, fixture, requested_url};
+    use crate::sdk::ResponseFormat;
 
-        data
+    #[test]
+    fn get_stakes() {
+        let url = requested_url(|kiln| {
+            kiln.tezos().get_stakes(&TezosGetStakesRequest {
+                wallets: Some(vec!["tz1a".into(), "tz1b".into()]),
+                ..Default::default()
+            })
+        });
+
+        assert_eq!(url, "https://api.kiln.fi/v1/xtz/stakes?wallets=tz1a,tz1b");
     }
 
-    pub fn get_tx_status(
-        &self,
-        tx_status_request: &TezosTxStatusRequest,
-    ) -> Result<ReturnedData<TezosTxStatusResponse>, ureq::Error> {
-        let TezosTxStatusRequest { block, tx_hash } = tx_status_request;
+    #[test]
+    fn get_rewards() {
+        let url = requested_url(|kiln| {
+            kiln.tezos().get_rewards(RewardRequest::Daily {
+                stakes_addresses: None,
+                wallets: Some(vec!["tz1a".into()]),
+                pool_ids: None,
+                accounts: None,
+                format: ResponseFormat::Daily,
+                start_date: date("2024-01-01T00:00:00Z"),
+                end_date: Some(date("2024-01-31T00:00:00Z")),
+                include_usd: false,
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/xtz/rewards?wallets=tz1a&format=daily&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z&include_usd=false"
+        );
+    }
 
-        let url: String = format!(
-            "{}/transaction/status?tx_hash={}&block={}",
-            self.base_url, tx_hash, block
+    #[test]
+    fn get_operations() {
+        let url = requested_url(|kiln| {
+            kiln.tezos().get_operations(&TezosOperationsRequest {
+                wallets: None,
+                accounts: None,
+                validators: Some(vec!["tz1v".into()]),
+                start_date: date("2024-01-01T00:00:00Z"),
+                end_date: Some(date("2024-01-31T00:00:00Z")),
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/xtz/operations?validators=tz1v&start_date=2024-01-01T00%3A00%3A00Z&end_date=2024-01-31T00%3A00%3A00Z"
         );
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<TezosTxStatusResponse>>();
+    #[test]
+    fn get_reports() {
+        let url = requested_url(|kiln| {
+            kiln.tezos().get_reports(&TezosReportsRequest {
+                accounts: Some(vec![Uuid::nil()]),
+                ..Default::default()
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/xtz/reports?accounts=00000000-0000-0000-0000-000000000000"
+        );
+    }
 
-        data
+    #[test]
+    fn get_tx_status() {
+        let url = requested_url(|kiln| {
+            kiln.tezos().get_tx_status(&TezosTxStatusRequest {
+                tx_hash: "oo1".into(),
+                block: 42,
+            })
+        });
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/xtz/transaction/status?tx_hash=oo1&block=42"
+        );
     }
 
-    pub fn get_tx_decoding(
-        &self,
-        tx_serialized: &str,
-    ) -> Result<ReturnedData<TezosTxDecodingResponse>, ureq::Error> {
-        let url: String = format!(
-            "{}/transaction/decode?tx_serialized={}",
-            self.base_url, tx_serialized
+    #[test]
+    fn get_tx_decoding() {
+        let url = requested_url(|kiln| kiln.tezos().get_tx_decoding("ab+/="));
+
+        assert_eq!(
+            url,
+            "https://api.kiln.fi/v1/xtz/transaction/decode?tx_serialized=ab%2B%2F%3D"
         );
+    }
 
-        let data = ureq::get(url)
-            .header("accept", "application/json; charset=utf-8")
-            .header("Authorization", &self.bearer_token)
-            .call()?
-            .body_mut()
-            .read_json::<ReturnedData<TezosTxDecodingResponse>>();
+    #[test]
+    fn responses_match_the_fixtures() {
+        let rewards: ReturnedData<Vec<TezosXTZReward>> = fixture("tezos/rewards.json");
+        let status: ReturnedData<TezosTxStatusResponse> = fixture("tezos/tx_status.json");
+        let delegate: ReturnedData<TezosTxResponse> = fixture("tezos/tx.json");
+
+        assert!(matches!(rewards.data[0], TezosXTZReward::ByDay { .. }));
+        assert!(matches!(
+            rewards.data[1],
+            TezosXTZReward::ByCycle { cycle: 712, .. }
+        ));
+        assert_eq!(
+            status.data.receipt.contents[0]
+                .metadata
+                .as_ref()
+                .unwrap()
+                .operation_result
+                .status,
+            TezosTxStatus::Applied
+        );
+        assert!(delegate.data.unsigned_tx.contents[0].delegate.is_some());
+
+        fixture::<ReturnedData<Vec<TezosStakesResponse>>>("tezos/stakes.json");
+        fixture::<ReturnedData<TezosNetworkStatsResponse>>("tezos/network_stats.json");
+        fixture::<ReturnedData<TezosTxResponse>>("tezos/stake_tx.json");
+        fixture::<ReturnedData<PrepareTxResponse>>("tezos/prepare_tx.json");
+        fixture::<ReturnedData<BroadcastTxResponse>>("tezos/broadcast_tx.json");
+        fixture::<ReturnedData<TezosTxDecodingResponse>>("tezos/tx_decoding.json");
+    }
 
-        data
+    #[test]
+    fn operations_are_told_apart_by_their_type() {
+        let operations: ReturnedData<Vec<TezosOperation>> = fixture("tezos/operations.json");
+
+        assert!(matches!(
+            operations.data[0],
+            TezosOperation::Delegate { .. }
+        ));
+        assert!(operations.data.last().unwrap().is_unknown());
+        assert_eq!(
+            operations
+                .data
+                .iter()
+                .filter(|operation| operation.is_unknown())
+                .count(),
+            1
+        );
     }
 }
@@ -1,19 +1,21 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Deserializer, Serialize};
-use strum_macros::{AsRefStr, EnumString};
+use strum_macros::AsRefStr;
 use uuid::Uuid;
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct ActivatingStakes {
-    pub amount: u64,
-    pub amount_usd: u64,
+    pub amount: Amount,
+    pub amount_usd: FloatAmount,
     pub total: u64,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct Any {
-    #[serde(rename = "camelCase")]
+    #[serde(rename = "typeUrl")]
     pub type_url: String,
     // Uint8Array
     pub value: Vec<u8>,
@@ -39,43 +41,171 @@ pub struct BitArray {
     pub elems: Vec<u8>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-#[repr(u64)]
+// EVM chain id, a json number : `1`, `17000`, `137`...
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum ChainID {
-    ArbitrumOne = 42161,
-    AvalanceCChain = 43114,
-    BNBSmartChainMainnet = 56,
-    CoinBase = 8453,
-    EthereumMainnet = 1,
-    PolygonMainnet = 137,
-    Sepolia = 11155111,
-    Holesky = 17000,
-    OptimismMainnet = 10,
-}
-
-#[derive(Serialize, Deserialize, Debug, EnumString)]
-pub enum Chain {
-    #[strum(serialize = "arb")]
-    Arb,
-    #[strum(serialize = "eth")]
-    Eth,
-    #[strum(serialize = "bsc")]
-    Bsc,
-    #[strum(serialize = "matic")]
-    Matic,
-    #[strum(serialize = "base")]
-    Base,
-    #[strum(serialize = "op")]
-    Op,
-    #[strum(serialize = "cosmoshub-4")]
-    CosmosHub4,
+    EthereumMainnet,
+    Sepolia,
+    Holesky,
+    Hoodi,
+    ArbitrumOne,
+    ArbitrumSepolia,
+    AvalanceCChain,
+    AvalancheFuji,
+    BNBSmartChainMainnet,
+    BNBSmartChainTestnet,
+    CoinBase,
+    BaseSepolia,
+    OptimismMainnet,
+    OptimismSepolia,
+    PolygonMainnet,
+    PolygonAmoy,
+    // a network this version of the SDK doesn't know yet
+    Unknown(u64),
+}
+
+impl ChainID {
+    pub fn id(&self) -> u64 {
+        match self {
+            ChainID::EthereumMainnet => 1,
+            ChainID::Sepolia => 11155111,
+            ChainID::Holesky => 17000,
+            ChainID::Hoodi => 560048,
+            ChainID::ArbitrumOne => 42161,
+            ChainID::ArbitrumSepolia => 421614,
+            ChainID::AvalanceCChain => 43114,
+            ChainID::AvalancheFuji => 43113,
+            ChainID::BNBSmartChainMainnet => 56,
+            ChainID::BNBSmartChainTestnet => 97,
+            ChainID::CoinBase => 8453,
+            ChainID::BaseSepolia => 84532,
+            ChainID::OptimismMainnet => 10,
+            ChainID::OptimismSepolia => 11155420,
+            ChainID::PolygonMainnet => 137,
+            ChainID::PolygonAmoy => 80002,
+            ChainID::Unknown(id) => *id,
+        }
+    }
+
+    pub fn is_testnet(&self) -> bool {
+        matches!(
+            self,
+            ChainID::Sepolia
+                | ChainID::Holesky
+                | ChainID::Hoodi
+                | ChainID::ArbitrumSepolia
+                | ChainID::AvalancheFuji
+                | ChainID::BNBSmartChainTestnet
+                | ChainID::BaseSepolia
+                | ChainID::OptimismSepolia
+                | ChainID::PolygonAmoy
+        )
+    }
+
+    // the chain of the network, its testnets included : Holesky is `Chain::Eth`
+    pub fn chain(&self) -> Option<Chain> {
+        match self {
+            ChainID::EthereumMainnet | ChainID::Sepolia | ChainID::Holesky | ChainID::Hoodi => {
+                Some(Chain::Eth)
+            }
+            ChainID::ArbitrumOne | ChainID::ArbitrumSepolia => Some(Chain::Arb),
+            ChainID::BNBSmartChainMainnet | ChainID::BNBSmartChainTestnet => Some(Chain::Bsc),
+            ChainID::CoinBase | ChainID::BaseSepolia => Some(Chain::Base),
+            ChainID::OptimismMainnet | ChainID::OptimismSepolia => Some(Chain::Op),
+            ChainID::PolygonMainnet | ChainID::PolygonAmoy => Some(Chain::Matic),
+            ChainID::AvalanceCChain | ChainID::AvalancheFuji | ChainID::Unknown(_) => None,
+        }
+    }
+}
+
+impl From<u64> for ChainID {
+    fn from(id: u64) -> Self {
+        match id {
+            1 => ChainID::EthereumMainnet,
+            11155111 => ChainID::Sepolia,
+            17000 => ChainID::Holesky,
+            560048 => ChainID::Hoodi,
+            42161 => ChainID::ArbitrumOne,
+            421614 => ChainID::ArbitrumSepolia,
+            43114 => ChainID::AvalanceCChain,
+            43113 => ChainID::AvalancheFuji,
+            56 => ChainID::BNBSmartChainMainnet,
+            97 => ChainID::BNBSmartChainTestnet,
+            8453 => ChainID::CoinBase,
+            84532 => ChainID::BaseSepolia,
+            10 => ChainID::OptimismMainnet,
+            11155420 => ChainID::OptimismSepolia,
+            137 => ChainID::PolygonMainnet,
+            80002 => ChainID::PolygonAmoy,
+            id => ChainID::Unknown(id),
+        }
+    }
 }
 
-#[derive(Serialize, Deserialize, AsRefStr, Debug)]
-#[serde(rename_all = "snake_case")]
-#[non_exhaustive]
-pub enum ChainStakeState {
-    Active,
+impl From<ChainID> for u64 {
+    fn from(chain_id: ChainID) -> Self {
+        chain_id.id()
+    }
+}
+
+impl Serialize for ChainID {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.id())
+    }
+}
+
+// a number, or a string of one ("17000") as some endpoints send it
+impl<'de> Deserialize<'de> for ChainID {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Id {
+            Number(u64),
+            Text(String),
+        }
+
+        match Id::deserialize(deserializer)? {
+            Id::Number(id) => Ok(id.into()),
+            Id::Text(id) => id
+                .parse::<u64>()
+                .map(Into::into)
+                .map_err(|_| serde::de::Error::custom(format!("invalid chain id `{id}`"))),
+        }
+    }
+}
+
+string_enum! {
+    pub enum Chain {
+        Arb => "arb",
+        Eth => "eth",
+        Bsc => "bsc",
+        Matic => "matic",
+        Base => "base",
+        Op => "op",
+        CosmosHub4 => "cosmoshub-4",
+    }
+}
+
+impl Chain {
+    // the mainnet of the chain, `None` for the non EVM ones
+    pub fn mainnet_id(&self) -> Option<ChainID> {
+        match self {
+            Chain::Arb => Some(ChainID::ArbitrumOne),
+            Chain::Eth => Some(ChainID::EthereumMainnet),
+            Chain::Bsc => Some(ChainID::BNBSmartChainMainnet),
+            Chain::Matic => Some(ChainID::PolygonMainnet),
+            Chain::Base => Some(ChainID::CoinBase),
+            Chain::Op => Some(ChainID::OptimismMainnet),
+            Chain::CosmosHub4 | Chain::Unknown(_) => None,
+        }
+    }
+}
+
+string_enum! {
+    #[non_exhaustive]
+    pub enum ChainStakeState {
+        Active => "active",
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -84,9 +214,9 @@ pub struct ChainStakes {
     pub wallet_addresses: Vec<String>,
     pub stake_address: String,
     pub pool_id: String,
-    pub balance: String,
-    pub rewards: String,
-    pub available_rewards: String,
+    pub balance: Amount,
+    pub rewards: Amount,
+    pub available_rewards: Amount,
     pub delegated_epoch: u64,
     pub delegated_at: chrono::DateTime<chrono::Utc>,
     pub activated_epoch: u64,
@@ -99,15 +229,14 @@ pub struct ChainStakes {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct CreateStakeRequest {
-    #[serde(rename = "camelCase")]
     pub stake_address: String,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct Eigenlayer {
-    pub restaked: f64,
-    pub total_restaked: u64,
+    pub restaked: FloatAmount,
+    pub total_restaked: IntAmount,
     // likely to be used a bool
     pub total_is_restakable: u64,
     pub total: u64,
@@ -117,16 +246,16 @@ pub struct Eigenlayer {
 #[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct ExitedStakes {
-    pub amount: u64,
-    pub amount_usd: f64,
+    pub amount: Amount,
+    pub amount_usd: FloatAmount,
     pub total: u64,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct ExitingStakes {
-    pub amount: u64,
-    pub amount_usd: f64,
+    pub amount: Amount,
+    pub amount_usd: FloatAmount,
     pub total: u64,
 }
 
@@ -137,12 +266,12 @@ pub struct EventAttribute {
     pub value: String,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 #[serde(rename_all = "snake_case")]
 pub struct GetStakesRequest {
-    pub validators: Vec<String>,
-    pub delegators: Vec<String>,
-    pub accounts: Vec<Uuid>,
+    pub validators: Option<Vec<String>>,
+    pub delegators: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -161,9 +290,9 @@ pub struct GetStakesResponse {
     pub delegated_block: Option<u64>,
     pub undelegated_at: Option<chrono::DateTime<chrono::Utc>>,
     pub undelegated_block: Option<u64>,
-    pub rewards: String,
-    pub available_rewards: String,
-    pub balance: String,
+    pub rewards: Amount,
+    pub available_rewards: Amount,
+    pub balance: Amount,
     pub net_apy: f64,
     pub state: StakeState,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
@@ -190,37 +319,37 @@ pub struct NetworkStats {
     pub asset_icon: String,
     pub asset_symbol: String,
     pub asset_decimals: String,
-    pub assets_price_usd: String,
+    pub assets_price_usd: Amount,
     pub share_symbol: String,
-    pub tvl: String,
+    pub tvl: Amount,
     pub protocol: Protocol,
     pub protocol_display_name: String,
     pub protocol_icon: String,
-    pub protocol_tvl: String,
+    pub protocol_tvl: Amount,
     pub protocol_supply_limit: String,
-    // Gross Reward Rate
-    pub grr: u64,
-    // Net Reward Rate
-    pub nrr: u64,
+    // Gross Reward Rate, in percent
+    pub grr: f64,
+    // Net Reward Rate, in percent
+    pub nrr: f64,
     pub vault: String,
     pub chain: String,
     pub chain_id: ChainID,
     pub updated_at_block: u64,
 }
 
-#[derive(Deserialize, Serialize, AsRefStr, Debug, Clone)]
-#[serde(rename_all = "snake_case")]
-#[non_exhaustive]
-pub enum Operation {
-    Deposit,
-    Withdrawal,
+string_enum! {
+    #[non_exhaustive]
+    pub enum Operation {
+        Deposit => "deposit",
+        Withdrawal => "withdrawal",
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct Operations {
     // Address
-    pub owner: String,
+    pub owner: Lenient<EthAddress>,
     pub r#type: Operation,
     pub assets: String,
     pub shares: String,
@@ -232,93 +361,66 @@ pub struct Operations {
     pub chain: String,
 }
 
-#[derive(Serialize, Deserialize, AsRefStr, Debug)]
-#[serde(rename_all = "snake_case")]
-#[non_exhaustive]
-pub enum OperationType {
-    StakeRegisteration,
-    StakeDeregisteration,
-    Delegation,
-    Reward,
-    Withdrawal,
-}
-
-#[derive(Serialize, Deserialize, AsRefStr, Debug)]
-#[serde(rename_all = "snake_case")]
-#[non_exhaustive]
-pub enum OperationsResponse {
-    StakeRegisteration {
-        #[serde(deserialize_with = "deserialize_type")]
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        stake_address: String,
-        epoch: u64,
-        block: u64,
-        tx_hash: String,
-    },
-    StakeDeregisteration {
-        #[serde(deserialize_with = "deserialize_type")]
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        stake_address: String,
-        epoch: u64,
-        block: u64,
-        tx_hash: String,
-    },
-    Delegation {
-        #[serde(deserialize_with = "deserialize_type")]
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        stake_address: String,
-        epoch: u64,
-        block: u64,
-        tx_hash: String,
-        pool_id: String,
-    },
-    Reward {
-        #[serde(deserialize_with = "deserialize_type")]
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        stake_address: String,
-        epoch: u64,
-        block: u64,
-        pool_id: String,
-        amount: String,
-    },
-    Withdrawal {
-        #[serde(deserialize_with = "deserialize_type")]
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        stake_address: String,
-        epoch: u64,
-        block: u64,
-        pool_id: String,
-        amount: String,
-    },
-}
-
-fn deserialize_type<'de, D>(deserializer: D) -> Result<String, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let the_type = OperationsResponse::deserialize(deserializer)?;
-
-    let the_type = match the_type {
-        OperationsResponse::StakeRegisteration { .. } => "stake_registration".into(),
-        OperationsResponse::StakeDeregisteration { .. } => "stake_deregistration".into(),
-        OperationsResponse::Delegation { .. } => "delegation".into(),
-        OperationsResponse::Reward { .. } => "reward".into(),
-        OperationsResponse::Withdrawal { .. } => "withdrawal".into(),
-    };
-
-    Ok(the_type)
+string_enum! {
+    #[non_exhaustive]
+    pub enum OperationType {
+        StakeRegisteration => "stake_registeration",
+        StakeDeregisteration => "stake_deregisteration",
+        Delegation => "delegation",
+        Reward => "reward",
+        Withdrawal => "withdrawal",
+    }
+}
+
+operation_enum! {
+    #[non_exhaustive]
+    pub enum OperationsResponse {
+        StakeRegisteration => "stake_registration" | "stake_registeration" {
+            time: chrono::DateTime<chrono::Utc>,
+            stake_address: String,
+            epoch: u64,
+            block: u64,
+            tx_hash: String,
+        },
+        StakeDeregisteration => "stake_deregistration" | "stake_deregisteration" {
+            time: chrono::DateTime<chrono::Utc>,
+            stake_address: String,
+            epoch: u64,
+            block: u64,
+            tx_hash: String,
+        },
+        Delegation => "delegation" {
+            time: chrono::DateTime<chrono::Utc>,
+            stake_address: String,
+            epoch: u64,
+            block: u64,
+            tx_hash: String,
+            pool_id: String,
+        },
+        Reward => "reward" {
+            time: chrono::DateTime<chrono::Utc>,
+            stake_address: String,
+            epoch: u64,
+            block: u64,
+            pool_id: String,
+            amount: Amount,
+        },
+        Withdrawal => "withdrawal" {
+            time: chrono::DateTime<chrono::Utc>,
+            stake_address: String,
+            epoch: u64,
+            block: u64,
+            pool_id: String,
+            amount: Amount,
+        },
+    }
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct PortofolioProtocol {
     pub token: String,
-    pub name: String,
+    pub name: Protocol,
     pub total_stakes: u64,
     pub total_active_stakes: u64,
     pub total_balance: TotalBalance,
@@ -332,34 +434,107 @@ pub struct PortofolioProtocol {
     pub eigenlayer: Option<Eigenlayer>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+impl PortofolioProtocol {
+    // 0 when the protocol has no stake activating
+    pub fn activating_usd(&self) -> FloatAmount {
+        self.activating_stakes
+            .as_ref()
+            .map(|stakes| stakes.amount_usd)
+            .unwrap_or_default()
+    }
+
+    pub fn exiting_usd(&self) -> FloatAmount {
+        self.exiting_stakes
+            .as_ref()
+            .map(|stakes| stakes.amount_usd)
+            .unwrap_or_default()
+    }
+
+    pub fn exited_usd(&self) -> FloatAmount {
+        self.exited_stakes
+            .as_ref()
+            .map(|stakes| stakes.amount_usd)
+            .unwrap_or_default()
+    }
+}
+
+// The portfolio of an account or an organisation : its balances and rewards, protocol by protocol
+#[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct Portofolio {
-    pub total_balance_usd: f64,
-    pub total_rewards_usd: f64,
+    pub total_balance_usd: FloatAmount,
+    pub total_rewards_usd: FloatAmount,
     pub total_stakes: u64,
     pub total_active_stakes: u64,
-    pub protocols: Vec<Protocol>,
+    pub protocols: Vec<PortofolioProtocol>,
     pub error: Option<String>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Deserialize, Serialize, AsRefStr, Debug)]
-#[serde(rename_all = "snake_case")]
-#[non_exhaustive]
-pub enum Protocol {
-    AaveV3,
-    Venus,
-    CompoundV3,
-    Morpho,
-    Sdai,
-    Ethereum,
-    Solana,
-    Near,
-    Tezos,
-    Cardano,
-    Cosmos,
-    Matic,
+impl Portofolio {
+    pub fn protocol(&self, name: &Protocol) -> Option<&PortofolioProtocol> {
+        self.protocols
+            .iter()
+            .find(|protocol| protocol.name == *name)
+    }
+
+    // any usd value of the protocols, summed by protocol name
+    pub fn total_by_protocol(
+        &self,
+        value: impl Fn(&PortofolioProtocol) -> FloatAmount,
+    ) -> HashMap<&Protocol, FloatAmount> {
+        let mut totals: HashMap<&Protocol, FloatAmount> = HashMap::new();
+
+        for protocol in &self.protocols {
+            *totals.entry(&protocol.name).or_default() += value(protocol);
+        }
+
+        totals
+    }
+
+    pub fn balance_usd_by_protocol(&self) -> HashMap<&Protocol, FloatAmount> {
+        self.total_by_protocol(|protocol| protocol.total_balance.amount_usd)
+    }
+
+    pub fn rewards_usd_by_protocol(&self) -> HashMap<&Protocol, FloatAmount> {
+        self.total_by_protocol(|protocol| protocol.total_rewards.amount_usd)
+    }
+
+    pub fn activating_usd_by_protocol(&self) -> HashMap<&Protocol, FloatAmount> {
+        self.total_by_protocol(PortofolioProtocol::activating_usd)
+    }
+
+    pub fn exiting_usd_by_protocol(&self) -> HashMap<&Protocol, FloatAmount> {
+        self.total_by_protocol(PortofolioProtocol::exiting_usd)
+    }
+
+    // only ethereum has one
+    pub fn eigenlayer(&self) -> impl Iterator<Item = (&Protocol, &Eigenlayer)> {
+        self.protocols.iter().filter_map(|protocol| {
+            protocol
+                .eigenlayer
+                .as_ref()
+                .map(|eigenlayer| (&protocol.name, eigenlayer))
+        })
+    }
+}
+
+string_enum! {
+    #[non_exhaustive]
+    pub enum Protocol {
+        AaveV3 => "aave_v3",
+        Venus => "venus",
+        CompoundV3 => "compound_v3",
+        Morpho => "morpho",
+        Sdai => "sdai",
+        Ethereum => "ethereum",
+        Solana => "solana",
+        Near => "near",
+        Tezos => "tezos",
+        Cardano => "cardano",
+        Cosmos => "cosmos",
+        Matic => "matic",
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -399,6 +574,7 @@ pub struct PostStakesRequest {
 
 #[derive(Serialize, Deserialize, AsRefStr, Default, Debug)]
 #[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
 #[non_exhaustive]
 pub enum ResponseFormat {
     #[default]
@@ -410,13 +586,13 @@ pub enum ResponseFormat {
 #[serde(rename_all = "snake_case")]
 pub struct Reward {
     pub date: chrono::DateTime<chrono::Utc>,
-    pub rewards: String,
-    pub active_balance: String,
+    pub rewards: Amount,
+    pub active_balance: Amount,
     pub net_apy: f64,
-    pub rewards_usd: String,
-    pub stake_balance_usd: String,
-    pub rewards_balance_usd: Option<u64>,
-    pub active_balance_usd: Option<u64>,
+    pub rewards_usd: Amount,
+    pub stake_balance_usd: Amount,
+    pub rewards_balance_usd: Option<FloatAmount>,
+    pub active_balance_usd: Option<FloatAmount>,
 }
 
 #[derive(Serialize, Deserialize, AsRefStr, Debug)]
@@ -424,34 +600,34 @@ pub struct Reward {
 #[non_exhaustive]
 pub enum RewardRequest {
     Daily {
-        stakes_addresses: Vec<String>,
-        wallets: Vec<String>,
-        pool_ids: Vec<String>,
-        accounts: Vec<Uuid>,
+        stakes_addresses: Option<Vec<String>>,
+        wallets: Option<Vec<String>>,
+        pool_ids: Option<Vec<String>>,
+        accounts: Option<Vec<Uuid>>,
         format: ResponseFormat,
         start_date: chrono::DateTime<chrono::Utc>,
-        end_date: chrono::DateTime<chrono::Utc>,
+        end_date: Option<chrono::DateTime<chrono::Utc>>,
         include_usd: bool,
     },
     Epoch {
-        stakes_addresses: Vec<String>,
-        wallets: Vec<String>,
-        pool_ids: Vec<String>,
-        accounts: Vec<Uuid>,
+        stakes_addresses: Option<Vec<String>>,
+        wallets: Option<Vec<String>>,
+        pool_ids: Option<Vec<String>>,
+        accounts: Option<Vec<Uuid>>,
         format: ResponseFormat,
         start_date: chrono::DateTime<chrono::Utc>,
-        end_date: chrono::DateTime<chrono::Utc>,
+        end_date: Option<chrono::DateTime<chrono::Utc>>,
         start_epoch: u64,
         end_epoch: u64,
     },
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 #[serde(rename_all = "snake_case")]
 pub struct ReportsRequest {
-    pub delegators: Vec<String>,
-    pub validators: Vec<String>,
-    pub accounts: Vec<Uuid>,
+    pub delegators: Option<Vec<String>>,
+    pub validators: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -460,12 +636,20 @@ pub struct RestakeRewardsTxRequest {
     // Pubkey
     pub pubkey: String,
     // Address
-    pub validator: String,
+    pub validator: CosmosAddress,
     // Address
-    pub grantee_address: String,
+    pub grantee_address: CosmosAddress,
+}
+
+impl RestakeRewardsTxRequest {
+    pub(crate) fn check_addresses(&self, prefix: &str) -> Result<(), AddressError> {
+        self.validator.check_validator(prefix)?;
+        self.grantee_address.check_account(prefix)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
 pub struct StakeMessageValue {
     pub validator_address: String,
     pub delegator_address: String,
@@ -507,26 +691,25 @@ pub struct StatusResponseEvent {
     pub attributes: Vec<EventAttribute>,
 }
 
-#[derive(Serialize, Deserialize, EnumString, AsRefStr, Debug)]
-pub enum StakeState {
-    Active,
-    Activating,
-    Inactive,
-    Deactivating,
-    Withdrawn,
+string_enum! {
+    pub enum StakeState {
+        Active => "Active",
+        Activating => "Activating",
+        Inactive => "Inactive",
+        Deactivating => "Deactivating",
+        Withdrawn => "Withdrawn",
+    }
 }
 
-#[derive(Serialize, Deserialize, EnumString, Debug, Default)]
-pub enum StakePermissionKind {
-    #[default]
-    #[strum(serialize = "Staking.MsgDelegate")]
-    Delegate,
-    #[strum(serialize = "Staking.MsgUndelegate")]
-    Undelegate,
-    #[strum(serialize = "Staking.Redelegate")]
-    Redelegate,
-    #[strum(serialize = "Distribution.MsgWithdrawDelegatorReward")]
-    WithdrawDelegatorReward,
+string_enum! {
+    #[derive(Default)]
+    pub enum StakePermissionKind {
+        #[default]
+        Delegate => "Staking.MsgDelegate",
+        Undelegate => "Staking.MsgUndelegate",
+        Redelegate => "Staking.Redelegate",
+        WithdrawDelegatorReward => "Distribution.MsgWithdrawDelegatorReward",
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -545,8 +728,8 @@ pub struct StakesResponsePermission {
 pub struct StakesResponseUnbonding {
     pub creation_height: u64,
     pub completion_time: chrono::DateTime<chrono::Utc>,
-    pub balance: String,
-    pub initial_balance: String,
+    pub balance: Amount,
+    pub initial_balance: Amount,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -555,14 +738,12 @@ pub struct StakeRequestStake {
     pub stake_id: String,
 }
 
-#[derive(Serialize, Deserialize, EnumString, Debug)]
-pub enum StakeStatus {
-    #[strum(serialize = "active")]
-    Active,
-    #[strum(serialize = "pending")]
-    Pending,
-    #[strum(serialize = "disabled")]
-    Disabled,
+string_enum! {
+    pub enum StakeStatus {
+        Active => "active",
+        Pending => "pending",
+        Disabled => "disabled",
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -578,119 +759,76 @@ pub struct StakeResponse {
     pub status: StakeStatus,
 }
 
-#[derive(Serialize, Deserialize, EnumString, Debug)]
-pub enum StakingOperation {
-    #[strum(serialize = "Staking.MsgDelegate", serialize = "staking.MsgDelegate")]
-    Delegate {
-        #[serde(deserialize_with = "deserialize_operation_type")]
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        block: u64,
-        tx_hash: String,
-        tx_gas_used: String,
-        message_index: u64,
-        validator_address: String,
-        delegator_address: String,
-        amount: String,
-        withdraw_rewards: String,
-    },
-    #[strum(
-        serialize = "Staking.MsgUndelegate",
-        serialize = "staking.MsgUndelegate"
-    )]
-    Undelegate {
-        #[serde(deserialize_with = "deserialize_operation_type")]
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        block: u64,
-        tx_hash: String,
-        tx_gas_used: String,
-        message_index: u64,
-        validator_address: String,
-        delegator_address: String,
-        amount: String,
-        withdraw_rewards: String,
-    },
-    #[strum(
-        serialize = "Staking.MsgBeginRedelegate",
-        serialize = "staking.MsgBeginRedelegate"
-    )]
-    BeginRedelegate {
-        #[serde(deserialize_with = "deserialize_operation_type")]
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        block: u64,
-        tx_hash: String,
-        tx_gas_used: String,
-        message_index: u64,
-        validator_address: String,
-        validator_address_source: String,
-        delegator_address: String,
-        amount: String,
-        withdraw_rewards: String,
-        withdraw_rewards_source: String,
-    },
-    #[strum(
-        serialize = "Distribution.MsgWithdrawDelegatorReward",
-        serialize = "distribution.MsgWithdrawDelegatorReward"
-    )]
-    WithdrawDelegatorReward {
-        #[serde(deserialize_with = "deserialize_operation_type")]
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        block: u64,
-        tx_hash: String,
-        tx_gas_used: String,
-        message_index: u64,
-        validator_address: String,
-        delegator_address: String,
-        withdraw_rewards: String,
-    },
-    Grant {
-        #[serde(deserialize_with = "deserialize_operation_type")]
-        r#type: String,
-        time: chrono::DateTime<chrono::Utc>,
-        block: u64,
-        tx_hash: String,
-        tx_gas_used: String,
-        message_index: u64,
-        grantee: String,
-        granter: String,
-        permission: StakePermissionKind,
-        allow_list: Option<Vec<String>>,
-        denly_list: Option<Vec<String>>,
-    },
-    Exec {
-        #[serde(deserialize_with = "deserialize_operation_type")]
-        r#type: String,
-        validator_address: String,
-        validator_address_source: String,
-        delegator_address: String,
-        amount: String,
-        withdraw_rewards: String,
-        withdraw_rewards_source: String,
-        executed_operations: Option<Vec<Operation>>,
-    },
-}
-
-fn deserialize_operation_type<'de, D>(deserializer: D) -> Result<String, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let the_type = StakingOperation::deserialize(deserializer)?;
-
-    let the_type = match the_type {
-        StakingOperation::Delegate { .. } => "staking.MsgDelegate".into(),
-        StakingOperation::Undelegate { .. } => "staking.MsgUndelegate".into(),
-        StakingOperation::WithdrawDelegatorReward { .. } => {
-            "distr.MsgWithdrawDelegatorReward".into()
-        }
-        StakingOperation::BeginRedelegate { .. } => "staking.MsgBeginRedelegate".into(),
-        StakingOperation::Grant { .. } => "auth.grant".into(),
-        StakingOperation::Exec { .. } => "auth.exec".into(),
-    };
-
-    Ok(the_type)
+operation_enum! {
+    pub enum StakingOperation {
+        Delegate => "staking.MsgDelegate" | "Staking.MsgDelegate" {
+            time: chrono::DateTime<chrono::Utc>,
+            block: u64,
+            tx_hash: String,
+            tx_gas_used: String,
+            message_index: u64,
+            validator_address: String,
+            delegator_address: String,
+            amount: Amount,
+            withdraw_rewards: Amount,
+        },
+        Undelegate => "staking.MsgUndelegate" | "Staking.MsgUndelegate" {
+            time: chrono::DateTime<chrono::Utc>,
+            block: u64,
+            tx_hash: String,
+            tx_gas_used: String,
+            message_index: u64,
+            validator_address: String,
+            delegator_address: String,
+            amount: Amount,
+            withdraw_rewards: Amount,
+        },
+        BeginRedelegate => "staking.MsgBeginRedelegate" | "Staking.MsgBeginRedelegate" {
+            time: chrono::DateTime<chrono::Utc>,
+            block: u64,
+            tx_hash: String,
+            tx_gas_used: String,
+            message_index: u64,
+            validator_address: String,
+            validator_address_source: String,
+            delegator_address: String,
+            amount: Amount,
+            withdraw_rewards: Amount,
+            withdraw_rewards_source: String,
+        },
+        WithdrawDelegatorReward => "distribution.MsgWithdrawDelegatorReward" | "Distribution.MsgWithdrawDelegatorReward" {
+            time: chrono::DateTime<chrono::Utc>,
+            block: u64,
+            tx_hash: String,
+            tx_gas_used: String,
+            message_index: u64,
+            validator_address: String,
+            delegator_address: String,
+            withdraw_rewards: Amount,
+        },
+        Grant => "authz.MsgGrant" | "Authz.MsgGrant" {
+            time: chrono::DateTime<chrono::Utc>,
+            block: u64,
+            tx_hash: String,
+            tx_gas_used: String,
+            message_index: u64,
+            grantee: String,
+            granter: String,
+            permission: StakePermissionKind,
+            allow_list: Option<Vec<String>>,
+            deny_list: Option<Vec<String>>,
+        },
+        Exec => "authz.MsgExec" | "Authz.MsgExec" {
+            time: chrono::DateTime<chrono::Utc>,
+            block: u64,
+            tx_hash: String,
+            tx_gas_used: String,
+            message_index: u64,
+            grantee: String,
+            // the operations the grantee executed on behalf of the granter
+            executed_operations: Vec<StakingOperation>,
+        },
+    }
 }
 
 // Not sure about this one, gotta pray
@@ -724,14 +862,14 @@ pub struct SignerInfo {
 #[serde(rename_all = "snake_case")]
 pub struct Stakes {
     // Address
-    pub owner: String,
-    pub current_balance: String,
-    pub total_rewards: String,
-    pub current_rewards: String,
-    pub total_deposited_amount: String,
-    pub total_withdrawn_amount: String,
+    pub owner: Lenient<EthAddress>,
+    pub current_balance: Amount,
+    pub total_rewards: Amount,
+    pub current_rewards: Amount,
+    pub total_deposited_amount: Amount,
+    pub total_withdrawn_amount: Amount,
     // Address
-    pub vault: String,
+    pub vault: Lenient<EthAddress>,
     pub chain: String,
     pub updated_at_block: u64,
 }
@@ -739,43 +877,51 @@ pub struct Stakes {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct StakeOperationsRequest {
-    pub stake_addresses: Vec<String>,
-    pub wallets: Vec<String>,
-    pub pool_ids: Vec<String>,
-    pub accounts: Vec<Uuid>,
+    pub stake_addresses: Option<Vec<String>>,
+    pub wallets: Option<Vec<String>>,
+    pub pool_ids: Option<Vec<String>>,
+    pub accounts: Option<Vec<Uuid>>,
     pub start_date: chrono::DateTime<chrono::Utc>,
-    pub end_date: chrono::DateTime<chrono::Utc>,
+    pub end_date: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+// `{ "typeUrl": "/cosmos.staking.v1beta1.MsgDelegate", "value": {..} }`, told apart by their fields
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(untagged)]
 pub enum TxStakeMessage {
-    #[serde(rename_all = "snake_case")]
+    #[serde(rename_all = "camelCase")]
     Value {
-        #[serde(rename = "camelCase")]
         type_url: String,
         value: StakeMessageValue,
     },
+    #[serde(rename_all = "camelCase")]
     Restake {
-        #[serde(rename = "camelCase")]
         type_url: String,
         allow_list: StakeMessageRestake,
-        #[serde(rename = "PascalCase")]
         authorization_type: u64,
     },
+    // any other message : a usdc burn, an ibc transfer...
+    #[serde(rename_all = "camelCase")]
+    Other {
+        type_url: String,
+        value: serde_json::Value,
+    },
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct TotalBalance {
-    pub amount_usd: f64,
-    pub amount: u64,
+    pub amount_usd: FloatAmount,
+    // base units of the protocol token, past u64 for a few ETH in wei
+    pub amount: Amount,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct TotalRewards {
-    pub amount_usd: f64,
-    pub amount: u64,
+    pub amount_usd: FloatAmount,
+    // base units of the protocol token, past u64 for a few ETH in wei
+    pub amount: Amount,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -818,14 +964,25 @@ pub struct TxDecodeGasLimit {
 pub struct Tip {
     pub amount: Vec<TxStakeCoin>,
     // Address
-    pub tipper: String,
+    pub tipper: Lenient<CosmosAddress>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct TxStakeCoin {
     pub denom: String,
-    pub amount: String,
+    pub amount: Amount,
+}
+
+impl TxStakeCoin {
+    // `None` when the coin is in another denom : `coin.amount_in::<UAtom>()`
+    pub fn amount_in<D: Denomination>(&self) -> Option<D> {
+        if self.denom != D::SYMBOL {
+            return None;
+        }
+
+        self.amount.to_string().parse().ok()
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -864,17 +1021,26 @@ pub struct TxStatusResponse {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct ValidatorOperationsRequest {
-    pub validators: Vec<String>,
-    pub delegators: Vec<String>,
+    pub validators: Option<Vec<String>>,
+    pub delegators: Option<Vec<String>>,
     pub authz: bool,
-    pub accounts: Vec<Uuid>,
+    pub accounts: Option<Vec<Uuid>>,
     pub start_date: chrono::DateTime<chrono::Utc>,
-    pub end_date: chrono::DateTime<chrono::Utc>,
+    pub end_date: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct WithdrawRewardsTxRequest {
     pub pubkey: String,
-    pub validator: String,
+    pub validator: CosmosAddress,
+}
+
+impl WithdrawRewardsTxRequest {
+    pub(crate) fn check_addresses(&self, prefix: &str) -> Result<(), AddressError> {
+        self.validator.check_validator(prefix)
+    }
 }
+use crate::address::{AddressError, CosmosAddress, EthAddress, Lenient};
+use crate::amount::{Amount, FloatAmount, IntAmount};
+use crate::denomination::Denomination;
@@ -0,0 +1,76 @@
+use std::fmt;
+
+use zeroize::Zeroize;
+
+// Holds the api token.
+// Redacted by Debug and Display, wiped from memory on drop, only readable inside the crate.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Secret(String);
+
+impl Secret {
+    pub(crate) fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(value: &str) -> Self {
+        Self(value.to_owned())
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret([REDACTED])")
+    }
+}
+
+impl fmt::Display for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod secret_test {
+
+    use super::*;
+    #[cfg(feature = "blocking")]
+    use crate::Kiln;
+
+    #[test]
+    fn secret_is_redacted() {
+        let secret = Secret::from("kiln_super_secret_token");
+
+        assert_eq!(format!("{secret}"), "[REDACTED]");
+        assert_eq!(format!("{secret:?}"), "Secret([REDACTED])");
+        assert_eq!(secret.expose(), "kiln_super_secret_token");
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn clients_do_not_leak_the_token() {
+        let kiln = Kiln::builder()
+            .api_token("kiln_super_secret_token")
+            .base_url("https://api.kiln.fi/v1")
+            .seal()
+            .build()
+            .unwrap();
+
+        assert!(!format!("{kiln:?}").contains("kiln_super_secret_token"));
+        assert!(!format!("{:?}", kiln.ethereum()).contains("kiln_super_secret_token"));
+        assert!(!format!("{:?}", kiln.polygon()).contains("kiln_super_secret_token"));
+    }
+}
@@ -0,0 +1,309 @@
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use zeroize::Zeroizing;
+
+use crate::secret::Secret;
+
+pub type TokenError = Box<dyn std::error::Error + Send + Sync>;
+
+// Asked for the api token before every request (retries included).
+// On a 401, `refresh` is called and the request is sent once more with a new token.
+// Works for a `Kiln` and an `AsyncKiln`, implement `AsyncTokenProvider` when getting the token must be awaited.
+pub trait TokenProvider: std::fmt::Debug + Send + Sync {
+    // the raw api token, without "Bearer "
+    fn token(&self) -> Result<Secret, TokenError>;
+
+    // the last token was rejected : drop any cached value
+    fn refresh(&self) {}
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+pub trait AsyncTokenProvider: std::fmt::Debug + Send + Sync {
+    // the raw api token, without "Bearer "
+    async fn token(&self) -> Result<Secret, TokenError>;
+
+    // the last token was rejected : drop any cached value
+    async fn refresh(&self) {}
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<T: TokenProvider + ?Sized> AsyncTokenProvider for T {
+    async fn token(&self) -> Result<Secret, TokenError> {
+        TokenProvider::token(self)
+    }
+
+    async fn refresh(&self) {
+        TokenProvider::refresh(self)
+    }
+}
+
+// What `KilnBuilder::api_token` uses.
+#[derive(Clone, Debug)]
+pub struct StaticToken(Secret);
+
+impl StaticToken {
+    pub fn new(token: impl Into<Secret>) -> Self {
+        Self(token.into())
+    }
+}
+
+impl TokenProvider for StaticToken {
+    fn token(&self) -> Result<Secret, TokenError> {
+        Ok(self.0.clone())
+    }
+}
+
+// Read from the environment variable on every request.
+#[derive(Clone, Debug)]
+pub struct EnvToken {
+    variable: String,
+}
+
+impl EnvToken {
+    pub fn new(variable: impl Into<String>) -> Self {
+        Self {
+            variable: variable.into(),
+        }
+    }
+}
+
+impl TokenProvider for EnvToken {
+    fn token(&self) -> Result<Secret, TokenError> {
+        // wiped once copied into the Secret
+        let token = Zeroizing::new(
+            std::env::var(&self.variable)
+                .map_err(|error| format!("{}: {}", self.variable, error))?,
+        );
+
+        Ok(Secret::from(token.trim()))
+    }
+}
+
+// Read from a file (a mounted kubernetes secret, a vault agent sink...),
+// read again only when its modification time changes.
+#[derive(Debug)]
+pub struct FileToken {
+    path: PathBuf,
+    cached: Mutex<Option<(SystemTime, Secret)>>,
+}
+
+impl FileToken {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            cached: Mutex::new(None),
+        }
+    }
+
+    // a blocking read, even for an `AsyncKiln` : the file is tiny and rarely read
+    fn read(&self) -> Result<Secret, TokenError> {
+        let modified = std::fs::metadata(&self.path)?.modified()?;
+
+        let mut cached = self
+            .cached
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if let Some((cached_modified, token)) = cached.as_ref() {
+            if *cached_modified == modified {
+                return Ok(token.clone());
+            }
+        }
+
+        // wiped once copied into the Secret, on every read after a change too
+        let contents = Zeroizing::new(std::fs::read_to_string(&self.path)?);
+
+        let token = Secret::from(contents.trim());
+
+        *cached = Some((modified, token.clone()));
+
+        Ok(token)
+    }
+}
+
+impl TokenProvider for FileToken {
+    fn token(&self) -> Result<Secret, TokenError> {
+        self.read()
+    }
+
+    fn refresh(&self) {
+        *self
+            .cached
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+    }
+}
+
+// Calls the closure on every request (cache inside it if fetching the token is expensive).
+pub struct CallbackToken<F> {
+    callback: F,
+}
+
+impl<F> CallbackToken<F>
+where
+    F: Fn() -> Result<Secret, TokenError> + Send + Sync,
+{
+    pub fn new(callback: F) -> Self {
+        Self { callback }
+    }
+}
+
+impl<F> std::fmt::Debug for CallbackToken<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CallbackToken").finish_non_exhaustive()
+    }
+}
+
+impl<F> TokenProvider for CallbackToken<F>
+where
+    F: Fn() -> Result<Secret, TokenError> + Send + Sync,
+{
+    fn token(&self) -> Result<Secret, TokenError> {
+        (self.callback)()
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod token_test {
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use super::{CallbackToken, EnvToken, FileToken, Mutex, Secret, SystemTime, TokenProvider};
+    use crate::transport::{HttpRequest, HttpResponse, Transport, TransportError};
+    use crate::Kiln;
+
+    // answers 401 to any token but "fresh"
+    #[derive(Debug, Default)]
+    struct RotatingTransport {
+        tokens: Mutex<Vec<String>>,
+    }
+
+    impl Transport for Arc<RotatingTransport> {
+        fn send(&self, request: HttpRequest) -> Result<HttpResponse, TransportError> {
+            let (_, authorization) = request
+                .headers
+                .iter()
+                .find(|(key, _)| key == "Authorization")
+                .cloned()
+                .unwrap();
+
+            let status = if authorization == "Bearer fresh" {
+                200
+            } else {
+                401
+            };
+
+            self.tokens.lock().unwrap().push(authorization);
+
+            Ok(HttpResponse {
+                status,
+                headers: Vec::new(),
+                body: br#"{"data":[]}"#.to_vec(),
+            })
+        }
+    }
+
+    #[test]
+    fn a_401_refreshes_the_token_once() {
+        let transport = Arc::new(RotatingTransport::default());
+
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&calls);
+
+        let kiln = Kiln::builder()
+            .token_provider(CallbackToken::new(move || {
+                match counter.fetch_add(1, Ordering::SeqCst) {
+                    0 => Ok(Secret::from("stale")),
+                    _ => Ok(Secret::from("fresh")),
+                }
+            }))
+            .base_url("https://api.kiln.fi/v1")
+            .transport(Arc::clone(&transport))
+            .seal()
+            .build()
+            .unwrap();
+
+        kiln.deployments().get().unwrap();
+
+        assert_eq!(
+            *transport.tokens.lock().unwrap(),
+            vec!["Bearer stale", "Bearer fresh"]
+        );
+    }
+
+    #[test]
+    fn a_second_401_is_returned() {
+        let transport = Arc::new(RotatingTransport::default());
+
+        let kiln = Kiln::builder()
+            .api_token("stale")
+            .base_url("https://api.kiln.fi/v1")
+            .transport(Arc::clone(&transport))
+            .seal()
+            .build()
+            .unwrap();
+
+        let error = kiln.deployments().get().unwrap_err();
+
+        assert_eq!(error.status(), Some(401));
+        assert_eq!(transport.tokens.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn file_token_is_read_again_when_it_changes() {
+        let path = std::env::temp_dir().join(format!("kiln_token_{}", std::process::id()));
+
+        let set_modified = |modified: SystemTime| {
+            std::fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(modified)
+                .unwrap();
+        };
+
+        std::fs::write(&path, "first\n").unwrap();
+
+        let first_modified = std::fs::metadata(&path).unwrap().modified().unwrap();
+
+        let provider = FileToken::new(&path);
+
+        assert_eq!(provider.token().unwrap().expose(), "first");
+
+        // same mtime : the cached token
+        std::fs::write(&path, "second").unwrap();
+        set_modified(first_modified);
+
+        assert_eq!(provider.token().unwrap().expose(), "first");
+
+        // a new mtime : read again, without any refresh
+        set_modified(first_modified + std::time::Duration::from_secs(10));
+
+        assert_eq!(provider.token().unwrap().expose(), "second");
+
+        std::fs::write(&path, "third").unwrap();
+        set_modified(first_modified + std::time::Duration::from_secs(10));
+
+        // refresh drops the cache whatever the mtime
+        provider.refresh();
+
+        assert_eq!(provider.token().unwrap().expose(), "third");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn env_token_reports_the_missing_variable() {
+        let error = EnvToken::new("KILN_TOKEN_THAT_DOES_NOT_EXIST")
+            .token()
+            .unwrap_err();
+
+        assert!(error.to_string().contains("KILN_TOKEN_THAT_DOES_NOT_EXIST"));
+    }
+}
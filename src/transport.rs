@@ -0,0 +1,524 @@
+use std::time::Duration;
+
+use strum_macros::AsRefStr;
+
+pub type TransportError = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AsRefStr)]
+pub enum Method {
+    #[strum(serialize = "GET")]
+    Get,
+    #[strum(serialize = "POST")]
+    Post,
+    #[strum(serialize = "PUT")]
+    Put,
+    #[strum(serialize = "DELETE")]
+    Delete,
+}
+
+// Debug redacts the `Authorization` header, like `Secret`
+#[derive(Clone)]
+pub struct HttpRequest {
+    pub method: Method,
+    // absolute url, query string included
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Vec<u8>>,
+}
+
+impl std::fmt::Debug for HttpRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let headers: Vec<(&str, &str)> = self
+            .headers
+            .iter()
+            .map(|(key, value)| {
+                if key.eq_ignore_ascii_case("authorization") {
+                    (key.as_str(), "[REDACTED]")
+                } else {
+                    (key.as_str(), value.as_str())
+                }
+            })
+            .collect();
+
+        f.debug_struct("HttpRequest")
+            .field("method", &self.method)
+            .field("url", &self.url)
+            .field("headers", &headers)
+            .field(
+                "body",
+                &self.body.as_ref().map(|body| String::from_utf8_lossy(body)),
+            )
+            .finish()
+    }
+}
+
+impl HttpRequest {
+    // "/v1/eth/stakes" for "https://api.kiln.fi/v1/eth/stakes?wallets=0x..."
+    pub fn path(&self) -> &str {
+        let without_scheme = self
+            .url
+            .split_once("://")
+            .map(|(_, rest)| rest)
+            .unwrap_or(&self.url);
+
+        let path = without_scheme
+            .find('/')
+            .map(|index| &without_scheme[index..])
+            .unwrap_or("/");
+
+        path.split('?').next().unwrap_or(path)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+// Settings of the connection-pooled client built by KilnBuilder.
+// Ignored when a custom Transport is given, configure that one instead.
+#[derive(Clone, Debug)]
+pub struct HttpOptions {
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    max_idle_connections: usize,
+    user_agent: String,
+    gzip: bool,
+}
+
+impl Default for HttpOptions {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Some(Duration::from_secs(10)),
+            timeout: Some(Duration::from_secs(30)),
+            max_idle_connections: 10,
+            user_agent: concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION")).into(),
+            gzip: true,
+        }
+    }
+}
+
+impl HttpOptions {
+    // None waits forever
+    pub fn connect_timeout(self, connect_timeout: Option<Duration>) -> Self {
+        Self {
+            connect_timeout,
+            ..self
+        }
+    }
+
+    // whole request, from connecting to reading the last byte of the body. None waits forever
+    pub fn timeout(self, timeout: Option<Duration>) -> Self {
+        Self { timeout, ..self }
+    }
+
+    // connections kept open for reuse, every request goes to the same Kiln host
+    pub fn max_idle_connections(self, max_idle_connections: usize) -> Self {
+        Self {
+            max_idle_connections,
+            ..self
+        }
+    }
+
+    pub fn user_agent(self, user_agent: impl Into<String>) -> Self {
+        Self {
+            user_agent: user_agent.into(),
+            ..self
+        }
+    }
+
+    // Accept-Encoding: gzip, and the gzipped responses decompressed
+    pub fn gzip(self, gzip: bool) -> Self {
+        Self { gzip, ..self }
+    }
+}
+
+// Everything the SDK sends goes through a Transport.
+// A Transport only moves bytes : it must return non-2xx responses as `Ok(HttpResponse)`,
+// status handling and (de)serialization are done by the SDK.
+// `Transport` is what a `Kiln` sends with, `AsyncTransport` what an `AsyncKiln` sends with.
+pub trait Transport: std::fmt::Debug + Send + Sync {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse, TransportError>;
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+pub trait AsyncTransport: std::fmt::Debug + Send + Sync {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, TransportError>;
+}
+
+#[cfg(feature = "blocking")]
+#[derive(Clone, Debug)]
+pub struct UreqTransport {
+    agent: ureq::Agent,
+}
+
+#[cfg(feature = "blocking")]
+impl Default for UreqTransport {
+    fn default() -> Self {
+        Self::from(&HttpOptions::default())
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl From<&HttpOptions> for UreqTransport {
+    fn from(options: &HttpOptions) -> Self {
+        let agent: ureq::Agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_connect(options.connect_timeout)
+            .timeout_global(options.timeout)
+            .max_idle_connections(options.max_idle_connections)
+            .max_idle_connections_per_host(options.max_idle_connections)
+            .user_agent(options.user_agent.as_str())
+            // an empty value removes the header
+            .accept_encoding(if options.gzip { "gzip" } else { "" })
+            .build()
+            .into();
+
+        Self { agent }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl From<ureq::Agent> for UreqTransport {
+    fn from(agent: ureq::Agent) -> Self {
+        Self { agent }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl Transport for UreqTransport {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse, TransportError> {
+        use std::io::Read;
+
+        let HttpRequest {
+            method,
+            url,
+            headers,
+            body,
+        } = request;
+
+        let mut builder = ureq::http::Request::builder()
+            .method(method.as_ref())
+            .uri(url);
+
+        for (key, value) in headers {
+            builder = builder.header(key, value);
+        }
+
+        let result = match body {
+            Some(body) => self.agent.run(builder.body(body)?),
+            None => self.agent.run(builder.body(())?),
+        };
+
+        let mut response = match result {
+            Ok(response) => response,
+            // an agent given by the caller may still have http_status_as_error enabled
+            Err(ureq::Error::StatusCode(status)) => {
+                return Ok(HttpResponse {
+                    status,
+                    headers: Vec::new(),
+                    body: Vec::new(),
+                })
+            }
+            Err(error) => return Err(error.into()),
+        };
+
+        let headers: Vec<(String, String)> = response
+            .headers()
+            .iter()
+            .filter_map(|(key, value)| {
+                value
+                    .to_str()
+                    .ok()
+                    .map(|value| (key.as_str().to_owned(), value.to_owned()))
+            })
+            .collect();
+
+        let mut body: Vec<u8> = Vec::new();
+
+        response.body_mut().as_reader().read_to_end(&mut body)?;
+
+        Ok(HttpResponse {
+            status: response.status().as_u16(),
+            headers,
+            body,
+        })
+    }
+}
+
+#[cfg(feature = "async")]
+#[derive(Clone, Debug)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "async")]
+impl Default for ReqwestTransport {
+    // panics like `reqwest::Client::new` when the TLS backend cannot be initialized
+    fn default() -> Self {
+        Self::try_from(&HttpOptions::default()).expect("the TLS backend cannot be initialized")
+    }
+}
+
+#[cfg(feature = "async")]
+impl TryFrom<&HttpOptions> for ReqwestTransport {
+    type Error = TransportError;
+
+    // fails when the TLS backend cannot be initialized
+    fn try_from(options: &HttpOptions) -> Result<Self, Self::Error> {
+        let mut builder = reqwest::Client::builder()
+            .pool_max_idle_per_host(options.max_idle_connections)
+            .user_agent(options.user_agent.as_str())
+            .gzip(options.gzip);
+
+        if let Some(connect_timeout) = options.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+
+        if let Some(timeout) = options.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        Ok(Self {
+            client: builder.build()?,
+        })
+    }
+}
+
+#[cfg(feature = "async")]
+impl From<reqwest::Client> for ReqwestTransport {
+    fn from(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl AsyncTransport for ReqwestTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, TransportError> {
+        let HttpRequest {
+            method,
+            url,
+            headers,
+            body,
+        } = request;
+
+        let method = match method {
+            Method::Get => reqwest::Method::GET,
+            Method::Post => reqwest::Method::POST,
+            Method::Put => reqwest::Method::PUT,
+            Method::Delete => reqwest::Method::DELETE,
+        };
+
+        let mut builder = self.client.request(method, url);
+
+        for (key, value) in headers {
+            builder = builder.header(key, value);
+        }
+
+        if let Some(body) = body {
+            builder = builder.body(body);
+        }
+
+        let response = builder.send().await?;
+
+        let status = response.status().as_u16();
+
+        let headers: Vec<(String, String)> = response
+            .headers()
+            .iter()
+            .filter_map(|(key, value)| {
+                value
+                    .to_str()
+                    .ok()
+                    .map(|value| (key.as_str().to_owned(), value.to_owned()))
+            })
+            .collect();
+
+        let body = response.bytes().await?.to_vec();
+
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+#[cfg(feature = "blocking")]
+pub(crate) fn default_transport(options: &HttpOptions) -> std::sync::Arc<dyn Transport> {
+    std::sync::Arc::new(UreqTransport::from(options))
+}
+
+#[cfg(feature = "async")]
+pub(crate) fn default_async_transport(
+    options: &HttpOptions,
+) -> Result<std::sync::Arc<dyn AsyncTransport>, TransportError> {
+    Ok(std::sync::Arc::new(ReqwestTransport::try_from(options)?))
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod transport_test {
+
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::{Kiln, KilnError};
+
+    #[derive(Debug, Default)]
+    struct FakeTransport {
+        status: u16,
+        body: &'static str,
+        requests: Mutex<Vec<HttpRequest>>,
+    }
+
+    impl Transport for Arc<FakeTransport> {
+        fn send(&self, request: HttpRequest) -> Result<HttpResponse, TransportError> {
+            self.requests.lock().unwrap().push(request);
+
+            Ok(HttpResponse {
+                status: self.status,
+                headers: Vec::new(),
+                body: self.body.as_bytes().to_vec(),
+            })
+        }
+    }
+
+    fn kiln_with(fake: &Arc<FakeTransport>) -> Kiln {
+        Kiln::builder()
+            .api_token("kiln_test_token")
+            .base_url("https://api.kiln.fi/v1")
+            .transport(Arc::clone(fake))
+            .seal()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn dispatches_through_the_transport() {
+        let fake = Arc::new(FakeTransport {
+            status: 200,
+            body: r#"{"data":[]}"#,
+            ..Default::default()
+        });
+
+        let kiln = kiln_with(&fake);
+
+        let data = kiln.deployments().get().unwrap();
+
+        assert!(data.data.is_empty());
+
+        let requests = fake.requests.lock().unwrap();
+
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, Method::Get);
+        assert_eq!(requests[0].url, "https://api.kiln.fi/v1/deployments");
+        assert!(requests[0]
+            .headers
+            .contains(&("Authorization".into(), "Bearer kiln_test_token".into())));
+    }
+
+    #[test]
+    fn mismatched_payload_reports_its_path() {
+        let fake = Arc::new(FakeTransport {
+            status: 200,
+            body: r#"{"data":[{"id":"not-a-uuid"}]}"#,
+            ..Default::default()
+        });
+
+        let kiln = kiln_with(&fake);
+
+        let error = kiln.deployments().get().unwrap_err();
+
+        match error {
+            KilnError::Deserialize { path, .. } => assert_eq!(path, "data[0].id"),
+            other => panic!("expected a deserialization error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn non_success_status_is_an_error() {
+        let fake = Arc::new(FakeTransport {
+            status: 500,
+            body: "oops",
+            ..Default::default()
+        });
+
+        let kiln = kiln_with(&fake);
+
+        let error = kiln.deployments().get().unwrap_err();
+
+        assert_eq!(error.status(), Some(500));
+        assert!(error.is_retryable());
+    }
+
+    #[test]
+    fn retries_follow_the_policy() {
+        let fake = Arc::new(FakeTransport {
+            status: 503,
+            body: "unavailable",
+            ..Default::default()
+        });
+
+        let kiln = Kiln::builder()
+            .api_token("kiln_test_token")
+            .base_url("https://api.kiln.fi/v1")
+            .transport(Arc::clone(&fake))
+            .retry_policy(
+                crate::RetryPolicy::default()
+                    .max_attempts(3)
+                    .initial_backoff(std::time::Duration::ZERO),
+            )
+            .seal()
+            .build()
+            .unwrap();
+
+        let error = kiln.deployments().get().unwrap_err();
+
+        assert_eq!(error.status(), Some(503));
+        assert_eq!(fake.requests.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn debug_redacts_the_token() {
+        let request = HttpRequest {
+            method: Method::Get,
+            url: "https://api.kiln.fi/v1/deployments".into(),
+            headers: vec![
+                ("accept".into(), "application/json".into()),
+                ("Authorization".into(), "Bearer kiln_test_token".into()),
+            ],
+            body: None,
+        };
+
+        let debug = format!("{request:?}");
+
+        assert!(!debug.contains("kiln_test_token"));
+        assert!(debug.contains("[REDACTED]"));
+        assert!(debug.contains("application/json"));
+    }
+
+    #[test]
+    fn path_drops_host_and_query() {
+        let request = HttpRequest {
+            method: Method::Get,
+            url: "https://api.kiln.fi/v1/eth/stakes?wallets=0x0".into(),
+            headers: Vec::new(),
+            body: None,
+        };
+
+        assert_eq!(request.path(), "/v1/eth/stakes");
+    }
+}